@@ -0,0 +1,108 @@
+//! ルート並列化したモンテカルロ木探索で，スレッド数ごとのプレイアウトの速度を比較する．
+//!
+//! 三目並べの初期局面でスレッドあたり同じ回数のシミュレーションを行い，1秒あたりのプレイアウト数と速度比を表示する．
+//! `cargo bench --bench mcts_parallel`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+/// 各スレッドで行うシミュレーションの回数．
+const SIMULATIONS: u64 = 200_000;
+
+/// 縦，横，斜めに並ぶマスの組．
+const LINES: [u16; 8] = [
+    0b000_000_111,
+    0b000_111_000,
+    0b111_000_000,
+    0b001_001_001,
+    0b010_010_010,
+    0b100_100_100,
+    0b100_010_001,
+    0b001_010_100,
+];
+
+/// 三目並べの盤面．各プレイヤーが置いたマスをビットで表す．
+#[derive(Clone, PartialEq)]
+struct Board {
+    marks: [u16; 2],
+}
+
+impl State for Board {}
+
+#[derive(Clone)]
+struct Mark {
+    cell: u16,
+    actor: Actor,
+}
+
+impl Action for Mark {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct TicTacToeRule;
+
+impl Rule for TicTacToeRule {
+    type S = Board;
+    type A = Mark;
+    type ActionIterator = std::vec::IntoIter<Mark>;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        for &actor in Actor::ALL.iter() {
+            let marks = state.marks[actor.index()];
+            if LINES.iter().any(|&line| line & !marks == 0) {
+                return Some(TerminalKind::Win(actor));
+            }
+        }
+        if state.marks[0] | state.marks[1] == 0b111_111_111 {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        let occupied = state.marks[0] | state.marks[1];
+        (0..9)
+            .map(|cell| 1 << cell)
+            .filter(|cell| occupied & cell == 0)
+            .map(|cell| Mark { cell, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Mark) -> Board {
+        let mut marks = state.marks;
+        marks[action.actor.index()] |= action.cell;
+        Board { marks }
+    }
+}
+
+impl OutcomeRule for TicTacToeRule {}
+
+fn main() {
+    let board = Board { marks: [0, 0] };
+    let max_threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let mut threads = 1;
+    let mut baseline = None;
+    while threads <= max_threads {
+        let mcts = MctsStrategy::<TicTacToeRule, _>::new(SIMULATIONS)
+            .with_seed(0)
+            .with_threads(threads);
+        let start = Instant::now();
+        mcts.select_action(&board, Actor::First);
+        let playouts_per_second =
+            (SIMULATIONS * threads as u64) as f64 / start.elapsed().as_secs_f64();
+        let baseline = *baseline.get_or_insert(playouts_per_second);
+        println!(
+            "{} threads: {:.0} playouts/s, speedup x{:.2}",
+            threads,
+            playouts_per_second,
+            playouts_per_second / baseline
+        );
+        threads *= 2;
+    }
+}
@@ -0,0 +1,154 @@
+//! 逐次のαβ法と，根ノードの子ノードを並列に調べるαβ法の探索時間を比較する．
+//!
+//! 分岐数の多いゲームを深さ7以上で探索し，それぞれの探索時間と速度比を表示する．
+//! また，置換表を用いる逐次の反復深化とLazy SMPについて，各深さの探索を終えるまでの時間を比較する．
+//! `cargo bench --bench parallel`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+/// 各手番で選べる行動の数．
+const BRANCHING: u64 = 24;
+/// ゲームが終了するまでの手数．
+const GAME_LENGTH: u32 = 12;
+
+/// 各プレイヤーが交互に数を選び，選んだ数と局面から決まる得点を獲得するゲームの状態．
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ScoreState {
+    /// これまでに選ばれた数から計算したハッシュ値．
+    history: u64,
+    /// 各プレイヤーの得点．
+    scores: [i64; 2],
+    /// これまでの手数．
+    plies: u32,
+}
+
+impl State for ScoreState {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Pick {
+    number: u64,
+    actor: Actor,
+}
+
+impl Action for Pick {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+fn index_of(actor: Actor) -> usize {
+    match actor {
+        Actor::First => 0,
+        Actor::Second => 1,
+    }
+}
+
+/// 64ビットの値を攪拌する (SplitMix64)．
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+struct ScoreRule;
+
+impl Rule for ScoreRule {
+    type S = ScoreState;
+    type A = Pick;
+    type ActionIterator = std::vec::IntoIter<Pick>;
+
+    fn terminal_kind(state: &ScoreState) -> Option<TerminalKind> {
+        if state.plies < GAME_LENGTH {
+            return None;
+        }
+        let [first, second] = state.scores;
+        Some(match first.cmp(&second) {
+            Ordering::Greater => TerminalKind::Win(Actor::First),
+            Ordering::Less => TerminalKind::Win(Actor::Second),
+            Ordering::Equal => TerminalKind::Draw,
+        })
+    }
+
+    fn iterate_available_actions(_state: &ScoreState, actor: Actor) -> Self::ActionIterator {
+        (0..BRANCHING)
+            .map(|number| Pick { number, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &ScoreState, action: &Pick) -> ScoreState {
+        let history = mix(state.history ^ action.number);
+        let mut scores = state.scores;
+        scores[index_of(action.actor)] += (history % 100) as i64;
+        ScoreState {
+            history,
+            scores,
+            plies: state.plies + 1,
+        }
+    }
+}
+
+struct ScoreDifference;
+
+impl Evaluator<ScoreState> for ScoreDifference {
+    type Payoff = i64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &ScoreState) -> i64 {
+        state.scores[index_of(actor)] - state.scores[index_of(actor.opponent())]
+    }
+}
+
+fn measure<F>(search: F) -> (Duration, Option<u64>)
+where
+    F: FnOnce() -> Option<Pick>,
+{
+    let start = Instant::now();
+    let action = search();
+    (start.elapsed(), action.map(|action| action.number))
+}
+
+fn main() {
+    let state = ScoreState {
+        history: 0,
+        scores: [0, 0],
+        plies: 0,
+    };
+    for &depth in [7u32, 8].iter() {
+        let sequential = AlphaBetaStrategy::<ScoreRule, ScoreDifference, _>::new(depth);
+        let parallel = ParallelAlphaBetaStrategy::<ScoreRule, ScoreDifference, _>::new(depth);
+        let (sequential_time, sequential_action) =
+            measure(|| sequential.select_action(&state, Actor::First));
+        let (parallel_time, parallel_action) =
+            measure(|| parallel.select_action(&state, Actor::First));
+        assert_eq!(sequential_action, parallel_action);
+        println!(
+            "depth {}: sequential {:?}, parallel {:?}, speedup x{:.2}",
+            depth,
+            sequential_time,
+            parallel_time,
+            sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+    }
+
+    // 置換表の記録が残らないよう，深さごとにエージェントを作り直す
+    for &depth in [5u32, 6, 7].iter() {
+        let sequential = AlphaBetaStrategy::<ScoreRule, ScoreDifference, _>::new(depth)
+            .with_transposition_table(DEFAULT_TRANSPOSITION_TABLE_CAPACITY);
+        let sequential: IterativeDeepeningStrategy<_, _, _> = sequential.into();
+        let lazy_smp = LazySmpStrategy::<ScoreRule, ScoreDifference, _>::new(depth);
+        let (sequential_time, _) = measure(|| sequential.select_action(&state, Actor::First));
+        let (lazy_smp_time, _) = measure(|| lazy_smp.select_action(&state, Actor::First));
+        println!(
+            "time to depth {}: iterative deepening {:?}, lazy SMP ({} threads) {:?}, speedup x{:.2}",
+            depth,
+            sequential_time,
+            lazy_smp.depth_offsets().len() + 1,
+            lazy_smp_time,
+            sequential_time.as_secs_f64() / lazy_smp_time.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! 6x6の盤面を持つゲームを最後まで進めながら，αβ法と主要変化探索 (PVS) の探索量を比較する．
+//!
+//! 各局面で両方のエージェントに行動を選択させ，訪れたノード数と探索時間を局面ごとと合計で表示する．
+//! 行動の並び替えを行わないので，局面によってはPVSの探索量がαβ法より多くなることもある．
+//! `cargo bench --bench pvs`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::time::Duration;
+
+/// 盤面の幅と高さ．
+const SIZE: usize = 6;
+
+/// 各列の下から石を積み上げるゲームの状態．
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Stacks {
+    /// 各マスに置かれた石．`y * SIZE + x`番目が(x, y)のマスを表す．
+    cells: [Option<Actor>; SIZE * SIZE],
+    /// 各列に積まれた石の数．
+    heights: [usize; SIZE],
+}
+
+impl State for Stacks {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Push {
+    column: usize,
+    actor: Actor,
+}
+
+impl Action for Push {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct StacksRule;
+
+impl Rule for StacksRule {
+    type S = Stacks;
+    type A = Push;
+    type ActionIterator = std::vec::IntoIter<Push>;
+
+    fn terminal_kind(state: &Stacks) -> Option<TerminalKind> {
+        if state.heights.iter().all(|&height| height == SIZE) {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Stacks, actor: Actor) -> Self::ActionIterator {
+        (0..SIZE)
+            .filter(|&column| state.heights[column] < SIZE)
+            .map(|column| Push { column, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Stacks, action: &Push) -> Stacks {
+        let mut next_state = state.clone();
+        let height = next_state.heights[action.column];
+        next_state.cells[height * SIZE + action.column] = Some(action.actor);
+        next_state.heights[action.column] += 1;
+        next_state
+    }
+}
+
+/// 中央に近い列ほど，高い位置ほど価値の高いマスとして，占めたマスの価値の差で評価する．
+struct PositionEvaluator;
+
+impl Evaluator<Stacks> for PositionEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Stacks) -> i32 {
+        state
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|owner| (index, owner)))
+            .map(|(index, owner)| {
+                let (x, y) = (index % SIZE, index / SIZE);
+                let center_distance = (2 * x as i32 - (SIZE as i32 - 1)).abs();
+                let value = SIZE as i32 - center_distance + y as i32;
+                if owner == actor {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum()
+    }
+}
+
+fn main() {
+    let depth = 6u32;
+    let alpha_beta = AlphaBetaStrategy::<StacksRule, PositionEvaluator, _>::new(depth);
+    let pvs = PvsStrategy::<StacksRule, PositionEvaluator, _>::new(depth);
+
+    let mut state = Stacks {
+        cells: [None; SIZE * SIZE],
+        heights: [0; SIZE],
+    };
+    let mut actor = Actor::First;
+    let mut ply = 0;
+    let (mut alpha_beta_nodes, mut pvs_nodes) = (0, 0);
+    let (mut alpha_beta_elapsed, mut pvs_elapsed) = (Duration::default(), Duration::default());
+    let mut pvs_fewer = 0;
+    while StacksRule::terminal_kind(&state).is_none() {
+        let (action, alpha_beta_stats) = alpha_beta.select_action_with_stats(&state, actor);
+        let (_, pvs_stats) = pvs.select_action_with_stats(&state, actor);
+        println!(
+            "ply {}: alpha-beta {} nodes, pvs {} nodes",
+            ply, alpha_beta_stats.nodes_evaluated, pvs_stats.nodes_evaluated
+        );
+        alpha_beta_nodes += alpha_beta_stats.nodes_evaluated;
+        pvs_nodes += pvs_stats.nodes_evaluated;
+        alpha_beta_elapsed += alpha_beta_stats.elapsed;
+        pvs_elapsed += pvs_stats.elapsed;
+        if pvs_stats.nodes_evaluated < alpha_beta_stats.nodes_evaluated {
+            pvs_fewer += 1;
+        }
+
+        state = StacksRule::translate_state(&state, &action.unwrap());
+        actor = actor.opponent();
+        ply += 1;
+    }
+
+    println!(
+        "alpha-beta (depth {}): {} nodes, {:?}",
+        depth, alpha_beta_nodes, alpha_beta_elapsed
+    );
+    println!(
+        "pvs (depth {}): {} nodes, {:?}",
+        depth, pvs_nodes, pvs_elapsed
+    );
+    println!(
+        "node ratio {:.3}, pvs visited fewer nodes in {}/{} positions",
+        pvs_nodes as f64 / alpha_beta_nodes as f64,
+        pvs_fewer,
+        ply
+    );
+}
@@ -0,0 +1,135 @@
+//! 6x6の盤面を持つゲームを最後まで進めながら，置換表の置き換え方針ごとに探索量と置換表の使用状況を比較する．
+//!
+//! 1つのエージェントが両方のプレイヤーの行動を選択するので，置換表は行動選択をまたいで再利用される．
+//! 容量の小さい置換表では衝突が頻発するので，どの探索結果を残すかが探索量に表れる．
+//! `cargo bench --bench transposition`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::time::Instant;
+
+/// 盤面の幅と高さ．
+const SIZE: usize = 6;
+
+/// 各列の下から石を積み上げるゲームの状態．
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Stacks {
+    /// 各マスに置かれた石．`y * SIZE + x`番目が(x, y)のマスを表す．
+    cells: [Option<Actor>; SIZE * SIZE],
+    /// 各列に積まれた石の数．
+    heights: [usize; SIZE],
+}
+
+impl State for Stacks {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Push {
+    column: usize,
+    actor: Actor,
+}
+
+impl Action for Push {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct StacksRule;
+
+impl Rule for StacksRule {
+    type S = Stacks;
+    type A = Push;
+    type ActionIterator = std::vec::IntoIter<Push>;
+
+    fn terminal_kind(state: &Stacks) -> Option<TerminalKind> {
+        if state.heights.iter().all(|&height| height == SIZE) {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Stacks, actor: Actor) -> Self::ActionIterator {
+        (0..SIZE)
+            .filter(|&column| state.heights[column] < SIZE)
+            .map(|column| Push { column, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Stacks, action: &Push) -> Stacks {
+        let mut next_state = state.clone();
+        let height = next_state.heights[action.column];
+        next_state.cells[height * SIZE + action.column] = Some(action.actor);
+        next_state.heights[action.column] += 1;
+        next_state
+    }
+}
+
+/// 中央に近い列ほど，高い位置ほど価値の高いマスとして，占めたマスの価値の差で評価する．
+struct PositionEvaluator;
+
+impl Evaluator<Stacks> for PositionEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Stacks) -> i32 {
+        state
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|owner| (index, owner)))
+            .map(|(index, owner)| {
+                let (x, y) = (index % SIZE, index / SIZE);
+                let center_distance = (2 * x as i32 - (SIZE as i32 - 1)).abs();
+                let value = SIZE as i32 - center_distance + y as i32;
+                if owner == actor {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum()
+    }
+}
+
+fn main() {
+    let depth = 6u32;
+    let capacity = 1 << 10;
+    let schemes = [
+        ReplacementScheme::AlwaysReplace,
+        ReplacementScheme::DepthPreferred,
+        ReplacementScheme::TwoTier,
+    ];
+    for &scheme in schemes.iter() {
+        let table = TranspositionTable::new(capacity).with_replacement_scheme(scheme);
+        let strategy = AlphaBetaStrategy::<StacksRule, PositionEvaluator, _>::new(depth)
+            .with_custom_transposition_table(table);
+
+        let mut state = Stacks {
+            cells: [None; SIZE * SIZE],
+            heights: [0; SIZE],
+        };
+        let mut actor = Actor::First;
+        let mut nodes = 0;
+        let start = Instant::now();
+        while StacksRule::terminal_kind(&state).is_none() {
+            let (action, stats) = strategy.select_action_with_stats(&state, actor);
+            nodes += stats.nodes_evaluated;
+            state = StacksRule::translate_state(&state, &action.unwrap());
+            actor = actor.opponent();
+        }
+        let elapsed = start.elapsed();
+
+        let table_stats = strategy.transposition_table_stats().unwrap();
+        println!(
+            "{:?} (depth {}, capacity {}): {} nodes, {:?}, hit rate {:.3}, occupancy {:.3}",
+            scheme,
+            depth,
+            table_stats.capacity,
+            nodes,
+            elapsed,
+            table_stats.hit_rate(),
+            table_stats.occupancy()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! 8x8の盤面を持つゲームで，状態を複製するαβ法と，状態を書き換えて戻すαβ法の探索時間を比較する．
+//!
+//! 盤面はヒープ上に確保されるので，`Rule::translate_state`はノードごとに盤面を複製する．
+//! `UndoRule`を実装すると，探索中の複製は行動選択ごとに1回だけになる．
+//! `cargo bench --bench undo`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::time::{Duration, Instant};
+
+/// 盤面の幅と高さ．
+const SIZE: usize = 8;
+
+/// 各列の下から石を積み上げるゲームの状態．
+#[derive(Clone, PartialEq)]
+struct Stacks {
+    /// 各マスに置かれた石．`y * SIZE + x`番目が(x, y)のマスを表す．
+    cells: Vec<Option<Actor>>,
+    /// 各列に積まれた石の数．
+    heights: Vec<usize>,
+}
+
+impl State for Stacks {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Push {
+    column: usize,
+    actor: Actor,
+}
+
+impl Action for Push {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct StacksRule;
+
+impl Rule for StacksRule {
+    type S = Stacks;
+    type A = Push;
+    type ActionIterator = std::vec::IntoIter<Push>;
+
+    fn terminal_kind(state: &Stacks) -> Option<TerminalKind> {
+        if state.heights.iter().all(|&height| height == SIZE) {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Stacks, actor: Actor) -> Self::ActionIterator {
+        (0..SIZE)
+            .filter(|&column| state.heights[column] < SIZE)
+            .map(|column| Push { column, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Stacks, action: &Push) -> Stacks {
+        let mut next_state = state.clone();
+        StacksRule::apply_action(&mut next_state, action);
+        next_state
+    }
+}
+
+impl UndoRule for StacksRule {
+    /// 石を積んだ列．
+    type Undo = usize;
+
+    fn apply_action(state: &mut Stacks, action: &Push) -> usize {
+        let height = state.heights[action.column];
+        state.cells[height * SIZE + action.column] = Some(action.actor);
+        state.heights[action.column] += 1;
+        action.column
+    }
+
+    fn undo_action(state: &mut Stacks, column: usize) {
+        state.heights[column] -= 1;
+        let height = state.heights[column];
+        state.cells[height * SIZE + column] = None;
+    }
+}
+
+/// 中央に近い列ほど，高い位置ほど価値の高いマスとして，占めたマスの価値の差で評価する．
+struct PositionEvaluator;
+
+impl Evaluator<Stacks> for PositionEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Stacks) -> i32 {
+        state
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|owner| (index, owner)))
+            .map(|(index, owner)| {
+                let (x, y) = (index % SIZE, index / SIZE);
+                let center_distance = (2 * x as i32 - (SIZE as i32 - 1)).abs();
+                let value = SIZE as i32 - center_distance + y as i32;
+                if owner == actor {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum()
+    }
+}
+
+fn measure<F>(search: F) -> (Duration, Option<Push>)
+where
+    F: FnOnce() -> Option<Push>,
+{
+    let start = Instant::now();
+    let action = search();
+    (start.elapsed(), action)
+}
+
+fn main() {
+    let state = Stacks {
+        cells: vec![None; SIZE * SIZE],
+        heights: vec![0; SIZE],
+    };
+    for &depth in [6u32, 7, 8].iter() {
+        let translate = AlphaBetaStrategy::<StacksRule, PositionEvaluator, _>::new(depth);
+        let undo = UndoAlphaBetaStrategy::<StacksRule, PositionEvaluator, _>::new(depth);
+        let (translate_time, translate_action) =
+            measure(|| translate.select_action(&state, Actor::First));
+        let (undo_time, undo_action) = measure(|| undo.select_action(&state, Actor::First));
+        assert_eq!(translate_action, undo_action);
+        println!(
+            "depth {}: translate_state {:?}, apply/undo {:?}, speedup x{:.2}",
+            depth,
+            translate_time,
+            undo_time,
+            translate_time.as_secs_f64() / undo_time.as_secs_f64()
+        );
+    }
+}
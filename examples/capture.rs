@@ -0,0 +1,241 @@
+//! 1列に並んだ駒を取り合うゲームで，静止探索の有無による行動選択の違いを示す．
+//!
+//! 各プレイヤーの駒は，隣の空きマスへの移動か，攻撃範囲にある相手の駒を取ることができる．
+//! ルークは隣のマスを，ポーンは2つ離れたマスを攻撃する．
+//! 評価値は駒の価値の合計の差である．
+//!
+//! 初期局面では，先手のルークは守られたポーンを，先手のナイトは守られていないポーンを取れる．
+//! 深さ1の探索では，価値の大きい守られたポーンを取ってしまい，取り返されてルークを失う．
+//! 静止探索を行えば，取り返されることを読んで守られていないポーンを取る．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::fmt;
+
+const BOARD_SIZE: usize = 10;
+/// ゲームが終了するまでの手数．
+const GAME_LENGTH: u32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// 隣のマスを攻撃する．価値は9．
+    Rook,
+    /// 隣のマスを攻撃する．価値は5．
+    Knight,
+    /// 2つ離れたマスを攻撃する．価値は駒ごとに異なる．
+    Pawn(i32),
+}
+
+impl Kind {
+    fn value(&self) -> i32 {
+        match self {
+            Kind::Rook => 9,
+            Kind::Knight => 5,
+            Kind::Pawn(value) => *value,
+        }
+    }
+
+    fn reach(&self) -> usize {
+        match self {
+            Kind::Rook | Kind::Knight => 1,
+            Kind::Pawn(_) => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    kind: Kind,
+    owner: Actor,
+}
+
+#[derive(Clone)]
+struct Board {
+    cells: [Option<Piece>; BOARD_SIZE],
+    plies: u32,
+}
+
+impl Board {
+    fn initial() -> Self {
+        let piece = |kind, owner| Some(Piece { kind, owner });
+        let mut cells = [None; BOARD_SIZE];
+        cells[1] = piece(Kind::Rook, Actor::First);
+        cells[2] = piece(Kind::Pawn(2), Actor::Second);
+        cells[4] = piece(Kind::Pawn(3), Actor::Second);
+        cells[8] = piece(Kind::Knight, Actor::First);
+        cells[9] = piece(Kind::Pawn(1), Actor::Second);
+        Self { cells, plies: 0 }
+    }
+
+    fn material(&self, actor: Actor) -> i32 {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|piece| piece.owner == actor)
+            .map(|piece| piece.kind.value())
+            .sum()
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for cell in self.cells.iter() {
+            let symbol = match cell {
+                Some(piece) => {
+                    let symbol = match piece.kind {
+                        Kind::Rook => 'R',
+                        Kind::Knight => 'N',
+                        Kind::Pawn(_) => 'P',
+                    };
+                    match piece.owner {
+                        Actor::First => symbol,
+                        Actor::Second => symbol.to_ascii_lowercase(),
+                    }
+                }
+                None => '-',
+            };
+            write!(f, "{}", symbol)?;
+        }
+        Ok(())
+    }
+}
+
+impl State for Board {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    from: usize,
+    to: usize,
+    captures: bool,
+    actor: Actor,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.captures { 'x' } else { '-' };
+        write!(f, "{}{}{}", self.from, separator, self.to)
+    }
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct CaptureRule;
+
+impl CaptureRule {
+    /// 指定したプレイヤーが取れる行動のうち，駒を取る行動だけを列挙するか指定して列挙する．
+    fn moves(state: &Board, actor: Actor, captures_only: bool) -> Vec<Move> {
+        let mut moves = vec![];
+        for (from, cell) in state.cells.iter().enumerate() {
+            let piece = match cell {
+                Some(piece) if piece.owner == actor => piece,
+                _ => continue,
+            };
+            let targets = [
+                from.checked_sub(piece.kind.reach()),
+                Some(from + piece.kind.reach()),
+            ];
+            for &to in targets.iter().flatten().filter(|&&to| to < BOARD_SIZE) {
+                if matches!(state.cells[to], Some(target) if target.owner != actor) {
+                    moves.push(Move {
+                        from,
+                        to,
+                        captures: true,
+                        actor,
+                    });
+                }
+            }
+            if captures_only {
+                continue;
+            }
+            let neighbors = [from.checked_sub(1), Some(from + 1)];
+            for &to in neighbors.iter().flatten().filter(|&&to| to < BOARD_SIZE) {
+                if state.cells[to].is_none() {
+                    moves.push(Move {
+                        from,
+                        to,
+                        captures: false,
+                        actor,
+                    });
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl Rule for CaptureRule {
+    type S = Board;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        // 駒をすべて失ったプレイヤーの負け
+        if let Some(&loser) = Actor::ALL.iter().find(|&&actor| state.material(actor) == 0) {
+            return Some(TerminalKind::Win(loser.opponent()));
+        }
+        if state.plies >= GAME_LENGTH {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        Self::moves(state, actor, false).into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Move) -> Board {
+        let mut next = state.clone();
+        next.cells[action.to] = next.cells[action.from].take();
+        next.plies += 1;
+        next
+    }
+
+    fn is_loud_action(action: &Move) -> bool {
+        action.captures
+    }
+}
+
+impl QuiescenceRule for CaptureRule {
+    type NoisyActions = Vec<Move>;
+
+    fn iterate_noisy_actions(state: &Board, actor: Actor) -> Vec<Move> {
+        Self::moves(state, actor, true)
+    }
+}
+
+struct MaterialEvaluator;
+
+impl Evaluator<Board> for MaterialEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> i32 {
+        state.material(actor) - state.material(actor.opponent())
+    }
+}
+
+fn main() {
+    let board = Board::initial();
+    println!("{}", board);
+
+    let horizon = AlphaBetaStrategy::<CaptureRule, MaterialEvaluator, _>::new(1);
+    let quiescence =
+        AlphaBetaStrategy::<CaptureRule, MaterialEvaluator, _>::new(1).with_quiescence_search(4);
+
+    for (name, strategy) in [("depth 1", &horizon), ("depth 1 + quiescence", &quiescence)].iter() {
+        let action = strategy.select_action(&board, Actor::First).unwrap();
+        let next = CaptureRule::translate_state(&board, &action);
+        // 選んだ行動の後，相手が最善を尽くした場合の評価値を深く読んで確かめる
+        let outcome = -AlphaBetaStrategy::<CaptureRule, MaterialEvaluator, _>::new(4)
+            .root_payoff(&next, Actor::Second)
+            .unwrap();
+        println!(
+            "{}: {} -> {} (payoff by 4-ply search: {})",
+            name, action, next, outcome
+        );
+    }
+}
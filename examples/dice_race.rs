@@ -0,0 +1,194 @@
+//! サイコロを使うすごろくで，期待値ミニマックス法のエージェントと，常にサイコロを振るエージェントを対戦させる．
+//!
+//! 各プレイヤーは，1マス進むか，サイコロを振って出た目の数だけ進むかを選ぶ．
+//! ゴールにちょうど止まったプレイヤーの勝ちであり，ゴールを越えた分は戻る．
+//! サイコロを振れば速く進めるが，ゴールの手前では1マスずつ進んだ方が確実な場合がある．
+//! `cargo run --example dice_race`で実行する．
+extern crate minimax_strategy;
+extern crate rand;
+
+use minimax_strategy::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// ゴールの位置．
+const GOAL: u8 = 12;
+/// 対戦する回数．
+const GAMES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Track {
+    positions: [u8; 2],
+}
+
+impl State for Track {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// 1マス進む．
+    Walk,
+    /// サイコロを振り，出た目の数だけ進む．
+    Roll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    kind: Kind,
+    actor: Actor,
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+/// 指定したプレイヤーを指定したマス数だけ進めた状態を返す．ゴールを越えた分は戻る．
+fn advance(track: &Track, actor: Actor, steps: u8) -> Track {
+    let mut positions = track.positions;
+    let position = positions[actor.index()] + steps;
+    positions[actor.index()] = if position > GOAL {
+        2 * GOAL - position
+    } else {
+        position
+    };
+    Track { positions }
+}
+
+struct DiceRaceRule;
+
+impl Rule for DiceRaceRule {
+    type S = Track;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Track) -> Option<TerminalKind> {
+        Actor::ALL
+            .iter()
+            .find(|actor| state.positions[actor.index()] == GOAL)
+            .map(|&actor| TerminalKind::Win(actor))
+    }
+
+    fn iterate_available_actions(_state: &Track, actor: Actor) -> Self::ActionIterator {
+        vec![
+            Move {
+                kind: Kind::Walk,
+                actor,
+            },
+            Move {
+                kind: Kind::Roll,
+                actor,
+            },
+        ]
+        .into_iter()
+    }
+
+    /// サイコロを振る場合は，出た目の期待値に近い3マス進むものとする．
+    fn translate_state(state: &Track, action: &Move) -> Track {
+        match action.kind {
+            Kind::Walk => advance(state, action.actor, 1),
+            Kind::Roll => advance(state, action.actor, 3),
+        }
+    }
+}
+
+impl StochasticRule for DiceRaceRule {
+    fn chance_outcomes(state: &Track, action: &Move) -> Vec<(Track, Probability)> {
+        match action.kind {
+            Kind::Walk => vec![(advance(state, action.actor, 1), 1.0)],
+            Kind::Roll => (1..=6)
+                .map(|pips| (advance(state, action.actor, pips), 1.0 / 6.0))
+                .collect(),
+        }
+    }
+}
+
+impl OutcomeRule for DiceRaceRule {}
+
+/// 勝ちを1，負けを-1とし，それ以外は位置の差に応じて-0.5から0.5の値を返す．
+struct DiceRaceEvaluator;
+
+impl Evaluator<Track> for DiceRaceEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Track) -> f64 {
+        match DiceRaceRule::winner(state) {
+            Some(winner) if winner == actor => 1.0,
+            Some(_) => -1.0,
+            None => {
+                let mine = state.positions[actor.index()] as f64;
+                let theirs = state.positions[actor.opponent().index()] as f64;
+                (mine - theirs) / GOAL as f64 * 0.5
+            }
+        }
+    }
+}
+
+/// 常にサイコロを振るエージェント．
+struct AlwaysRoll;
+
+impl Strategy<Track, Move> for AlwaysRoll {
+    fn select_action(&self, _state: &Track, actor: Actor) -> Option<Move> {
+        Some(Move {
+            kind: Kind::Roll,
+            actor,
+        })
+    }
+}
+
+/// 確率に従って偶然事象の結果を1つ選ぶ．
+fn sample_outcome<G: Rng>(outcomes: Vec<(Track, Probability)>, rng: &mut G) -> Track {
+    let mut threshold = rng.gen::<f64>();
+    let last = outcomes.len() - 1;
+    for (index, (track, probability)) in outcomes.into_iter().enumerate() {
+        if threshold < probability || index == last {
+            return track;
+        }
+        threshold -= probability;
+    }
+    unreachable!("outcomes must not be empty")
+}
+
+/// 1回対戦し，勝者を返す．
+fn play<G: Rng>(
+    first: &dyn Strategy<Track, Move>,
+    second: &dyn Strategy<Track, Move>,
+    rng: &mut G,
+) -> Actor {
+    let mut track = Track { positions: [0, 0] };
+    let mut actor = Actor::First;
+    loop {
+        if let Some(winner) = DiceRaceRule::winner(&track) {
+            return winner;
+        }
+        let strategy = match actor {
+            Actor::First => first,
+            Actor::Second => second,
+        };
+        let action = strategy.select_action(&track, actor).unwrap();
+        track = sample_outcome(DiceRaceRule::chance_outcomes(&track, &action), rng);
+        actor = actor.opponent();
+    }
+}
+
+fn main() {
+    let expectimax = ExpectimaxStrategy::<DiceRaceRule, DiceRaceEvaluator, _>::new(4)
+        .with_payoff_bounds(-1.0, 1.0);
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut wins = 0;
+    for game in 0..GAMES {
+        // 先手と後手を交互に入れ替える
+        let won = if game % 2 == 0 {
+            play(&expectimax, &AlwaysRoll, &mut rng) == Actor::First
+        } else {
+            play(&AlwaysRoll, &expectimax, &mut rng) == Actor::Second
+        };
+        if won {
+            wins += 1;
+        }
+    }
+    println!(
+        "expectimax won {} of {} games against always rolling",
+        wins, GAMES
+    );
+}
@@ -0,0 +1,263 @@
+//! 五目並べで，脅威となる手を深く探索する延長方針の効果を示す．
+//!
+//! 初期局面では，先手は1手で縦と横の2つの三を同時に作れる (三三)．
+//! 後手はどちらか一方しか止められないので，先手は残った三を四に伸ばし，五を作って勝つ．
+//! 勝ちが確定するのは5手先なので，深さ3の探索では勝ちを読み切れない．
+//! 三や四を作る手を1手ずつ延長すれば，同じ深さ3の探索でも勝ちを読み切れる．
+//! 評価値には`f64`を用い，とりうる範囲を`PayoffBounds`で指定する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::fmt;
+
+const BOARD_SIZE: usize = 7;
+/// 勝ちに必要な，縦・横・斜めに連続する石の数．
+const WIN_LENGTH: usize = 5;
+/// 勝ちの評価値．
+const WIN_PAYOFF: f64 = 10000.0;
+/// 石が連続しうる4方向．
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Board {
+    cells: [[Option<Actor>; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Board {
+    fn initial() -> Self {
+        let mut cells = [[None; BOARD_SIZE]; BOARD_SIZE];
+        for &(x, y) in [(2, 3), (3, 3), (4, 1), (4, 2)].iter() {
+            cells[y][x] = Some(Actor::First);
+        }
+        for &(x, y) in [(1, 1), (5, 5), (6, 4)].iter() {
+            cells[y][x] = Some(Actor::Second);
+        }
+        Self { cells }
+    }
+
+    fn at(&self, x: isize, y: isize) -> Option<Option<Actor>> {
+        let size = BOARD_SIZE as isize;
+        if (0..size).contains(&x) && (0..size).contains(&y) {
+            Some(self.cells[y as usize][x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// 指定したマスから指定した方向に，指定したプレイヤーの石が続く数と，その先のマスが空いているかを返す．
+    fn run(&self, x: usize, y: usize, (dx, dy): (isize, isize), actor: Actor) -> (usize, bool) {
+        let (mut x, mut y) = (x as isize + dx, y as isize + dy);
+        let mut length = 0;
+        while self.at(x, y) == Some(Some(actor)) {
+            length += 1;
+            x += dx;
+            y += dy;
+        }
+        (length, self.at(x, y) == Some(None))
+    }
+
+    /// 指定したマスの石を含み，指定した方向に連続する石の数と，両端が空いている数を返す．
+    fn line_through(&self, x: usize, y: usize, direction: (isize, isize)) -> (usize, usize) {
+        let actor = match self.cells[y][x] {
+            Some(actor) => actor,
+            None => return (0, 0),
+        };
+        let (dx, dy) = direction;
+        let (forward, forward_open) = self.run(x, y, (dx, dy), actor);
+        let (backward, backward_open) = self.run(x, y, (-dx, -dy), actor);
+        let open_ends = forward_open as usize + backward_open as usize;
+        (1 + forward + backward, open_ends)
+    }
+
+    fn winner(&self) -> Option<Actor> {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                for &direction in DIRECTIONS.iter() {
+                    if self.line_through(x, y, direction).0 >= WIN_LENGTH {
+                        return self.cells[y][x];
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// 石が置かれたマスの周囲1マス以内にある，空いたマスを列挙する．
+    fn candidates(&self) -> Vec<(usize, usize)> {
+        let mut candidates = vec![];
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                if self.cells[y][x].is_some() {
+                    continue;
+                }
+                let has_neighbor = (-1..=1).any(|dy| {
+                    (-1..=1).any(|dx| {
+                        matches!(self.at(x as isize + dx, y as isize + dy), Some(Some(_)))
+                    })
+                });
+                if has_neighbor {
+                    candidates.push((x, y));
+                }
+            }
+        }
+        candidates
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.iter() {
+            for cell in row.iter() {
+                let symbol = match cell {
+                    Some(Actor::First) => 'X',
+                    Some(Actor::Second) => 'O',
+                    None => '.',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl State for Board {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stone {
+    x: usize,
+    y: usize,
+    actor: Actor,
+}
+
+impl fmt::Display for Stone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Action for Stone {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct GomokuRule;
+
+impl Rule for GomokuRule {
+    type S = Board;
+    type A = Stone;
+    type ActionIterator = std::vec::IntoIter<Stone>;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        match state.winner() {
+            Some(winner) => Some(TerminalKind::Win(winner)),
+            None if state.candidates().is_empty() => Some(TerminalKind::Draw),
+            None => None,
+        }
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        state
+            .candidates()
+            .into_iter()
+            .map(|(x, y)| Stone { x, y, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Stone) -> Board {
+        let mut next = state.clone();
+        next.cells[action.y][action.x] = Some(action.actor);
+        next
+    }
+}
+
+/// 5マスの窓のうち，1人の石だけを含むものを，石の数に応じて評価する評価関数．
+struct WindowEvaluator;
+
+impl WindowEvaluator {
+    fn window_score(state: &Board, actor: Actor) -> f64 {
+        let size = BOARD_SIZE as isize;
+        let mut score = 0.0;
+        for y in 0..size {
+            for x in 0..size {
+                for &(dx, dy) in DIRECTIONS.iter() {
+                    let cells = (0..WIN_LENGTH as isize)
+                        .map(|i| state.at(x + dx * i, y + dy * i))
+                        .collect::<Option<Vec<_>>>();
+                    let cells = match cells {
+                        Some(cells) => cells,
+                        None => continue,
+                    };
+                    if cells.iter().any(|&cell| cell == Some(actor.opponent())) {
+                        continue;
+                    }
+                    let stones = cells.iter().filter(|cell| cell.is_some()).count() as i32;
+                    score += 4f64.powi(stones) - 1.0;
+                }
+            }
+        }
+        score
+    }
+}
+
+impl Evaluator<Board> for WindowEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> f64 {
+        match state.winner() {
+            Some(winner) if winner == actor => WIN_PAYOFF,
+            Some(_) => -WIN_PAYOFF,
+            None => Self::window_score(state, actor) - Self::window_score(state, actor.opponent()),
+        }
+    }
+}
+
+/// 両端の空いた三，または四を作った手を1手延長する．
+fn extend_threats(state: &Board, stone: &Stone) -> u8 {
+    let is_threat = DIRECTIONS.iter().any(|&direction| {
+        let (length, open_ends) = state.line_through(stone.x, stone.y, direction);
+        (length == 3 && open_ends == 2) || (length == 4 && open_ends > 0)
+    });
+    is_threat as u8
+}
+
+fn main() {
+    let board = Board::initial();
+    println!("{}", board);
+
+    let strategy = || {
+        // 評価値は浮動小数点数なので，とりうる範囲を明示する
+        let bounds = PayoffBounds::new(f64::NEG_INFINITY, f64::INFINITY);
+        AlphaBetaStrategy::<GomokuRule, WindowEvaluator, _>::new_with_payoff_bounds(3, bounds)
+            .with_transposition_table(1 << 16)
+            .with_killer_moves()
+    };
+    let fixed = strategy();
+    let extended = strategy().with_extension_policy(extend_threats, 2);
+
+    for (name, strategy) in [
+        ("depth 3", &fixed),
+        ("depth 3 + threat extensions", &extended),
+    ]
+    .iter()
+    {
+        let (action, stats) = strategy.select_action_with_stats(&board, Actor::First);
+        let payoff = strategy.root_payoff(&board, Actor::First).unwrap();
+        let verdict = if payoff == WIN_PAYOFF {
+            "forced win"
+        } else {
+            "no forced win found"
+        };
+        println!(
+            "{}: {} ({}, payoff {}, {} nodes, {} extensions)",
+            name,
+            action.unwrap(),
+            verdict,
+            payoff,
+            stats.nodes_evaluated,
+            stats.extensions
+        );
+    }
+}
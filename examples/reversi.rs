@@ -8,13 +8,7 @@ use std::ops::Neg;
 
 const FIELD_SIZE: usize = 3;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum GameResult {
-    Win(Actor),
-    Draw,
-}
-
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 struct Board {
     occupancies: [[Option<Actor>; FIELD_SIZE]; FIELD_SIZE],
 }
@@ -34,7 +28,7 @@ impl Board {
         &mut self.occupancies[x][y]
     }
 
-    fn game_result(&self) -> Option<GameResult> {
+    fn terminal_kind(&self) -> Option<TerminalKind> {
         // 横方向に揃っているところがあるか
         'row_check: for row in 0..FIELD_SIZE {
             let start = self.at(0, row);
@@ -45,7 +39,7 @@ impl Board {
                             continue 'row_check;
                         }
                     }
-                    return Some(GameResult::Win(*actor));
+                    return Some(TerminalKind::Win(*actor));
                 }
                 None => continue,
             }
@@ -60,7 +54,7 @@ impl Board {
                             continue 'column_check;
                         }
                     }
-                    return Some(GameResult::Win(*actor));
+                    return Some(TerminalKind::Win(*actor));
                 }
                 None => continue,
             }
@@ -70,7 +64,7 @@ impl Board {
             let start = self.at(0, 0);
             if let Some(actor) = start {
                 if (0..FIELD_SIZE).map(|i| self.at(i, i)).all(|a| a == start) {
-                    return Some(GameResult::Win(*actor));
+                    return Some(TerminalKind::Win(*actor));
                 }
             }
         }
@@ -81,7 +75,7 @@ impl Board {
                     .map(|i| self.at(i, FIELD_SIZE - i - 1))
                     .all(|a| a == start)
                 {
-                    return Some(GameResult::Win(*actor));
+                    return Some(TerminalKind::Win(*actor));
                 }
             }
         }
@@ -94,7 +88,7 @@ impl Board {
             }
         }
         // すべてのマスが埋まっているが，揃っているものがないので，勝負は引き分け
-        Some(GameResult::Draw)
+        Some(TerminalKind::Draw)
     }
 }
 
@@ -109,7 +103,7 @@ impl fmt::Display for Board {
                 };
                 write!(f, "{} ", displayed_item)?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
@@ -117,6 +111,7 @@ impl fmt::Display for Board {
 
 impl State for Board {}
 
+#[derive(Clone)]
 struct Placement {
     x: usize,
     y: usize,
@@ -152,15 +147,15 @@ impl Rule for ReversiRule {
     type A = Placement;
     type ActionIterator = std::vec::IntoIter<Placement>;
 
-    fn is_game_over(state: &Self::S) -> bool {
-        state.game_result().is_some()
+    fn terminal_kind(state: &Self::S) -> Option<TerminalKind> {
+        state.terminal_kind()
     }
 
     fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
         let mut actions = vec![];
         for row in 0..FIELD_SIZE {
             for column in 0..FIELD_SIZE {
-                if let None = state.at(column, row) {
+                if state.at(column, row).is_none() {
                     let action = Placement::new(column, row, actor);
                     actions.push(action);
                 }
@@ -177,6 +172,37 @@ impl Rule for ReversiRule {
     }
 }
 
+impl OutcomeRule for ReversiRule {}
+
+/// Zobristハッシュの乱数表．各マスと各プレイヤーの組み合わせに1つずつ割り当てる．
+const ZOBRIST_KEYS: ZobristKeys<{ 2 * FIELD_SIZE * FIELD_SIZE }> = ZobristKeys::new(0);
+
+fn zobrist_index(x: usize, y: usize, actor: Actor) -> usize {
+    let player = match actor {
+        Actor::First => 0,
+        Actor::Second => 1,
+    };
+    (y * FIELD_SIZE + x) * 2 + player
+}
+
+impl ZobristHashable for ReversiRule {
+    fn zobrist_hash(state: &Board) -> u64 {
+        let mut hash = 0;
+        for y in 0..FIELD_SIZE {
+            for x in 0..FIELD_SIZE {
+                if let Some(actor) = state.at(x, y) {
+                    hash ^= ZOBRIST_KEYS.key(zobrist_index(x, y, *actor));
+                }
+            }
+        }
+        hash
+    }
+
+    fn update_hash(hash: u64, _state: &Board, action: &Placement) -> u64 {
+        hash ^ ZOBRIST_KEYS.key(zobrist_index(action.x, action.y, action.actor))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum BoardEvaluation {
     Lose,
@@ -215,35 +241,64 @@ struct BoardEvaluator;
 impl Evaluator<Board> for BoardEvaluator {
     type Payoff = BoardEvaluation;
     fn evaluate_payoff_for(actor: Actor, state: &Board) -> Self::Payoff {
-        match state.game_result() {
-            Some(GameResult::Win(a)) if a == actor => BoardEvaluation::Win,
-            Some(GameResult::Win(_)) => BoardEvaluation::Lose,
-            Some(GameResult::Draw) => BoardEvaluation::Equal,
-            _ => match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
+        match state.terminal_kind() {
+            Some(kind) => Self::evaluate_terminal_payoff_for(actor, state, kind),
+            None => match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
                 Some(a) if a == &actor => BoardEvaluation::OccupyCenterMass,
                 Some(_) => BoardEvaluation::OccupiedCenterMass,
                 None => BoardEvaluation::Equal,
             },
         }
     }
+
+    fn evaluate_terminal_payoff_for(
+        actor: Actor,
+        _state: &Board,
+        kind: TerminalKind,
+    ) -> Self::Payoff {
+        match kind {
+            TerminalKind::Win(a) if a == actor => BoardEvaluation::Win,
+            TerminalKind::Win(_) => BoardEvaluation::Lose,
+            TerminalKind::Draw | TerminalKind::NoMovesAvailable => BoardEvaluation::Equal,
+        }
+    }
 }
 
+/// `--mcts`を指定すると，後手はモンテカルロ木探索で思考する．
 fn main() {
     let consideration_depth = FIELD_SIZE * FIELD_SIZE;
+    // 置換表に記録する状態のハッシュ値は，Zobristハッシュで差分更新する
     let agent_strategy =
-        construct_alpha_beta_strategy::<ReversiRule, BoardEvaluator, _>(consideration_depth);
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(consideration_depth)
+            .with_zobrist_transposition_table(1 << 16);
+    let mcts_strategy = MctsStrategy::<ReversiRule, _>::new(10000);
+    let uses_mcts = std::env::args().any(|arg| arg == "--mcts");
     let mut board = Board::new();
     let mut current_actor = Actor::First;
 
-    while !ReversiRule::is_game_over(&board) {
+    while ReversiRule::terminal_kind(&board).is_none() {
         println!("{}", board);
         println!("{:?}'s action", current_actor);
-        if let Some(action) = agent_strategy.select_action(&board, current_actor) {
+        let action = if current_actor == Actor::Second && uses_mcts {
+            mcts_strategy.select_action(&board, current_actor)
+        } else {
+            // αβ法で思考する場合は，探索で見込まれる結果も表示する
+            agent_strategy
+                .select_action_with_payoff(&board, current_actor)
+                .map(|(action, payoff)| {
+                    println!("{:?} expects {:?}", current_actor, payoff);
+                    action
+                })
+        };
+        if let Some(action) = action {
             board = ReversiRule::translate_state(&board, &action);
         }
         current_actor = current_actor.opponent();
     }
 
     println!("{}", board);
-    println!("The result is {:?}", board.game_result().unwrap());
+    println!(
+        "The result is {:?}",
+        ReversiRule::terminal_kind(&board).unwrap()
+    );
 }
@@ -0,0 +1,180 @@
+//! 3人で遊ぶ4x4の三目並べで，Max^n法で思考するエージェント同士を対戦させる．
+//!
+//! 3人のプレイヤーが順に石を置き，縦，横，斜めのいずれかに自分の石を3つ並べたプレイヤーの勝ちである．
+//! 盤面が埋まった場合は引き分けとなる．
+//! `cargo run --example three_player`で実行する．
+extern crate minimax_strategy;
+
+use minimax_strategy::*;
+use std::fmt;
+
+const FIELD_SIZE: usize = 4;
+const PLAYER_COUNT: usize = 3;
+/// 各プレイヤーの石を表す文字．
+const MARKS: [char; PLAYER_COUNT] = ['X', 'O', '#'];
+
+#[derive(Clone, PartialEq)]
+struct Board {
+    occupancies: [[Option<MultiActor>; FIELD_SIZE]; FIELD_SIZE],
+}
+
+impl Board {
+    fn new() -> Self {
+        Self {
+            occupancies: [[None; FIELD_SIZE]; FIELD_SIZE],
+        }
+    }
+
+    /// 3つのマスが並ぶ列をすべて返す．
+    fn lines() -> Vec<[(usize, usize); 3]> {
+        let mut lines = vec![];
+        for a in 0..FIELD_SIZE {
+            for b in 0..FIELD_SIZE - 2 {
+                lines.push([(a, b), (a, b + 1), (a, b + 2)]);
+                lines.push([(b, a), (b + 1, a), (b + 2, a)]);
+            }
+        }
+        for x in 0..FIELD_SIZE - 2 {
+            for y in 0..FIELD_SIZE - 2 {
+                lines.push([(x, y), (x + 1, y + 1), (x + 2, y + 2)]);
+                lines.push([(x + 2, y), (x + 1, y + 1), (x, y + 2)]);
+            }
+        }
+        lines
+    }
+
+    /// 自分の石を3つ並べたプレイヤーを返す．
+    fn winner(&self) -> Option<MultiActor> {
+        Self::lines().into_iter().find_map(|line| {
+            let [first, second, third] = line.map(|(x, y)| self.occupancies[x][y]);
+            match first {
+                Some(actor) if second == first && third == first => Some(actor),
+                _ => None,
+            }
+        })
+    }
+
+    fn is_full(&self) -> bool {
+        self.occupancies.iter().flatten().all(Option::is_some)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..FIELD_SIZE {
+            for x in 0..FIELD_SIZE {
+                let mark = match self.occupancies[x][y] {
+                    Some(actor) => MARKS[actor.index()],
+                    None => '-',
+                };
+                write!(f, "{}", mark)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct Placement {
+    x: usize,
+    y: usize,
+    actor: MultiActor,
+}
+
+impl MultiAction for Placement {
+    fn actor(&self) -> MultiActor {
+        self.actor
+    }
+}
+
+struct ThreePlayerRule;
+
+impl MultiRule for ThreePlayerRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = std::vec::IntoIter<Placement>;
+
+    fn player_count() -> usize {
+        PLAYER_COUNT
+    }
+
+    fn is_game_over(state: &Board) -> bool {
+        state.winner().is_some() || state.is_full()
+    }
+
+    fn iterate_available_actions(state: &Board, actor: MultiActor) -> Self::ActionIterator {
+        let mut actions = vec![];
+        for x in 0..FIELD_SIZE {
+            for y in 0..FIELD_SIZE {
+                if state.occupancies[x][y].is_none() {
+                    actions.push(Placement { x, y, actor });
+                }
+            }
+        }
+        actions.into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        let mut next = state.clone();
+        next.occupancies[action.x][action.y] = Some(action.actor);
+        next
+    }
+}
+
+/// 勝者の利得を1，他のプレイヤーの利得を0とする．
+/// 勝敗が決まっていない場合は，まだ他人の石がなく自分の石がある列の数に応じて，合計1を分け合う．
+/// 利得はすべて0以上で合計が1以下なので，浅い枝刈りを行える．
+struct ShareEvaluator;
+
+impl MultiEvaluator<Board> for ShareEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoffs(state: &Board) -> Vec<f64> {
+        let mut payoffs = vec![0.0; PLAYER_COUNT];
+        if let Some(winner) = state.winner() {
+            payoffs[winner.index()] = 1.0;
+            return payoffs;
+        }
+        for line in Board::lines() {
+            let mut owners = line
+                .iter()
+                .filter_map(|&(x, y)| state.occupancies[x][y])
+                .collect::<Vec<_>>();
+            owners.dedup();
+            if let [owner] = owners[..] {
+                payoffs[owner.index()] += 1.0;
+            }
+        }
+        let total = payoffs.iter().sum::<f64>();
+        if total > 0.0 {
+            payoffs.iter_mut().for_each(|payoff| *payoff /= total);
+        } else {
+            payoffs.fill(1.0 / PLAYER_COUNT as f64);
+        }
+        payoffs
+    }
+}
+
+fn main() {
+    let strategy =
+        MaxNStrategy::<ThreePlayerRule, ShareEvaluator, _>::new(3).with_shallow_pruning(1.0);
+    let mut board = Board::new();
+    let mut actor = MultiActor(0);
+    while !ThreePlayerRule::is_game_over(&board) {
+        let action = strategy.select_action(&board, actor).unwrap();
+        board = ThreePlayerRule::translate_state(&board, &action);
+        println!(
+            "{} placed at ({}, {})",
+            MARKS[actor.index()],
+            action.x,
+            action.y
+        );
+        println!("{}", board);
+        actor = ThreePlayerRule::next_actor(&board, actor);
+    }
+    match board.winner() {
+        Some(winner) => println!("{} wins", MARKS[winner.index()]),
+        None => println!("draw"),
+    }
+}
@@ -0,0 +1,75 @@
+use crate::SearchStats;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// `AlphaBetaStrategy::analyze`で探索した，ある深さの探索の記録．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisDepth<P> {
+    /// 探索したゲーム木の深さ．
+    pub depth: u32,
+    /// この深さの探索で訪れたノードの数．
+    pub nodes_evaluated: u64,
+    /// この深さの探索で，根ノードの関心範囲とした評価値の下限 (α)．
+    pub alpha: P,
+    /// この深さの探索で，根ノードの関心範囲とした評価値の上限 (β)．
+    pub beta: P,
+    /// この深さで得られた，根ノードの評価値．
+    pub payoff: Option<P>,
+}
+
+/// `AlphaBetaStrategy::analyze`による探索の詳細な記録．
+///
+/// `Display`で，最善手順と各深さの探索の記録を人が読める形式で表示する．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisReport<A, P> {
+    /// 最も深い探索で得られた，根ノードの評価値．
+    /// 取れる行動がない場合も，根ノードの状態の評価値となる．
+    pub payoff: Option<P>,
+    /// 最も深い探索で得られた最善手順 (主要変化)．根ノードの状態から順にとる行動を並べる．
+    /// 置換表による枝刈りが起こった場合など，探索深さより短くなる場合がある．
+    pub principal_variation: Vec<A>,
+    /// 各深さの探索の記録．浅いものから順に並べる．
+    pub depths: Vec<AnalysisDepth<P>>,
+    /// すべての深さの探索を合わせた統計情報．
+    pub stats: SearchStats,
+}
+
+impl<A, P> AnalysisReport<A, P> {
+    /// 最善と判断された行動を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn best_action(&self) -> Option<&A> {
+        self.principal_variation.first()
+    }
+}
+
+impl<A, P> fmt::Display for AnalysisReport<A, P>
+where
+    A: fmt::Debug,
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "best action={:?} payoff={:?}",
+            self.best_action(),
+            self.payoff
+        )?;
+        write!(f, "pv:")?;
+        for action in &self.principal_variation {
+            write!(f, " {:?}", action)?;
+        }
+        writeln!(f)?;
+        for depth in &self.depths {
+            writeln!(
+                f,
+                "depth={} nodes={} window=[{:?}, {:?}] payoff={:?}",
+                depth.depth, depth.nodes_evaluated, depth.alpha, depth.beta, depth.payoff
+            )?;
+        }
+        write!(
+            f,
+            "nodes={} elapsed={:?}",
+            self.stats.nodes_evaluated, self.stats.elapsed
+        )
+    }
+}
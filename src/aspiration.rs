@@ -0,0 +1,94 @@
+use crate::iterative_deepening::{IterativeDeepeningResult, IterativeDeepeningStrategy};
+use crate::{
+    Action, Actor, AspirationWidening, Evaluator, Rule, ScoredAction, ScoredStrategy, SearchDepth,
+    State,
+};
+use num::{Bounded, CheckedAdd, CheckedSub};
+
+/// 反復深化の各深さを，直前の深さの評価値を中心とした狭い関心範囲で探索するエージェント．
+///
+/// 直前の深さの評価値を`e`として，関心範囲`[e - delta, e + delta]`で探索する．
+/// 評価値が関心範囲から外れた場合は，外れた側の幅`delta`を2倍にして探索し直す．
+/// 幅は評価値の最小値と最大値の範囲に収まるよう切り詰められる．
+/// 評価値が深さによってあまり変わらないゲームでは，枝刈りが増えて探索量が減る．
+pub struct AspirationStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各深さの探索に用いる反復深化のエージェント．
+    iterative_deepening: IterativeDeepeningStrategy<R, E, N>,
+}
+
+impl<R, E, N> AspirationStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    /// 指定した深さまで，アスピレーションウィンドウを用いた反復深化で探索するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            iterative_deepening: IterativeDeepeningStrategy::new(search_depth),
+        }
+    }
+}
+
+impl<R, E, N> AspirationStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 関心範囲の幅を指定する．
+    /// 指定しない場合は，各深さを関心範囲全体で探索する．
+    ///
+    /// 評価値の加減算が必要なので，評価値の型が`CheckedAdd`と`CheckedSub`を実装している場合にのみ利用できる．
+    pub fn with_aspiration_delta(self, delta: E::Payoff) -> Self
+    where
+        E::Payoff: CheckedAdd + CheckedSub + Bounded,
+    {
+        Self {
+            iterative_deepening: self
+                .iterative_deepening
+                .with_aspiration_window(delta, AspirationWidening::Double),
+        }
+    }
+}
+
+impl<S, A, R, E, N> AspirationStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    /// 探索深さを最大の深さまで増やしながらゲーム木を探索する．
+    /// 探索し直した回数は，統計情報の`aspiration_fail_highs`と`aspiration_fail_lows`で確認できる．
+    pub fn search(&self, state: &S, actor: Actor) -> IterativeDeepeningResult<A, E::Payoff, N> {
+        self.iterative_deepening.search(state, actor)
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.iterative_deepening.root_payoff(state, actor)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for AspirationStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.iterative_deepening.select_scored_action(state, actor)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, Rule, ScoredAction, ScoredStrategy, SearchDepth,
+    State,
+};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// 分岐数の多いゲームにおける行動を，ビームサーチで思考するエージェント．
+///
+/// 各深さで，ビームに残ったすべての状態の子ノードを展開し，評価値の高い`beam_width`個の状態だけを次の深さに残す．
+/// 各深さの状態は，その状態に至る行動をとったプレイヤーにとっての評価値で比べる．
+/// 探索深さに達した時点のビームのうち，根ノードで行動するプレイヤーにとって評価値が最も高い状態に至る行動を選ぶ．
+///
+/// 相手の応手をすべては調べないので，αβ法とは異なり最善の行動を選ぶとは限らないが，
+/// 探索量はビームの幅と分岐数に比例する程度に抑えられる．
+/// ゲームが終了した状態や取れる行動がない状態は，展開せずに次の深さのビームへ持ち越す．
+pub struct BeamSearchStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    /// 各深さで残す状態の数．
+    beam_width: usize,
+    _r: PhantomData<fn() -> R>,
+    _e: PhantomData<fn() -> E>,
+}
+
+impl<R, E, N> BeamSearchStrategy<R, E, N> {
+    /// ビームサーチで思考するエージェントを作る．
+    /// `search_depth`は探索する手数，`beam_width`は各深さで残す状態の数である．
+    /// # Panics
+    /// `beam_width`が0の場合
+    pub fn new(search_depth: N, beam_width: usize) -> Self {
+        assert!(beam_width > 0, "beam width must be positive");
+        Self {
+            search_depth,
+            beam_width,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<R, E, N> BeamSearchStrategy<R, E, N> {
+    /// 各深さで残す状態の数を返す．
+    pub fn beam_width(&self) -> usize {
+        self.beam_width
+    }
+}
+
+impl<R, E, N> Clone for BeamSearchStrategy<R, E, N>
+where
+    N: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            search_depth: self.search_depth.clone(),
+            beam_width: self.beam_width,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<R, E, N> Copy for BeamSearchStrategy<R, E, N> where N: Copy {}
+
+impl<R, E, N> fmt::Debug for BeamSearchStrategy<R, E, N>
+where
+    N: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BeamSearchStrategy")
+            .field("search_depth", &self.search_depth)
+            .field("beam_width", &self.beam_width)
+            .finish()
+    }
+}
+
+impl<R, E, N> PartialEq for BeamSearchStrategy<R, E, N>
+where
+    N: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.search_depth == other.search_depth && self.beam_width == other.beam_width
+    }
+}
+
+impl<R, E, N> Eq for BeamSearchStrategy<R, E, N> where N: Eq {}
+
+/// ビームに残った状態．
+struct BeamEntry<S> {
+    /// 根ノードで最初にとった行動の添字．
+    root_action: usize,
+    state: S,
+    /// この状態で行動するプレイヤー．
+    next_actor: Actor,
+    /// 根ノードからこの状態までの深さ．
+    ply: u32,
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for BeamSearchStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        if self.search_depth.is_zero() || R::terminal_kind(state).is_some() {
+            return None;
+        }
+        let mut root_actions = R::iterate_available_actions(state, actor).collect::<Vec<_>>();
+        let mut beam = root_actions
+            .iter()
+            .enumerate()
+            .map(|(root_action, action)| BeamEntry {
+                root_action,
+                state: R::translate_state(state, action),
+                next_actor: actor.opponent(),
+                ply: 1,
+            })
+            .collect::<Vec<_>>();
+        if beam.is_empty() {
+            return None;
+        }
+        prune::<R, E>(&mut beam, self.beam_width);
+
+        let mut depth = N::one();
+        while depth < self.search_depth {
+            let mut next_beam = Vec::new();
+            for entry in beam {
+                if R::terminal_kind(&entry.state).is_some() {
+                    next_beam.push(entry);
+                    continue;
+                }
+                let children = R::iterate_available_actions(&entry.state, entry.next_actor)
+                    .map(|action| BeamEntry {
+                        root_action: entry.root_action,
+                        state: R::translate_state(&entry.state, &action),
+                        next_actor: entry.next_actor.opponent(),
+                        ply: entry.ply + 1,
+                    })
+                    .collect::<Vec<_>>();
+                if children.is_empty() {
+                    next_beam.push(entry);
+                } else {
+                    next_beam.extend(children);
+                }
+            }
+            beam = next_beam;
+            depth = depth + N::one();
+            prune::<R, E>(&mut beam, self.beam_width);
+        }
+
+        // 根ノードで行動するプレイヤーにとって最も評価値の高い状態に至る行動を選ぶ．
+        // 評価値が等しい場合は，先に列挙された状態を選ぶ
+        let (payoff, _, root_action) = beam
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| {
+                let terminal = R::terminal_kind(&entry.state);
+                let payoff = evaluate_at_ply::<_, E>(actor, &entry.state, terminal, entry.ply);
+                (payoff, Reverse(position), entry.root_action)
+            })
+            .max_by_key(|&(payoff, position, _)| (payoff, position))?;
+        let action = root_actions.swap_remove(root_action);
+        Some(ScoredAction::new(action, payoff))
+    }
+}
+
+/// ビームに残った状態を，その状態に至る行動をとったプレイヤーにとっての評価値が高い順に`beam_width`個に絞る．
+/// 評価値が等しい状態は，先に列挙された順に残す．
+fn prune<R, E>(beam: &mut Vec<BeamEntry<R::S>>, beam_width: usize)
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Ord,
+{
+    if beam.len() <= beam_width {
+        return;
+    }
+    beam.sort_by_cached_key(|entry| {
+        let mover = entry.next_actor.opponent();
+        let terminal = R::terminal_kind(&entry.state);
+        Reverse(evaluate_at_ply::<_, E>(
+            mover,
+            &entry.state,
+            terminal,
+            entry.ply,
+        ))
+    });
+    beam.truncate(beam_width);
+}
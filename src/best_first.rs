@@ -0,0 +1,483 @@
+use crate::clock::Stopwatch;
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, Rule, ScoredAction, ScoredStrategy, SearchStats,
+    State,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Range;
+
+/// 2人零和ゲームにおける適切な行動を，最良優先ミニマックス探索で思考するエージェント．
+///
+/// ゲーム木全体をメモリ上に保持し，根ノードから評価値の最善の子ノードをたどって得られる末端ノード
+/// (主要変化の末端) を展開することを繰り返す．
+/// 展開したノードの子ノードはすべて静的に評価し，その評価値を根ノードまで逆伝播させる．
+/// 有望な手順だけが深く読まれるので，評価関数の計算が重く，探索するノード数を抑えたい場合に向いている．
+///
+/// ゲームが終了するまで展開し尽くした部分木は，評価値が確定しているのでそれ以上たどらない．
+/// 主要変化がそのような部分木に入る場合は，確定していない子ノードのうち最善のものをたどる．
+/// 保持するノードの数が上限に達するか，ゲーム木全体の評価値が確定した時点で展開をやめる．
+/// `with_eviction`を指定した場合は，上限に達した後も有望でない部分木を破棄しながら展開を続ける．
+pub struct BestFirstStrategy<R, E> {
+    /// 保持するノードの数の上限．
+    node_budget: usize,
+    /// 部分木を破棄しながら展開を続ける場合は，1回の探索で展開するノードの数の上限．
+    max_expansions: Option<usize>,
+    _r: PhantomData<fn() -> (R, E)>,
+}
+
+impl<R, E> BestFirstStrategy<R, E> {
+    /// 最良優先ミニマックス探索で思考するエージェントを作る．
+    /// `node_budget`は1回の行動選択でゲーム木に保持するノードの数の上限である．
+    pub fn new(node_budget: usize) -> Self {
+        Self {
+            node_budget,
+            max_expansions: None,
+            _r: PhantomData,
+        }
+    }
+}
+
+impl<R, E> BestFirstStrategy<R, E> {
+    /// 保持するノードの数の上限を返す．
+    pub fn node_budget(&self) -> usize {
+        self.node_budget
+    }
+
+    /// 保持するノードの数が上限に達した後も，有望でない部分木を破棄しながら探索を続けるようにする (SMA*に似た方法)．
+    /// 展開したノードの数が`max_expansions`に達した時点で探索をやめる．
+    ///
+    /// 子ノードがすべて未展開であるノードのうち，根ノードからの手順で最善でない子ノードを選んだ回数が最も多いものから順に，
+    /// 子ノードを破棄して未展開のノードに戻す．
+    /// 戻したノードは子ノードから逆伝播した評価値を保つので，再び展開すると同じ子ノードが作られる．
+    /// 根ノードの子ノードと，次に展開するノードに至る手順上のノードは破棄しない．
+    ///
+    /// 根ノードを除き，保持するノードの数は上限を超えない．
+    /// 上限内に子ノードを追加できない場合は，その時点で探索をやめる．
+    /// 破棄したノードの数と，同時に保持したノードの数の最大値は，統計情報の`nodes_evicted`と`peak_nodes_retained`で確認できる．
+    pub fn with_eviction(self, max_expansions: usize) -> Self {
+        Self {
+            max_expansions: Some(max_expansions),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E> BestFirstStrategy<R, E>
+where
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+{
+    /// 保持するノードの数の上限を，ノードが占めるバイト数で指定する．
+    /// 上限は`bytes`を1つのノードの大きさで割った数となる．
+    /// 状態や行動がヒープ上に確保する領域は数えない．
+    pub fn with_byte_budget(self, bytes: usize) -> Self {
+        Self {
+            node_budget: bytes / mem::size_of::<BestFirstNode<S, A, E::Payoff>>(),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E> BestFirstStrategy<R, E>
+where
+    S: State + Clone,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord,
+{
+    /// 指定した状態を根とするゲーム木を，ノード数の上限まで展開して返す．
+    /// 根ノードは上限にかかわらず展開するので，取れる行動があれば必ず選択できる．
+    pub fn search(&self, state: &S, actor: Actor) -> BestFirstTree<S, A, E::Payoff> {
+        let mut tree = BestFirstTree::new::<R, E>(state.clone(), actor);
+        let mut expansions = 0;
+        while !tree.nodes[0].resolved {
+            let mut leaf = tree.principal_leaf();
+            if leaf != 0 {
+                let exhausted = match self.max_expansions {
+                    Some(max_expansions) => expansions >= max_expansions,
+                    None => tree.nodes.len() >= self.node_budget,
+                };
+                if exhausted {
+                    break;
+                }
+            }
+            let children = tree.generate_children::<R, E>(leaf, actor);
+            if leaf != 0
+                && self.max_expansions.is_some()
+                && !tree.make_room(&mut leaf, children.len(), self.node_budget)
+            {
+                break;
+            }
+            tree.attach_children(leaf, children);
+            tree.back_up(leaf);
+            expansions += 1;
+        }
+        tree
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    /// 統計情報の`nodes_evaluated`は，静的に評価したノードの数 (保持したノードと破棄したノードの数の和) である．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let stopwatch = Stopwatch::start();
+        let tree = self.search(state, actor);
+        let stats = SearchStats {
+            nodes_evaluated: tree.len() as u64 + tree.nodes_evicted,
+            nodes_evicted: tree.nodes_evicted,
+            peak_nodes_retained: tree.peak_len as u64,
+            max_depth_reached: tree.nodes.iter().map(|node| node.depth).max().unwrap_or(0) as u32,
+            elapsed: stopwatch.elapsed(),
+            ..SearchStats::default()
+        };
+        (tree.into_scored_action(), stats)
+    }
+}
+
+impl<S, A, R, E> ScoredStrategy<S, A> for BestFirstStrategy<R, E>
+where
+    S: State + Clone,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.search(state, actor).into_scored_action()
+    }
+}
+
+/// 最良優先ミニマックス探索で構築したゲーム木．
+/// ノードは配列に格納され，添字0が根ノードとなる．
+#[derive(Debug, Clone)]
+pub struct BestFirstTree<S, A, P> {
+    nodes: Vec<BestFirstNode<S, A, P>>,
+    /// 破棄したノードの数．
+    nodes_evicted: u64,
+    /// 同時に保持したノードの数の最大値．
+    peak_len: usize,
+}
+
+/// 最良優先ミニマックス探索で構築したゲーム木のノード．
+#[derive(Debug, Clone)]
+pub struct BestFirstNode<S, A, P> {
+    /// 現在の状態．
+    pub state: S,
+    /// この状態に至る際に実行された行動．根ノードでは`None`となる．
+    pub cause_action: Option<A>,
+    /// この状態で行動するプレイヤー．
+    pub next_actor: Actor,
+    /// 根ノードで行動するプレイヤーにとっての評価値．
+    /// 展開済みのノードでは子ノードから逆伝播した値，未展開のノードでは静的な評価値である．
+    pub payoff: P,
+    /// 根ノードからの深さ．
+    pub depth: usize,
+    /// この状態でゲームが終了しているか．
+    pub terminal: bool,
+    /// このノードを根とする部分木を展開し尽くし，評価値が確定したか．
+    pub resolved: bool,
+    /// 親ノードの添字．根ノードでは`None`となる．
+    parent: Option<usize>,
+    /// 子ノードの添字の範囲．子ノードは連続して格納される．未展開のノードでは空となる．
+    children: Range<usize>,
+}
+
+impl<S, A, P> BestFirstTree<S, A, P> {
+    /// 根ノードを返す．
+    pub fn root(&self) -> &BestFirstNode<S, A, P> {
+        &self.nodes[0]
+    }
+
+    /// 保持しているノードの数を返す．
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 探索中に同時に保持したノードの数の最大値を返す．
+    pub fn peak_len(&self) -> usize {
+        self.peak_len
+    }
+
+    /// 探索中に破棄したノードの数を返す．
+    pub fn nodes_evicted(&self) -> u64 {
+        self.nodes_evicted
+    }
+
+    /// ノードを保持していないか．根ノードは常に存在するので，常に`false`を返す．
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 保持しているすべてのノードを，展開した順に列挙する．
+    pub fn iter_nodes(&self) -> impl Iterator<Item = &BestFirstNode<S, A, P>> {
+        self.nodes.iter()
+    }
+
+    /// 指定したノードの子ノードを列挙する．
+    pub fn children<'a>(
+        &'a self,
+        node: &'a BestFirstNode<S, A, P>,
+    ) -> impl Iterator<Item = &'a BestFirstNode<S, A, P>> {
+        node.children.clone().map(move |child| &self.nodes[child])
+    }
+
+    /// 根ノードから最善の子ノードをたどった手順 (主要変化) の各ノードを，根ノードから順に列挙する．
+    pub fn principal_variation(&self) -> impl Iterator<Item = &BestFirstNode<S, A, P>>
+    where
+        P: Copy + Ord,
+    {
+        let mut next = Some(0);
+        core::iter::from_fn(move || {
+            let index = next?;
+            next = self.best_child(index);
+            Some(&self.nodes[index])
+        })
+    }
+
+    /// 根ノードで最善と判断された行動とその評価値を取り出す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn into_scored_action(mut self) -> Option<ScoredAction<A, P>>
+    where
+        P: Copy + Ord,
+    {
+        let best_child = self.best_child(0)?;
+        let payoff = self.nodes[0].payoff;
+        let action = self.nodes.swap_remove(best_child).cause_action?;
+        Some(ScoredAction::new(action, payoff))
+    }
+
+    /// 指定したノードの子ノードのうち，そのノードで行動するプレイヤーにとって最善のものの添字を返す．
+    /// 評価値が等しい場合は，先に列挙された行動を優先する．
+    fn best_child(&self, parent: usize) -> Option<usize>
+    where
+        P: Copy + Ord,
+    {
+        let parent = &self.nodes[parent];
+        let is_own_turn = parent.next_actor == self.nodes[0].next_actor;
+        parent.children.clone().reduce(|best, child| {
+            let (best_payoff, payoff) = (self.nodes[best].payoff, self.nodes[child].payoff);
+            let improves = if is_own_turn {
+                payoff > best_payoff
+            } else {
+                payoff < best_payoff
+            };
+            if improves {
+                child
+            } else {
+                best
+            }
+        })
+    }
+
+    /// 評価値が確定していない部分木をたどった，主要変化の末端ノードの添字を返す．
+    /// 根ノードの評価値が確定していない場合にのみ呼び出す．
+    fn principal_leaf(&self) -> usize
+    where
+        P: Copy + Ord,
+    {
+        let mut current = 0;
+        while let Some(child) = self.best_unresolved_child(current) {
+            current = child;
+        }
+        current
+    }
+
+    /// 指定したノードの子ノードのうち，評価値が確定しておらず，
+    /// そのノードで行動するプレイヤーにとって最善のものの添字を返す．
+    fn best_unresolved_child(&self, parent: usize) -> Option<usize>
+    where
+        P: Copy + Ord,
+    {
+        let parent = &self.nodes[parent];
+        let is_own_turn = parent.next_actor == self.nodes[0].next_actor;
+        let unresolved = parent
+            .children
+            .clone()
+            .filter(|&child| !self.nodes[child].resolved);
+        if is_own_turn {
+            unresolved.max_by_key(|&child| (self.nodes[child].payoff, Reverse(child)))
+        } else {
+            unresolved.min_by_key(|&child| (self.nodes[child].payoff, child))
+        }
+    }
+}
+
+impl<S, A, P> BestFirstTree<S, A, P>
+where
+    A: Action,
+    P: Copy + Ord,
+{
+    fn new<R, E>(state: S, actor: Actor) -> Self
+    where
+        R: Rule<S = S, A = A>,
+        E: Evaluator<S, Payoff = P>,
+    {
+        let terminal = R::terminal_kind(&state);
+        let payoff = evaluate_at_ply::<_, E>(actor, &state, terminal, 0);
+        Self {
+            nodes: vec![BestFirstNode {
+                state,
+                cause_action: None,
+                next_actor: actor,
+                payoff,
+                depth: 0,
+                terminal: terminal.is_some(),
+                resolved: terminal.is_some(),
+                parent: None,
+                children: 0..0,
+            }],
+            nodes_evicted: 0,
+            peak_len: 1,
+        }
+    }
+
+    /// 指定したノードで取れるすべての行動について子ノードを作り，静的に評価する．
+    fn generate_children<R, E>(&self, parent: usize, actor: Actor) -> Vec<BestFirstNode<S, A, P>>
+    where
+        R: Rule<S = S, A = A>,
+        E: Evaluator<S, Payoff = P>,
+    {
+        let (next_actor, depth) = (self.nodes[parent].next_actor, self.nodes[parent].depth + 1);
+        R::iterate_available_actions(&self.nodes[parent].state, next_actor)
+            .map(|action| {
+                let state = R::translate_state(&self.nodes[parent].state, &action);
+                let terminal = R::terminal_kind(&state);
+                let payoff = evaluate_at_ply::<_, E>(actor, &state, terminal, depth as u32);
+                BestFirstNode {
+                    state,
+                    cause_action: Some(action),
+                    next_actor: next_actor.opponent(),
+                    payoff,
+                    depth,
+                    terminal: terminal.is_some(),
+                    resolved: terminal.is_some(),
+                    // 子ノードを追加するまでに親ノードの添字が変わりうるので，追加する際に設定する
+                    parent: None,
+                    children: 0..0,
+                }
+            })
+            .collect()
+    }
+
+    /// 指定したノードに，`generate_children`で作った子ノードを追加する．
+    /// 子ノードがなかった場合は，静的な評価値で確定したものとする．
+    fn attach_children(&mut self, parent: usize, children: Vec<BestFirstNode<S, A, P>>) {
+        debug_assert!(self.nodes[parent].children.is_empty());
+        let first_child = self.nodes.len();
+        self.nodes[parent].children = first_child..first_child + children.len();
+        self.nodes[parent].resolved = children.is_empty();
+        self.nodes
+            .extend(children.into_iter().map(|child| BestFirstNode {
+                parent: Some(parent),
+                ..child
+            }));
+        self.peak_len = self.peak_len.max(self.nodes.len());
+    }
+
+    /// 保持するノードの数が上限を超えずに`additional`個のノードを追加できるよう，有望でないノードの子ノードを破棄する．
+    /// 破棄によりノードの添字が変わるので，次に展開するノード`leaf`の添字を更新する．
+    /// 破棄できるノードがなく，追加できない場合は`false`を返す．
+    fn make_room(&mut self, leaf: &mut usize, additional: usize, node_budget: usize) -> bool {
+        while self.nodes.len() + additional > node_budget {
+            let victim = match self.least_promising_frontier(*leaf) {
+                Some(victim) => victim,
+                None => return false,
+            };
+            let removed = self.evict_children(victim);
+            if *leaf >= removed.end {
+                *leaf -= removed.len();
+            }
+        }
+        true
+    }
+
+    /// 子ノードがすべて未展開であるノードのうち，最も有望でないものの添字を返す．
+    /// 根ノードと，指定したノードに至る手順上のノードは除く．
+    fn least_promising_frontier(&self, leaf: usize) -> Option<usize> {
+        let mut protected = vec![false; self.nodes.len()];
+        let mut current = Some(leaf);
+        while let Some(index) = current {
+            protected[index] = true;
+            current = self.nodes[index].parent;
+        }
+        (1..self.nodes.len())
+            .filter(|&index| {
+                let children = &self.nodes[index].children;
+                !protected[index]
+                    && !children.is_empty()
+                    && children
+                        .clone()
+                        .all(|child| self.nodes[child].children.is_empty())
+            })
+            .max_by_key(|&index| (self.deviations(index), self.nodes[index].depth, index))
+    }
+
+    /// 根ノードから指定したノードまでの手順で，最善でない子ノードを選んだ回数を返す．
+    fn deviations(&self, index: usize) -> usize {
+        let mut count = 0;
+        let mut current = index;
+        while let Some(parent) = self.nodes[current].parent {
+            if self.best_child(parent) != Some(current) {
+                count += 1;
+            }
+            current = parent;
+        }
+        count
+    }
+
+    /// 指定したノードの子ノードを破棄し，未展開のノードに戻す．
+    /// 破棄したノードが格納されていた添字の範囲を返す．
+    fn evict_children(&mut self, parent: usize) -> Range<usize> {
+        let removed = mem::replace(&mut self.nodes[parent].children, 0..0);
+        self.nodes.drain(removed.clone());
+        // 後ろに格納されていたノードは，破棄した数だけ前に詰まる
+        let shift = removed.len();
+        for node in self.nodes.iter_mut() {
+            if let Some(parent) = node
+                .parent
+                .as_mut()
+                .filter(|parent| **parent >= removed.end)
+            {
+                *parent -= shift;
+            }
+            if node.children.start >= removed.end {
+                node.children = node.children.start - shift..node.children.end - shift;
+            }
+        }
+        self.nodes_evicted += shift as u64;
+        removed
+    }
+
+    /// 指定したノードから根ノードに向かって，子ノードの評価値と，評価値が確定したかを逆伝播させる．
+    /// どちらも変わらなくなった時点で打ち切る．
+    fn back_up(&mut self, from: usize) {
+        let mut current = Some(from);
+        while let Some(index) = current {
+            let best = match self.best_child(index) {
+                Some(best) => best,
+                None => return,
+            };
+            let payoff = self.nodes[best].payoff;
+            let resolved = self.nodes[index]
+                .children
+                .clone()
+                .all(|child| self.nodes[child].resolved);
+            let node = &mut self.nodes[index];
+            if index != from && node.payoff == payoff && node.resolved == resolved {
+                return;
+            }
+            node.payoff = payoff;
+            node.resolved = resolved;
+            current = node.parent;
+        }
+    }
+}
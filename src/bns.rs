@@ -0,0 +1,278 @@
+use crate::clock::Stopwatch;
+use crate::{
+    extended_depth, Action, Actor, AlphaBetaStrategy, Evaluator, GameTree, Rule, ScoredAction,
+    ScoredStrategy, SearchContext, SearchDepth, SearchLimits, SearchStats, State,
+    TranspositionStore, TranspositionTable,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use data_structure::Range;
+use num::{Bounded, Integer, One};
+
+/// 2人零和ゲームにおける適切な行動を，Best Node Search (BNS) で思考するエージェント (実験的)．
+///
+/// 根ノードの評価値が含まれる範囲`[lo, hi]`の中から区切りの値`t`を選び，
+/// 根ノードの各子ノードの評価値が`t`以上かどうかを，幅0の関心範囲`[t, t]`での探索で調べる．
+/// `t`以上の子ノードがあれば，根ノードの評価値は`t`以上なので，それらの子ノードだけを残して`lo`を`t`とする．
+/// なければ`hi`を`t - 1`とする．
+/// これを残った子ノードが1つになるか，範囲が1点になるまで繰り返し，残った子ノードを最善とする．
+/// 最善の子ノードは，最後に範囲`[lo, hi]`で探索して評価値を求める．
+///
+/// 区切りの値を求めるために，評価値の型は`Integer`を実装している必要がある．
+/// 同じ子ノードを繰り返し探索するので，`with_transposition_table`で置換表を用いると効率が良い．
+///
+/// 各回の探索はfail-softでなければならない．
+/// 探索結果は`t`以上か未満かの判定にだけ用いるが，最後の探索で真の評価値を得るためである．
+pub struct BnsStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各子ノードの探索に用いるαβ法のエージェント．
+    alpha_beta: AlphaBetaStrategy<R, E, N>,
+}
+
+impl<R, E, N> BnsStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    /// Best Node Searchで思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        AlphaBetaStrategy::new(search_depth).into()
+    }
+}
+
+impl<R, E, N> BnsStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定した数の状態を記録できる置換表を用いて探索するようにする．
+    /// 置換表は行動選択をまたいで再利用される．
+    /// # Panics
+    /// `capacity`が0の場合
+    #[cfg(feature = "std")]
+    pub fn with_transposition_table(self, capacity: usize) -> Self
+    where
+        R::S: Hash,
+    {
+        Self {
+            alpha_beta: self.alpha_beta.with_transposition_table(capacity),
+        }
+    }
+
+    /// 指定した置換表を用いて探索するようにする．
+    /// 状態のハッシュ値の計算方法を変更したい場合に使用する．
+    pub fn with_custom_transposition_table(
+        self,
+        table: TranspositionTable<R::S, E::Payoff, N>,
+    ) -> Self {
+        Self {
+            alpha_beta: self.alpha_beta.with_custom_transposition_table(table),
+        }
+    }
+}
+
+impl<R, E, N> From<AlphaBetaStrategy<R, E, N>> for BnsStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定したαβ法のエージェントと同じ設定で，根ノードの行動をBNSで選ぶようにする．
+    fn from(alpha_beta: AlphaBetaStrategy<R, E, N>) -> Self {
+        Self { alpha_beta }
+    }
+}
+
+impl<S, A, R, E, N> BnsStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Integer + Bounded,
+    N: SearchDepth,
+{
+    /// 最善の子ノードが1つに絞られるまで幅0の関心範囲での探索を繰り返し，
+    /// 最善の行動とその評価値，探索の統計情報を返す．
+    fn search(&self, state: &S, actor: Actor) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let search_depth = self.alpha_beta.search_depth;
+        if search_depth.is_zero() || R::terminal_kind(state).is_some() {
+            let stats = SearchStats {
+                nodes_evaluated: 1,
+                ..SearchStats::default()
+            };
+            return (None, stats);
+        }
+
+        self.alpha_beta.advance_transposition_generation();
+        // 置換表は探索の間ずっと使うので，最初にロックしておく
+        let mut table = self
+            .alpha_beta
+            .transposition_table
+            .as_ref()
+            .map(|table| table.lock());
+        let key = table.as_deref().map(|table| table.hash_state(state));
+        let mut history = self
+            .alpha_beta
+            .history
+            .as_ref()
+            .map(|history| history.lock());
+        let killers = self
+            .alpha_beta
+            .killer_moves
+            .map(|new_killers| new_killers());
+        let countermoves = self
+            .alpha_beta
+            .countermoves
+            .map(|new_countermoves| new_countermoves());
+        let mut context = SearchContext::new(
+            actor,
+            table.as_deref_mut(),
+            SearchLimits::default(),
+            history.as_deref_mut(),
+            killers,
+            countermoves,
+        );
+        // 根ノードも訪れたノードに含める
+        context.stats.nodes_evaluated = 1;
+
+        let mut candidates = self
+            .alpha_beta
+            .ordered_actions(state, actor, search_depth, None, None, &context)
+            .map(|(_, action)| self.alpha_beta.child_node(state, 0, key, 0, action))
+            .collect::<Vec<_>>();
+
+        // 根ノードの評価値は，常に[lo, hi]に含まれる
+        let mut lo = self.alpha_beta.payoff_bounds.min;
+        let mut hi = self.alpha_beta.payoff_bounds.max;
+        while candidates.len() > 1 && lo < hi {
+            let separation = separation_value(lo, hi);
+            let mut better = Vec::new();
+            let mut worse = Vec::new();
+            for mut child in candidates {
+                let payoff =
+                    self.search_child(&mut child, Range::new(separation, separation), &mut context);
+                context.stats.zero_window_searches += 1;
+                // 評価値が区切りの値に一致する場合も，その子ノードの真の評価値は区切りの値以上である
+                if payoff.is_some_and(|payoff| payoff >= separation) {
+                    better.push(child);
+                } else {
+                    worse.push(child);
+                }
+            }
+            if better.is_empty() {
+                hi = separation - E::Payoff::one();
+                candidates = worse;
+            } else {
+                lo = separation;
+                candidates = better;
+            }
+        }
+
+        // 残った子ノードの評価値は[lo, hi]に含まれるので，この範囲で探索すれば真の評価値が得られる．
+        // 複数の子ノードが残った場合，それらの評価値はすべて等しいので，先に並べられたものを選ぶ
+        let scored = candidates.into_iter().next().and_then(|mut child| {
+            let payoff = self.search_child(&mut child, Range::new(lo, hi), &mut context)?;
+            let action = child.into_inner().cause_action?;
+            Some(ScoredAction::new(action, payoff))
+        });
+        (scored, context.stats)
+    }
+
+    /// 根ノードの子ノードを，指定した関心範囲で探索し直す．
+    fn search_child<T>(
+        &self,
+        child: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        // 前回の探索結果を捨てる．置換表に記録された結果は残るので，探索し直す手間は小さい
+        child.payoff = None;
+        child.clear_child();
+        context.ply = 1;
+        let extension = self.alpha_beta.child_extension(false, child, context);
+        context.line_extension = extension;
+        let payoff = self.alpha_beta.construct_best_game_tree_alpha_beta(
+            extended_depth(self.alpha_beta.search_depth - N::one(), extension),
+            child,
+            payoff_range,
+            context,
+        );
+        context.ply = 0;
+        context.line_extension = 0;
+        payoff
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        let search_depth = self.alpha_beta.search_depth;
+        let terminal = R::terminal_kind(state);
+        if search_depth.is_zero() || terminal.is_some() {
+            return Some(self.alpha_beta.evaluate_node(actor, state, terminal, 0));
+        }
+        self.search(state, actor).0.map(|scored| scored.payoff)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    /// 統計情報の`zero_window_searches`は，子ノードを幅0の関心範囲で探索した回数である．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    /// 統計情報の`zero_window_searches`は，子ノードを幅0の関心範囲で探索した回数である．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let stopwatch = Stopwatch::start();
+        let (scored, mut stats) = self.search(state, actor);
+        stats.elapsed = stopwatch.elapsed();
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for BnsStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Integer + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
+
+/// 範囲`[lo, hi]`を2つに分ける区切りの値`t`を返す．`lo < t <= hi`を満たす．
+/// # Panics
+/// `lo >= hi`の場合 (デバッグビルドのみ)
+fn separation_value<P>(lo: P, hi: P) -> P
+where
+    P: Copy + Integer,
+{
+    debug_assert!(lo < hi);
+    // (lo + hi) / 2 を，桁あふれしないように求める
+    let two = P::one() + P::one();
+    let middle = lo / two + hi / two + (lo % two + hi % two) / two;
+    // 中央の値がloに一致する場合も，区切りの値がloを超えるようにする
+    if middle > lo {
+        middle
+    } else {
+        middle + P::one()
+    }
+}
@@ -0,0 +1,137 @@
+use crate::BoundType;
+use data_structure::Range;
+use num::Bounded;
+
+/// 評価値がとりうる範囲．
+///
+/// αβ法の根ノードは，この範囲を関心範囲として探索する．
+/// `Bounded`を実装しない評価値の型でも，範囲を明示すれば探索できる．
+/// 浮動小数点型では，`f64::NEG_INFINITY`と`f64::INFINITY`などを指定する．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoffBounds<P> {
+    /// 評価値の最小値．
+    pub min: P,
+    /// 評価値の最大値．
+    pub max: P,
+}
+
+impl<P> PayoffBounds<P>
+where
+    P: PartialOrd,
+{
+    /// 指定した最小値と最大値の間を，評価値がとりうる範囲とする．
+    /// # Panics
+    /// `min <= max`でない場合
+    pub fn new(min: P, max: P) -> Self {
+        assert!(min <= max, "payoff bounds must not be empty");
+        Self { min, max }
+    }
+}
+
+impl<P> PayoffBounds<P>
+where
+    P: Copy + PartialOrd,
+{
+    /// 評価値がとりうる範囲全体を関心範囲として返す．
+    pub(crate) fn full_range(&self) -> Range<P> {
+        Range::new(self.min, self.max)
+    }
+}
+
+impl<P> Default for PayoffBounds<P>
+where
+    P: Bounded,
+{
+    /// 評価値の型の最小値から最大値までを返す．
+    fn default() -> Self {
+        Self {
+            min: P::min_value(),
+            max: P::max_value(),
+        }
+    }
+}
+
+/// 関心範囲を指定した探索で得られた評価値と，それが真の評価値に対してどのような値であるか．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScoreBound<P> {
+    /// 真の評価値そのもの．
+    Exact(P),
+    /// 真の評価値の下界．探索結果が関心範囲を上回った場合に得られる．
+    Lower(P),
+    /// 真の評価値の上界．探索結果が関心範囲を下回った場合に得られる．
+    Upper(P),
+}
+
+impl<P> ScoreBound<P> {
+    /// 評価値を返す．
+    pub fn value(self) -> P {
+        match self {
+            ScoreBound::Exact(payoff) | ScoreBound::Lower(payoff) | ScoreBound::Upper(payoff) => {
+                payoff
+            }
+        }
+    }
+
+    /// 評価値が真の評価値そのものか．
+    pub fn is_exact(&self) -> bool {
+        matches!(self, ScoreBound::Exact(_))
+    }
+
+    /// 置換表に記録する際の評価値の種類を返す．
+    pub fn bound_type(&self) -> BoundType {
+        match self {
+            ScoreBound::Exact(_) => BoundType::Exact,
+            ScoreBound::Lower(_) => BoundType::LowerBound,
+            ScoreBound::Upper(_) => BoundType::UpperBound,
+        }
+    }
+}
+
+impl<P> ScoreBound<P>
+where
+    P: Copy + PartialOrd,
+{
+    /// 指定した関心範囲で探索して得られた評価値が，真の評価値に対してどのような値であるかを判定する．
+    /// 関心範囲の外にある値は真の評価値の上界または下界でしかなく，関心範囲の端に一致する値は真の評価値そのものである．
+    pub(crate) fn classify(payoff: P, payoff_range: Range<P>) -> Self {
+        if payoff < payoff_range.min {
+            ScoreBound::Upper(payoff)
+        } else if payoff > payoff_range.max {
+            ScoreBound::Lower(payoff)
+        } else {
+            ScoreBound::Exact(payoff)
+        }
+    }
+
+    /// 指定した方式に従い，関心範囲の外にある評価値を扱う．
+    pub(crate) fn with_window_mode(self, mode: WindowMode, payoff_range: Range<P>) -> Self {
+        match (mode, self) {
+            (WindowMode::FailHard, ScoreBound::Lower(_)) => ScoreBound::Lower(payoff_range.max),
+            (WindowMode::FailHard, ScoreBound::Upper(_)) => ScoreBound::Upper(payoff_range.min),
+            (_, bound) => bound,
+        }
+    }
+}
+
+/// 関心範囲の外にある評価値の扱い方．
+///
+/// `AlphaBetaStrategy`の内部の探索は常にfail-softで行われる．
+/// この方式は，関心範囲を指定した探索の結果を呼び出し元に返す際の扱いだけを決める．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowMode {
+    /// 関心範囲の外にある評価値を，そのまま返す (fail-soft)．
+    /// 真の評価値の上界・下界として，関心範囲の端より厳しい値が得られる．
+    FailSoft,
+    /// 関心範囲の外にある評価値を，関心範囲の端に切り詰めて返す (fail-hard)．
+    FailHard,
+}
+
+impl Default for WindowMode {
+    /// `AlphaBetaStrategy`の内部の探索と同じく，`FailSoft`を返す．
+    fn default() -> Self {
+        WindowMode::FailSoft
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    AlphaBetaStrategy, Evaluator, MemoryBudget, MoveOrderer, MoveOrdering, PayoffBounds, Rule,
+};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+
+/// `AlphaBetaStrategy`の設定を順に指定するビルダー．
+/// `AlphaBetaStrategy::builder`で作る．
+///
+/// ここにない設定は，`build`で作ったエージェントに`with_`で始まるメソッドで指定できる．
+/// 探索の統計情報は設定によらず`AlphaBetaStrategy::select_action_with_stats`で得られる．
+pub struct AlphaBetaStrategyBuilder<R, E, N, O = crate::NoOrdering>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定された設定を持つエージェント．
+    strategy: AlphaBetaStrategy<R, E, N, O>,
+}
+
+impl<R, E, N, O> AlphaBetaStrategyBuilder<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    pub(crate) fn new(strategy: AlphaBetaStrategy<R, E, N, O>) -> Self {
+        Self { strategy }
+    }
+
+    /// 探索するゲーム木の深さを指定する．
+    pub fn search_depth(mut self, search_depth: N) -> Self {
+        self.strategy.search_depth = search_depth;
+        self
+    }
+
+    /// 評価値がとりうる範囲を指定する．
+    /// `AlphaBetaStrategy::with_payoff_bounds`と同じである．
+    pub fn payoff_bounds(self, payoff_bounds: PayoffBounds<E::Payoff>) -> Self {
+        Self::new(self.strategy.with_payoff_bounds(payoff_bounds))
+    }
+
+    /// 指定した数の状態を記録できる置換表を用いて探索するようにする．
+    /// `AlphaBetaStrategy::with_transposition_table`と同じである．
+    /// # Panics
+    /// `capacity`が0の場合
+    #[cfg(feature = "std")]
+    pub fn transposition_table(self, capacity: usize) -> Self
+    where
+        R::S: Hash,
+    {
+        Self::new(self.strategy.with_transposition_table(capacity))
+    }
+
+    /// 各ノードで，`MoveOrdering`を実装する型`M`で並べ替えた順に行動を調べるようにする．
+    /// `AlphaBetaStrategy::with_move_ordering`と同じである．
+    pub fn move_ordering<M>(self) -> AlphaBetaStrategyBuilder<R, E, N, M>
+    where
+        M: MoveOrdering<R::S, R::A>,
+    {
+        AlphaBetaStrategyBuilder::new(self.strategy.with_move_ordering())
+    }
+
+    /// 各ノードで，指定した方法で並べ替えた順に行動を調べるようにする．
+    /// `AlphaBetaStrategy::with_move_orderer`と同じである．
+    pub fn move_orderer<M>(self, move_orderer: M) -> Self
+    where
+        M: MoveOrderer<R::S, R::A> + Send + Sync + 'static,
+    {
+        Self::new(self.strategy.with_move_orderer(move_orderer))
+    }
+
+    /// 1回の行動選択で訪れるノード数の上限を指定する．
+    /// `AlphaBetaStrategy::with_node_budget`と同じである．
+    pub fn node_budget(self, node_budget: u64) -> Self {
+        Self::new(self.strategy.with_node_budget(node_budget))
+    }
+
+    /// 置換表と各表が使うメモリの合計の上限を指定する．
+    /// `AlphaBetaStrategy::with_memory_budget`と同じである．
+    pub fn memory_budget(self, memory_budget: MemoryBudget) -> Self {
+        Self::new(self.strategy.with_memory_budget(memory_budget))
+    }
+
+    /// 指定された設定を持つエージェントを作る．
+    pub fn build(self) -> AlphaBetaStrategy<R, E, N, O> {
+        self.strategy
+    }
+}
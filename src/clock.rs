@@ -0,0 +1,54 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// 探索を打ち切る時刻．
+#[cfg(feature = "std")]
+pub(crate) type Deadline = Instant;
+
+/// 探索を打ち切る時刻．
+/// 時刻を取得できない環境では値を持たないので，探索は打ち切られない．
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Deadline {}
+
+/// 指定した時刻を過ぎたか．
+#[cfg(feature = "std")]
+pub(crate) fn is_past(deadline: Deadline) -> bool {
+    Instant::now() >= deadline
+}
+
+/// 指定した時刻を過ぎたか．
+#[cfg(not(feature = "std"))]
+pub(crate) fn is_past(deadline: Deadline) -> bool {
+    match deadline {}
+}
+
+/// 探索にかかった時間を計測する．
+/// 時刻を取得できない環境では，常に0を返す．
+pub(crate) struct Stopwatch {
+    #[cfg(feature = "std")]
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// 現在の時刻から計測を始める．
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            start: Instant::now(),
+        }
+    }
+
+    /// 計測を始めてからの経過時間を返す．
+    #[cfg(feature = "std")]
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// 計測を始めてからの経過時間を返す．
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
@@ -0,0 +1,36 @@
+/// クロージャのような記法で，`Evaluator`を実装する型を宣言する．
+///
+/// `Evaluator`は評価関数を型に結びつけ，探索中は値を持たずに呼び出すので，クロージャの値をそのまま評価関数として渡すことはできない．
+/// 代わりにこのマクロで，指定した式を`evaluate_payoff_for`とするユニット構造体を宣言する．
+/// 評価関数を試しに書き換えるときなどに，実装を1か所にまとめて書ける．
+/// 式から外側の変数を参照することはできない．
+///
+/// # Examples
+/// ```
+/// use minimax_strategy::{closure_evaluator, Actor, Evaluator};
+///
+/// closure_evaluator!(struct CountEvaluator: u32 => i64 = |actor, state| {
+///     let count = *state as i64;
+///     match actor {
+///         Actor::First => count,
+///         Actor::Second => -count,
+///     }
+/// });
+///
+/// assert_eq!(3, CountEvaluator::evaluate_payoff_for(Actor::First, &3));
+/// assert_eq!(-3, CountEvaluator::evaluate_payoff_for(Actor::Second, &3));
+/// ```
+#[macro_export]
+macro_rules! closure_evaluator {
+    ($vis:vis struct $name:ident: $state:ty => $payoff:ty = |$actor:pat, $state_pattern:pat| $body:expr) => {
+        $vis struct $name;
+
+        impl $crate::Evaluator<$state> for $name {
+            type Payoff = $payoff;
+
+            fn evaluate_payoff_for($actor: $crate::Actor, $state_pattern: &$state) -> $payoff {
+                $body
+            }
+        }
+    };
+}
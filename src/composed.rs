@@ -0,0 +1,59 @@
+use crate::{Actor, ScoredAction, ScoredStrategy};
+
+/// 状態に応じて，2つのエージェントを切り替えて行動を選択するエージェント．
+///
+/// `predicate(state, actor)`が`true`を返す場合は`primary`が，`false`を返す場合は`fallback`が行動を選択する．
+/// 序盤は定跡に従い，それ以降はαβ法で探索する場合などに用いる．
+/// `fallback`に別の`ComposedStrategy`を指定すると，3つ以上のエージェントを切り替えられる．
+///
+/// 選択した行動の評価値は，行動を選択したエージェントが返したものである．
+/// そのため，2つのエージェントの評価値の型は一致している必要がある．
+pub struct ComposedStrategy<P, S1, S2> {
+    /// `primary`に行動を選択させるかを判定する関数．
+    predicate: P,
+    /// 判定が`true`の場合に行動を選択するエージェント．
+    primary: S1,
+    /// 判定が`false`の場合に行動を選択するエージェント．
+    fallback: S2,
+}
+
+impl<P, S1, S2> ComposedStrategy<P, S1, S2> {
+    /// 指定した判定関数で，2つのエージェントを切り替えるエージェントを作る．
+    pub fn new<S>(predicate: P, primary: S1, fallback: S2) -> Self
+    where
+        P: Fn(&S, Actor) -> bool,
+    {
+        Self {
+            predicate,
+            primary,
+            fallback,
+        }
+    }
+
+    /// 判定が`true`の場合に行動を選択するエージェントを返す．
+    pub fn primary(&self) -> &S1 {
+        &self.primary
+    }
+
+    /// 判定が`false`の場合に行動を選択するエージェントを返す．
+    pub fn fallback(&self) -> &S2 {
+        &self.fallback
+    }
+}
+
+impl<S, A, P, S1, S2> ScoredStrategy<S, A> for ComposedStrategy<P, S1, S2>
+where
+    P: Fn(&S, Actor) -> bool,
+    S1: ScoredStrategy<S, A>,
+    S2: ScoredStrategy<S, A, Payoff = S1::Payoff>,
+{
+    type Payoff = S1::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, S1::Payoff>> {
+        if (self.predicate)(state, actor) {
+            self.primary.select_scored_action(state, actor)
+        } else {
+            self.fallback.select_scored_action(state, actor)
+        }
+    }
+}
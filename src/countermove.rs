@@ -0,0 +1,90 @@
+use crate::HistoryKey;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+/// 相手の直前の行動ごとに，それに対して枝刈りを起こした応手 (カウンター手) を記録する表．
+///
+/// 多くのゲームでは，有効な応手は局面全体よりも相手の直前の行動で決まる．
+/// 相手の行動を`HistoryKey::history_key`で番号に変換し，番号ごとに最近枝刈りを起こした応手を1つ記録する．
+pub(crate) struct CountermoveTable<A> {
+    /// 相手の行動の番号ごとのカウンター手．
+    replies: Vec<Option<A>>,
+    /// 行動の番号を計算する関数．
+    /// 行動の型に制約を課さずに探索に組み込めるよう，作成時に受け取っておく．
+    key: fn(&A) -> usize,
+    /// 行動が等しいか判定する関数．
+    equals: fn(&A, &A) -> bool,
+    /// 行動を複製する関数．
+    clone: fn(&A) -> A,
+}
+
+impl<A> CountermoveTable<A> {
+    pub(crate) fn new() -> Self
+    where
+        A: HistoryKey + PartialEq + Clone,
+    {
+        Self {
+            replies: vec![],
+            key: A::history_key,
+            equals: A::eq,
+            clone: A::clone,
+        }
+    }
+
+    /// 指定した行動が，相手の指定した行動に対するカウンター手か．
+    pub(crate) fn is_countermove(&self, previous_action: &A, action: &A) -> bool {
+        self.replies
+            .get((self.key)(previous_action))
+            .and_then(Option::as_ref)
+            .is_some_and(|reply| (self.equals)(reply, action))
+    }
+
+    /// 相手の指定した行動に対して，指定した行動が枝刈りを起こしたことを記録する．
+    pub(crate) fn record_cutoff(&mut self, previous_action: &A, action: &A) {
+        let key = (self.key)(previous_action);
+        if self.replies.len() <= key {
+            self.replies.resize_with(key + 1, || None);
+        }
+        self.replies[key] = Some((self.clone)(action));
+    }
+
+    /// 記録したカウンター手をすべて消去し，確保していたメモリを解放する．
+    pub(crate) fn clear(&mut self) {
+        self.replies = vec![];
+    }
+
+    /// カウンター手を記録するために確保しているメモリのバイト数を返す．
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.replies.capacity() * mem::size_of::<Option<A>>()
+    }
+}
+
+/// 探索ごとに空の表を作る関数．
+pub(crate) type CountermoveFactory<A> = fn() -> CountermoveTable<A>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl HistoryKey for u8 {
+        fn history_key(&self) -> usize {
+            *self as usize
+        }
+    }
+
+    #[test]
+    fn test_keeps_latest_reply() {
+        let mut table = CountermoveTable::<u8>::new();
+        assert!(!table.is_countermove(&3, &1));
+
+        table.record_cutoff(&3, &1);
+        assert!(table.is_countermove(&3, &1));
+        assert!(!table.is_countermove(&3, &2));
+        assert!(!table.is_countermove(&0, &1));
+
+        table.record_cutoff(&3, &2);
+        assert!(!table.is_countermove(&3, &1));
+        assert!(table.is_countermove(&3, &2));
+    }
+}
@@ -1,8 +1,48 @@
+/// 借用した値か，所有する値のどちらかを保持する．
+/// `std::borrow::Cow`と異なり，`ToOwned`を実装していない型も保持できる．
+#[derive(Debug)]
 pub enum CowRef<'a, T> {
+    /// 借用した値．
     Borrowed(&'a T),
+    /// 所有する値．
     Owned(T),
 }
 
+impl<'a, T> CowRef<'a, T> {
+    /// 保持している値の所有権を返す．
+    /// 借用した値を保持している場合は，その値を複製して返す．
+    pub fn into_owned(self) -> T
+    where
+        T: Clone,
+    {
+        match self {
+            CowRef::Borrowed(b) => b.clone(),
+            CowRef::Owned(o) => o,
+        }
+    }
+
+    /// 借用した値を保持しているか．
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, CowRef::Borrowed(_))
+    }
+
+    /// 所有する値を保持しているか．
+    pub fn is_owned(&self) -> bool {
+        matches!(self, CowRef::Owned(_))
+    }
+}
+
+impl<'a, T> Clone for CowRef<'a, T>
+where
+    T: Clone,
+{
+    /// 保持している値を複製し，それを所有する`CowRef`を返す．
+    /// 借用した値を保持している場合も，値を複製する．
+    fn clone(&self) -> Self {
+        CowRef::Owned(self.as_ref().clone())
+    }
+}
+
 impl<'a, T> From<T> for CowRef<'a, T> {
     fn from(t: T) -> CowRef<'a, T> {
         CowRef::Owned(t)
@@ -19,7 +59,7 @@ impl<'a, T> AsRef<T> for CowRef<'a, T> {
     fn as_ref(&self) -> &T {
         match self {
             CowRef::Borrowed(b) => b,
-            CowRef::Owned(o) => &o,
+            CowRef::Owned(o) => o,
         }
     }
 }
@@ -38,4 +78,38 @@ mod tests {
         let cr = CowRef::Borrowed(s);
         assert_eq!("abc", cr.as_ref())
     }
+    #[test]
+    fn test_cow_ref_into_owned() {
+        let cr = CowRef::Owned(String::from("abc"));
+        assert_eq!("abc", cr.into_owned());
+        let s = &String::from("abc");
+        let cr = CowRef::Borrowed(s);
+        assert_eq!("abc", cr.into_owned());
+    }
+    #[test]
+    fn test_cow_ref_predicates() {
+        let cr: CowRef<String> = CowRef::Owned(String::from("abc"));
+        assert!(cr.is_owned());
+        assert!(!cr.is_borrowed());
+        let s = &String::from("abc");
+        let cr = CowRef::Borrowed(s);
+        assert!(cr.is_borrowed());
+        assert!(!cr.is_owned());
+    }
+    #[test]
+    fn test_cow_ref_clone() {
+        let s = &String::from("abc");
+        let cr = CowRef::Borrowed(s);
+        let cloned = cr.clone();
+        assert!(cloned.is_owned());
+        assert_eq!("abc", cloned.as_ref());
+        assert!(cr.is_borrowed());
+    }
+    #[test]
+    fn test_cow_ref_debug() {
+        let s = &String::from("abc");
+        assert_eq!(r#"Borrowed("abc")"#, format!("{:?}", CowRef::Borrowed(s)));
+        let cr: CowRef<String> = CowRef::Owned(String::from("abc"));
+        assert_eq!(r#"Owned("abc")"#, format!("{:?}", cr));
+    }
 }
@@ -0,0 +1,15 @@
+use num::Integer;
+
+/// 探索深さを表す整数型．
+///
+/// 探索深さは1ずつ減らしながら0と比べるので，整数型に限る．
+/// 浮動小数点型は実装しない．
+pub trait SearchDepth: Copy + Integer {}
+
+macro_rules! impl_search_depth {
+    ($($t:ty),*) => {
+        $(impl SearchDepth for $t {})*
+    };
+}
+
+impl_search_depth!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
@@ -0,0 +1,449 @@
+use crate::proof_number::{initial_numbers, saturating_sum, INFINITE_PROOF_NUMBER};
+use crate::{Action, Actor, OutcomeRule, ProofOutcome, Rule, StateHasher};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasherDefault, Hash};
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+
+/// 探索中の手順に同じ状態が再び現れた (循環した) 場合の扱い．
+///
+/// 循環による結論は，その状態に至った手順に依存する．
+/// 置換表は手順を区別しないので，別の手順からその状態に至った場合にも同じ結論が使われることがある
+/// (Graph History Interaction問題)．
+/// この問題は扱わないので，循環を含むゲームでは結論が正しくない場合がある．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CyclePolicy {
+    /// 循環した状態を反証できたものとみなす．
+    /// 千日手のように，同一局面の繰り返しが攻め方の目標を満たさないゲームに用いる．
+    RepetitionIsDisproof,
+    /// 循環した状態を証明できたものとみなす．
+    RepetitionIsProof,
+    /// 循環を検出しない．状態が循環しないゲームでだけ用いる．
+    Ignore,
+}
+
+impl Default for CyclePolicy {
+    /// 攻め方に不利な扱いとなる，`RepetitionIsDisproof`を返す．
+    fn default() -> Self {
+        CyclePolicy::RepetitionIsDisproof
+    }
+}
+
+/// 指定したプレイヤー (攻め方) が，ゲームの終了時に目標を必ず達成できるかを，深さ優先の証明数探索 (df-pn) で調べる．
+///
+/// `ProofNumberSearch`と同じく最有望証明ノードを展開するが，ゲーム木全体を保持する代わりに，
+/// 証明数と反証数の閾値を定めて深さ優先で探索し，各状態の証明数と反証数を容量を固定した置換表に記録する．
+/// 置換表に記録しきれない状態は探索し直すので，保持するノードの数よりはるかに大きいゲーム木も扱える．
+///
+/// 使い方は`ProofNumberSearch`と同じで，`solve`と`solve_win`は同じ引数をとる．
+pub struct DfpnSearch<R>
+where
+    R: Rule,
+{
+    /// 置換表に記録できる状態の数．
+    capacity: usize,
+    /// 展開するノードの数の上限．
+    expansion_limit: Option<u64>,
+    /// 循環した状態の扱い．
+    cycle_policy: CyclePolicy,
+    /// 状態のハッシュ値の計算方法．
+    hasher: Box<dyn StateHasher<R::S> + Send + Sync>,
+}
+
+#[cfg(feature = "std")]
+impl<R> DfpnSearch<R>
+where
+    R: Rule,
+    R::S: Hash,
+{
+    /// 指定した数の状態を記録できる置換表を用いて探索するものを作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, BuildHasherDefault::<DefaultHasher>::default())
+    }
+}
+
+impl<R> DfpnSearch<R>
+where
+    R: Rule,
+{
+    /// 状態のハッシュ値の計算方法を指定して，指定した数の状態を記録できる置換表を用いて探索するものを作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_hasher<H>(capacity: usize, hasher: H) -> Self
+    where
+        H: StateHasher<R::S> + Send + Sync + 'static,
+    {
+        assert!(
+            capacity > 0,
+            "capacity of transposition table must be positive"
+        );
+        Self {
+            capacity,
+            expansion_limit: None,
+            cycle_policy: CyclePolicy::default(),
+            hasher: Box::new(hasher),
+        }
+    }
+
+    /// 展開するノードの数の上限を指定する．
+    /// 上限に達した場合は探索を打ち切り，`ProofOutcome::Unknown`を返す．
+    /// 指定しない場合，結論が出るまで探索する．
+    pub fn with_expansion_limit(self, expansion_limit: u64) -> Self {
+        Self {
+            expansion_limit: Some(expansion_limit),
+            ..self
+        }
+    }
+
+    /// 循環した状態の扱いを指定する．
+    /// 既定では`CyclePolicy::RepetitionIsDisproof`である．
+    pub fn with_cycle_policy(self, cycle_policy: CyclePolicy) -> Self {
+        Self {
+            cycle_policy,
+            ..self
+        }
+    }
+}
+
+impl<S, A, R> DfpnSearch<R>
+where
+    S: PartialEq,
+    A: Action,
+    R: Rule<S = S, A = A>,
+{
+    /// 指定した状態から`actor`が行動する場合に，`attacker`がゲームの終了時に`goal`を満たす状態に必ず至らせられるかを調べ，
+    /// 結果とその根拠を返す．
+    ///
+    /// `goal`はゲームが終了した状態と，取れる行動がない状態に対してだけ呼び出される．
+    pub fn solve<F>(
+        &self,
+        state: &S,
+        actor: Actor,
+        attacker: Actor,
+        goal: F,
+    ) -> (ProofOutcome, DfpnProof<A>)
+    where
+        F: Fn(&S) -> bool,
+    {
+        let mut entries = Vec::with_capacity(self.capacity);
+        entries.resize_with(self.capacity, || None);
+        let mut context = DfpnContext {
+            search: self,
+            goal: &goal,
+            attacker,
+            entries,
+            path: vec![],
+            expansions: 0,
+            aborted: false,
+        };
+        let numbers = context.multiple_iterative_deepening(
+            state,
+            actor,
+            INFINITE_PROOF_NUMBER,
+            INFINITE_PROOF_NUMBER,
+        );
+        let outcome = match numbers {
+            _ if context.aborted => ProofOutcome::Unknown,
+            (0, _) => ProofOutcome::Proved,
+            (_, 0) => ProofOutcome::Disproved,
+            _ => ProofOutcome::Unknown,
+        };
+        let principal_line = match outcome {
+            ProofOutcome::Unknown => vec![],
+            _ => context.principal_line(state, actor, outcome == ProofOutcome::Proved),
+        };
+        let proof = DfpnProof {
+            proof_number: numbers.0,
+            disproof_number: numbers.1,
+            expansions: context.expansions,
+            principal_line,
+        };
+        (outcome, proof)
+    }
+}
+
+impl<S, A, R> DfpnSearch<R>
+where
+    S: PartialEq,
+    A: Action,
+    R: OutcomeRule<S = S, A = A>,
+{
+    /// 指定した状態から`actor`が行動する場合に，`attacker`が必ず勝てるかを調べ，結果とその根拠を返す．
+    /// 引き分けは勝ちとみなさない．
+    pub fn solve_win(
+        &self,
+        state: &S,
+        actor: Actor,
+        attacker: Actor,
+    ) -> (ProofOutcome, DfpnProof<A>) {
+        self.solve(state, actor, attacker, |state| {
+            R::winner(state) == Some(attacker)
+        })
+    }
+}
+
+/// df-pnによる探索の結果の根拠．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfpnProof<A> {
+    /// 根ノードの証明数．
+    pub proof_number: u64,
+    /// 根ノードの反証数．
+    pub disproof_number: u64,
+    /// 展開したノードの数．
+    pub expansions: u64,
+    /// 結論の根拠となる手順．
+    principal_line: Vec<A>,
+}
+
+impl<A> DfpnProof<A> {
+    /// 証明 (反証) できた場合，その根拠となる手順を根ノードから順に返す．
+    ///
+    /// 各状態では，結論が出ている子ノードのうち最初のものをたどる．
+    /// 置換表から記録が失われた状態に達した場合，手順はそこで途切れる．
+    /// 結論が出ていない場合は空の手順を返す．
+    pub fn principal_line(&self) -> Vec<&A> {
+        self.principal_line.iter().collect()
+    }
+}
+
+/// 置換表に記録される，ある状態の証明数と反証数．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DfpnEntry {
+    /// 状態のハッシュ値．
+    key: u64,
+    /// この状態で行動するプレイヤー．
+    next_actor: Actor,
+    /// 証明数．
+    proof_number: u64,
+    /// 反証数．
+    disproof_number: u64,
+    /// この状態を根とする探索で展開したノードの数．
+    /// 記録先が衝突した場合，より多くの手間をかけた記録を残す．
+    work: u64,
+}
+
+/// 1回の`solve`の間だけ使用する情報．
+struct DfpnContext<'a, R, F>
+where
+    R: Rule,
+{
+    search: &'a DfpnSearch<R>,
+    /// 攻め方の目標．
+    goal: &'a F,
+    /// 目標の達成を目指すプレイヤー．
+    attacker: Actor,
+    /// 置換表．
+    entries: Vec<Option<DfpnEntry>>,
+    /// 根ノードから注目ノードまでの状態のハッシュ値と，その状態で行動するプレイヤー．
+    path: Vec<(u64, Actor)>,
+    /// 展開したノードの数．
+    expansions: u64,
+    /// 展開するノードの数が上限に達し，探索を打ち切ったか．
+    aborted: bool,
+}
+
+impl<'a, S, A, R, F> DfpnContext<'a, R, F>
+where
+    S: PartialEq,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    F: Fn(&S) -> bool,
+{
+    /// 指定した状態の証明数か反証数が閾値以上になるまで探索し，得られた証明数と反証数を返す．
+    fn multiple_iterative_deepening(
+        &mut self,
+        state: &S,
+        actor: Actor,
+        proof_threshold: u64,
+        disproof_threshold: u64,
+    ) -> (u64, u64) {
+        let key = self.search.hasher.hash_state(state);
+        let leaf_numbers = initial_numbers::<R, _>(state, actor, self.goal);
+        if leaf_numbers != (1, 1) {
+            return leaf_numbers;
+        }
+        self.path.push((key, actor));
+        let is_or_node = actor == self.attacker;
+        let children = R::iterate_available_actions(state, actor)
+            .map(|action| {
+                let next_state = R::translate_state(state, &action);
+                let next_key = self.search.hasher.hash_state(&next_state);
+                (next_state, next_key)
+            })
+            .collect::<Vec<_>>();
+        let expansions_before = self.expansions;
+        self.expansions += 1;
+
+        // 子ノードの探索結果は置換表から追い出されることがあるので，手元にも保持する
+        let mut child_numbers = children
+            .iter()
+            .map(|(child, key)| self.child_numbers(child, *key, actor.opponent()))
+            .collect::<Vec<_>>();
+        let numbers = loop {
+            let (proof_number, disproof_number) = combine(is_or_node, &child_numbers);
+            if proof_number >= proof_threshold || disproof_number >= disproof_threshold {
+                break (proof_number, disproof_number);
+            }
+            if let Some(limit) = self.search.expansion_limit {
+                if self.expansions >= limit {
+                    self.aborted = true;
+                    break (proof_number, disproof_number);
+                }
+            }
+
+            // ORノードでは証明数，ANDノードでは反証数の最も小さい子ノードを，閾値を定めて探索する
+            let (best, second) = best_two(is_or_node, &child_numbers);
+            let (best_proof, best_disproof) = child_numbers[best];
+            let (child_proof_threshold, child_disproof_threshold) = if is_or_node {
+                (
+                    proof_threshold.min(second.saturating_add(1)),
+                    disproof_threshold - disproof_number + best_disproof,
+                )
+            } else {
+                (
+                    proof_threshold - proof_number + best_proof,
+                    disproof_threshold.min(second.saturating_add(1)),
+                )
+            };
+            let (child, _) = &children[best];
+            child_numbers[best] = self.multiple_iterative_deepening(
+                child,
+                actor.opponent(),
+                child_proof_threshold,
+                child_disproof_threshold,
+            );
+        };
+        self.path.pop();
+        self.store(key, actor, numbers, self.expansions - expansions_before);
+        numbers
+    }
+
+    /// 指定した子ノードの証明数と反証数を返す．
+    /// 置換表に記録がなければ，初期値を返す．
+    fn child_numbers(&self, child: &S, key: u64, actor: Actor) -> (u64, u64) {
+        if self.path.contains(&(key, actor)) {
+            match self.search.cycle_policy {
+                CyclePolicy::RepetitionIsDisproof => return (INFINITE_PROOF_NUMBER, 0),
+                CyclePolicy::RepetitionIsProof => return (0, INFINITE_PROOF_NUMBER),
+                CyclePolicy::Ignore => {}
+            }
+        }
+        match self.probe(key, actor) {
+            Some(entry) => (entry.proof_number, entry.disproof_number),
+            None => initial_numbers::<R, _>(child, actor, self.goal),
+        }
+    }
+
+    /// 指定した状態の記録を返す．
+    fn probe(&self, key: u64, next_actor: Actor) -> Option<&DfpnEntry> {
+        self.entries[self.index_of(key)]
+            .as_ref()
+            .filter(|entry| entry.key == key && entry.next_actor == next_actor)
+    }
+
+    /// 指定した状態の証明数と反証数を記録する．
+    /// 記録先にすでに別の状態が記録されている場合，より多くの手間をかけた記録を残す．
+    fn store(&mut self, key: u64, next_actor: Actor, numbers: (u64, u64), work: u64) {
+        let index = self.index_of(key);
+        let slot = &mut self.entries[index];
+        let work = match slot {
+            Some(current) if current.key == key && current.next_actor == next_actor => {
+                current.work.max(work)
+            }
+            Some(current) if current.work > work => return,
+            _ => work,
+        };
+        *slot = Some(DfpnEntry {
+            key,
+            next_actor,
+            proof_number: numbers.0,
+            disproof_number: numbers.1,
+            work,
+        });
+    }
+
+    fn index_of(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+
+    /// 置換表の記録をたどり，結論の根拠となる手順を返す．
+    /// 各状態で，結論が出ている子ノードのうち最初のものをたどる．
+    fn principal_line(&mut self, state: &S, actor: Actor, proved: bool) -> Vec<A> {
+        let mut line = vec![];
+        let mut current: Option<S> = None;
+        let mut actor = actor;
+        self.path.clear();
+        loop {
+            let state = current.as_ref().unwrap_or(state);
+            if initial_numbers::<R, _>(state, actor, self.goal) != (1, 1) {
+                return line;
+            }
+            self.path
+                .push((self.search.hasher.hash_state(state), actor));
+            let next = R::iterate_available_actions(state, actor).find_map(|action| {
+                let next_state = R::translate_state(state, &action);
+                let key = self.search.hasher.hash_state(&next_state);
+                if self.path.contains(&(key, actor.opponent())) {
+                    return None;
+                }
+                let (proof_number, disproof_number) =
+                    self.child_numbers(&next_state, key, actor.opponent());
+                let concluded = if proved {
+                    proof_number == 0
+                } else {
+                    disproof_number == 0
+                };
+                concluded.then_some((action, next_state))
+            });
+            match next {
+                Some((action, next_state)) => {
+                    line.push(action);
+                    current = Some(next_state);
+                    actor = actor.opponent();
+                }
+                // 置換表から記録が失われた場合
+                None => return line,
+            }
+        }
+    }
+}
+
+/// 子ノードの証明数と反証数から，ノードの証明数と反証数を求める．
+fn combine(is_or_node: bool, children: &[(u64, u64)]) -> (u64, u64) {
+    let min_proof = children.iter().map(|&(proof, _)| proof).min();
+    let min_disproof = children.iter().map(|&(_, disproof)| disproof).min();
+    let sum_proof = children
+        .iter()
+        .map(|&(proof, _)| proof)
+        .fold(0, saturating_sum);
+    let sum_disproof = children
+        .iter()
+        .map(|&(_, disproof)| disproof)
+        .fold(0, saturating_sum);
+    if is_or_node {
+        (min_proof.unwrap_or(INFINITE_PROOF_NUMBER), sum_disproof)
+    } else {
+        (sum_proof, min_disproof.unwrap_or(INFINITE_PROOF_NUMBER))
+    }
+}
+
+/// ORノードでは証明数，ANDノードでは反証数の最も小さい子ノードの添字と，2番目に小さい値を返す．
+fn best_two(is_or_node: bool, children: &[(u64, u64)]) -> (usize, u64) {
+    let number = |&(proof, disproof): &(u64, u64)| if is_or_node { proof } else { disproof };
+    let mut best = 0;
+    let mut second = INFINITE_PROOF_NUMBER;
+    for (index, child) in children.iter().enumerate().skip(1) {
+        if number(child) < number(&children[best]) {
+            second = number(&children[best]);
+            best = index;
+        } else {
+            second = second.min(number(child));
+        }
+    }
+    (best, second)
+}
@@ -0,0 +1,286 @@
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, Rule, ScoredAction, ScoredStrategy, SearchDepth,
+    State,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Add, Sub};
+
+/// 偶然事象の結果が起こる確率．0以上1以下の値である．
+pub type Probability = f64;
+
+/// サイコロなどの偶然事象を含むゲームルール．
+///
+/// 行動を実行した後の状態は，偶然事象の結果によって複数ありうる．
+/// 偶然事象を含まない行動では，`translate_state`の遷移先が確率1で起こるものとする．
+pub trait StochasticRule: Rule {
+    /// 指定した状態で指定した行動を実行した後に起こりうる状態と，その確率を列挙する．
+    /// 確率の合計は1でなければならない．
+    /// `ExpectimaxStrategy`は，`translate_state`の代わりにこの関数で遷移先を求める．
+    ///
+    /// 既定では`translate_state`の遷移先を確率1で返す．
+    fn chance_outcomes(state: &Self::S, action: &Self::A) -> Vec<(Self::S, Probability)> {
+        vec![(Self::translate_state(state, action), 1.0)]
+    }
+}
+
+/// 確率で重み付けした期待値を計算できる評価値．
+pub trait ExpectedPayoff: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    /// 評価値に確率を掛けた値を返す．
+    /// *-minimaxの枝刈りでは，確率の逆数を掛けることもある．
+    fn scale_by(self, probability: Probability) -> Self;
+}
+
+impl ExpectedPayoff for f64 {
+    fn scale_by(self, probability: Probability) -> f64 {
+        self * probability
+    }
+}
+
+impl ExpectedPayoff for f32 {
+    fn scale_by(self, probability: Probability) -> f32 {
+        (self as f64 * probability) as f32
+    }
+}
+
+/// 偶然事象を含む2人零和ゲームにおける適切な行動を，期待値ミニマックス法 (Expectimax) で思考するエージェント．
+///
+/// 自分の手番では評価値が最大の行動を，相手の手番では評価値が最小の行動を選び，
+/// 偶然事象では起こりうる状態の評価値を確率で重み付けした期待値をとる．
+/// 探索深さはプレイヤーの行動ごとに1つ減り，偶然事象では減らない．
+/// 手番のプレイヤーに取れる行動がない状態は，ゲームが続く状態として静的に評価する．
+///
+/// 手番のノードではαβ法で枝刈りを行う．
+/// `with_payoff_bounds`で評価値の範囲を指定すると，偶然事象のノードでも*-minimax (Star1) の枝刈りを行う．
+pub struct ExpectimaxStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    /// 評価関数がとりうる値の下限と上限．
+    payoff_bounds: Option<(E::Payoff, E::Payoff)>,
+    _r: PhantomData<fn() -> R>,
+    _e: PhantomData<fn() -> E>,
+}
+
+impl<R, E, N> ExpectimaxStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 期待値ミニマックス法で思考するエージェントを作る．
+    /// `search_depth`は探索するプレイヤーの行動の手数である．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            payoff_bounds: None,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<R, E, N> ExpectimaxStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: PartialOrd,
+{
+    /// 評価関数がとりうる値の下限と上限を指定し，偶然事象のノードで*-minimaxの枝刈りを行うようにする．
+    /// 調べていない結果の評価値をこの範囲で見積もり，期待値が関心範囲に入りえないと分かった時点で残りの結果を調べない．
+    /// ゲームが終了した状態を含め，評価値が範囲外になると誤った枝刈りが起こる．
+    /// # Panics
+    /// `lower`が`upper`より大きい場合
+    pub fn with_payoff_bounds(self, lower: E::Payoff, upper: E::Payoff) -> Self {
+        assert!(lower <= upper, "lower bound must not exceed upper bound");
+        Self {
+            payoff_bounds: Some((lower, upper)),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E, N> ExpectimaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: StochasticRule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: ExpectedPayoff,
+    N: SearchDepth,
+{
+    /// 手番のノードの評価値と，最善の行動を計算する．
+    /// # Params
+    /// 1. state 注目ノードの状態．
+    /// 1. actor 注目ノードで行動するプレイヤー．
+    /// 1. consideration_target 評価値を計算する対象のプレイヤー．
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. ply 根ノードから注目ノードまでの深さ．
+    /// 1. alpha 評価値の関心範囲の下限．`None`は下限がないことを表す．
+    /// 1. beta 評価値の関心範囲の上限．`None`は上限がないことを表す．
+    ///
+    /// # Returns
+    /// 評価値と最善の行動．末端ノードでは行動は`None`である．
+    /// 評価値が関心範囲の外にある場合，真の評価値の上界または下界である．
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_decision(
+        &self,
+        state: &S,
+        actor: Actor,
+        consideration_target: Actor,
+        remaining_depth: N,
+        ply: u32,
+        mut alpha: Option<E::Payoff>,
+        mut beta: Option<E::Payoff>,
+    ) -> (E::Payoff, Option<A>) {
+        let terminal = R::terminal_kind(state);
+        let static_payoff = || evaluate_at_ply::<_, E>(consideration_target, state, terminal, ply);
+        if remaining_depth.is_zero() || terminal.is_some() {
+            return (static_payoff(), None);
+        }
+
+        let maximizing = actor == consideration_target;
+        let mut best: Option<(E::Payoff, A)> = None;
+        for action in R::iterate_available_actions(state, actor) {
+            let payoff = self.evaluate_chance(
+                state,
+                &action,
+                consideration_target,
+                remaining_depth - N::one(),
+                ply + 1,
+                alpha,
+                beta,
+            );
+            let is_better = match best.as_ref() {
+                None => true,
+                // 自分の手番では自分が有利になる行動を，相手の手番では自分が不利になる行動を選択する
+                Some((e, _)) if maximizing => payoff > *e,
+                Some((e, _)) => payoff < *e,
+            };
+            if is_better {
+                best = Some((payoff, action));
+            }
+            // 関心範囲を狭め，範囲外に出た時点で残りの行動を調べない
+            if maximizing {
+                if alpha.is_none_or(|alpha| payoff > alpha) {
+                    alpha = Some(payoff);
+                }
+                if beta.is_some_and(|beta| payoff >= beta) {
+                    break;
+                }
+            } else {
+                if beta.is_none_or(|beta| payoff < beta) {
+                    beta = Some(payoff);
+                }
+                if alpha.is_some_and(|alpha| payoff <= alpha) {
+                    break;
+                }
+            }
+        }
+
+        match best {
+            Some((payoff, action)) => (payoff, Some(action)),
+            None => (static_payoff(), None),
+        }
+    }
+
+    /// 指定した行動を実行した後の偶然事象のノードについて，評価値の期待値を計算する．
+    /// 評価値の範囲が指定されている場合，期待値が関心範囲に入りえないと分かった時点で，その上界または下界を返す．
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_chance(
+        &self,
+        state: &S,
+        action: &A,
+        consideration_target: Actor,
+        remaining_depth: N,
+        ply: u32,
+        alpha: Option<E::Payoff>,
+        beta: Option<E::Payoff>,
+    ) -> E::Payoff {
+        let next_actor = action.actor().opponent();
+        let outcomes = R::chance_outcomes(state, action);
+        debug_assert!(!outcomes.is_empty());
+
+        // これまでに調べた結果の，確率で重み付けした評価値の合計
+        let mut sum: Option<E::Payoff> = None;
+        // まだ調べていない結果の確率の合計
+        let mut rest: Probability = 1.0;
+        let accumulate = |sum: Option<E::Payoff>, payoff: E::Payoff| match sum {
+            Some(sum) => sum + payoff,
+            None => payoff,
+        };
+        for (next_state, probability) in outcomes.iter() {
+            let probability = *probability;
+            // 残りの結果がすべて下限 (上限) の評価値をとる場合に，期待値が関心範囲に入るための評価値の範囲
+            let (child_alpha, child_beta) = match self.payoff_bounds {
+                Some((lower, upper)) if probability > 0.0 => {
+                    let others = rest - probability;
+                    let window = |bound: E::Payoff, other: E::Payoff| {
+                        let remainder = match sum {
+                            Some(sum) => bound - sum - other.scale_by(others),
+                            None => bound - other.scale_by(others),
+                        };
+                        remainder.scale_by(1.0 / probability)
+                    };
+                    (
+                        alpha.map(|alpha| window(alpha, upper)),
+                        beta.map(|beta| window(beta, lower)),
+                    )
+                }
+                _ => (None, None),
+            };
+            let (payoff, _) = self.evaluate_decision(
+                next_state,
+                next_actor,
+                consideration_target,
+                remaining_depth,
+                ply,
+                child_alpha,
+                child_beta,
+            );
+            sum = Some(accumulate(sum, payoff.scale_by(probability)));
+            rest -= probability;
+
+            if let Some((lower, upper)) = self.payoff_bounds {
+                let upper_bound = accumulate(sum, upper.scale_by(rest.max(0.0)));
+                if alpha.is_some_and(|alpha| upper_bound <= alpha) {
+                    return upper_bound;
+                }
+                let lower_bound = accumulate(sum, lower.scale_by(rest.max(0.0)));
+                if beta.is_some_and(|beta| lower_bound >= beta) {
+                    return lower_bound;
+                }
+            }
+        }
+        sum.expect("chance_outcomes must not be empty")
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値の期待値を返す．
+    /// ゲームが終了している場合や取れる行動がない場合は，静的な評価値を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> E::Payoff {
+        self.evaluate_decision(state, actor, actor, self.search_depth, 0, None, None)
+            .0
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for ExpectimaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: StochasticRule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: ExpectedPayoff,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        match self.evaluate_decision(state, actor, actor, self.search_depth, 0, None, None) {
+            (payoff, Some(action)) => Some(ScoredAction::new(action, payoff)),
+            (_, None) => None,
+        }
+    }
+}
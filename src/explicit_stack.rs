@@ -0,0 +1,299 @@
+use crate::{
+    evaluate_at_ply, Action, Actor, CowRef, Evaluator, Rule, ScoredAction, ScoredStrategy,
+    SearchDepth, SearchStats, State,
+};
+use alloc::vec;
+use core::fmt;
+use core::marker::PhantomData;
+use data_structure::Range;
+use num::Bounded;
+
+/// 2人零和ゲームにおける適切な行動を，再帰呼び出しを用いないαβ法で思考するエージェント．
+///
+/// 探索中のノードを`Vec`で管理するスタックに積むので，探索深さが大きくてもスレッドのスタックを使い果たさない．
+/// 子ノードを調べる順序，枝刈りの条件，評価値の等しい行動の選び方は，
+/// `AlphaBetaStrategy::new`で作ったエージェントと同じである．
+/// 勝敗の決まる評価値が等しい場合は，勝つ側は早く，負ける側は遅く勝敗が決まる行動を選び，
+/// その手数を`ScoredAction::depth_to_terminal`として返す．
+/// 置換表などの探索の設定には対応しない．
+pub struct ExplicitStackAlphaBetaStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    _r: PhantomData<R>,
+    _e: PhantomData<E>,
+}
+
+impl<R, E, N> ExplicitStackAlphaBetaStrategy<R, E, N> {
+    /// 指定した深さまで探索する，再帰呼び出しを用いないαβ法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+// ルールと評価関数は型として指定するだけなので，探索深さの型にだけ制約を課す
+impl<R, E, N> Clone for ExplicitStackAlphaBetaStrategy<R, E, N>
+where
+    N: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.search_depth.clone())
+    }
+}
+
+impl<R, E, N> Copy for ExplicitStackAlphaBetaStrategy<R, E, N> where N: Copy {}
+
+impl<R, E, N> fmt::Debug for ExplicitStackAlphaBetaStrategy<R, E, N>
+where
+    N: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExplicitStackAlphaBetaStrategy")
+            .field("search_depth", &self.search_depth)
+            .finish()
+    }
+}
+
+/// スタックに積まれた，子ノードを調べている途中のノード．
+struct Frame<'a, S, A, I, P, N> {
+    state: CowRef<'a, S>,
+    /// 親ノードからこのノードに遷移した行動．根ノードでは`None`である．
+    cause_action: Option<A>,
+    /// このノードで行動するプレイヤー．
+    next_actor: Actor,
+    /// このノードからの残りの探索深さ．
+    remaining_depth: N,
+    /// 評価値の関心範囲．よりよい子ノードが見つかるたびに狭められる．
+    payoff_range: Range<P>,
+    /// まだ調べていない行動．
+    actions: I,
+    /// それまでに調べた子ノードのうち，最善のものの評価値と行動，勝敗が決まる末端ノードの根ノードからの深さ．
+    best: Option<(P, A, Option<u32>)>,
+    /// 枝刈りが起こり，残りの子ノードを調べる必要がなくなったか．
+    cut: bool,
+}
+
+impl<S, A, R, E, N> ExplicitStackAlphaBetaStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    /// 探索するゲーム木の深さを返す．
+    pub fn search_depth(&self) -> N {
+        self.search_depth
+    }
+
+    /// 指定した状態を根とするゲーム木をαβ法で探索し，最善の行動とその評価値，探索の統計情報を返す．
+    /// 根ノードが末端ノードの場合は，行動を`None`として根ノードの評価値を返す．
+    /// 探索の統計情報の`depth_to_terminal`には，評価値が勝ちまたは負けである場合に勝敗が決まるまでの手数を記録する．
+    fn search(&self, state: &S, actor: Actor) -> (Option<E::Payoff>, Option<A>, SearchStats) {
+        // 根ノードも訪れたノードに含める
+        let mut stats = SearchStats {
+            nodes_evaluated: 1,
+            ..SearchStats::default()
+        };
+        let terminal = R::terminal_kind(state);
+        if self.search_depth.is_zero() || terminal.is_some() {
+            let payoff = evaluate_at_ply::<_, E>(actor, state, terminal, 0);
+            stats.depth_to_terminal = decisive_ply(payoff, 0);
+            return (Some(payoff), None, stats);
+        }
+
+        let mut stack = vec![Frame {
+            state: state.into(),
+            cause_action: None,
+            next_actor: actor,
+            remaining_depth: self.search_depth,
+            payoff_range: Range::new(E::Payoff::min_value(), E::Payoff::max_value()),
+            actions: R::iterate_available_actions(state, actor),
+            best: None,
+            cut: false,
+        }];
+        // 直前に調べ終えた子ノードの評価値と，その子ノードに遷移した行動，勝敗が決まる末端ノードの深さ
+        let mut returned: Option<(Option<E::Payoff>, A, Option<u32>)> = None;
+
+        loop {
+            let ply = stack.len() as u32;
+            let frame = stack.last_mut().unwrap();
+            if let Some((Some(child_payoff), action, terminal_ply)) = returned.take() {
+                Self::update_best(frame, actor, child_payoff, action, terminal_ply, &mut stats);
+            }
+
+            let action = if frame.cut {
+                None
+            } else {
+                frame.actions.next()
+            };
+            let action = match action {
+                Some(action) => action,
+                None => {
+                    // すべての子ノードを調べ終えたので，親ノードに評価値を返す
+                    let frame = stack.pop().unwrap();
+                    let (payoff, best_action, terminal_ply) = match frame.best {
+                        Some((payoff, action, terminal_ply)) => {
+                            (Some(payoff), Some(action), terminal_ply)
+                        }
+                        None => (None, None, None),
+                    };
+                    match frame.cause_action {
+                        Some(cause_action) => returned = Some((payoff, cause_action, terminal_ply)),
+                        None => {
+                            stats.depth_to_terminal = terminal_ply;
+                            return (payoff, best_action, stats);
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            stats.nodes_evaluated += 1;
+            let next_state = R::translate_state(frame.state.as_ref(), &action);
+            let remaining_depth = frame.remaining_depth - N::one();
+            let terminal = R::terminal_kind(&next_state);
+            if remaining_depth.is_zero() || terminal.is_some() {
+                // 末端ノードはスタックに積まずに評価する
+                let payoff = evaluate_at_ply::<_, E>(actor, &next_state, terminal, ply);
+                returned = Some((Some(payoff), action, decisive_ply(payoff, ply)));
+                continue;
+            }
+            let next_actor = frame.next_actor.opponent();
+            let payoff_range = frame.payoff_range;
+            let actions = R::iterate_available_actions(&next_state, next_actor);
+            stack.push(Frame {
+                state: next_state.into(),
+                cause_action: Some(action),
+                next_actor,
+                remaining_depth,
+                payoff_range,
+                actions,
+                best: None,
+                cut: false,
+            });
+        }
+    }
+
+    /// 調べ終えた子ノードの評価値により，注目ノードの最善の子ノードと関心範囲を更新する．
+    /// 関心範囲が空になった場合は，残りの子ノードを調べないようにする．
+    fn update_best(
+        frame: &mut Frame<S, A, R::ActionIterator, E::Payoff, N>,
+        consideration_target: Actor,
+        child_payoff: E::Payoff,
+        action: A,
+        terminal_ply: Option<u32>,
+        stats: &mut SearchStats,
+    ) {
+        let maximizes = frame.next_actor == consideration_target;
+        // 自分の手番では自分が有利になる行動を，相手の手番では自分が不利になる行動を選択する
+        if let Some((best_payoff, _, best_terminal_ply)) = frame.best.as_ref() {
+            let improves = if maximizes {
+                child_payoff > *best_payoff
+            } else {
+                child_payoff < *best_payoff
+            };
+            // 勝敗の決まる評価値が等しい場合は，勝つ側は早く，負ける側は遅く勝敗が決まる行動を選択する
+            let prefers_line = child_payoff == *best_payoff
+                && prefers_terminal_ply(child_payoff, maximizes, *best_terminal_ply, terminal_ply);
+            if !improves && !prefers_line {
+                return;
+            }
+        }
+        frame.best = Some((child_payoff, action, terminal_ply));
+        let range = frame.payoff_range;
+        let next_range = if maximizes {
+            Range::try_new(child_payoff, range.max)
+        } else {
+            Range::try_new(range.min, child_payoff)
+        };
+        match next_range {
+            Some(range) => frame.payoff_range = range,
+            None => {
+                if maximizes {
+                    stats.beta_cutoffs += 1;
+                } else {
+                    stats.alpha_cutoffs += 1;
+                }
+                frame.cut = true;
+            }
+        }
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).0
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (_, action, stats) = self.search(state, actor);
+        (action, stats)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for ExplicitStackAlphaBetaStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        match self.search(state, actor) {
+            (Some(payoff), Some(action), stats) => Some(ScoredAction {
+                depth_to_terminal: stats.depth_to_terminal,
+                ..ScoredAction::new(action, payoff)
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// 評価値が勝ち (評価値の最大値) または負け (最小値) であれば，指定した深さを返す．
+fn decisive_ply<P>(payoff: P, ply: u32) -> Option<u32>
+where
+    P: PartialEq + Bounded,
+{
+    if payoff == P::max_value() || payoff == P::min_value() {
+        Some(ply)
+    } else {
+        None
+    }
+}
+
+/// 等しい評価値`payoff`を持つ2つの子ノードのうち，新しい子ノードの手順を優先するか判定する．
+/// 勝敗が決まる評価値である場合，手番のプレイヤーが勝つなら早く，負けるなら遅く勝敗が決まる手順を優先する．
+fn prefers_terminal_ply<P>(
+    payoff: P,
+    is_own_turn: bool,
+    current: Option<u32>,
+    child: Option<u32>,
+) -> bool
+where
+    P: PartialEq + Bounded,
+{
+    let (current, child) = match (current, child) {
+        (Some(current), Some(child)) => (current, child),
+        _ => return false,
+    };
+    let mover_wins = if is_own_turn {
+        payoff == P::max_value()
+    } else {
+        payoff == P::min_value()
+    };
+    if mover_wins {
+        child < current
+    } else {
+        child > current
+    }
+}
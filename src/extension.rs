@@ -0,0 +1,78 @@
+/// 脅威のある局面など，ゲームに固有の重要なノードで探索を延長するための方針．
+///
+/// 王手や，連珠の三や四のように，何が重要な局面かはゲームによって異なる．
+/// 探索の各ノードで，そのノードに至った行動をもとに延長する手数を返す．
+/// `Fn(&S, &A) -> u8`を実装するクロージャは，このトレイトを自動的に実装する．
+pub trait ExtensionPolicy<S, A> {
+    /// 指定した行動により指定した状態に至ったノードを，何手深く探索するか返す．
+    /// 延長しない場合は0を返す．
+    /// # Params
+    /// 1. state 行動をとった後の状態．
+    /// 1. cause_action この状態に至る際に実行された行動．
+    fn extend(&self, state: &S, cause_action: &A) -> u8;
+}
+
+impl<S, A, F> ExtensionPolicy<S, A> for F
+where
+    F: Fn(&S, &A) -> u8,
+{
+    fn extend(&self, state: &S, cause_action: &A) -> u8 {
+        self(state, cause_action)
+    }
+}
+
+/// Singular Extensionで，置換表に記録された最善の行動が他の行動より際立って良いか判定するための設定．
+pub(crate) struct SingularExtension<A, P> {
+    /// 最善の行動の評価値と，他の行動の評価値の差の下限．
+    margin: P,
+    /// 行動を複製する関数．
+    /// 他の行動を確かめた後に，改めて探索するために使う．
+    pub(crate) clone_action: fn(&A) -> A,
+    /// 評価値に余裕を加える関数．結果が評価値の最大値を超える場合は最大値を返す．
+    saturating_add: fn(P, P) -> P,
+    /// 評価値から余裕を引く関数．結果が評価値の最小値を下回る場合は最小値を返す．
+    saturating_sub: fn(P, P) -> P,
+}
+
+impl<A, P> Clone for SingularExtension<A, P>
+where
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            margin: self.margin.clone(),
+            clone_action: self.clone_action,
+            saturating_add: self.saturating_add,
+            saturating_sub: self.saturating_sub,
+        }
+    }
+}
+
+impl<A, P> SingularExtension<A, P>
+where
+    P: Copy,
+{
+    pub(crate) fn new(
+        margin: P,
+        clone_action: fn(&A) -> A,
+        saturating_add: fn(P, P) -> P,
+        saturating_sub: fn(P, P) -> P,
+    ) -> Self {
+        Self {
+            margin,
+            clone_action,
+            saturating_add,
+            saturating_sub,
+        }
+    }
+
+    /// 最善の行動の評価値から，他の行動が超えてはならない評価値を求める．
+    /// 自分の手番では評価値から余裕を引き，相手の手番では余裕を加える．
+    pub(crate) fn singular_bound(&self, payoff: P, is_own_turn: bool) -> P {
+        if is_own_turn {
+            (self.saturating_sub)(payoff, self.margin)
+        } else {
+            (self.saturating_add)(payoff, self.margin)
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{Action, Actor, OutcomeRule, SearchDepth, TerminalKind};
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Write};
+use core::marker::PhantomData;
+
+/// ゲーム終了時の結果．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameOutcome {
+    /// 指定したプレイヤーの勝ち．
+    Win(Actor),
+    /// 引き分け．
+    Draw,
+}
+
+/// DOT形式のラベルに含める，状態の`Debug`表現の最大の文字数．
+const DOT_LABEL_LENGTH: usize = 40;
+
+/// 指定した深さまでのすべての分岐を保持するゲーム木．
+///
+/// ゲーム木の可視化や解析など，最善の行動だけでなく木全体が必要な場合に用いる．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullGameTree<S, A> {
+    /// このノードの状態．
+    pub state: S,
+    /// このノードの状態で取れる行動と，その行動をとった後のゲーム木．
+    /// 末端ノードでは空となる．
+    pub children: Vec<(A, FullGameTree<S, A>)>,
+    /// このノードの状態でゲームが終了している場合，その結果．
+    pub game_result: Option<GameOutcome>,
+}
+
+impl<S, A> FullGameTree<S, A> {
+    /// 根ノードから幅優先でノードを列挙する．
+    /// 各ノードについて，根ノードからの深さ，状態，そのノードに至った行動を返す．
+    /// 根ノードに至った行動は`None`となる．
+    pub fn iter_nodes(&self) -> impl Iterator<Item = (usize, &S, Option<&A>)> {
+        let mut queue = VecDeque::new();
+        queue.push_back((0, self, None));
+        core::iter::from_fn(move || {
+            let (depth, node, action) = queue.pop_front()?;
+            for (child_action, child) in node.children.iter() {
+                queue.push_back((depth + 1, child, Some(child_action)));
+            }
+            Some((depth, &node.state, action))
+        })
+    }
+
+    /// ゲーム木をGraphvizのDOT形式で表した文字列を返す．
+    /// 既定の設定`DotConfig::default()`を用いる．
+    pub fn to_dot(&self) -> String
+    where
+        S: Debug,
+        A: Action + Debug,
+    {
+        self.to_dot_with(&DotConfig::default())
+    }
+
+    /// ゲーム木を，指定した設定でGraphvizのDOT形式で表した文字列を返す．
+    ///
+    /// 各ノードのラベルは状態の`Debug`表現を短く切り詰めたものであり，ゲームが終了したノードではその結果も含む．
+    /// 各辺のラベルは行動の`Debug`表現である．
+    pub fn to_dot_with(&self, config: &DotConfig) -> String
+    where
+        S: Debug,
+        A: Action + Debug,
+    {
+        let principal_line = if config.highlight_pv {
+            Some(self.principal_line())
+        } else {
+            None
+        };
+        let mut dot = String::from("digraph game_tree {\n");
+        dot.push_str("    node [shape=box];\n");
+        let mut next_id = 0;
+        self.write_dot(&mut dot, &mut next_id, 0, config, principal_line.as_deref());
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// このノードと，設定した深さまでの子孫をDOT形式で書き出し，このノードの識別番号を返す．
+    /// `principal_line`は，このノードが最善手順上にある場合の，このノードから先の最善手順である．
+    fn write_dot(
+        &self,
+        dot: &mut String,
+        next_id: &mut usize,
+        depth: usize,
+        config: &DotConfig,
+        principal_line: Option<&[usize]>,
+    ) -> usize
+    where
+        S: Debug,
+        A: Debug,
+    {
+        let id = *next_id;
+        *next_id += 1;
+
+        let mut label = truncate(&format!("{:?}", self.state), DOT_LABEL_LENGTH);
+        match self.game_result {
+            Some(GameOutcome::Win(winner)) => {
+                let _ = write!(label, "\n{:?} wins", winner);
+            }
+            Some(GameOutcome::Draw) => label.push_str("\ndraw"),
+            None => {}
+        }
+        let highlight = if principal_line.is_some() {
+            ", color=red"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            dot,
+            "    n{} [label=\"{}\"{}];",
+            id,
+            escape(&label),
+            highlight
+        );
+
+        if depth >= config.max_depth {
+            return id;
+        }
+        for (index, (action, child)) in self.children.iter().enumerate() {
+            let child_line = match principal_line {
+                Some([first, rest @ ..]) if *first == index => Some(rest),
+                _ => None,
+            };
+            let child_id = child.write_dot(dot, next_id, depth + 1, config, child_line);
+            let highlight = if child_line.is_some() {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [label=\"{}\"{}];",
+                id,
+                child_id,
+                escape(&format!("{:?}", action)),
+                highlight
+            );
+        }
+        id
+    }
+
+    /// 根ノードから，各ノードで行動するプレイヤーが最善の子ノードを選び続けた手順を，子ノードの添字の列として返す．
+    /// 最善の子ノードはゲームの結果だけで決め，展開されていない末端ノードは引き分けとみなす．
+    /// 結果が等しい子ノードの間では，先に並ぶものを選ぶ．
+    fn principal_line(&self) -> Vec<usize>
+    where
+        A: Action,
+    {
+        let mut line = vec![];
+        let mut node = self;
+        while let Some(index) = node.best_child() {
+            line.push(index);
+            node = &node.children[index].1;
+        }
+        line
+    }
+
+    /// 行動するプレイヤーにとって結果が最も良い子ノードの添字を返す．
+    fn best_child(&self) -> Option<usize>
+    where
+        A: Action,
+    {
+        let (action, _) = self.children.first()?;
+        let mover = action.actor();
+        let mut best: Option<(usize, i8)> = None;
+        for (index, (_, child)) in self.children.iter().enumerate() {
+            let value = child.outcome_value(mover);
+            if best.is_none_or(|(_, best)| value > best) {
+                best = Some((index, value));
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+
+    /// 互いに最善を尽くした場合の結果を，指定したプレイヤーにとって勝ちを1，引き分けを0，負けを-1として返す．
+    fn outcome_value(&self, actor: Actor) -> i8
+    where
+        A: Action,
+    {
+        match self.game_result {
+            Some(GameOutcome::Win(winner)) if winner == actor => return 1,
+            Some(GameOutcome::Win(_)) => return -1,
+            Some(GameOutcome::Draw) => return 0,
+            None => {}
+        }
+        match self.best_child() {
+            Some(index) => {
+                let (action, child) = &self.children[index];
+                let mover = action.actor();
+                let value = child.outcome_value(mover);
+                if mover == actor {
+                    value
+                } else {
+                    -value
+                }
+            }
+            None => 0,
+        }
+    }
+}
+
+/// ゲーム木をDOT形式で表す際の設定．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DotConfig {
+    /// 書き出すノードの，根ノードからの深さの上限．
+    pub max_depth: usize,
+    /// 互いに最善を尽くした場合の手順を強調するか．
+    pub highlight_pv: bool,
+}
+
+/// 深さを制限せず，最善手順を強調しない．
+impl Default for DotConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            highlight_pv: false,
+        }
+    }
+}
+
+/// 文字列が指定した文字数を超える場合，切り詰めて末尾に`...`を付ける．
+fn truncate(text: &str, length: usize) -> String {
+    match text.char_indices().nth(length) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => String::from(text),
+    }
+}
+
+/// DOT形式の文字列リテラルに含められるように，引用符と逆斜線をエスケープし，改行を`\n`に置き換える．
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 指定した状態から，指定した深さまでのすべての分岐を展開したゲーム木を作る．
+pub struct GameTreeBuilder<R> {
+    _r: PhantomData<R>,
+}
+
+impl<R> GameTreeBuilder<R> {
+    pub fn new() -> Self {
+        Self { _r: PhantomData }
+    }
+}
+
+impl<R> Default for GameTreeBuilder<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, A, R> GameTreeBuilder<R>
+where
+    S: Clone,
+    R: OutcomeRule<S = S, A = A>,
+{
+    /// 指定した状態を根とし，指定した深さまでのすべての分岐を展開したゲーム木を作る．
+    /// # Params
+    /// 1. state 根ノードの状態．
+    /// 1. depth 展開する深さ．
+    /// 1. actor 根ノードの状態で行動するプレイヤー．
+    ///
+    /// 分岐の数は深さに対して指数的に増えるので，深すぎる展開はメモリを大量に消費する．
+    pub fn build<N>(&self, state: &S, depth: N, actor: Actor) -> FullGameTree<S, A>
+    where
+        N: SearchDepth,
+    {
+        if let Some(kind) = R::terminal_kind(state) {
+            let game_result = match kind {
+                TerminalKind::Win(winner) => GameOutcome::Win(winner),
+                TerminalKind::Draw | TerminalKind::NoMovesAvailable => GameOutcome::Draw,
+            };
+            return FullGameTree {
+                state: state.clone(),
+                children: vec![],
+                game_result: Some(game_result),
+            };
+        }
+
+        let children = if depth.is_zero() {
+            vec![]
+        } else {
+            R::iterate_available_actions(state, actor)
+                .map(|action| {
+                    let next_state = R::translate_state(state, &action);
+                    let child = self.build(&next_state, depth - N::one(), actor.opponent());
+                    (action, child)
+                })
+                .collect()
+        };
+        FullGameTree {
+            state: state.clone(),
+            children,
+            game_result: None,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Actor, Evaluator};
+use alloc::vec::Vec;
+use data_structure::Range;
+
+/// 探索中に，状態の評価値を軽い計算で見積もれる評価関数．
+///
+/// Futility PruningやRazoringでは，末端に近いノードでこの見積もりに余裕を加えても関心範囲に届かない場合，そのノードの探索を省略する．
+pub trait StaticEvaluator<S>: Evaluator<S> {
+    /// 指定された状態について，利得を軽い計算で見積もる．
+    /// 既定では`Evaluator::evaluate_payoff_for`の値をそのまま用いる．
+    fn static_payoff_for(actor: Actor, state: &S) -> Self::Payoff {
+        Self::evaluate_payoff_for(actor, state)
+    }
+}
+
+/// Futility PruningやRazoringで，評価値の見積もりが関心範囲に届く見込みがあるか判定するための設定．
+pub(crate) struct MarginPruning<S, P, N> {
+    /// 残りの探索深さが`i + 1`のノードで，見積もりに加える余裕`margins[i]`．
+    margins: Vec<P>,
+    /// 状態の評価値を見積もる関数．
+    static_payoff: fn(Actor, &S) -> P,
+    /// 残りの探索深さを`margins`の添字に変換する関数．
+    depth_index: fn(N) -> Option<usize>,
+    /// 評価値に余裕を加える関数．結果が評価値の最大値を超える場合は最大値を返す．
+    saturating_add: fn(P, P) -> P,
+    /// 評価値から余裕を引く関数．結果が評価値の最小値を下回る場合は最小値を返す．
+    saturating_sub: fn(P, P) -> P,
+}
+
+impl<S, P, N> Clone for MarginPruning<S, P, N>
+where
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            margins: self.margins.clone(),
+            static_payoff: self.static_payoff,
+            depth_index: self.depth_index,
+            saturating_add: self.saturating_add,
+            saturating_sub: self.saturating_sub,
+        }
+    }
+}
+
+impl<S, P, N> MarginPruning<S, P, N> {
+    pub(crate) fn new(
+        margins: Vec<P>,
+        static_payoff: fn(Actor, &S) -> P,
+        depth_index: fn(N) -> Option<usize>,
+        saturating_add: fn(P, P) -> P,
+        saturating_sub: fn(P, P) -> P,
+    ) -> Self {
+        Self {
+            margins,
+            static_payoff,
+            depth_index,
+            saturating_add,
+            saturating_sub,
+        }
+    }
+}
+
+impl<S, P, N> MarginPruning<S, P, N>
+where
+    P: Copy + PartialOrd,
+{
+    /// 注目ノードの評価値が関心範囲に届く見込みがないか判定する．
+    /// # Params
+    /// 1. remaining_depth 注目ノードの残りの探索深さ．
+    /// 1. state 注目ノードの状態．
+    /// 1. consideration_target 評価値を計算する対象のプレイヤー．
+    /// 1. next_actor 注目ノードで行動するプレイヤー．
+    /// 1. payoff_range 注目ノードの評価値の関心範囲．
+    ///
+    /// # Returns
+    /// 見込みがない場合は，注目ノードの状態の見積もり．
+    pub(crate) fn hopeless_payoff(
+        &self,
+        remaining_depth: N,
+        state: &S,
+        consideration_target: Actor,
+        next_actor: Actor,
+        payoff_range: Range<P>,
+    ) -> Option<P> {
+        let margin = *self.margins.get((self.depth_index)(remaining_depth)?)?;
+        let payoff = (self.static_payoff)(consideration_target, state);
+        // 自分の手番では見積もりに余裕を加えても下限に届かず，相手の手番では余裕を引いても上限を下回らない
+        let is_hopeless = if next_actor == consideration_target {
+            (self.saturating_add)(payoff, margin) < payoff_range.min
+        } else {
+            (self.saturating_sub)(payoff, margin) > payoff_range.max
+        };
+        Some(payoff).filter(|_| is_hopeless)
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use num::ToPrimitive;
+
+/// History Heuristicで，行動の実績を記録する際に用いる番号を与える．
+///
+/// 行動の実績は，この番号ごとに記録される．
+/// カウンター手を用いる場合は，相手の行動もこの番号で区別する．
+/// 異なる局面で同じ意味を持つ行動 (同じマスに置く，同じ駒を同じマスへ動かすなど) には，同じ番号を与えるとよい．
+/// 番号はそのまま表の添字として使われるので，小さい値であるほど表が小さくなる．
+pub trait HistoryKey {
+    /// この行動の番号を返す．
+    fn history_key(&self) -> usize;
+}
+
+/// 各行動が枝刈りを起こした実績を記録する表 (History Heuristic)．
+///
+/// ある局面で枝刈りを起こした行動は，別の局面でも有望であることが多い．
+/// 実績の大きい行動から順に調べることで，枝刈りが起こりやすくなる．
+/// 深い探索で起こった枝刈りほど信頼できるので，残りの探索深さを重みとして加算する．
+pub(crate) struct HistoryTable<A, N> {
+    /// 行動の番号ごとの実績．
+    scores: Vec<u64>,
+    /// 行動の番号を計算する関数．
+    /// 行動の型に制約を課さずに探索に組み込めるよう，作成時に受け取っておく．
+    key: fn(&A) -> usize,
+    /// 残りの探索深さを重みに変換する関数．
+    weight: fn(N) -> u64,
+}
+
+// 関数ポインタは常に複製できるので，行動や深さの型には制約を課さない
+impl<A, N> Clone for HistoryTable<A, N> {
+    fn clone(&self) -> Self {
+        Self {
+            scores: self.scores.clone(),
+            key: self.key,
+            weight: self.weight,
+        }
+    }
+}
+
+impl<A, N> HistoryTable<A, N> {
+    pub(crate) fn new() -> Self
+    where
+        A: HistoryKey,
+        N: ToPrimitive,
+    {
+        Self {
+            scores: vec![],
+            key: A::history_key,
+            weight: depth_weight::<N>,
+        }
+    }
+
+    /// 指定した行動の実績を返す．
+    pub(crate) fn score(&self, action: &A) -> u64 {
+        self.scores.get((self.key)(action)).copied().unwrap_or(0)
+    }
+
+    /// 残りの探索深さが指定した値であるノードで，指定した行動が枝刈りを起こしたことを記録する．
+    pub(crate) fn record_cutoff(&mut self, remaining_depth: N, action: &A) {
+        let key = (self.key)(action);
+        if self.scores.len() <= key {
+            self.scores.resize(key + 1, 0);
+        }
+        self.scores[key] = self.scores[key].saturating_add((self.weight)(remaining_depth));
+    }
+
+    /// 記録した実績をすべて消去し，確保していたメモリを解放する．
+    pub(crate) fn clear(&mut self) {
+        self.scores = vec![];
+    }
+
+    /// 実績を記録するために確保しているメモリのバイト数を返す．
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.scores.capacity() * mem::size_of::<u64>()
+    }
+}
+
+/// 残りの探索深さを，そのまま重みとする．
+fn depth_weight<N>(remaining_depth: N) -> u64
+where
+    N: ToPrimitive,
+{
+    remaining_depth.to_u64().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl HistoryKey for usize {
+        fn history_key(&self) -> usize {
+            *self
+        }
+    }
+
+    #[test]
+    fn test_record_cutoff() {
+        let mut table = HistoryTable::<usize, u32>::new();
+        assert_eq!(0, table.score(&3));
+        table.record_cutoff(2, &3);
+        table.record_cutoff(5, &3);
+        table.record_cutoff(1, &0);
+        assert_eq!(7, table.score(&3));
+        assert_eq!(1, table.score(&0));
+        assert_eq!(0, table.score(&1));
+        assert_eq!(0, table.score(&100));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut table = HistoryTable::<usize, u32>::new();
+        table.record_cutoff(2, &3);
+        table.clear();
+        assert_eq!(0, table.score(&3));
+    }
+}
@@ -0,0 +1,443 @@
+use crate::clock::{Deadline, Stopwatch};
+use crate::{
+    Action, Actor, AlphaBetaStrategy, Evaluator, MoveOrdering, RootSearch, Rule, ScoreBound,
+    ScoredAction, ScoredStrategy, SearchDepth, SearchHook, SearchLimits, SearchStats, State,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use data_structure::Range;
+use num::{Bounded, CheckedAdd, CheckedSub};
+
+/// 反復深化における，ある深さでの探索結果．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepeningIteration<A, P, N> {
+    /// 探索したゲーム木の深さ．
+    pub depth: N,
+    /// この深さで最善と判断された行動．
+    pub best_action: Option<A>,
+    /// この深さで得られた，根ノードの評価値．
+    pub payoff: Option<P>,
+    /// 根ノードの評価値が勝ちまたは負けである場合，その勝敗が決まる末端ノードまでの手数．
+    pub depth_to_terminal: Option<u32>,
+    /// この深さの探索で訪れたノードの数．窓を広げて探索し直した分も含む．
+    pub nodes_evaluated: u64,
+    /// この深さの探索を最後まで終えたか．
+    /// 時間切れで打ち切られた場合は`false`となり，探索を終えた根ノードの行動のうち最善のものが記録される．
+    pub completed: bool,
+}
+
+/// 反復深化による探索結果．
+/// 探索を終えた各深さの結果を，浅いものから順に保持する．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IterativeDeepeningResult<A, P, N> {
+    iterations: Vec<DeepeningIteration<A, P, N>>,
+    stats: SearchStats,
+}
+
+impl<A, P, N> IterativeDeepeningResult<A, P, N>
+where
+    P: Copy,
+    N: Copy,
+{
+    /// 探索を終えた各深さの結果を，浅いものから順に返す．
+    pub fn iterations(&self) -> &[DeepeningIteration<A, P, N>] {
+        &self.iterations
+    }
+
+    /// 探索を最後まで終えた最も深い深さを返す．
+    /// 1度も探索を終えなかった場合は`None`を返す．
+    pub fn completed_depth(&self) -> Option<N> {
+        self.iterations
+            .iter()
+            .rev()
+            .find(|iteration| iteration.completed)
+            .map(|iteration| iteration.depth)
+    }
+
+    /// すべての深さの探索を合わせた統計情報を返す．
+    pub fn stats(&self) -> &SearchStats {
+        &self.stats
+    }
+
+    /// 最も深い探索で最善と判断された行動を返す．
+    pub fn best_action(&self) -> Option<&A> {
+        self.iterations
+            .last()
+            .and_then(|iteration| iteration.best_action.as_ref())
+    }
+
+    /// 最も深い探索で得られた，根ノードの評価値を返す．
+    pub fn payoff(&self) -> Option<P> {
+        self.iterations
+            .last()
+            .and_then(|iteration| iteration.payoff)
+    }
+
+    /// 最も深い探索で最善と判断された行動を取り出す．
+    pub fn into_best_action(self) -> Option<A> {
+        self.iterations
+            .into_iter()
+            .last()
+            .and_then(|iteration| iteration.best_action)
+    }
+
+    /// 最も深い探索で最善と判断された行動を，根ノードの評価値とともに取り出す．
+    pub fn into_scored_action(self) -> Option<ScoredAction<A, P>> {
+        let iteration = self.iterations.into_iter().last()?;
+        Some(ScoredAction {
+            action: iteration.best_action?,
+            payoff: iteration.payoff?,
+            depth_to_terminal: iteration.depth_to_terminal,
+        })
+    }
+}
+
+/// アスピレーションウィンドウから評価値が外れた場合の，窓の広げ方．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AspirationWidening {
+    /// 評価値が外れた側の窓の幅を2倍にして探索し直す．
+    Double,
+    /// 評価値が外れた側の窓の端を，評価値の最小値 (最大値) まで広げて探索し直す．
+    FullWindow,
+}
+
+/// 直前の深さの評価値を中心とした，狭い関心範囲で探索するための設定．
+pub(crate) struct AspirationWindow<P> {
+    /// 直前の深さの評価値から，窓の端までの幅．
+    delta: P,
+    widening: AspirationWidening,
+    /// 窓を関心範囲全体まで広げる際に用いる幅．評価値の最大値である．
+    full_delta: P,
+    /// 評価値に幅を加える関数．結果が評価値の最大値を超える場合は最大値を返す．
+    saturating_add: fn(P, P) -> P,
+    /// 評価値から幅を引く関数．結果が評価値の最小値を下回る場合は最小値を返す．
+    saturating_sub: fn(P, P) -> P,
+}
+
+impl<P> AspirationWindow<P>
+where
+    P: Copy + PartialOrd,
+{
+    /// 指定した評価値を中心とする，最初に探索する窓を返す．
+    fn initial_range(&self, center: P) -> Range<P> {
+        Range::new(
+            (self.saturating_sub)(center, self.delta),
+            (self.saturating_add)(center, self.delta),
+        )
+    }
+
+    /// 評価値が外れた側に窓を広げる．
+    /// # Params
+    /// 1. center 窓の中心とした評価値．
+    /// 1. delta 現在の窓の，評価値が外れた側の幅．
+    /// 1. range 現在の窓．
+    /// 1. payoff 窓から外れた評価値．
+    ///
+    /// # Returns
+    /// 広げた窓と，評価値が外れた側の新たな幅．
+    fn widen(&self, center: P, delta: P, range: Range<P>, payoff: P) -> (Range<P>, P) {
+        let delta = match self.widening {
+            // 幅が0の場合など，2倍にしても広がらない場合は関心範囲全体まで広げる
+            AspirationWidening::Double => Some((self.saturating_add)(delta, delta))
+                .filter(|&doubled| doubled != delta)
+                .unwrap_or(self.full_delta),
+            AspirationWidening::FullWindow => self.full_delta,
+        };
+        let range = if payoff < range.min {
+            Range::new((self.saturating_sub)(center, delta), range.max)
+        } else {
+            Range::new(range.min, (self.saturating_add)(center, delta))
+        };
+        (range, delta)
+    }
+}
+
+pub(crate) fn saturating_add<P>(left: P, right: P) -> P
+where
+    P: CheckedAdd + Bounded,
+{
+    left.checked_add(&right).unwrap_or_else(P::max_value)
+}
+
+pub(crate) fn saturating_sub<P>(left: P, right: P) -> P
+where
+    P: CheckedSub + Bounded,
+{
+    left.checked_sub(&right).unwrap_or_else(P::min_value)
+}
+
+/// 探索深さを1から順に増やしながらαβ法で探索するエージェント．
+///
+/// ある深さで最善と判断された行動は，次の深さの探索で最初に調べられるので，
+/// 浅い探索の結果が深い探索での枝刈りに活用される．
+pub struct IterativeDeepeningStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各深さの探索に用いるαβ法のエージェント．
+    /// このエージェントの探索深さが，探索する最大の深さとなる．
+    pub(crate) alpha_beta: AlphaBetaStrategy<R, E, N>,
+    /// アスピレーションウィンドウの設定．
+    /// `None`の場合，各深さを関心範囲全体で探索する．
+    pub(crate) aspiration: Option<AspirationWindow<E::Payoff>>,
+}
+
+impl<R, E, N> IterativeDeepeningStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    /// 指定した深さまで，探索深さを1から順に増やしながら探索するエージェントを作る．
+    pub fn new(max_depth: N) -> Self {
+        AlphaBetaStrategy::new(max_depth).into()
+    }
+}
+
+impl<R, E, N> IterativeDeepeningStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 2番目以降の深さを，直前の深さの評価値`e`を中心とした狭い関心範囲`[e - delta, e + delta]`で探索するようにする．
+    ///
+    /// 評価値が関心範囲から外れた場合は，`widening`に従って外れた側の範囲を広げて探索し直す．
+    /// 探索はfail-softなので，関心範囲から外れた評価値を真の評価値の目安として範囲を広げられる．
+    /// 評価値が深さによってあまり変わらない場合，枝刈りが増えて探索量が減る．
+    /// 探索し直した回数は，統計情報の`aspiration_fail_highs`と`aspiration_fail_lows`で確認できる．
+    ///
+    /// 評価値の加減算が必要なので，評価値の型が`CheckedAdd`と`CheckedSub`を実装している場合にのみ利用できる．
+    /// 加減算の結果が評価値の範囲を超える場合は，評価値の最大値 (最小値) として扱う．
+    pub fn with_aspiration_window(self, delta: E::Payoff, widening: AspirationWidening) -> Self
+    where
+        E::Payoff: CheckedAdd + CheckedSub + Bounded,
+    {
+        Self {
+            aspiration: Some(AspirationWindow {
+                delta,
+                widening,
+                full_delta: E::Payoff::max_value(),
+                saturating_add: saturating_add::<E::Payoff>,
+                saturating_sub: saturating_sub::<E::Payoff>,
+            }),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E, N> IterativeDeepeningStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+{
+    /// 探索する最大の深さを返す．
+    pub fn max_depth(&self) -> N {
+        self.alpha_beta.search_depth
+    }
+
+    /// 探索深さを最大の深さまで増やしながらゲーム木を探索する．
+    pub fn search(&self, state: &S, actor: Actor) -> IterativeDeepeningResult<A, E::Payoff, N> {
+        self.search_while(state, actor, |_| true)
+    }
+
+    /// 探索深さを増やしながらゲーム木を探索する．
+    /// 深さ1の探索は必ず行い，それ以降は各深さの探索を始める前に`should_continue`を呼び出す．
+    /// `should_continue`に直前の深さでの探索結果を渡して`false`が返された場合，探索をやめる．
+    pub fn search_while<F>(
+        &self,
+        state: &S,
+        actor: Actor,
+        should_continue: F,
+    ) -> IterativeDeepeningResult<A, E::Payoff, N>
+    where
+        F: FnMut(&DeepeningIteration<A, E::Payoff, N>) -> bool,
+    {
+        deepen(
+            &self.alpha_beta,
+            state,
+            actor,
+            None,
+            self.aspiration.as_ref(),
+            None,
+            should_continue,
+        )
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).payoff()
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for IterativeDeepeningStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.search(state, actor).into_scored_action()
+    }
+}
+
+impl<R, E, N> From<AlphaBetaStrategy<R, E, N>> for IterativeDeepeningStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定したαβ法のエージェントの探索深さまで，反復深化で探索するエージェントを作る．
+    fn from(alpha_beta: AlphaBetaStrategy<R, E, N>) -> Self {
+        Self {
+            alpha_beta,
+            aspiration: None,
+        }
+    }
+}
+
+/// αβ法のエージェントの探索深さまで，探索深さを1から順に増やしながら探索する．
+/// 深さ1の探索は必ず行い，それ以降は各深さの探索を始める前に`should_continue`を呼び出す．
+/// `deadline`を過ぎた場合や，すべての深さを合わせて訪れたノード数がαβ法のエージェントに指定された上限に達した場合は探索を打ち切り，
+/// 打ち切った深さで探索を終えた行動があれば，その結果も記録する．
+/// `aspiration`を指定した場合，2番目以降の深さは直前の深さの評価値を中心とした窓で探索する．
+/// `hook`を指定した場合，各深さの探索の途中経過を通知する．
+pub(crate) fn deepen<S, A, R, E, N, O, F>(
+    alpha_beta: &AlphaBetaStrategy<R, E, N, O>,
+    state: &S,
+    actor: Actor,
+    deadline: Option<Deadline>,
+    aspiration: Option<&AspirationWindow<E::Payoff>>,
+    hook: Option<&dyn SearchHook<S, A, E::Payoff>>,
+    mut should_continue: F,
+) -> IterativeDeepeningResult<A, E::Payoff, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+    O: MoveOrdering<S, A>,
+    F: FnMut(&DeepeningIteration<A, E::Payoff, N>) -> bool,
+{
+    let stopwatch = Stopwatch::start();
+    alpha_beta.advance_transposition_generation();
+    let mut iterations = vec![];
+    let mut stats = SearchStats::default();
+    let mut best_action_index = None;
+    let mut depth = N::zero();
+
+    while depth < alpha_beta.search_depth {
+        if let Some(last) = iterations.last() {
+            if !should_continue(last) {
+                break;
+            }
+        }
+        // ノード数の上限は，すべての深さの探索で共有する
+        let limits = |stats: &SearchStats| SearchLimits {
+            deadline,
+            node_budget: alpha_beta
+                .node_budget
+                .map(|budget| budget.saturating_sub(stats.nodes_evaluated)),
+        };
+        if limits(&stats).node_budget == Some(0) {
+            stats.node_budget_exhausted = true;
+            break;
+        }
+        depth = depth + N::one();
+        let nodes_before = stats.nodes_evaluated;
+        let full_range = alpha_beta.payoff_bounds.full_range();
+        // 直前の深さの評価値があれば，その周辺だけを探索する
+        let previous_payoff = iterations
+            .last()
+            .and_then(|iteration: &DeepeningIteration<A, E::Payoff, N>| iteration.payoff);
+        let mut window = match (aspiration, previous_payoff) {
+            (Some(aspiration), Some(payoff)) => Some((aspiration, payoff, aspiration.delta)),
+            _ => None,
+        };
+        let mut payoff_range = window.map_or(full_range, |(aspiration, center, _)| {
+            aspiration.initial_range(center)
+        });
+
+        let (root, index, aborted) = loop {
+            // 前回の探索で最善だった行動を最初に調べる
+            let search = alpha_beta.search_root_observed(
+                state,
+                actor,
+                depth,
+                best_action_index,
+                limits(&stats),
+                payoff_range,
+                hook,
+            );
+            let RootSearch {
+                root,
+                best_action_index: index,
+                aborted,
+                stats: iteration_stats,
+            } = search;
+            stats.merge(&iteration_stats);
+
+            // 評価値が窓から外れた場合，真の評価値を得るために窓を広げて探索し直す．
+            // 探索はfail-softなので，窓の外の評価値を広げる目安に使える
+            let failed_bound = root
+                .payoff
+                .map(|e| ScoreBound::classify(e, payoff_range))
+                .filter(|bound| !bound.is_exact());
+            match (window, failed_bound) {
+                (Some((aspiration, center, delta)), Some(bound)) if !aborted => {
+                    if let ScoreBound::Upper(_) = bound {
+                        stats.aspiration_fail_lows += 1;
+                    } else {
+                        stats.aspiration_fail_highs += 1;
+                    }
+                    let (range, delta) =
+                        aspiration.widen(center, delta, payoff_range, bound.value());
+                    payoff_range = range;
+                    window = Some((aspiration, center, delta));
+                }
+                // 窓を下回った評価値は上界でしかないので，打ち切られた探索の行動は比較できない
+                (Some(_), Some(ScoreBound::Upper(_))) => break (None, None, true),
+                _ => break (Some(root), index, aborted),
+            }
+        };
+        let root = match root {
+            Some(root) => root,
+            None => break,
+        };
+        let payoff = root.payoff;
+        let depth_to_terminal = root.terminal_ply;
+        let best_action = payoff
+            .and_then(|_| root.into_child())
+            .and_then(|best_node| best_node.into_inner().cause_action);
+
+        // 打ち切られた探索でも，探索を終えた行動があればその結果は利用できる
+        if !aborted || best_action.is_some() {
+            best_action_index = index;
+            iterations.push(DeepeningIteration {
+                depth,
+                best_action,
+                payoff,
+                depth_to_terminal,
+                nodes_evaluated: stats.nodes_evaluated - nodes_before,
+                completed: !aborted,
+            });
+        }
+        if aborted {
+            break;
+        }
+    }
+
+    stats.elapsed = stopwatch.elapsed();
+    IterativeDeepeningResult { iterations, stats }
+}
@@ -0,0 +1,115 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+/// 各深さで枝刈りを起こした行動 (キラー手) を記録する表．
+///
+/// ある局面で枝刈りを起こした行動は，同じ深さの兄弟局面でも枝刈りを起こしやすい．
+/// 各深さについて，最近枝刈りを起こした行動を2つまで記録する．
+pub(crate) struct KillerTable<A, N> {
+    /// 残りの探索深さと，その深さで記録されたキラー手．
+    /// 先頭のキラー手ほど新しい．
+    slots: Vec<(N, [Option<A>; 2])>,
+    /// 行動が等しいか判定する関数．
+    /// 行動の型に制約を課さずに探索に組み込めるよう，作成時に受け取っておく．
+    equals: fn(&A, &A) -> bool,
+    /// 行動を複製する関数．
+    clone: fn(&A) -> A,
+}
+
+impl<A, N> KillerTable<A, N> {
+    pub(crate) fn new() -> Self
+    where
+        A: PartialEq + Clone,
+    {
+        Self {
+            slots: vec![],
+            equals: A::eq,
+            clone: A::clone,
+        }
+    }
+
+    /// 記録したキラー手をすべて消去し，確保していたメモリを解放する．
+    pub(crate) fn clear(&mut self) {
+        self.slots = vec![];
+    }
+
+    /// キラー手を記録するために確保しているメモリのバイト数を返す．
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.slots.capacity() * mem::size_of::<(N, [Option<A>; 2])>()
+    }
+}
+
+impl<A, N> KillerTable<A, N>
+where
+    N: PartialEq,
+{
+    /// 指定した行動が，指定した深さのキラー手か．
+    pub(crate) fn is_killer(&self, remaining_depth: &N, action: &A) -> bool {
+        self.slots
+            .iter()
+            .find(|(depth, _)| depth == remaining_depth)
+            .is_some_and(|(_, killers)| {
+                killers
+                    .iter()
+                    .flatten()
+                    .any(|killer| (self.equals)(killer, action))
+            })
+    }
+
+    /// 指定した深さで，指定した行動が枝刈りを起こしたことを記録する．
+    pub(crate) fn record_cutoff(&mut self, remaining_depth: N, action: &A) {
+        let index = match self
+            .slots
+            .iter()
+            .position(|(depth, _)| *depth == remaining_depth)
+        {
+            Some(index) => index,
+            None => {
+                self.slots.push((remaining_depth, [None, None]));
+                self.slots.len() - 1
+            }
+        };
+        let (equals, clone) = (self.equals, self.clone);
+        let killers = &mut self.slots[index].1;
+        // 同じ行動で2つの枠を埋めないよう，既に先頭にある行動は記録し直さない
+        if !killers[0]
+            .as_ref()
+            .is_some_and(|killer| equals(killer, action))
+        {
+            killers[1] = killers[0].take();
+            killers[0] = Some(clone(action));
+        }
+    }
+}
+
+/// 探索ごとに空の表を作る関数．
+pub(crate) type KillerFactory<A, N> = fn() -> KillerTable<A, N>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_two_recent_killers() {
+        let mut table = KillerTable::new();
+        table.record_cutoff(3, &"a");
+        table.record_cutoff(3, &"b");
+        table.record_cutoff(3, &"b");
+        assert!(table.is_killer(&3, &"a"));
+        assert!(table.is_killer(&3, &"b"));
+
+        table.record_cutoff(3, &"c");
+        assert!(!table.is_killer(&3, &"a"));
+        assert!(table.is_killer(&3, &"b"));
+        assert!(table.is_killer(&3, &"c"));
+    }
+
+    #[test]
+    fn test_killers_are_per_depth() {
+        let mut table = KillerTable::new();
+        table.record_cutoff(2, &"a");
+        assert!(table.is_killer(&2, &"a"));
+        assert!(!table.is_killer(&1, &"a"));
+    }
+}
@@ -0,0 +1,339 @@
+use crate::parallel::build_thread_pool;
+use crate::{
+    Action, Actor, AlphaBetaStrategy, AtomicPackable, ConcurrentTranspositionTable, Evaluator,
+    LockFreeTranspositionTable, Rule, ScoredAction, ScoredStrategy, SearchContext, SearchDepth,
+    SearchLimits, SearchStats, State, TranspositionStore, DEFAULT_TRANSPOSITION_TABLE_CAPACITY,
+};
+use core::hash::Hash;
+use core::sync::atomic::{AtomicBool, Ordering};
+use num::Bounded;
+use rayon::ThreadPool;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+/// 2人零和ゲームにおける適切な行動を，複数のスレッドが同じ根ノードを反復深化で探索するαβ法 (Lazy SMP) で思考するエージェント．
+///
+/// すべてのスレッドは1つの置換表を共有し，他のスレッドが記録した評価値や最善の行動を利用して探索量を減らす．
+/// 補助スレッドは探索深さをずらして探索するので，メインスレッドとは異なる順に子ノードを調べることになる．
+/// 行動の選択にはメインスレッドの探索結果だけを用い，メインスレッドが探索を終えると補助スレッドの探索を打ち切る．
+/// 補助スレッドがより深く探索して記録した評価値も用いるので，根ノードの評価値は同じ深さの逐次探索と一致するとは限らない．
+///
+/// 置換表は行動選択をまたいで再利用される．
+/// `T`は共有する置換表の型であり，共有参照`&T`が`TranspositionStore`を実装する置換表を用いることができる．
+/// History Heuristicはスレッド間で共有できないので用いない．
+///
+/// `rayon`フィーチャが有効な場合にだけ使える．
+pub struct LazySmpStrategy<
+    R,
+    E,
+    N,
+    T = ConcurrentTranspositionTable<<R as Rule>::S, <E as Evaluator<<R as Rule>::S>>::Payoff, N>,
+> where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各スレッドの探索に用いるαβ法のエージェント．
+    /// このエージェントの探索深さが，メインスレッドが探索する最大の深さとなる．
+    alpha_beta: AlphaBetaStrategy<R, E, N>,
+    /// スレッド間で共有する置換表．
+    transposition_table: T,
+    /// 補助スレッドごとの，最大の深さに加える深さ．
+    /// 要素数が補助スレッドの数となる．
+    depth_offsets: Vec<N>,
+    /// 探索に用いるスレッドプール．
+    /// `None`の場合，`rayon`の既定のスレッドプールを用いる．
+    thread_pool: Option<ThreadPool>,
+}
+
+impl<R, E, N> LazySmpStrategy<R, E, N>
+where
+    R: Rule,
+    R::S: Hash,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+    N: SearchDepth,
+{
+    /// 複数のスレッドが置換表を共有して反復深化で探索する (Lazy SMP) αβ法で思考するエージェントを作る．
+    /// 置換表は`DEFAULT_TRANSPOSITION_TABLE_CAPACITY`個の状態を記録できる．
+    pub fn new(search_depth: N) -> Self {
+        AlphaBetaStrategy::new(search_depth).into()
+    }
+}
+
+impl<R, E, N, T> LazySmpStrategy<R, E, N, T>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    N: SearchDepth,
+{
+    /// 指定した数の状態を記録できる置換表を，スレッド間で共有して探索するようにする．
+    /// 既定では`DEFAULT_TRANSPOSITION_TABLE_CAPACITY`個の状態を記録できる．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_transposition_table(self, capacity: usize) -> LazySmpStrategy<R, E, N>
+    where
+        R::S: Hash,
+    {
+        self.with_custom_transposition_table(ConcurrentTranspositionTable::new(capacity))
+    }
+
+    /// 指定した置換表を，スレッド間で共有して探索するようにする．
+    /// 状態のハッシュ値の計算方法や，置換表の実装を変更したい場合に使用する．
+    /// 探索するには，共有参照`&U`が`TranspositionStore`を実装している必要がある．
+    pub fn with_custom_transposition_table<U>(self, table: U) -> LazySmpStrategy<R, E, N, U> {
+        LazySmpStrategy {
+            alpha_beta: self.alpha_beta,
+            transposition_table: table,
+            depth_offsets: self.depth_offsets,
+            thread_pool: self.thread_pool,
+        }
+    }
+
+    /// 指定した数の状態を記録できる，排他制御を用いない置換表をスレッド間で共有して探索するようにする．
+    /// 評価値と探索深さが`AtomicPackable`に収まらない探索結果は記録されない．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_lock_free_transposition_table(
+        self,
+        capacity: usize,
+    ) -> LazySmpStrategy<R, E, N, LockFreeTranspositionTable<R::S, E::Payoff, N>>
+    where
+        R::S: Hash,
+        E::Payoff: AtomicPackable,
+        N: AtomicPackable,
+    {
+        self.with_custom_transposition_table(LockFreeTranspositionTable::new(capacity))
+    }
+
+    /// メインスレッドを含めて，指定した数のスレッドで探索するようにする．
+    /// 補助スレッドは，1つおきに最大の深さより1だけ深く探索する．
+    /// 既定では`rayon`の既定のスレッドプールのスレッド数だけ探索する．
+    /// # Panics
+    /// `threads`が0の場合，またはスレッドプールを作れなかった場合
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            depth_offsets: default_depth_offsets(threads),
+            thread_pool: Some(build_thread_pool(threads)),
+            ..self
+        }
+    }
+
+    /// 補助スレッドごとに，最大の深さに加える深さを指定する．
+    /// スレッドの数は，メインスレッドを含めて`depth_offsets.len() + 1`となる．
+    /// # Panics
+    /// スレッドプールを作れなかった場合
+    pub fn with_depth_offsets(self, depth_offsets: Vec<N>) -> Self {
+        let thread_pool = build_thread_pool(depth_offsets.len() + 1);
+        Self {
+            depth_offsets,
+            thread_pool: Some(thread_pool),
+            ..self
+        }
+    }
+
+    /// 補助スレッドごとの，最大の深さに加える深さを返す．
+    pub fn depth_offsets(&self) -> &[N] {
+        &self.depth_offsets
+    }
+}
+
+/// 指定した数のスレッドのうち，補助スレッドの深さを1つおきに1だけ深くする．
+fn default_depth_offsets<N>(threads: usize) -> Vec<N>
+where
+    N: SearchDepth,
+{
+    (1..threads)
+        .map(|thread| if thread % 2 == 1 { N::one() } else { N::zero() })
+        .collect()
+}
+
+impl<R, E, N> From<AlphaBetaStrategy<R, E, N>> for LazySmpStrategy<R, E, N>
+where
+    R: Rule,
+    R::S: Hash,
+    E: Evaluator<R::S>,
+    N: SearchDepth,
+{
+    /// 指定したαβ法のエージェントと同じ設定で，複数のスレッドで探索するようにする．
+    /// 元のエージェントの置換表とHistory Heuristicは用いない．
+    fn from(alpha_beta: AlphaBetaStrategy<R, E, N>) -> Self {
+        Self {
+            // 差分更新したハッシュ値は元の置換表のものなので，共有する置換表では使えない
+            alpha_beta: AlphaBetaStrategy {
+                incremental_hash: None,
+                ..alpha_beta
+            },
+            transposition_table: ConcurrentTranspositionTable::new(
+                DEFAULT_TRANSPOSITION_TABLE_CAPACITY,
+            ),
+            depth_offsets: default_depth_offsets(rayon::current_num_threads()),
+            thread_pool: None,
+        }
+    }
+}
+
+impl<S, A, R, E, N, T> LazySmpStrategy<R, E, N, T>
+where
+    S: State + Sync,
+    A: Action + Send,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Send + Sync,
+    N: SearchDepth + Send + Sync,
+    T: Sync,
+    for<'t> &'t T: TranspositionStore<S, E::Payoff, N>,
+{
+    /// すべてのスレッドで探索し，メインスレッドの探索結果と，すべてのスレッドの統計情報を返す．
+    fn search(&self, state: &S, actor: Actor) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let search_depth = self.alpha_beta.search_depth;
+        if search_depth.is_zero() || R::terminal_kind(state).is_some() {
+            let stats = SearchStats {
+                nodes_evaluated: 1,
+                ..SearchStats::default()
+            };
+            return (None, stats);
+        }
+
+        let stop = AtomicBool::new(false);
+        let stats = Mutex::new(SearchStats::default());
+        let search = || {
+            let mut main = None;
+            rayon::scope(|scope| {
+                for &offset in self.depth_offsets.iter() {
+                    let (stop, stats) = (&stop, &stats);
+                    scope.spawn(move |_| {
+                        let (_, helper_stats) =
+                            self.deepen(state, actor, search_depth + offset, Some(stop));
+                        Self::lock(stats).merge(&helper_stats);
+                    });
+                }
+                let (best, main_stats) = self.deepen(state, actor, search_depth, None);
+                // メインスレッドの探索を終えたら，補助スレッドの探索は不要になる
+                stop.store(true, Ordering::Relaxed);
+                Self::lock(&stats).merge(&main_stats);
+                main = best;
+            });
+            main
+        };
+        let best = match self.thread_pool.as_ref() {
+            Some(pool) => pool.install(search),
+            None => search(),
+        };
+        let stats = *Self::lock(&stats);
+        (best, stats)
+    }
+
+    /// 探索深さを1から指定した深さまで順に増やしながら探索し，最も深い探索の結果を返す．
+    /// `stop`を指定した場合，`true`になった時点で探索を打ち切る．
+    fn deepen(
+        &self,
+        state: &S,
+        actor: Actor,
+        max_depth: N,
+        stop: Option<&AtomicBool>,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let is_stopped = || stop.is_some_and(|stop| stop.load(Ordering::Relaxed));
+        let full_range = self.alpha_beta.payoff_bounds.full_range();
+        let mut stats = SearchStats::default();
+        let mut best = None;
+        let mut best_action_index = None;
+        let mut depth = N::zero();
+
+        while depth < max_depth && !is_stopped() {
+            depth = depth + N::one();
+            let mut context = self.context(actor);
+            context.cancelled = Some(&is_stopped);
+            // 前回の探索で最善だった行動を最初に調べる
+            let search = self.alpha_beta.search_root_in(
+                state,
+                depth,
+                best_action_index,
+                full_range,
+                &mut context,
+            );
+            stats.merge(&search.stats);
+            if search.aborted {
+                break;
+            }
+            best_action_index = search.best_action_index;
+            best = search.root.payoff.and_then(|payoff| {
+                let action = search.root.into_child()?.into_inner().cause_action?;
+                Some(ScoredAction::new(action, payoff))
+            });
+        }
+        (best, stats)
+    }
+
+    /// 1回の探索の間だけ使用する情報を作る．
+    fn context(&self, actor: Actor) -> SearchContext<'_, S, A, E::Payoff, N, &T> {
+        let killers = self
+            .alpha_beta
+            .killer_moves
+            .map(|new_killers| new_killers());
+        let countermoves = self
+            .alpha_beta
+            .countermoves
+            .map(|new_countermoves| new_countermoves());
+        SearchContext::new(
+            actor,
+            Some(&self.transposition_table),
+            SearchLimits::default(),
+            None,
+            killers,
+            countermoves,
+        )
+    }
+
+    fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        let search_depth = self.alpha_beta.search_depth;
+        let terminal = R::terminal_kind(state);
+        if search_depth.is_zero() || terminal.is_some() {
+            return Some(self.alpha_beta.evaluate_node(actor, state, terminal, 0));
+        }
+        self.search(state, actor).0.map(|scored| scored.payoff)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    /// 統計情報は，すべてのスレッドの探索を合計したものである．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    /// 統計情報は，すべてのスレッドの探索を合計したものである．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let start = Instant::now();
+        let (scored, mut stats) = self.search(state, actor);
+        stats.elapsed = start.elapsed();
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N, T> ScoredStrategy<S, A> for LazySmpStrategy<R, E, N, T>
+where
+    S: State + Sync,
+    A: Action + Send,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Send + Sync,
+    N: SearchDepth + Send + Sync,
+    T: Sync,
+    for<'t> &'t T: TranspositionStore<S, E::Payoff, N>,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
@@ -1,14 +1,181 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(feature = "alloc"))]
+compile_error!(
+    "minimax_strategy requires the `alloc` feature (enabled by the default `std` feature)"
+);
+
+mod analysis;
+mod aspiration;
+mod beam;
+mod best_first;
+mod bns;
+mod bound;
+mod builder;
+mod clock;
+mod closure_evaluator;
+mod composed;
+mod countermove;
 mod cow_ref;
+mod depth;
+mod dfpn;
+mod expectimax;
+mod explicit_stack;
+mod extension;
+mod full_tree;
+mod futility;
+mod history;
+mod iterative_deepening;
+mod killer;
+#[cfg(feature = "rayon")]
+mod lazy_smp;
+#[cfg(feature = "rayon")]
+mod lock_free;
+mod macros;
+mod max_n;
+#[cfg(feature = "std")]
+mod mcts;
+mod memory;
+mod minimax;
+mod move_ordering;
+#[cfg(feature = "std")]
+mod mtdf;
+mod negamax;
 mod node;
+mod observe;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod principal_variation;
+mod proof_number;
+mod pvs;
+mod quiescence;
+#[cfg(feature = "std")]
+mod random;
+mod record;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "std")]
+mod solver;
+mod stats;
+mod symmetric;
+mod symmetry;
+mod sync;
+#[cfg(feature = "std")]
+mod time_limited;
+mod transposition;
+mod undo;
+mod weighted;
+mod zobrist;
 
-use cow_ref::CowRef;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use clock::{Deadline, Stopwatch};
+use core::cell::RefCell;
+use core::cmp::Reverse;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::BuildHasherDefault;
+use core::hash::Hash;
+use core::iter::Enumerate;
+use core::marker::PhantomData;
+use countermove::{CountermoveFactory, CountermoveTable};
 use data_structure::Range;
+use extension::SingularExtension;
+use futility::MarginPruning;
+use history::HistoryTable;
+use killer::{KillerFactory, KillerTable};
 use node::TreeNode;
-use num::{Bounded, Integer};
-use std::marker::PhantomData;
+use num::{Bounded, CheckedAdd, CheckedSub, Integer, ToPrimitive};
+use quiescence::NoisyActionGenerator;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "std")]
+use std::time::Instant;
+use sync::Lock;
+use transposition::SharedTranspositionTable;
+use zobrist::{HashUpdater, ZobristRuleHasher};
+
+pub use analysis::{AnalysisDepth, AnalysisReport};
+pub use aspiration::AspirationStrategy;
+pub use beam::BeamSearchStrategy;
+pub use best_first::{BestFirstNode, BestFirstStrategy, BestFirstTree};
+pub use bns::BnsStrategy;
+pub use bound::{PayoffBounds, ScoreBound, WindowMode};
+pub use builder::AlphaBetaStrategyBuilder;
+pub use composed::ComposedStrategy;
+pub use cow_ref::CowRef;
+pub use depth::SearchDepth;
+pub use dfpn::{CyclePolicy, DfpnProof, DfpnSearch};
+pub use expectimax::{ExpectedPayoff, ExpectimaxStrategy, Probability, StochasticRule};
+pub use explicit_stack::ExplicitStackAlphaBetaStrategy;
+pub use extension::ExtensionPolicy;
+pub use full_tree::{DotConfig, FullGameTree, GameOutcome, GameTreeBuilder};
+pub use futility::StaticEvaluator;
+pub use history::HistoryKey;
+pub use iterative_deepening::{
+    AspirationWidening, DeepeningIteration, IterativeDeepeningResult, IterativeDeepeningStrategy,
+};
+#[cfg(feature = "rayon")]
+pub use lazy_smp::LazySmpStrategy;
+#[cfg(feature = "rayon")]
+pub use lock_free::{AtomicPackable, LockFreeTranspositionTable};
+pub use max_n::{MaxNStrategy, MultiAction, MultiActor, MultiEvaluator, MultiRule, MultiStrategy};
+#[cfg(feature = "std")]
+pub use mcts::{MctsStrategy, PayoffToScore, PlayoutPolicy, UniformPlayout};
+pub use memory::{MemoryBudget, MemoryUsage};
+pub use minimax::MinimaxStrategy;
+pub use move_ordering::{CombinedOrdering, MoveOrderer, MoveOrdering, NoOrdering, ReverseOrdering};
+#[cfg(feature = "std")]
+pub use mtdf::{MtdfStrategy, DEFAULT_TRANSPOSITION_TABLE_CAPACITY};
+pub use negamax::NegamaxStrategy;
+#[cfg(feature = "std")]
+pub use observe::PrintingHook;
+pub use observe::{
+    CollectingHook, CutoffReason, ObservableSearch, ObservableStrategy, SearchEvent, SearchHook,
+};
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelAlphaBetaStrategy;
+pub use principal_variation::PrincipalVariation;
+pub use proof_number::{
+    ProofNode, ProofNumberSearch, ProofOutcome, ProofTree, INFINITE_PROOF_NUMBER,
+};
+pub use pvs::PvsStrategy;
+pub use quiescence::QuiescenceRule;
+#[cfg(feature = "std")]
+pub use random::RandomStrategy;
+pub use record::{GameRecord, GameRecordIter, ReplayError};
+#[cfg(feature = "std")]
+pub use solver::{policy_key, solve, solve_with_policy, RuleOutcome, Solution, TerminalOutcome};
+pub use stats::SearchStats;
+pub use symmetric::{FirstActorEvaluator, SymmetricEvaluator};
+pub use symmetry::{CanonicalState, SymmetricStateHasher};
+#[cfg(feature = "std")]
+pub use time_limited::TimeLimitedStrategy;
+#[cfg(feature = "rayon")]
+pub use transposition::ConcurrentTranspositionTable;
+pub use transposition::{
+    BoundType, ReplacementScheme, StateHasher, TranspositionEntry, TranspositionStore,
+    TranspositionTable, TranspositionTableStats,
+};
+pub use undo::{UndoAlphaBetaStrategy, UndoRule};
+pub use weighted::{
+    Weighted, WeightedEvaluator, WeightedEvaluatorBuilder, WeightedEvaluatorSource,
+};
+pub use zobrist::{
+    ZobristHash, ZobristHashable, ZobristKeys, ZobristState, ZobristStateHasher, ZobristTable,
+};
 
 /// 2人ゲームにおけるプレイヤー．
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// 先手は後手より小さいものとして順序付けられる．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Actor {
     /// 先手
     First,
@@ -17,6 +184,9 @@ pub enum Actor {
 }
 
 impl Actor {
+    /// 2人ゲームにおける各プレイヤー．添字の順に並ぶ．
+    pub const ALL: [Actor; 2] = [Actor::First, Actor::Second];
+
     /// この手番に対する相手側の手番を返す．
     pub fn opponent(&self) -> Self {
         match self {
@@ -24,8 +194,48 @@ impl Actor {
             Actor::Second => Actor::First,
         }
     }
+
+    /// プレイヤーごとの配列の添字として，先手は0，後手は1を返す．
+    pub fn index(&self) -> usize {
+        match self {
+            Actor::First => 0,
+            Actor::Second => 1,
+        }
+    }
+
+    /// 指定した添字のプレイヤーを返す．`index`の逆である．
+    /// 添字が2以上の場合は`None`を返す．
+    pub fn from_index(index: usize) -> Option<Actor> {
+        Actor::ALL.get(index).copied()
+    }
+}
+
+impl From<Actor> for usize {
+    fn from(actor: Actor) -> usize {
+        actor.index()
+    }
+}
+
+impl TryFrom<usize> for Actor {
+    type Error = InvalidActorIndex;
+
+    fn try_from(index: usize) -> Result<Actor, InvalidActorIndex> {
+        Actor::from_index(index).ok_or(InvalidActorIndex(index))
+    }
+}
+
+/// プレイヤーの添字として不正な値．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidActorIndex(pub usize);
+
+impl fmt::Display for InvalidActorIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "actor index {} is out of range", self.0)
+    }
 }
 
+impl Error for InvalidActorIndex {}
+
 /// ゲームの状態．
 pub trait State {}
 
@@ -33,6 +243,13 @@ pub trait State {}
 pub trait Action {
     /// この行動の手番．
     fn actor(&self) -> Actor;
+
+    /// 手番のプレイヤーが何もせずに相手に手番を渡す行動 (パス) か．
+    /// αβ法では，両者が続けてパスした状態を，ルールが終了を判定しなくても末端ノードとして扱う．
+    /// 既定ではパスとみなさず，常に`false`を返す．
+    fn is_pass(&self) -> bool {
+        false
+    }
 }
 
 /// ゲーム内の状態遷移条件を記述する．
@@ -42,16 +259,135 @@ pub trait Rule {
     /// このゲームルールにおけるプレイヤーの行動．
     type A;
     /// ある状態において実行可能な行動を列挙する際に使用する型．
+    /// 行動は子ノードを調べる直前に1つずつ取り出されるので，枝刈りが起こった後の行動は列挙されない．
+    /// ただし，行動を並べ替えて調べる場合は，すべての行動が先に列挙される．
     type ActionIterator: Iterator<Item = Self::A>;
 
+    /// 指定した状態がすでにゲーム終了条件を満たしていれば，その終了の種類を返す．
+    /// ゲームが続く場合は`None`を返す．
+    fn terminal_kind(state: &Self::S) -> Option<TerminalKind>;
+
     /// 指定した状態がすでにゲーム終了条件を満たしているか．
-    fn is_game_over(state: &Self::S) -> bool;
+    #[deprecated(note = "use `terminal_kind` instead")]
+    fn is_game_over(state: &Self::S) -> bool {
+        Self::terminal_kind(state).is_some()
+    }
 
     /// 指定された状態下で実行可能な行動を列挙する．
     fn iterate_available_actions(state: &Self::S, actor: Actor) -> Self::ActionIterator;
 
+    /// αβ法で，`iterate_available_actions`の代わりに`collect_available_actions`で行動を列挙するか．
+    /// `true`の場合，行動を格納するバッファを探索の深さごとに使い回すので，ノードごとにメモリを確保せずに済む．
+    /// ただし，行動は子ノードを調べる前にすべて列挙される．
+    /// 既定では`false`である．
+    const COLLECTS_ACTIONS: bool = false;
+
+    /// 指定された状態下で実行可能な行動を，`out`の末尾に追加する．
+    /// 追加する行動とその順序は，`iterate_available_actions`が列挙するものと一致しなければならない．
+    /// 既定では`iterate_available_actions`が列挙する行動を追加する．
+    ///
+    /// 実装する場合は`COLLECTS_ACTIONS`を`true`にする．
+    fn collect_available_actions(state: &Self::S, actor: Actor, out: &mut Vec<Self::A>) {
+        out.extend(Self::iterate_available_actions(state, actor));
+    }
+
+    /// 指定された状態下で実行可能な行動の数を返す．
+    /// 既定では`iterate_available_actions`が列挙する行動を数える．
+    ///
+    /// 行動を列挙せずに数えられる場合に実装する．
+    /// 実装する場合，`iterate_available_actions`が列挙する行動の数と常に一致しなければならない．
+    fn count_available_actions(state: &Self::S, actor: Actor) -> usize {
+        Self::iterate_available_actions(state, actor).count()
+    }
+
+    /// 指定された状態下で実行可能な行動があるか．
+    /// 既定では`count_available_actions`が1以上かを返す．
+    ///
+    /// 実装する場合，`iterate_available_actions`が行動を列挙するかと常に一致しなければならない．
+    fn has_available_actions(state: &Self::S, actor: Actor) -> bool {
+        Self::count_available_actions(state, actor) > 0
+    }
+
     /// 状態を遷移させる．
     fn translate_state(state: &Self::S, action: &Self::A) -> Self::S;
+
+    /// αβ法で，各ノードの行動を`heuristic_action_score`の大きい順に並べ替えてから調べるか．
+    /// `true`の場合，行動は子ノードを調べる前にすべて列挙される．
+    /// 既定では`false`である．
+    const SCORES_ACTIONS: bool = false;
+
+    /// 指定した状態で指定した行動をとることの有望さを，手番のプレイヤーの視点で表す簡易的な点数．
+    /// αβ法では点数の大きい行動から順に調べるので，駒を取る行動や王手などの強制力のある行動に大きな点数を与えると枝刈りが起こりやすくなる．
+    /// 点数の等しい行動は`iterate_available_actions`が列挙する順に調べる．
+    /// `MoveOrdering`や`MoveOrderer`も指定した場合，この点数で並べ替えた後にそれらで並べ替える．
+    /// 既定では常に0を返す．
+    ///
+    /// 各行動について1回ずつ呼ばれるので，状態の評価よりも十分軽い計算で求める．
+    /// 実装する場合は`SCORES_ACTIONS`を`true`にする．
+    fn heuristic_action_score(_state: &Self::S, _action: &Self::A) -> i32 {
+        0
+    }
+
+    /// 指定した行動が，駒を取るなどの局面を大きく変える行動か．
+    /// 静止探索では，この値が`true`となる行動だけを調べる．
+    fn is_loud_action(_action: &Self::A) -> bool {
+        false
+    }
+
+    /// 指定した状態が，静的評価値を信頼できる静かな局面か．
+    /// 静止探索では，静かな局面に達した時点で，`quiescence_depth`の残りにかかわらず静的評価値を用いる．
+    /// 静かでない局面では，`quiescence_depth`を使い切るか，調べる行動がなくなるまで静止探索を続ける．
+    /// 静止探索を行わない場合は呼び出されない．
+    ///
+    /// 既定では静かな局面と判定せず，常に`false`を返す．
+    /// このため，既定では静止探索の終わりは`quiescence_depth`と調べる行動の有無だけで決まる．
+    fn is_quiet_position(_state: &Self::S) -> bool {
+        false
+    }
+
+    /// 指定した状態で手番のプレイヤーがパスし，相手に手番を渡した状態を返す．
+    /// Null Move Pruningでは，パスしてもなお十分に有利な局面の探索を省略する．
+    /// パスした方が有利になりうる状態 (ツークツワンク) では，誤った枝刈りを避けるために`None`を返す．
+    /// 既定ではパスを認めず，常に`None`を返す．
+    fn try_null_move(_state: &Self::S) -> Option<Self::S> {
+        None
+    }
+
+    /// 盤面の回転や反転などにより，指定した状態と戦略上同一とみなせる状態を，指定した状態自身を除いて列挙する．
+    /// 対称な状態は，同じプレイヤーの手番では同じ評価値を持たなければならない．
+    /// 既定では何も列挙しない．
+    ///
+    /// `AlphaBetaStrategy::with_symmetric_transposition_table`などで`SymmetricStateHasher`を用いると，
+    /// 対称な状態の探索結果が置換表で共有される．
+    /// 対称性の多いゲームでは，探索すべき状態の数を大幅に減らせる．
+    fn symmetry_equivalents(_state: &Self::S) -> impl Iterator<Item = Self::S> {
+        core::iter::empty()
+    }
+}
+
+/// ゲームが終了した状態の種類．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TerminalKind {
+    /// 指定したプレイヤーの勝ち．
+    Win(Actor),
+    /// 引き分け．
+    Draw,
+    /// 手番のプレイヤーが取れる行動がないことによる引き分け (ステイルメイト)．
+    NoMovesAvailable,
+}
+
+/// ゲーム終了時の勝敗を判定できるゲームルール．
+pub trait OutcomeRule: Rule {
+    /// ゲーム終了条件を満たした状態における勝者を返す．
+    /// 引き分けの場合は`None`を返す．
+    /// 既定では`Rule::terminal_kind`から勝者を求める．
+    fn winner(state: &Self::S) -> Option<Actor> {
+        match Self::terminal_kind(state) {
+            Some(TerminalKind::Win(winner)) => Some(winner),
+            _ => None,
+        }
+    }
 }
 
 /// ゲーム状態の評価関数．
@@ -61,6 +397,54 @@ pub trait Evaluator<S> {
 
     /// 指定された状態について，利得を評価する．
     fn evaluate_payoff_for(actor: Actor, state: &S) -> Self::Payoff;
+
+    /// ゲームが終了した状態について，その終了の種類を考慮して利得を評価する．
+    /// ステイルメイトとそれ以外の引き分けを区別する場合などに実装する．
+    /// 既定では`evaluate_payoff_for`の値をそのまま用いる．
+    fn evaluate_terminal_payoff_for(actor: Actor, state: &S, _kind: TerminalKind) -> Self::Payoff {
+        Self::evaluate_payoff_for(actor, state)
+    }
+
+    /// 根ノードから`ply`手先のノードで評価した利得を，その深さに応じて調整する．
+    /// 既定では調整しない．
+    ///
+    /// 勝ちの利得を深さに応じて小さく，負けの利得を深さに応じて大きくすると，
+    /// 同じ勝ちなら早く勝てる行動を，同じ負けなら遅く負ける行動を選ぶようになる．
+    /// 置換表を用いる場合，記録された評価値は別の深さで現れた同じ状態にも使われるので，調整が正確でなくなることがある．
+    fn adjust_for_depth(payoff: Self::Payoff, _ply: u32) -> Self::Payoff {
+        payoff
+    }
+
+    /// 指定された複数の状態について，利得をまとめて評価する．
+    /// 返す利得は`states`と同じ順に並べる．
+    /// 既定では状態ごとに`evaluate_payoff_for`を呼び出す．
+    ///
+    /// 機械学習モデルによる推論など，まとめて評価すると1状態あたりの負担が小さくなる場合に実装する．
+    /// `AlphaBetaStrategy::with_batch_evaluation`を指定すると，αβ法が末端ノードをまとめて評価するようになる．
+    fn evaluate_batch(actor: Actor, states: &[&S]) -> Vec<Self::Payoff> {
+        states
+            .iter()
+            .map(|state| Self::evaluate_payoff_for(actor, state))
+            .collect()
+    }
+}
+
+/// 根ノードから指定した深さのノードの状態を評価し，深さに応じて調整した利得を返す．
+/// ゲームが終了した状態では，その終了の種類を評価関数に渡す．
+pub(crate) fn evaluate_at_ply<S, E>(
+    actor: Actor,
+    state: &S,
+    terminal: Option<TerminalKind>,
+    ply: u32,
+) -> E::Payoff
+where
+    E: Evaluator<S>,
+{
+    let payoff = match terminal {
+        Some(kind) => E::evaluate_terminal_payoff_for(actor, state, kind),
+        None => E::evaluate_payoff_for(actor, state),
+    };
+    E::adjust_for_depth(payoff, ply)
 }
 
 /// ゲームの戦略生成器．
@@ -70,195 +454,2906 @@ pub trait Strategy<S, A> {
     fn select_action(&self, state: &S, actor: Actor) -> Option<A>;
 }
 
-/// 2人零和ゲームにおける適切な行動をαβ法で思考するエージェント．
-pub struct AlphaBetaStrategy<R, E, N> {
-    /// 探索するゲーム木の深さ．
-    search_depth: N,
-    _r: PhantomData<R>,
-    _e: PhantomData<E>,
+/// エージェントが選択した行動と，その行動をとった場合に見込まれる評価値．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoredAction<A, P> {
+    /// 選択した行動．
+    pub action: A,
+    /// 選択した行動をとった場合の，エージェントにとっての評価値．
+    pub payoff: P,
+    /// 評価値が勝ち (評価値の最大値) または負け (最小値) である場合，その勝敗が決まる末端ノードまでの手数．
+    /// 最善の手順をたどった場合に，何手で勝敗が決まるかを表す．
+    /// `AlphaBetaStrategy`の探索に基づくエージェントだけが求め，それ以外のエージェントや，勝敗が決まらない場合は`None`となる．
+    /// 置換表の記録から評価値が決まったノードを経由する場合も`None`となることがある．
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depth_to_terminal: Option<u32>,
 }
 
-impl<S, A, R, E, N> AlphaBetaStrategy<R, E, N>
-where
-    S: State,
-    A: Action,
-    R: Rule<S = S, A = A>,
-    E: Evaluator<S>,
-    E::Payoff: Copy + Ord + Bounded,
-    N: Copy + Integer,
-{
-    /// αβ法により，指定したノードの評価値を再帰的に計算する．
-    /// # Params
-    /// 1. remaining_depth 残りの探索深さ．
-    /// 1. current_node 注目ノード．
-    /// 1. alpha 評価値の関心範囲の下限．
-    /// 1. beta 評価値の関心範囲の上限．
-    ///
-    /// # Returns
-    /// `Some(e)`: このノードの評価値`e`
-    ///
-    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
-    fn construct_best_game_tree_alpha_beta(
-        &self,
-        remaining_depth: N,
-        consideration_target: Actor,
-        current_node: &mut TreeNode<MinimaxNode<S, A, E::Payoff>>,
-        payoff_range: Range<E::Payoff>,
-    ) -> Option<E::Payoff> {
-        // デバッグ用アサーション (消しても問題ないけど，コード変更した際の挙動検証のために一応とっておく)
-        debug_assert!(current_node.payoff.is_none());
-
-        // 注目ノードが末端ノードなら，現在の状態に対する静的評価値をそのまま適用する
-        if remaining_depth.is_zero() || R::is_game_over(current_node.ref_state()) {
-            let payoff = E::evaluate_payoff_for(consideration_target, current_node.ref_state());
-            current_node.payoff = Some(payoff);
-            return Some(payoff);
+impl<A, P> ScoredAction<A, P> {
+    /// 指定した行動と評価値を持つ`ScoredAction`を作る．
+    /// 勝敗が決まる末端ノードまでの手数は`None`とする．
+    pub fn new(action: A, payoff: P) -> Self {
+        Self {
+            action,
+            payoff,
+            depth_to_terminal: None,
         }
+    }
 
-        // who WILL act on the current state?
-        let next_actor = match current_node.cause_action.as_ref() {
-            Some(action) => action.actor().opponent(),
-            None => consideration_target,
-        };
-
-        // 状態遷移などに使用するので，注目ノードの状態をとっておく．
-        // ここでは構造体の，後の処理で変更されないメンバだけの参照を保持するだけなので，
-        // unsafeブロックの処理は安全である．
-        let current_state = {
-            let pointer: *const _ = current_node.ref_state();
-            unsafe { pointer.as_ref().unwrap() }
-        };
-        let mut current_payoff_range = payoff_range;
+    /// 探索済みのゲーム木の根ノードから，最善の行動とその評価値を取り出す．
+    /// 根ノードの評価値や最善の子ノードが存在しない場合は`None`を返す．
+    pub(crate) fn from_game_tree<S>(root: GameTree<S, A, P>) -> Option<Self> {
+        let mut path = root.into_iter_path();
+        let root = path.next()?;
+        let payoff = root.payoff?;
+        let depth_to_terminal = root.terminal_ply;
+        let action = path.next()?.cause_action?;
+        Some(Self {
+            action,
+            payoff,
+            depth_to_terminal,
+        })
+    }
+}
 
-        // 次の実現しうる状態をすべて列挙し，ひとつひとつ調べる
-        for mut child in R::iterate_available_actions(current_state, next_actor)
-            .map(|action| {
-                let next_state = R::translate_state(current_state, &action);
-                MinimaxNode::new(next_state.into(), Some(action), None)
-            })
-            .map(|minimax_node| TreeNode::new(minimax_node))
-        {
-            // 子ノードの評価値を再帰的に求める．
-            // ここでNoneが帰ってきた場合，その子ノードはゲーム終了でもなく，かつ取れる行動がないパターンなので，探索対象としない．
-            let child_payoff = match self.construct_best_game_tree_alpha_beta(
-                remaining_depth - N::one(),
-                consideration_target,
-                &mut child,
-                current_payoff_range,
-            ) {
-                Some(e) => e,
-                None => continue,
-            };
-            // ミニマックス法により，探索する必要がある枝だけを選択する
-            if let Some(e) = current_node.payoff {
-                if next_actor == consideration_target {
-                    // 自分の手番では，自分が有利になる行動を選択するので，
-                    // 自分が不利になる行動は候補から除外する
-                    if e >= child_payoff {
-                        continue;
-                    }
-                } else {
-                    // 相手の手番では，相手は自分が不利になる行動を選択するので，
-                    // 自分が有利になる行動は候補から除外する．
-                    if e <= child_payoff {
-                        continue;
-                    }
-                }
-            }
-            // ここに来たということは，より良い子ノードが見つかったということなので，子ノードの情報を入れ替える．
-            // また，注目ノードの評価値には，子ノードの値を反映させる．
-            current_node.replace_child(child);
-            current_node.payoff = Some(child_payoff);
-            // 評価値の注目範囲を更新する．
-            // 可能なら，αβカットして探索量を減らす．
-            let maybe_next_range = if next_actor == consideration_target {
-                Range::try_new(child_payoff, current_payoff_range.max)
-            } else {
-                Range::try_new(current_payoff_range.min, child_payoff)
-            };
-            match maybe_next_range {
-                Some(range) => current_payoff_range = range,
-                None => break,
-            }
-        }
+/// 行動とともに，その行動の評価値を返すエージェント．
+///
+/// このトレイトを実装した型は，`Strategy`も実装する．
+pub trait ScoredStrategy<S, A> {
+    /// 評価値の型．
+    type Payoff;
 
-        // 注目ノードの最終的な評価値を返す．
-        // ここに到達した時点で評価値が決定していないということは，
-        // 注目ノードの状態から取れる行動がないということなので，
-        // そのようなノードは探索の対象にしない．
-        current_node.payoff
-    }
+    /// 指定した状態における，指定したエージェントの行動`a`を選択し，その評価値`p`とともに`Some(ScoredAction::new(a, p))`として返す．
+    /// 取れる行動がない場合は`None`を返す．
+    fn select_scored_action(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> Option<ScoredAction<A, Self::Payoff>>;
 }
 
-impl<S, A, R, E, N> Strategy<S, A> for AlphaBetaStrategy<R, E, N>
+impl<S, A, T> Strategy<S, A> for T
 where
-    S: State,
-    A: Action,
-    R: Rule<S = S, A = A>,
-    E: Evaluator<S>,
-    E::Payoff: Copy + Ord + Bounded,
-    N: Copy + Integer,
+    T: ScoredStrategy<S, A>,
 {
     fn select_action(&self, state: &S, actor: Actor) -> Option<A> {
-        let mut root = TreeNode::new(MinimaxNode::<S, A, E::Payoff>::new(
-            state.into(),
-            None,
-            None,
-        ));
-        self.construct_best_game_tree_alpha_beta(
-            self.search_depth,
-            actor,
-            &mut root,
-            Range::new(E::Payoff::min_value(), E::Payoff::max_value()),
-        )
-        .and_then(|_| root.into_child())
-        .and_then(|best_node| best_node.into_inner().cause_action)
+        self.select_scored_action(state, actor)
+            .map(|scored| scored.action)
     }
 }
 
-/// 2人ゲームにおける各プレイヤーを返す．
-pub fn actors() -> [Actor; 2] {
-    [Actor::First, Actor::Second]
+// `Strategy`は`ScoredStrategy`を実装した型すべてに実装されており，トレイトオブジェクトに直接実装すると衝突する．
+// そこで，トレイトオブジェクトには評価値を持たない`ScoredStrategy`を実装し，`Strategy`も実装されるようにする．
+
+/// 実行時に選んだエージェントを，`Strategy`を要求する箇所に渡せるようにする．
+/// 評価値は返さないので，評価値の型は`()`とする．
+impl<'a, S, A> ScoredStrategy<S, A> for &'a (dyn Strategy<S, A> + 'a) {
+    type Payoff = ();
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, ()>> {
+        let action = (**self).select_action(state, actor)?;
+        Some(ScoredAction::new(action, ()))
+    }
 }
 
-pub fn construct_alpha_beta_strategy<R, E, N>(search_depth: N) -> AlphaBetaStrategy<R, E, N> {
-    AlphaBetaStrategy {
-        search_depth,
-        _r: PhantomData,
-        _e: PhantomData,
+/// 実行時に選んだエージェントを，`Strategy`を要求する箇所に渡せるようにする．
+/// 評価値は返さないので，評価値の型は`()`とする．
+impl<'a, S, A> ScoredStrategy<S, A> for Box<dyn Strategy<S, A> + 'a> {
+    type Payoff = ();
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, ()>> {
+        let action = (**self).select_action(state, actor)?;
+        Some(ScoredAction::new(action, ()))
     }
 }
 
-/// ミニマックス法で利用するゲーム木のノード．
-struct MinimaxNode<'a, S, A, E> {
-    /// 現在の状態．
-    state: CowRef<'a, S>,
-    /// この状態に至る際に実行された行動．
-    cause_action: Option<A>,
-    /// エージェントにとっての現在状態の評価値．
-    payoff: Option<E>,
+/// 実行時に選んだエージェントを，`Strategy`を要求する箇所に渡せるようにする．
+/// 評価値は返さないので，評価値の型は`()`とする．
+impl<'a, S, A> ScoredStrategy<S, A> for Arc<dyn Strategy<S, A> + 'a> {
+    type Payoff = ();
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, ()>> {
+        let action = (**self).select_action(state, actor)?;
+        Some(ScoredAction::new(action, ()))
+    }
 }
 
-impl<'a, S, A, E> MinimaxNode<'a, S, A, E> {
-    fn new(state: CowRef<'a, S>, cause_action: Option<A>, payoff: Option<E>) -> Self {
+/// 2人零和ゲームにおける適切な行動をαβ法で思考するエージェント．
+///
+/// 型引数`O`で，各ノードで行動を調べる順を指定できる．既定では並べ替えない．
+///
+/// `serde`フィーチャを有効にすると，探索深さなどの値で表せる設定を保存・復元できる．
+pub struct AlphaBetaStrategy<R, E, N, O = NoOrdering>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    /// 評価値がとりうる範囲．
+    payoff_bounds: PayoffBounds<E::Payoff>,
+    /// 探索済みの状態の評価値を記録する置換表．
+    /// 行動選択をまたいで再利用される．
+    transposition_table: Option<SharedTranspositionTable<R::S, E::Payoff, N>>,
+    /// 状態遷移の前後でハッシュ値を差分更新する関数．
+    /// `None`の場合，各状態のハッシュ値は置換表が状態全体から計算する．
+    incremental_hash: Option<HashUpdater<R::S, R::A>>,
+    /// 1回の行動選択で訪れるノード数の上限．
+    node_budget: Option<u64>,
+    /// 探索深さに達した後，静止探索で追加で調べる深さ．
+    quiescence_depth: Option<N>,
+    /// 静止探索で調べる行動を列挙する関数．
+    /// `None`の場合，`Rule::is_loud_action`が`true`となる行動を調べる．
+    noisy_actions: Option<NoisyActionGenerator<R::S, R::A>>,
+    /// 各ノードで子ノードを調べる方法．
+    search_algorithm: SearchAlgorithm,
+    /// Null Move Pruningで，パスした後の探索深さを通常より浅くする量．
+    /// `None`の場合，Null Move Pruningを行わない．
+    null_move_reduction: Option<N>,
+    /// History Heuristicで用いる，各行動が枝刈りを起こした実績．
+    /// 行動選択をまたいで再利用される．
+    /// `None`の場合，行動の実績に基づいて探索順を変えない．
+    history: Option<Lock<HistoryTable<R::A, N>>>,
+    /// キラー手を記録する表を作る関数．
+    /// `None`の場合，キラー手を優先して調べない．
+    killer_moves: Option<KillerFactory<R::A, N>>,
+    /// カウンター手を記録する表を作る関数．
+    /// `None`の場合，カウンター手を優先して調べない．
+    countermoves: Option<CountermoveFactory<R::A>>,
+    /// 各ノードで行動を調べる順を決める．
+    /// `None`の場合，`Rule::iterate_available_actions`が列挙する順に調べる．
+    move_orderer: Option<Arc<dyn MoveOrderer<R::S, R::A> + Send + Sync>>,
+    /// Late Move Reductionsの設定．
+    /// `None`の場合，すべての子ノードを同じ深さで探索する．
+    late_move_reduction: Option<LateMoveReduction<N>>,
+    /// Late Move Reductionsで浅く探索しない行動を判定する．
+    reduction_exemption: Option<ActionPredicate<R::S, R::A>>,
+    /// Futility Pruningの設定．
+    /// `None`の場合，Futility Pruningを行わない．
+    futility_pruning: Option<MarginPruning<R::S, E::Payoff, N>>,
+    /// Razoringの設定．
+    /// `None`の場合，Razoringを行わない．
+    razoring: Option<MarginPruning<R::S, E::Payoff, N>>,
+    /// 内部反復深化の設定．
+    /// `None`の場合，内部反復深化を行わない．
+    internal_iterative_deepening: Option<InternalIterativeDeepening<N>>,
+    /// 引き分けの評価値を下げる量．
+    /// `None`の場合，引き分けの評価値を調整しない．
+    contempt: Option<Contempt<E::Payoff>>,
+    /// Futility Pruningで枝刈りしない行動を判定する．
+    futility_exemption: Option<ActionPredicate<R::S, R::A>>,
+    /// 1回の探索で，探索を延長できる子ノードの数．
+    /// `None`の場合，探索を延長しない．
+    extension_budget: Option<u32>,
+    /// Singular Extensionの設定．
+    /// `None`の場合，取れる行動が1つしかないノードでだけ探索を延長する．
+    singular_extension: Option<SingularExtension<R::A, E::Payoff>>,
+    /// ゲームに固有の重要なノードで探索を延長する方針．
+    extension_policy: Option<Arc<dyn ExtensionPolicy<R::S, R::A> + Send + Sync>>,
+    /// 根ノードから末端ノードまでの1つの手順で，探索を延長できる手数の合計．
+    /// `None`の場合，手順ごとには制限しない．
+    max_line_extension: Option<u32>,
+    /// 末端ノードの兄弟を`Evaluator::evaluate_batch`でまとめて評価するか．
+    batch_evaluation: bool,
+    /// 探索に用いる構造のメモリ使用量の上限．
+    /// `None`の場合，使用量を制限しない．
+    memory_budget: Option<MemoryBudget>,
+    // ルールと評価関数の値は持たないので，それらが`Send`や`Sync`でなくても，
+    // エージェントをスレッド間で受け渡したり共有したりできるようにする
+    _r: PhantomData<fn() -> R>,
+    _e: PhantomData<fn() -> E>,
+    _o: PhantomData<fn() -> O>,
+}
+
+impl<R, E, N> AlphaBetaStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定した深さまで探索する，αβ法で思考するエージェントを作る．
+    /// 評価値の型の最小値から最大値までを，評価値がとりうる範囲とする．
+    pub fn new(search_depth: N) -> Self
+    where
+        E::Payoff: Bounded,
+    {
+        Self::new_with_payoff_bounds(search_depth, PayoffBounds::default())
+    }
+
+    /// 評価値がとりうる範囲を指定して，αβ法で思考するエージェントを作る．
+    /// 浮動小数点型など，`Bounded`を実装しない評価値の型を用いる場合に使う．
+    pub fn new_with_payoff_bounds(search_depth: N, payoff_bounds: PayoffBounds<E::Payoff>) -> Self {
         Self {
-            state,
-            cause_action,
-            payoff,
+            search_depth,
+            payoff_bounds,
+            transposition_table: None,
+            incremental_hash: None,
+            node_budget: None,
+            quiescence_depth: None,
+            noisy_actions: None,
+            search_algorithm: SearchAlgorithm::AlphaBeta,
+            null_move_reduction: None,
+            history: None,
+            killer_moves: None,
+            countermoves: None,
+            move_orderer: None,
+            late_move_reduction: None,
+            reduction_exemption: None,
+            futility_pruning: None,
+            futility_exemption: None,
+            razoring: None,
+            internal_iterative_deepening: None,
+            contempt: None,
+            extension_budget: None,
+            singular_extension: None,
+            extension_policy: None,
+            max_line_extension: None,
+            batch_evaluation: false,
+            memory_budget: None,
+            _r: PhantomData,
+            _e: PhantomData,
+            _o: PhantomData,
         }
     }
 
-    fn ref_state(&self) -> &S {
-        self.state.as_ref()
+    /// 設定を順に指定してエージェントを作るビルダーを返す．
+    /// 探索深さを指定しない場合は1となる．
+    pub fn builder() -> AlphaBetaStrategyBuilder<R, E, N>
+    where
+        E::Payoff: Bounded,
+        N: SearchDepth,
+    {
+        AlphaBetaStrategyBuilder::new(Self::new(N::one()))
     }
 }
 
-#[cfg(test)]
-mod test_cmp {
-    #[test]
-    fn test_cmp() {
-        let v = vec![5, 2, 0, 9, 4, 3];
-        let max = v.into_iter().max_by(|left, right| left.cmp(right));
-        assert_eq!(Some(9), max);
+impl<R, E, N, O> AlphaBetaStrategy<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定した数の状態を記録できる置換表を用いて探索するようにする．
+    /// 探索はfail-softで行われるので，関心範囲の外の評価値も真の評価値の上界・下界として記録され，
+    /// 関心範囲の異なる後の探索でも枝刈りに使える．
+    /// # Panics
+    /// `capacity`が0の場合
+    #[cfg(feature = "std")]
+    pub fn with_transposition_table(self, capacity: usize) -> Self
+    where
+        R::S: Hash,
+    {
+        self.with_custom_transposition_table(TranspositionTable::new(capacity))
+    }
+
+    /// 指定した置換表を用いて探索するようにする．
+    /// 状態のハッシュ値の計算方法を変更したい場合に使用する．
+    pub fn with_custom_transposition_table(
+        self,
+        table: TranspositionTable<R::S, E::Payoff, N>,
+    ) -> Self {
+        Self {
+            transposition_table: Some(Lock::new(table)),
+            incremental_hash: None,
+            ..self
+        }
+    }
+
+    /// 置換表を用いる場合，その使用状況を返す．
+    /// 記録を調べた回数などは，それまでのすべての行動選択を通したものである．
+    pub fn transposition_table_stats(&self) -> Option<TranspositionTableStats> {
+        self.transposition_table
+            .as_ref()
+            .map(|table| table.lock().stats())
+    }
+
+    /// 置換表を用いる場合，その世代を進める．
+    /// 行動選択を始めるたびに呼び出し，以前の行動選択で記録された結果を置き換えやすくする．
+    pub(crate) fn advance_transposition_generation(&self) {
+        if let Some(table) = self.transposition_table.as_ref() {
+            table.lock().advance_generation();
+        }
+    }
+
+    /// 指定した数の状態を記録できる置換表を，盤面の対称性を考慮して用いるようにする．
+    /// `Rule::symmetry_equivalents`が列挙する対称な状態の探索結果は，置換表の1つの記録として共有される．
+    /// # Panics
+    /// `capacity`が0の場合
+    #[cfg(feature = "std")]
+    pub fn with_symmetric_transposition_table(self, capacity: usize) -> Self
+    where
+        R: 'static,
+        R::S: Hash + Clone + Ord,
+    {
+        let hasher =
+            SymmetricStateHasher::<R, _>::new(BuildHasherDefault::<DefaultHasher>::default());
+        self.with_custom_transposition_table(TranspositionTable::with_hasher(capacity, hasher))
+    }
+
+    /// 指定した数の状態を記録できる置換表を用いて探索するようにする．
+    /// 状態のハッシュ値は`ZobristHashable`により，状態遷移のたびに差分だけで更新される．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_zobrist_transposition_table(self, capacity: usize) -> Self
+    where
+        R: ZobristHashable + 'static,
+    {
+        let table = TranspositionTable::with_hasher(capacity, ZobristRuleHasher::<R>::new());
+        Self {
+            incremental_hash: Some(R::update_hash),
+            ..self.with_custom_transposition_table(table)
+        }
+    }
+
+    /// 評価値がとりうる範囲を指定する．
+    /// 根ノードはこの範囲を関心範囲として探索するので，評価関数が返す値はすべてこの範囲に収まらなければならない．
+    pub fn with_payoff_bounds(self, payoff_bounds: PayoffBounds<E::Payoff>) -> Self {
+        Self {
+            payoff_bounds,
+            ..self
+        }
+    }
+
+    /// 1回の行動選択で訪れるノード数の上限を指定する．
+    ///
+    /// 上限を指定した場合，行動選択では探索深さを1から順に増やしながら探索し，
+    /// 訪れたノード数が上限に達した時点で探索を打ち切る．
+    /// 探索深さの上限とノード数の上限のうち，先に達した方で探索が終わる．
+    /// 計算機の性能によらず探索量を揃えたい場合に使用する．
+    pub fn with_node_budget(self, node_budget: u64) -> Self {
+        Self {
+            node_budget: Some(node_budget),
+            ..self
+        }
+    }
+
+    /// 置換表，History Heuristicの実績，キラー手とカウンター手の表が使うメモリの合計を，指定した上限に収める．
+    ///
+    /// 上限を超えた場合に記録を消去する順や，使用量の数え方は`MemoryBudget`を参照．
+    /// 置換表の容量は，探索を始める時点で上限に収まるまで減らされる．
+    /// 使用量は`memory_usage`と，統計情報の`peak_memory_bytes`と`peak_memory_usage`で確認できる．
+    pub fn with_memory_budget(self, memory_budget: MemoryBudget) -> Self {
+        Self {
+            memory_budget: Some(memory_budget),
+            ..self
+        }
+    }
+
+    /// 行動選択をまたいで保持される置換表とHistory Heuristicの実績の，現在のメモリ使用量を返す．
+    /// キラー手とカウンター手の表は1回の探索の間だけ作られ，ゲーム木も保持しないので，これらは常に0となる．
+    /// 探索中のキラー手とカウンター手の表の使用量は，統計情報の`peak_memory_usage`で確認できる．
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            transposition_table: self
+                .transposition_table
+                .as_ref()
+                .map_or(0, |table| table.lock().memory_bytes()),
+            history: self
+                .history
+                .as_ref()
+                .map_or(0, |history| history.lock().memory_bytes()),
+            ..MemoryUsage::default()
+        }
+    }
+
+    /// 探索深さに達した後，`Rule::is_loud_action`が`true`となる行動だけを指定した深さまで追加で調べるようにする．
+    ///
+    /// 駒を取り合っている途中のような，静的評価値が信頼できない局面で探索を打ち切ることを防ぐ (静止探索)．
+    /// 静止探索では，各プレイヤーは行動せずに静的評価値を受け入れることもできるとみなす．
+    /// `Rule::is_quiet_position`が`true`となる局面に達した場合は，指定した深さに達する前でも静止探索をやめる．
+    pub fn with_quiescence_depth(self, quiescence_depth: N) -> Self {
+        Self {
+            quiescence_depth: Some(quiescence_depth),
+            ..self
+        }
+    }
+
+    /// 探索深さに達した後，`QuiescenceRule::iterate_noisy_actions`が列挙する行動だけを，
+    /// 列挙される行動がなくなるまで，最大で指定した深さまで追加で調べるようにする．
+    ///
+    /// `with_quiescence_depth`と同様に，各プレイヤーは行動せずに静的評価値を受け入れることもできるとみなす．
+    pub fn with_quiescence_search(self, max_quiescence_depth: N) -> Self
+    where
+        R: QuiescenceRule,
+    {
+        Self {
+            quiescence_depth: Some(max_quiescence_depth),
+            noisy_actions: Some(quiescence::collect_noisy_actions::<R>),
+            ..self
+        }
+    }
+
+    /// 各ノードで子ノードを調べる方法を指定する．
+    /// 置換表や静止探索などの他の設定と組み合わせて使用できる．
+    pub fn with_search_algorithm(self, search_algorithm: SearchAlgorithm) -> Self {
+        Self {
+            search_algorithm,
+            ..self
+        }
+    }
+
+    /// Null Move Pruningを行うようにする．
+    ///
+    /// 根ノード以外のノードで手番のプレイヤーがパスした状態を`Rule::try_null_move`で作り，
+    /// 通常より`reduction`だけ浅く探索する．それでもなお関心範囲を超えるほど有利なら，このノードの探索を省略する．
+    /// パスした後の探索深さが1未満になるノードや，パスにより到達したノードでは行わない．
+    /// 探索量は大きく減るが，パスが不利とは限らないゲームでは評価値が変わりうる．
+    /// ツークツワンクが起こりうる局面では，`Rule::try_null_move`が`None`を返すことでNull Move Pruningを行わないようにできる．
+    /// 既定ではNull Move Pruningを行わない．
+    pub fn with_null_move_pruning(self, reduction: N) -> Self {
+        Self {
+            null_move_reduction: Some(reduction),
+            ..self
+        }
+    }
+
+    /// 枝刈りを起こした実績の大きい行動から順に調べるようにする (History Heuristic)．
+    ///
+    /// 行動が枝刈りを起こすたびに，`HistoryKey::history_key`で求めた番号ごとに，残りの探索深さを実績として加算する．
+    /// 実績は行動選択をまたいで蓄積され，`clear_history`で消去できる．
+    /// 置換表に記録された最善の行動，キラー手，カウンター手がある場合は，それらを先に調べる．
+    pub fn with_history_heuristic(self) -> Self
+    where
+        R::A: HistoryKey,
+        N: ToPrimitive,
+    {
+        Self {
+            history: Some(Lock::new(HistoryTable::new())),
+            ..self
+        }
+    }
+
+    /// History Heuristicで蓄積した実績を消去する．
+    /// 異なる対局を始める場合など，これまでの実績が参考にならない場合に用いる．
+    pub fn clear_history(&self) {
+        if let Some(history) = self.history.as_ref() {
+            history.lock().clear();
+        }
+    }
+
+    /// 同じ深さの別のノードで枝刈りを起こした行動 (キラー手) を，他の行動より先に調べるようにする．
+    /// キラー手は各深さにつき2つまで，1回の探索の間だけ記録する．
+    /// `MoveOrderer`を指定した場合，キラー手を調べた後に残りの行動を`MoveOrderer`の順に調べる．
+    /// キラー手が枝刈りを起こした回数は，統計情報の`killer_hits`で確認できる．
+    /// 既定ではキラー手を用いない．
+    pub fn with_killer_moves(self) -> Self
+    where
+        R::A: PartialEq + Clone,
+    {
+        Self {
+            killer_moves: Some(KillerTable::new),
+            ..self
+        }
+    }
+
+    /// 相手の直前の行動に対して枝刈りを起こした応手 (カウンター手) を，他の行動より先に調べるようにする．
+    /// 相手の行動は`HistoryKey::history_key`で求めた番号で区別し，番号ごとに最近のカウンター手を1つ，1回の探索の間だけ記録する．
+    /// 既定ではカウンター手を用いない．
+    ///
+    /// 各ノードでは，次の優先順で行動を調べる．
+    /// 1. 置換表に記録された最善の行動
+    /// 1. キラー手
+    /// 1. カウンター手
+    /// 1. History Heuristicの実績の大きい行動
+    /// 1. `MoveOrderer`で並べ替えた順 (指定しない場合は`Rule::heuristic_action_score`の大きい順)
+    pub fn with_countermoves(self) -> Self
+    where
+        R::A: HistoryKey + PartialEq + Clone,
+    {
+        Self {
+            countermoves: Some(CountermoveTable::new),
+            ..self
+        }
+    }
+
+    /// 各ノードで後半に調べる行動を，通常より浅く探索するようにする (Late Move Reductions)．
+    ///
+    /// 行動の並べ替えが有効なら，後半に調べる行動が最善であることは少ない．
+    /// 各ノードで`start_after`個の行動を調べた後は，`reduction(注目ノードの残りの探索深さ, 行動を調べる順番)`だけ浅く，
+    /// それまでの最善の評価値を上回るかどうかだけを幅0の窓で調べる．
+    /// 上回った場合は，通常の深さで探索し直す．その回数は統計情報の`late_move_re_searches`で確認できる．
+    /// 浅く探索した結果を信頼するので，評価値や選択する行動が変わりうる．
+    /// 既定ではLate Move Reductionsを行わない．
+    pub fn with_late_move_reductions<F>(self, start_after: usize, reduction: F) -> Self
+    where
+        F: Fn(N, usize) -> N + Send + Sync + 'static,
+    {
+        Self {
+            late_move_reduction: Some(LateMoveReduction {
+                start_after,
+                reduction: Arc::new(reduction),
+            }),
+            ..self
+        }
+    }
+
+    /// 指定した条件を満たす行動を，Late Move Reductionsで浅く探索しないようにする．
+    /// 駒を取る行動など，局面を大きく変える行動を除外するために用いる．
+    pub fn with_reduction_exemption<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&R::S, &R::A) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            reduction_exemption: Some(Arc::new(predicate)),
+            ..self
+        }
+    }
+
+    /// 末端に近いノードで，関心範囲に届く見込みのない行動を調べないようにする (Futility Pruning)．
+    ///
+    /// 残りの探索深さが`i + 1`のノードで，`StaticEvaluator::static_payoff_for`による見積もりに余裕`margins[i]`を加えても
+    /// 関心範囲の下限に届かない場合 (相手の手番では，余裕を引いても上限を下回らない場合)，`with_futility_exemption`で除外した行動だけを調べる．
+    /// 調べる行動がなければ，見積もりをそのノードの評価値とする．
+    /// 通常は`margins`に1つか2つの値を指定し，残りの探索深さが1や2のノードでだけ枝刈りする．
+    ///
+    /// 見積もりと余裕が実際の評価値の変化を常に上回るとは限らないので，最悪の場合は最善の行動を見落とす．
+    /// 既定ではFutility Pruningを行わない．
+    /// 評価値の加減算が必要なので，評価値の型が`CheckedAdd`と`CheckedSub`を実装している場合にのみ利用できる．
+    pub fn with_futility_pruning(self, margins: Vec<E::Payoff>) -> Self
+    where
+        E: StaticEvaluator<R::S>,
+        E::Payoff: CheckedAdd + CheckedSub + Bounded,
+        N: ToPrimitive,
+    {
+        Self {
+            futility_pruning: Some(MarginPruning::new(
+                margins,
+                E::static_payoff_for,
+                margin_depth_index::<N>,
+                iterative_deepening::saturating_add::<E::Payoff>,
+                iterative_deepening::saturating_sub::<E::Payoff>,
+            )),
+            ..self
+        }
+    }
+
+    /// 引き分けで終了した状態の評価値を，行動を選ぶプレイヤーにとって`contempt`だけ低く評価するようにする (Contempt Factor)．
+    ///
+    /// 相手より強い場合に引き分けを避け，勝ちを目指す行動を選ばせるために用いる．
+    /// 負の値を指定すると，逆に引き分けを好むようになる．
+    /// 対象はゲームが終了した状態のうち`TerminalKind::Draw`と`TerminalKind::NoMovesAvailable`だけで，
+    /// 静的評価値が均衡しているだけの状態の評価値は変わらない．
+    ///
+    /// 評価値の減算が必要なので，評価値の型が`CheckedSub`を実装している場合にのみ利用できる．
+    /// 減算の結果が評価値の範囲を超える場合は，評価値の最小値として扱う．
+    pub fn with_contempt(self, contempt: E::Payoff) -> Self
+    where
+        E::Payoff: CheckedSub + Bounded,
+    {
+        Self {
+            contempt: Some(Contempt {
+                value: contempt,
+                saturating_sub: iterative_deepening::saturating_sub::<E::Payoff>,
+            }),
+            ..self
+        }
+    }
+
+    /// 末端に近いノードで，評価値が関心範囲から大きく外れると見込まれる場合は，浅い探索で確かめてノードの探索を省略する (Razoring)．
+    ///
+    /// 残りの探索深さが`i + 1`のノードで，`StaticEvaluator::static_payoff_for`による見積もりに余裕`margins[i]`を加えても
+    /// 関心範囲の下限に届かない場合 (相手の手番では，余裕を引いても上限を下回らない場合)，
+    /// 注目ノードを1だけ浅く，関心範囲の端を幅0の窓として探索する．
+    /// 残りの探索深さが1のノードでは，静止探索を設定していれば静止探索，そうでなければ静的評価値で確かめることになる．
+    /// その結果も関心範囲から外れるなら，その評価値を注目ノードの評価値とし，通常の探索を省略する．
+    /// 省略した回数は，統計情報の`razored_nodes`で確認できる．
+    ///
+    /// 浅い探索で確かめるだけなので，最悪の場合は最善の行動を見落とす．
+    /// 既定ではRazoringを行わない．
+    /// 評価値の加減算が必要なので，評価値の型が`CheckedAdd`と`CheckedSub`を実装している場合にのみ利用できる．
+    pub fn with_razoring(self, margins: Vec<E::Payoff>) -> Self
+    where
+        E: StaticEvaluator<R::S>,
+        E::Payoff: CheckedAdd + CheckedSub + Bounded,
+        N: ToPrimitive,
+    {
+        Self {
+            razoring: Some(MarginPruning::new(
+                margins,
+                E::static_payoff_for,
+                margin_depth_index::<N>,
+                iterative_deepening::saturating_add::<E::Payoff>,
+                iterative_deepening::saturating_sub::<E::Payoff>,
+            )),
+            ..self
+        }
+    }
+
+    /// 指定した条件を満たす行動を，Futility Pruningで枝刈りしないようにする．
+    /// 勝ちが決まる行動や王手など，見積もりを大きく変えうる行動を除外するために用いる．
+    pub fn with_futility_exemption<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&R::S, &R::A) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            futility_exemption: Some(Arc::new(predicate)),
+            ..self
+        }
+    }
+
+    /// 置換表に最善の行動が記録されていないノードでは，先に浅く探索して最初に調べる行動を見つけるようにする (内部反復深化)．
+    ///
+    /// 残りの探索深さが`trigger_depth`以上のノードで，残りの探索深さを`reduction`だけ浅くして同じノードを探索する．
+    /// 浅い探索で最善と判断された行動を置換表から取り出し，本来の探索で最初に調べるので，枝刈りが起こりやすくなる．
+    /// 浅い探索の評価値は用いないので，行動選択の結果は変わらない．
+    /// 関心範囲の幅が0のノードでは，最初に調べる行動の良し悪しの影響が小さいので行わない．
+    /// 浅い探索の結果を置換表に記録する必要があるので，置換表を用いて探索する場合にだけ効果がある．
+    /// 浅い探索を行った回数は，統計情報の`iid_searches`で確認できる．
+    /// # Panics
+    /// `reduction`が0の場合
+    pub fn with_internal_iterative_deepening(self, trigger_depth: N, reduction: N) -> Self
+    where
+        N: SearchDepth,
+    {
+        assert!(
+            !reduction.is_zero(),
+            "reduction of internal iterative deepening must be positive"
+        );
+        Self {
+            internal_iterative_deepening: Some(InternalIterativeDeepening {
+                trigger_depth,
+                reduction,
+            }),
+            ..self
+        }
+    }
+
+    /// 取れる行動が1つしかないノードでは，その行動を1手深く探索するようにする (Single Reply Extension)．
+    ///
+    /// 行動を選ぶ余地のないノードを深く探索しても探索量はほとんど増えず，探索深さの境界での見落としを防げる．
+    /// 探索が際限なく深くならないよう，1回の探索で延長する子ノードの数は`budget`までとする．
+    /// 延長した子ノードの数は，統計情報の`extensions`で確認できる．
+    /// 既定では探索を延長しない．
+    pub fn with_extensions(self, budget: u32) -> Self {
+        Self {
+            extension_budget: Some(budget),
+            ..self
+        }
+    }
+
+    /// `with_extensions`による延長に加えて，置換表に記録された最善の行動が，
+    /// 他のどの行動よりも`margin`以上良いノードでは，その行動を1手深く探索するようにする (Singular Extension)．
+    ///
+    /// 他の行動は，記録された評価値から`margin`だけ離れた値を幅0の窓として，半分程度の深さで確かめる．
+    /// 置換表の記録を用いるので，置換表を用いて探索する場合にだけ効果がある．
+    /// `with_extensions`を指定しない場合は，探索を延長しない．
+    /// 評価値の加減算が必要なので，評価値の型が`CheckedAdd`と`CheckedSub`を実装している場合にのみ利用できる．
+    pub fn with_singular_extensions(self, margin: E::Payoff) -> Self
+    where
+        R::A: Clone,
+        E::Payoff: Copy + CheckedAdd + CheckedSub + Bounded,
+    {
+        Self {
+            singular_extension: Some(SingularExtension::new(
+                margin,
+                R::A::clone,
+                iterative_deepening::saturating_add::<E::Payoff>,
+                iterative_deepening::saturating_sub::<E::Payoff>,
+            )),
+            ..self
+        }
+    }
+
+    /// 指定した方法に従い，ゲームに固有の重要なノードを深く探索するようにする．
+    ///
+    /// 王手や連珠の三のような脅威を見落とさないよう，そのようなノードに至る行動を`policy`が返す手数だけ深く探索する．
+    /// 探索が際限なく深くならないよう，根ノードから末端ノードまでの1つの手順で延長する手数の合計は`max_line_extension`までとする．
+    /// この上限は`with_extensions`による延長にも適用され，取れる行動が1つしかないノードでの延長と組み合わせて使える．
+    /// 延長した子ノードの数は，統計情報の`extensions`で確認できる．
+    pub fn with_extension_policy<P>(self, policy: P, max_line_extension: u32) -> Self
+    where
+        P: ExtensionPolicy<R::S, R::A> + Send + Sync + 'static,
+    {
+        Self {
+            extension_policy: Some(Arc::new(policy)),
+            max_line_extension: Some(max_line_extension),
+            ..self
+        }
+    }
+
+    /// 末端ノードを`Evaluator::evaluate_batch`でまとめて評価するようにする．
+    ///
+    /// 子ノードがすべて探索深さに達するノードでは，子ノードを調べる前に，ゲームが終了していない子ノードの状態をまとめて評価する．
+    /// αβ法は兄弟ノードの評価値を順に使って枝刈りするので，まとめて評価するのは同じ親を持つ末端ノードに限られる．
+    /// 枝刈りで調べなかった子ノードも評価されるので，評価関数の呼び出しはまとめない場合より多くなりうるが，
+    /// 選ぶ行動と評価値は変わらない．
+    /// 静止探索を行う場合は，探索深さに達したノードも静止探索で調べるので，まとめて評価しない．
+    /// 既定ではまとめて評価しない．
+    pub fn with_batch_evaluation(self) -> Self {
+        Self {
+            batch_evaluation: true,
+            ..self
+        }
+    }
+
+    /// 各ノードで，指定した方法で並べ替えた順に行動を調べるようにする．
+    /// 静止探索で調べる行動にも適用される．
+    /// 置換表に記録された最善の行動，キラー手，カウンター手，History Heuristicによる並べ替えは，この並べ替えの後に行われる．
+    pub fn with_move_orderer<M>(self, move_orderer: M) -> Self
+    where
+        M: MoveOrderer<R::S, R::A> + Send + Sync + 'static,
+    {
+        Self {
+            move_orderer: Some(Arc::new(move_orderer)),
+            ..self
+        }
+    }
+
+    /// 各ノードで，`MoveOrdering`を実装する型`M`で並べ替えた順に行動を調べるようにする．
+    /// 静止探索で調べる行動にも適用される．
+    /// `with_move_orderer`も指定した場合，`M`で並べ替えた後に`MoveOrderer`で並べ替える．
+    pub fn with_move_ordering<M>(self) -> AlphaBetaStrategy<R, E, N, M>
+    where
+        M: MoveOrdering<R::S, R::A>,
+    {
+        AlphaBetaStrategy {
+            search_depth: self.search_depth,
+            payoff_bounds: self.payoff_bounds,
+            transposition_table: self.transposition_table,
+            incremental_hash: self.incremental_hash,
+            node_budget: self.node_budget,
+            quiescence_depth: self.quiescence_depth,
+            noisy_actions: self.noisy_actions,
+            search_algorithm: self.search_algorithm,
+            null_move_reduction: self.null_move_reduction,
+            history: self.history,
+            killer_moves: self.killer_moves,
+            countermoves: self.countermoves,
+            move_orderer: self.move_orderer,
+            late_move_reduction: self.late_move_reduction,
+            reduction_exemption: self.reduction_exemption,
+            futility_pruning: self.futility_pruning,
+            razoring: self.razoring,
+            internal_iterative_deepening: self.internal_iterative_deepening,
+            contempt: self.contempt,
+            futility_exemption: self.futility_exemption,
+            extension_budget: self.extension_budget,
+            singular_extension: self.singular_extension,
+            extension_policy: self.extension_policy,
+            max_line_extension: self.max_line_extension,
+            batch_evaluation: self.batch_evaluation,
+            memory_budget: self.memory_budget,
+            _r: PhantomData,
+            _e: PhantomData,
+            _o: PhantomData,
+        }
+    }
+}
+
+/// 設定を複製したエージェントを作る．
+///
+/// 置換表とHistory Heuristicの実績は，複製した時点の記録を持つ別の表となり，以後は互いに影響しない．
+/// 行動の並べ替えなどに指定した関数は，複製元と共有する．
+impl<R, E, N, O> Clone for AlphaBetaStrategy<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Clone,
+    N: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            search_depth: self.search_depth.clone(),
+            payoff_bounds: self.payoff_bounds.clone(),
+            transposition_table: self.transposition_table.clone(),
+            incremental_hash: self.incremental_hash,
+            node_budget: self.node_budget,
+            quiescence_depth: self.quiescence_depth.clone(),
+            noisy_actions: self.noisy_actions,
+            search_algorithm: self.search_algorithm,
+            null_move_reduction: self.null_move_reduction.clone(),
+            history: self.history.clone(),
+            killer_moves: self.killer_moves,
+            countermoves: self.countermoves,
+            move_orderer: self.move_orderer.clone(),
+            late_move_reduction: self.late_move_reduction.clone(),
+            reduction_exemption: self.reduction_exemption.clone(),
+            futility_pruning: self.futility_pruning.clone(),
+            razoring: self.razoring.clone(),
+            internal_iterative_deepening: self.internal_iterative_deepening.clone(),
+            contempt: self.contempt.clone(),
+            futility_exemption: self.futility_exemption.clone(),
+            extension_budget: self.extension_budget,
+            singular_extension: self.singular_extension.clone(),
+            extension_policy: self.extension_policy.clone(),
+            max_line_extension: self.max_line_extension,
+            batch_evaluation: self.batch_evaluation,
+            memory_budget: self.memory_budget,
+            _r: PhantomData,
+            _e: PhantomData,
+            _o: PhantomData,
+        }
+    }
+}
+
+/// 数値で表される設定と，各機能を有効にしているかを表示する．
+impl<R, E, N, O> fmt::Debug for AlphaBetaStrategy<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: fmt::Debug,
+    N: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlphaBetaStrategy")
+            .field("search_depth", &self.search_depth)
+            .field("payoff_bounds", &self.payoff_bounds)
+            .field("transposition_table", &self.transposition_table.is_some())
+            .field("node_budget", &self.node_budget)
+            .field("quiescence_depth", &self.quiescence_depth)
+            .field("search_algorithm", &self.search_algorithm)
+            .field("null_move_reduction", &self.null_move_reduction)
+            .field("history", &self.history.is_some())
+            .field("killer_moves", &self.killer_moves.is_some())
+            .field("countermoves", &self.countermoves.is_some())
+            .field("move_orderer", &self.move_orderer.is_some())
+            .field("late_move_reduction", &self.late_move_reduction.is_some())
+            .field("futility_pruning", &self.futility_pruning.is_some())
+            .field("razoring", &self.razoring.is_some())
+            .field(
+                "internal_iterative_deepening",
+                &self.internal_iterative_deepening.is_some(),
+            )
+            .field(
+                "contempt",
+                &self.contempt.as_ref().map(|contempt| &contempt.value),
+            )
+            .field("extension_budget", &self.extension_budget)
+            .field("singular_extension", &self.singular_extension.is_some())
+            .field("extension_policy", &self.extension_policy.is_some())
+            .field("max_line_extension", &self.max_line_extension)
+            .field("batch_evaluation", &self.batch_evaluation)
+            .field("memory_budget", &self.memory_budget)
+            .finish()
+    }
+}
+
+/// 残りの探索深さを，指定した手数だけ延長する．
+pub(crate) fn extended_depth<N>(remaining_depth: N, extension: u32) -> N
+where
+    N: Integer,
+{
+    (0..extension).fold(remaining_depth, |depth, _| depth + N::one())
+}
+
+/// Futility PruningやRazoringで，残りの探索深さを余裕の添字に変換する．
+fn margin_depth_index<N>(remaining_depth: N) -> Option<usize>
+where
+    N: ToPrimitive,
+{
+    remaining_depth.to_usize()?.checked_sub(1)
+}
+
+/// 状態と行動に対する条件．
+type ActionPredicate<S, A> = Arc<dyn Fn(&S, &A) -> bool + Send + Sync>;
+
+/// Late Move Reductionsの設定．
+#[derive(Clone)]
+struct LateMoveReduction<N> {
+    /// 各ノードで，この数の行動を調べた後の行動から浅く探索する．
+    start_after: usize,
+    /// 注目ノードの残りの探索深さと，行動を調べる順番 (0始まり) から，探索深さを浅くする量を計算する．
+    reduction: Arc<dyn Fn(N, usize) -> N + Send + Sync>,
+}
+
+/// 内部反復深化の設定．
+#[derive(Clone)]
+struct InternalIterativeDeepening<N> {
+    /// 残りの探索深さがこの値以上のノードでだけ，浅い探索を行う．
+    trigger_depth: N,
+    /// 浅い探索で，残りの探索深さを浅くする量．
+    reduction: N,
+}
+
+/// 引き分けの評価値を調整する設定．
+#[derive(Clone)]
+struct Contempt<P> {
+    /// 引き分けの評価値から引く量．
+    value: P,
+    /// 評価値から量を引く関数．結果が評価値の最小値を下回る場合は最小値を返す．
+    saturating_sub: fn(P, P) -> P,
+}
+
+/// `AlphaBetaStrategy`が各ノードで子ノードを調べる方法．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchAlgorithm {
+    /// すべての子ノードを，注目ノードの関心範囲で探索する．
+    AlphaBeta,
+    /// 最初の子ノードだけを関心範囲で探索し，残りの子ノードは最善の評価値を上回るかどうかだけを幅0の窓で調べる．
+    /// 上回ることが分かった子ノードは探索し直す (主要変化探索)．
+    /// 最善の行動を先に調べられるほど，探索量が少なくなる．
+    PrincipalVariation,
+}
+
+impl Default for SearchAlgorithm {
+    /// `AlphaBetaStrategy::new`で作ったエージェントと同じく，`AlphaBeta`を返す．
+    fn default() -> Self {
+        SearchAlgorithm::AlphaBeta
+    }
+}
+
+/// 子ノードの探索方法．
+#[derive(Debug, Clone, Copy)]
+enum ChildSearch<N> {
+    /// 子ノードから，指定した深さまで通常の探索を行う．
+    Full(N),
+    /// 子ノードから，指定した深さまで静止探索を行う．
+    Quiescence(N),
+}
+
+/// 1回の探索に課す制限．
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SearchLimits {
+    /// 探索を打ち切る時刻．
+    pub(crate) deadline: Option<Deadline>,
+    /// 訪れるノード数の上限．
+    pub(crate) node_budget: Option<u64>,
+}
+
+/// 探索の打ち切り時刻を確認する間隔 (訪れたノード数)．
+/// 時刻の取得は比較的重い処理なので，ノードごとには確認しない．
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+/// αβ法による1回の探索の間だけ使用する情報．
+/// `T`は，探索の間に置換表を読み書きするための参照である．
+struct SearchContext<'t, S, A, P, N, T> {
+    /// 評価値を計算する対象のプレイヤー．
+    consideration_target: Actor,
+    /// 置換表．
+    transposition_table: Option<T>,
+    /// 探索に課す制限．
+    limits: SearchLimits,
+    /// 各行動が枝刈りを起こした回数．
+    history: Option<&'t mut HistoryTable<A, N>>,
+    /// 各深さで枝刈りを起こした行動．
+    killers: Option<KillerTable<A, N>>,
+    /// 相手の直前の行動ごとに枝刈りを起こした応手．
+    countermoves: Option<CountermoveTable<A>>,
+    /// 置換表と各表のメモリ使用量の上限．
+    memory_budget: Option<MemoryBudget>,
+    /// 探索の統計情報．
+    stats: SearchStats,
+    /// 探索の途中経過を受け取るフック．
+    hook: Option<&'t dyn SearchHook<S, A, P>>,
+    /// 他のスレッドの探索結果により，この探索が不要になったか判定する関数．
+    cancelled: Option<&'t dyn Fn() -> bool>,
+    /// 根ノードから注目ノードまでの深さ．
+    ply: u32,
+    /// 根ノードから注目ノードまでの手順で，探索を延長した手数の合計．
+    line_extension: u32,
+    /// 探索が打ち切られたか．
+    /// 打ち切られた探索で得られた評価値は信頼できない．
+    aborted: bool,
+    /// `Rule::COLLECTS_ACTIONS`が`true`の場合に，行動を格納するバッファ．
+    action_buffers: ActionBuffers<A>,
+}
+
+impl<'t, S, A, P, N, T> SearchContext<'t, S, A, P, N, T> {
+    fn new(
+        consideration_target: Actor,
+        transposition_table: Option<T>,
+        limits: SearchLimits,
+        history: Option<&'t mut HistoryTable<A, N>>,
+        killers: Option<KillerTable<A, N>>,
+        countermoves: Option<CountermoveTable<A>>,
+    ) -> Self {
+        Self {
+            consideration_target,
+            transposition_table,
+            limits,
+            history,
+            killers,
+            countermoves,
+            memory_budget: None,
+            stats: SearchStats::default(),
+            hook: None,
+            cancelled: None,
+            ply: 0,
+            line_extension: 0,
+            aborted: false,
+            action_buffers: ActionBuffers::default(),
+        }
+    }
+
+    /// 新たなノードを訪れたことを記録し，探索を打ち切るべきか返す．
+    fn visit_node(&mut self) -> bool {
+        if self.aborted {
+            return true;
+        }
+        if self.cancelled.is_some_and(|cancelled| cancelled()) {
+            self.aborted = true;
+            return true;
+        }
+        // 上限に達したら，新たなノードは訪れない
+        if let Some(node_budget) = self.limits.node_budget {
+            if self.stats.nodes_evaluated >= node_budget {
+                self.stats.node_budget_exhausted = true;
+                self.aborted = true;
+                return true;
+            }
+        }
+        self.stats.nodes_evaluated += 1;
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(self.ply);
+        if self
+            .stats
+            .nodes_evaluated
+            .is_multiple_of(DEADLINE_CHECK_INTERVAL)
+        {
+            if let Some(deadline) = self.limits.deadline {
+                self.aborted = clock::is_past(deadline);
+            }
+        }
+        self.aborted
+    }
+
+    /// 訪れたノードをフックに通知する．
+    fn notify_expanded(&self, state: &S, actor: Actor) {
+        if let Some(hook) = self.hook {
+            hook.on_node_expanded(self.ply, state, actor);
+        }
+    }
+
+    /// 注目ノードで起こった枝刈りをフックに通知する．
+    fn notify_cutoff(&self, reason: CutoffReason) {
+        if let Some(hook) = self.hook {
+            hook.on_cutoff(self.ply, reason);
+        }
+    }
+}
+
+impl<S, A, P, N, T> SearchContext<'_, S, A, P, N, T>
+where
+    N: Ord,
+    T: TranspositionStore<S, P, N>,
+{
+    /// 置換表と各表のメモリ使用量を返す．
+    fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            transposition_table: self
+                .transposition_table
+                .as_ref()
+                .map_or(0, |table| table.memory_bytes()),
+            history: self
+                .history
+                .as_ref()
+                .map_or(0, |history| history.memory_bytes()),
+            killers: self.killers.as_ref().map_or(0, KillerTable::memory_bytes),
+            countermoves: self
+                .countermoves
+                .as_ref()
+                .map_or(0, CountermoveTable::memory_bytes),
+            ..MemoryUsage::default()
+        }
+    }
+
+    /// メモリ使用量が上限を超えていれば，`MemoryBudget`に記載した順に記録を消去して上限に収める．
+    /// 収めた後の使用量を，統計情報に記録する．
+    fn enforce_memory_budget(&mut self) {
+        let memory_budget = match self.memory_budget {
+            Some(memory_budget) => memory_budget,
+            None => return,
+        };
+        let mut usage = self.memory_usage();
+        if memory_budget.is_exceeded_by(&usage) {
+            if let Some(killers) = self.killers.as_mut() {
+                killers.clear();
+            }
+            usage = self.memory_usage();
+        }
+        if memory_budget.is_exceeded_by(&usage) {
+            if let Some(countermoves) = self.countermoves.as_mut() {
+                countermoves.clear();
+            }
+            usage = self.memory_usage();
+        }
+        if memory_budget.is_exceeded_by(&usage) {
+            if let Some(history) = self.history.as_deref_mut() {
+                history.clear();
+            }
+            usage = self.memory_usage();
+        }
+        while memory_budget.is_exceeded_by(&usage) {
+            let shrunk = self
+                .transposition_table
+                .as_mut()
+                .is_some_and(|table| table.shrink());
+            if !shrunk {
+                break;
+            }
+            usage = self.memory_usage();
+        }
+        self.stats.peak_memory_bytes = self.stats.peak_memory_bytes.max(usage.total() as u64);
+        self.stats.peak_memory_usage = self.stats.peak_memory_usage.peak(&usage);
+    }
+}
+
+/// αβ法で根ノードから探索した結果．
+pub(crate) struct RootSearch<'a, S, A, P> {
+    /// 根ノード．
+    pub(crate) root: GameTree<'a, S, A, P>,
+    /// 最善と判断された行動が，`MoveOrderer`で並べ替えた行動の何番目か．
+    pub(crate) best_action_index: Option<usize>,
+    /// 探索が打ち切られたか．
+    /// 打ち切られた場合でも，根ノードの評価値は探索を終えた行動のうち最善のものに基づく．
+    pub(crate) aborted: bool,
+    /// 探索の統計情報．
+    pub(crate) stats: SearchStats,
+}
+
+impl<S, A, R, E, N, O> AlphaBetaStrategy<R, E, N, O>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+    O: MoveOrdering<S, A>,
+{
+    /// 根ノードから指定した深さのノードの状態を評価する．
+    /// 引き分けで終了した状態では，Contempt Factorの設定に従って評価値を下げる．
+    pub(crate) fn evaluate_node(
+        &self,
+        actor: Actor,
+        state: &S,
+        terminal: Option<TerminalKind>,
+        ply: u32,
+    ) -> E::Payoff {
+        match (terminal, self.contempt.as_ref()) {
+            (Some(kind @ TerminalKind::Draw), Some(contempt))
+            | (Some(kind @ TerminalKind::NoMovesAvailable), Some(contempt)) => {
+                let payoff = E::evaluate_terminal_payoff_for(actor, state, kind);
+                E::adjust_for_depth((contempt.saturating_sub)(payoff, contempt.value), ply)
+            }
+            _ => evaluate_at_ply::<_, E>(actor, state, terminal, ply),
+        }
+    }
+
+    /// 評価値が勝ち (評価値の最大値) または負け (最小値) であれば，指定した深さを返す．
+    fn decisive_ply(&self, payoff: E::Payoff, ply: u32) -> Option<u32> {
+        if payoff == self.payoff_bounds.max || payoff == self.payoff_bounds.min {
+            Some(ply)
+        } else {
+            None
+        }
+    }
+
+    /// 等しい評価値`payoff`を持つ2つの子ノードのうち，新しい子ノードの手順を優先するか判定する．
+    /// 勝敗が決まる評価値である場合，手番のプレイヤーが勝つなら早く，負けるなら遅く勝敗が決まる手順を優先する．
+    fn prefers_terminal_ply(
+        &self,
+        payoff: E::Payoff,
+        is_own_turn: bool,
+        current: Option<u32>,
+        child: Option<u32>,
+    ) -> bool {
+        let (current, child) = match (current, child) {
+            (Some(current), Some(child)) => (current, child),
+            _ => return false,
+        };
+        let mover_wins = if is_own_turn {
+            payoff == self.payoff_bounds.max
+        } else {
+            payoff == self.payoff_bounds.min
+        };
+        if mover_wins {
+            child < current
+        } else {
+            child > current
+        }
+    }
+
+    /// αβ法により，指定したノードの評価値を再帰的に計算する．
+    /// # Params
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. current_node 注目ノード．
+    /// 1. payoff_range 評価値の関心範囲．
+    /// 1. context 探索中に使用する情報．
+    ///
+    /// # Returns
+    /// `Some(e)`: このノードの評価値`e`
+    ///
+    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
+    fn construct_best_game_tree_alpha_beta<T>(
+        &self,
+        remaining_depth: N,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        // デバッグ用アサーション (消しても問題ないけど，コード変更した際の挙動検証のために一応とっておく)
+        debug_assert!(current_node.payoff.is_none());
+        current_node.terminal_ply = None;
+
+        // 探索が打ち切られたら，以降のノードは調べない
+        if context.visit_node() {
+            return None;
+        }
+        context.notify_expanded(
+            current_node.ref_state(),
+            current_node.next_actor(context.consideration_target),
+        );
+
+        // 探索深さに達したら，必要に応じて静止探索に切り替える
+        if remaining_depth.is_zero() {
+            if let Some(quiescence_depth) = self.quiescence_depth {
+                return self.quiescence(quiescence_depth, current_node, payoff_range, context);
+            }
+        }
+
+        let consideration_target = context.consideration_target;
+        // 注目ノードが末端ノードなら，現在の状態に対する静的評価値をそのまま適用する
+        let terminal = R::terminal_kind(current_node.ref_state());
+        // 両者が続けてパスした状態からは，パスを繰り返すだけでゲームが進まないので，末端ノードとみなす
+        let is_double_pass = current_node.consecutive_passes >= 2;
+        if remaining_depth.is_zero() || terminal.is_some() || is_double_pass {
+            let payoff = match current_node.batched_payoff.take() {
+                Some(payoff) if terminal.is_none() => E::adjust_for_depth(payoff, context.ply),
+                _ => self.evaluate_node(
+                    consideration_target,
+                    current_node.ref_state(),
+                    terminal,
+                    context.ply,
+                ),
+            };
+            current_node.payoff = Some(payoff);
+            current_node.terminal_ply = self.decisive_ply(payoff, context.ply);
+            return Some(payoff);
+        }
+
+        // who WILL act on the current state?
+        let next_actor = current_node.next_actor(consideration_target);
+
+        // 置換表に十分な深さまで探索した結果があり，それだけで評価値が決まるなら探索を省略する．
+        // ただし，根ノードでは最善の行動を決める必要があるので必ず探索する．
+        let key = context.transposition_table.as_ref().map(|table| {
+            current_node
+                .hash
+                .unwrap_or_else(|| table.hash_state(current_node.ref_state()))
+        });
+        let entry = match (context.transposition_table.as_mut(), key) {
+            (Some(table), Some(key)) => table.probe(key, consideration_target, next_actor),
+            _ => None,
+        };
+        let is_root = current_node.is_root();
+        let stored_payoff = entry
+            .filter(|entry| !is_root && entry.depth >= remaining_depth)
+            .and_then(|entry| match entry.bound {
+                BoundType::Exact => Some(entry.payoff),
+                BoundType::LowerBound if entry.payoff > payoff_range.max => Some(entry.payoff),
+                BoundType::UpperBound if entry.payoff < payoff_range.min => Some(entry.payoff),
+                _ => None,
+            });
+        if stored_payoff.is_some() {
+            current_node.payoff = stored_payoff;
+            return stored_payoff;
+        }
+        // 関心範囲から大きく外れると見込まれ，浅い探索でも外れるなら探索を省略する
+        if !is_root {
+            if let Some(payoff) = self.razored_payoff(
+                remaining_depth,
+                next_actor,
+                current_node,
+                payoff_range,
+                context,
+            ) {
+                return Some(payoff);
+            }
+            if context.aborted {
+                return None;
+            }
+        }
+        // 状態遷移などに使用するので，注目ノードの状態をとっておく．
+        // ここでは構造体の，後の処理で変更されないメンバだけの参照を保持するだけなので，
+        // unsafeブロックの処理は安全である．
+        let current_state = {
+            let pointer: *const _ = current_node.ref_state();
+            unsafe { pointer.as_ref().unwrap() }
+        };
+        let depth = current_node.depth();
+        let passes = current_node.consecutive_passes;
+        // 探索の深さが足りず評価値を使えなくても，記録された最善の行動は有望なので最初に調べる．
+        // 記録がなければ，浅い探索で最善と判断された行動を最初に調べる
+        let first_action_index = match entry.and_then(|entry| entry.best_action_index) {
+            Some(index) => Some(index),
+            None => {
+                self.iid_action_index(remaining_depth, current_node, key, payoff_range, context)
+            }
+        };
+        if context.aborted {
+            return None;
+        }
+        let actions = self.ordered_actions(
+            current_state,
+            next_actor,
+            remaining_depth,
+            current_node.cause_action.as_ref(),
+            first_action_index,
+            context,
+        );
+        let mut action_indices = vec![];
+        // パスしてもなお関心範囲を超えるなら，行動を調べるまでもない
+        let null_move_payoff = if is_root {
+            None
+        } else {
+            self.null_move_payoff(
+                remaining_depth,
+                next_actor,
+                current_node,
+                payoff_range,
+                context,
+            )
+        };
+        let best_position = match null_move_payoff {
+            Some(payoff) => {
+                current_node.payoff = Some(payoff);
+                None
+            }
+            None => {
+                // 関心範囲に届く見込みがなければ，除外された行動だけを調べる
+                let futile_payoff = if is_root {
+                    None
+                } else {
+                    self.futility_pruning.as_ref().and_then(|futility_pruning| {
+                        futility_pruning.hopeless_payoff(
+                            remaining_depth,
+                            current_state,
+                            consideration_target,
+                            next_actor,
+                            payoff_range,
+                        )
+                    })
+                };
+                // 枝刈りする行動がなければ，延長する子ノードを決める
+                let (actions, extended_position) = match futile_payoff {
+                    Some(_) => (actions, None),
+                    None => self.extended_position(
+                        remaining_depth,
+                        next_actor,
+                        current_state,
+                        key,
+                        passes,
+                        entry,
+                        actions,
+                        context,
+                    ),
+                };
+                if context.aborted {
+                    return None;
+                }
+                let mut futility_prunes = 0;
+                let (hook, ply) = (context.hook, context.ply);
+                // 行動は子ノードを調べる直前に取り出すので，枝刈りが起こった後の行動は列挙されない
+                let children = actions
+                    .filter(|(_, action)| {
+                        let is_exempt = self
+                            .futility_exemption
+                            .as_ref()
+                            .is_some_and(|is_exempt| is_exempt(current_state, action));
+                        let is_pruned = futile_payoff.is_some() && !is_exempt;
+                        if is_pruned {
+                            futility_prunes += 1;
+                            if let Some(hook) = hook {
+                                hook.on_cutoff(ply, CutoffReason::Futility);
+                            }
+                        }
+                        !is_pruned
+                    })
+                    .map(|(index, action)| {
+                        action_indices.push(index);
+                        self.child_node(current_state, depth, key, passes, action)
+                    });
+                let best_position = self.select_best_child(
+                    ChildSearch::Full(remaining_depth - N::one()),
+                    extended_position,
+                    next_actor,
+                    current_node,
+                    children,
+                    payoff_range,
+                    context,
+                );
+                context.stats.futility_prunes += futility_prunes;
+                // すべての行動を枝刈りした場合は，見積もりを評価値とする
+                if best_position.is_none() && futility_prunes > 0 {
+                    current_node.payoff = futile_payoff;
+                }
+                best_position
+            }
+        };
+
+        // 打ち切られた探索の結果は信頼できないので，記録しない
+        if context.aborted {
+            return None;
+        }
+
+        // 探索結果を置換表に記録する．
+        // 評価値が関心範囲の外にある場合，その値は真の評価値の上界または下界でしかない．
+        // 関心範囲の端に一致する場合は，真の評価値そのものである．
+        if let (Some(table), Some(key), Some(payoff)) = (
+            context.transposition_table.as_mut(),
+            key,
+            current_node.payoff,
+        ) {
+            let bound = ScoreBound::classify(payoff, payoff_range).bound_type();
+            table.store(TranspositionEntry {
+                key,
+                consideration_target,
+                next_actor,
+                depth: remaining_depth,
+                payoff,
+                bound,
+                best_action_index: best_position.map(|position| action_indices[position]),
+            });
+        }
+
+        // 注目ノードの最終的な評価値を返す．
+        // ここに到達した時点で評価値が決定していないということは，
+        // 注目ノードの状態から取れる行動がないということなので，
+        // そのようなノードは探索の対象にしない．
+        current_node.payoff
+    }
+
+    /// 静止探索により，指定したノードの評価値を再帰的に計算する．
+    /// `QuiescenceRule::iterate_noisy_actions`が列挙する行動，
+    /// または`Rule::is_loud_action`が`true`となる行動だけを調べ，行動しない場合の評価値として静的評価値を用いる．
+    /// `Rule::is_quiet_position`が`true`となるノードでは，行動を調べずに静的評価値を用いる．
+    /// # Params
+    /// 1. remaining_depth 残りの静止探索の深さ．
+    /// 1. current_node 注目ノード．
+    /// 1. payoff_range 評価値の関心範囲．
+    /// 1. context 探索中に使用する情報．
+    fn quiescence<T>(
+        &self,
+        remaining_depth: N,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        debug_assert!(current_node.payoff.is_none());
+
+        if context.visit_node() {
+            return None;
+        }
+        context.notify_expanded(
+            current_node.ref_state(),
+            current_node.next_actor(context.consideration_target),
+        );
+
+        let consideration_target = context.consideration_target;
+        let terminal = R::terminal_kind(current_node.ref_state());
+        let stand_pat = self.evaluate_node(
+            consideration_target,
+            current_node.ref_state(),
+            terminal,
+            context.ply,
+        );
+        current_node.payoff = Some(stand_pat);
+        if remaining_depth.is_zero()
+            || terminal.is_some()
+            || R::is_quiet_position(current_node.ref_state())
+        {
+            return Some(stand_pat);
+        }
+
+        let next_actor = current_node.next_actor(consideration_target);
+        // 行動しないことも選べるので，静的評価値は自分の手番では下界，相手の手番では上界となる
+        let maybe_range = if next_actor == consideration_target {
+            let min = if stand_pat > payoff_range.min {
+                stand_pat
+            } else {
+                payoff_range.min
+            };
+            Range::try_new(min, payoff_range.max)
+        } else {
+            let max = if stand_pat < payoff_range.max {
+                stand_pat
+            } else {
+                payoff_range.max
+            };
+            Range::try_new(payoff_range.min, max)
+        };
+        let payoff_range = match maybe_range {
+            Some(range) => range,
+            None => return Some(stand_pat),
+        };
+
+        // 構築済みのノードと同様に，注目ノードの状態の参照をとっておく
+        let current_state = {
+            let pointer: *const _ = current_node.ref_state();
+            unsafe { pointer.as_ref().unwrap() }
+        };
+        let depth = current_node.depth();
+        let passes = current_node.consecutive_passes;
+        let actions = match self.noisy_actions {
+            Some(noisy_actions) => {
+                let actions = noisy_actions(current_state, next_actor);
+                let actions = self.sort_actions(current_state, next_actor, actions);
+                let actions = actions.into_iter().enumerate().collect::<Vec<_>>();
+                OrderedActions::Sorted(actions.into_iter())
+            }
+            None => {
+                let actions = R::iterate_available_actions(current_state, next_actor)
+                    .filter(R::is_loud_action);
+                if O::PRESERVES_ORDER && !R::SCORES_ACTIONS && self.move_orderer.is_none() {
+                    OrderedActions::Lazy(actions.enumerate())
+                } else {
+                    let actions = self.sort_actions(current_state, next_actor, actions.collect());
+                    let actions = actions.into_iter().enumerate().collect::<Vec<_>>();
+                    OrderedActions::Sorted(actions.into_iter())
+                }
+            }
+        };
+        let children =
+            actions.map(|(_, action)| self.child_node(current_state, depth, None, passes, action));
+        self.select_best_child(
+            ChildSearch::Quiescence(remaining_depth - N::one()),
+            None,
+            next_actor,
+            current_node,
+            children,
+            payoff_range,
+            context,
+        );
+
+        if context.aborted {
+            return None;
+        }
+        current_node.payoff
+    }
+
+    /// 内部反復深化により，注目ノードを浅く探索して最善と判断された行動を求める．
+    /// # Returns
+    /// 最善と判断された行動が，`MoveOrderer`で並べ替えた行動の何番目か．
+    /// 内部反復深化を行わない場合や，置換表に結果が残らなかった場合は`None`．
+    fn iid_action_index<T>(
+        &self,
+        remaining_depth: N,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        key: Option<u64>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<usize>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let iid = self.internal_iterative_deepening.as_ref()?;
+        let key = key?;
+        if payoff_range.min == payoff_range.max
+            || remaining_depth < iid.trigger_depth
+            || remaining_depth <= iid.reduction
+        {
+            return None;
+        }
+        context.stats.iid_searches += 1;
+        self.construct_best_game_tree_alpha_beta(
+            remaining_depth - iid.reduction,
+            current_node,
+            payoff_range,
+            context,
+        );
+        // 浅い探索の結果は最初に調べる行動を決めるためだけに使うので，注目ノードを元に戻す
+        current_node.payoff = None;
+        current_node.clear_child();
+        if context.aborted {
+            return None;
+        }
+        let consideration_target = context.consideration_target;
+        let next_actor = current_node.next_actor(consideration_target);
+        context
+            .transposition_table
+            .as_mut()?
+            .probe(key, consideration_target, next_actor)?
+            .best_action_index
+    }
+
+    /// 注目ノードの子ノードのうち，1手深く探索するものを決める．
+    /// 取れる行動が1つしかない場合はその行動を，Singular Extensionの条件を満たす場合は置換表に記録された最善の行動を延長する．
+    /// # Returns
+    /// 調べる順に並べた行動と，延長する行動の位置．
+    #[allow(clippy::too_many_arguments)]
+    fn extended_position<T>(
+        &self,
+        remaining_depth: N,
+        next_actor: Actor,
+        state: &S,
+        key: Option<u64>,
+        passes: u32,
+        entry: Option<TranspositionEntry<E::Payoff, N>>,
+        actions: OrderedActions<R::ActionIterator, A>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> (OrderedActions<R::ActionIterator, A>, Option<usize>)
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        match self.extension_budget {
+            Some(budget) if context.stats.extensions < u64::from(budget) => {}
+            _ => return (actions, None),
+        }
+        // 行動の数を調べるために，すべての行動を先に列挙する
+        let actions = actions.collect::<Vec<_>>();
+        let extended_position = if actions.len() == 1 {
+            Some(0)
+        } else {
+            self.singular_position(
+                remaining_depth,
+                next_actor,
+                state,
+                key,
+                passes,
+                entry,
+                &actions,
+                context,
+            )
+        };
+        (
+            OrderedActions::Sorted(actions.into_iter()),
+            extended_position,
+        )
+    }
+
+    /// 指定した子ノードを何手深く探索するか決め，延長する場合は統計情報に記録する．
+    /// `extended`が`true`の場合，`with_extensions`による1手の延長を加える．
+    /// 延長する手数は，手順ごとの上限を超えない．
+    pub(crate) fn child_extension<T>(
+        &self,
+        extended: bool,
+        child: &GameTree<S, A, E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> u32
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let requested = self
+            .extension_policy
+            .as_ref()
+            .zip(child.cause_action.as_ref())
+            .map_or(0, |(policy, action)| {
+                u32::from(policy.extend(child.ref_state(), action))
+            });
+        let requested = requested + u32::from(extended);
+        let extension = match self.max_line_extension {
+            Some(max) => requested.min(max.saturating_sub(context.line_extension)),
+            None => requested,
+        };
+        if extension > 0 {
+            context.stats.extensions += 1;
+        }
+        extension
+    }
+
+    /// 置換表に記録された最善の行動が，他のどの行動よりも際立って良いか確かめる (Singular Extension)．
+    /// # Returns
+    /// 際立って良い場合，その行動の位置．
+    #[allow(clippy::too_many_arguments)]
+    fn singular_position<T>(
+        &self,
+        remaining_depth: N,
+        next_actor: Actor,
+        state: &S,
+        key: Option<u64>,
+        passes: u32,
+        entry: Option<TranspositionEntry<E::Payoff, N>>,
+        actions: &[(usize, A)],
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<usize>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let singular_extension = self.singular_extension.as_ref()?;
+        let entry = entry?;
+        // 記録された評価値が，現在の探索とほぼ同じ深さで得られたものである場合に限る
+        if remaining_depth <= N::one() || entry.depth + N::one() < remaining_depth {
+            return None;
+        }
+        let is_own_turn = next_actor == context.consideration_target;
+        // 最善の行動の評価値が，少なくとも記録された値以上 (相手の手番では以下) である必要がある
+        match entry.bound {
+            BoundType::UpperBound if is_own_turn => return None,
+            BoundType::LowerBound if !is_own_turn => return None,
+            _ => {}
+        }
+        let best_action_index = entry.best_action_index?;
+        let position = actions
+            .iter()
+            .position(|(index, _)| *index == best_action_index)?;
+
+        // 他の行動が，記録された評価値から余裕を引いた値 (相手の手番では加えた値) に届かないことを浅い探索で確かめる
+        let bound = singular_extension.singular_bound(entry.payoff, is_own_turn);
+        let verification_depth = (remaining_depth - N::one()) / (N::one() + N::one());
+        for (_, action) in actions
+            .iter()
+            .filter(|(index, _)| *index != best_action_index)
+        {
+            let action = (singular_extension.clone_action)(action);
+            let mut child = self.child_node(state, context.ply as usize, key, passes, action);
+            context.ply += 1;
+            let payoff = self.construct_best_game_tree_alpha_beta(
+                verification_depth,
+                &mut child,
+                Range::new(bound, bound),
+                context,
+            );
+            context.ply -= 1;
+            if context.aborted {
+                return None;
+            }
+            match payoff {
+                Some(e) if (is_own_turn && e >= bound) || (!is_own_turn && e <= bound) => {
+                    return None
+                }
+                _ => {}
+            }
+        }
+        Some(position)
+    }
+
+    /// Razoringにより，関心範囲から大きく外れると見込まれる注目ノードを，1だけ浅く幅0の窓で探索する．
+    /// # Returns
+    /// 浅い探索でも評価値が関心範囲から外れ，注目ノードの探索を省略できる場合は，その評価値．
+    fn razored_payoff<T>(
+        &self,
+        remaining_depth: N,
+        next_actor: Actor,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let consideration_target = context.consideration_target;
+        self.razoring.as_ref()?.hopeless_payoff(
+            remaining_depth,
+            current_node.ref_state(),
+            consideration_target,
+            next_actor,
+            payoff_range,
+        )?;
+        let is_own_turn = next_actor == consideration_target;
+        let bound = if is_own_turn {
+            payoff_range.min
+        } else {
+            payoff_range.max
+        };
+        let payoff = self.construct_best_game_tree_alpha_beta(
+            remaining_depth - N::one(),
+            current_node,
+            Range::new(bound, bound),
+            context,
+        );
+        let is_confirmed =
+            payoff.is_some_and(|e| (is_own_turn && e < bound) || (!is_own_turn && e > bound));
+        if context.aborted || !is_confirmed {
+            // 通常の深さで探索し直す
+            current_node.payoff = None;
+            return None;
+        }
+        context.stats.razored_nodes += 1;
+        context.notify_cutoff(CutoffReason::Razoring);
+        payoff
+    }
+
+    /// 注目ノードで手番のプレイヤーがパスした状態を，通常より浅く幅0の窓で探索する．
+    /// # Returns
+    /// パスした後の評価値が関心範囲を超え，注目ノードの探索を省略できる場合は，その評価値．
+    /// 自分の手番では関心範囲の上限を上回る値，相手の手番では下限を下回る値となる．
+    fn null_move_payoff<T>(
+        &self,
+        remaining_depth: N,
+        next_actor: Actor,
+        current_node: &GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let reduction = self.null_move_reduction?;
+        // パスを続けると探索が進まないので，パスにより到達したノードではパスしない
+        if current_node.null_move_actor.is_some() {
+            return None;
+        }
+        // 浅すぎる探索の結果は信頼できないので，十分な深さが残っている場合にだけパスする
+        if remaining_depth <= reduction + N::one() {
+            return None;
+        }
+        let is_own_turn = next_actor == context.consideration_target;
+        let bound = if is_own_turn {
+            payoff_range.max
+        } else {
+            payoff_range.min
+        };
+        // 関心範囲の端が評価値の最大値 (最小値) なら，それを超えることはない
+        if (is_own_turn && bound == self.payoff_bounds.max)
+            || (!is_own_turn && bound == self.payoff_bounds.min)
+        {
+            return None;
+        }
+        let null_state = R::try_null_move(current_node.ref_state())?;
+        let mut node = MinimaxNode::new(null_state.into(), None, None);
+        node.null_move_actor = Some(next_actor);
+        let mut child = current_node.new_child(node);
+        context.ply += 1;
+        let payoff = self.construct_best_game_tree_alpha_beta(
+            remaining_depth - N::one() - reduction,
+            &mut child,
+            Range::new(bound, bound),
+            context,
+        );
+        context.ply -= 1;
+        if context.aborted {
+            return None;
+        }
+        let payoff =
+            payoff.filter(|&e| (is_own_turn && e > bound) || (!is_own_turn && e < bound))?;
+        context.stats.null_move_cutoffs += 1;
+        context.notify_cutoff(CutoffReason::NullMove);
+        Some(payoff)
+    }
+
+    /// 指定した状態で取れる行動を，`Rule::heuristic_action_score`の大きい順に並べた後，`MoveOrdering`と`MoveOrderer`で並べ替える．
+    fn sort_actions(&self, state: &S, actor: Actor, mut actions: Vec<A>) -> Vec<A> {
+        if R::SCORES_ACTIONS {
+            // 安定ソートなので，点数が等しい行動は列挙された順に調べる
+            actions.sort_by_cached_key(|action| Reverse(R::heuristic_action_score(state, action)));
+        }
+        let mut actions = O::order_actions(state, actor, actions);
+        if let Some(move_orderer) = self.move_orderer.as_ref() {
+            move_orderer.order(state, actor, &mut actions);
+        }
+        actions
+    }
+
+    /// 指定した状態で取れる行動を，調べる順に並べる．
+    /// `MoveOrderer`で並べ替えた行動のうち，指定した番号の行動を先頭とする．
+    /// 残りの行動は，キラー手，相手の直前の行動`previous_action`に対するカウンター手，
+    /// History Heuristicの実績の大きい行動の順に並べる．
+    /// 並べ替える必要がなければ，行動は`Rule::iterate_available_actions`から必要な分だけ取り出される．
+    /// # Returns
+    /// 並べた各行動の`MoveOrderer`で並べ替えた時点での番号と，並べた行動を列挙するイテレータ．
+    fn ordered_actions<T>(
+        &self,
+        state: &S,
+        actor: Actor,
+        remaining_depth: N,
+        previous_action: Option<&A>,
+        first_action_index: Option<usize>,
+        context: &SearchContext<S, A, E::Payoff, N, T>,
+    ) -> OrderedActions<R::ActionIterator, A>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let (killers, history) = (context.killers.as_ref(), context.history.as_deref());
+        // 根ノードやパスした後のノードには直前の行動がないので，カウンター手を用いない
+        let countermoves = context.countermoves.as_ref().zip(previous_action);
+        if O::PRESERVES_ORDER
+            && !R::SCORES_ACTIONS
+            && self.move_orderer.is_none()
+            && first_action_index.is_none()
+            && killers.is_none()
+            && countermoves.is_none()
+            && history.is_none()
+        {
+            if R::COLLECTS_ACTIONS {
+                let actions = BufferedActions::collect::<R>(state, actor, &context.action_buffers);
+                return OrderedActions::Buffered(actions.enumerate());
+            }
+            let actions = R::iterate_available_actions(state, actor);
+            return OrderedActions::Lazy(actions.enumerate());
+        }
+
+        let actions = R::iterate_available_actions(state, actor);
+        let actions = self.sort_actions(state, actor, actions.collect());
+        let mut actions = actions.into_iter().enumerate().collect::<Vec<_>>();
+        let first_action_index = first_action_index.filter(|&index| index < actions.len());
+        if let Some(index) = first_action_index {
+            let first = actions.remove(index);
+            actions.insert(0, first);
+        }
+        if killers.is_some() || countermoves.is_some() || history.is_some() {
+            // 安定ソートなので，優先度が等しい行動は元の順に調べる
+            let rest = if first_action_index.is_some() { 1 } else { 0 };
+            actions[rest..].sort_by_key(|(_, action)| {
+                let is_killer =
+                    killers.is_some_and(|killers| killers.is_killer(&remaining_depth, action));
+                let is_countermove = countermoves.is_some_and(|(countermoves, previous)| {
+                    countermoves.is_countermove(previous, action)
+                });
+                let score = history.map_or(0, |history| history.score(action));
+                (Reverse(is_killer), Reverse(is_countermove), Reverse(score))
+            });
+        }
+        OrderedActions::Sorted(actions.into_iter())
+    }
+
+    /// 指定した状態で指定した行動をとった後の状態を持つ子ノードを作る．
+    /// `parent_depth`には指定した状態を持つノードの，根ノードからの深さを渡す．
+    /// `key`には指定した状態のハッシュ値を渡す．ハッシュ値を差分更新できる場合，子ノードのハッシュ値も記録する．
+    /// `parent_passes`には指定した状態に至るまでに続けてパスが行われた回数を渡す．
+    fn child_node<'a>(
+        &self,
+        state: &S,
+        parent_depth: usize,
+        key: Option<u64>,
+        parent_passes: u32,
+        action: A,
+    ) -> GameTree<'a, S, A, E::Payoff> {
+        let hash = key.and_then(|key| {
+            self.incremental_hash
+                .map(|update_hash| update_hash(key, state, &action))
+        });
+        let consecutive_passes = if action.is_pass() {
+            parent_passes.saturating_add(1)
+        } else {
+            0
+        };
+        let next_state = R::translate_state(state, &action);
+        let mut node = MinimaxNode::new(next_state.into(), Some(action), None);
+        node.hash = hash;
+        node.consecutive_passes = consecutive_passes;
+        TreeNode::with_depth(node, parent_depth + 1)
+    }
+
+    /// 子ノードが注目ノードの最善の評価値を上回るかどうかを幅0の窓で調べ，
+    /// 上回る場合は関心範囲で探索し直して子ノードの評価値を返す．
+    /// # Params
+    /// 1. remaining_depth 子ノードからの残りの探索深さ．
+    /// 1. next_actor 注目ノードで行動するプレイヤー．
+    /// 1. child 子ノード．
+    /// 1. payoff_range 注目ノードの評価値の関心範囲．
+    /// 1. context 探索中に使用する情報．
+    fn scout_child<T>(
+        &self,
+        remaining_depth: N,
+        next_actor: Actor,
+        child: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let is_own_turn = next_actor == context.consideration_target;
+        // 自分の手番では関心範囲の下限を，相手の手番では上限を超えるかを調べる
+        let bound = if is_own_turn {
+            payoff_range.min
+        } else {
+            payoff_range.max
+        };
+        let scout_payoff = self.construct_best_game_tree_alpha_beta(
+            remaining_depth,
+            child,
+            Range::new(bound, bound),
+            context,
+        )?;
+        if context.aborted {
+            return None;
+        }
+
+        // 窓を超え，かつ関心範囲に収まる場合は，真の評価値を得るために探索し直す．
+        // 窓を超えた評価値は真の評価値の下界 (相手の手番では上界) なので，新たな関心範囲の端に使える．
+        let re_search_range =
+            if is_own_turn && bound < scout_payoff && scout_payoff < payoff_range.max {
+                Range::new(scout_payoff, payoff_range.max)
+            } else if !is_own_turn && payoff_range.min < scout_payoff && scout_payoff < bound {
+                Range::new(payoff_range.min, scout_payoff)
+            } else {
+                return Some(scout_payoff);
+            };
+        context.stats.re_searches += 1;
+        child.payoff = None;
+        self.construct_best_game_tree_alpha_beta(remaining_depth, child, re_search_range, context)
+    }
+
+    /// Late Move Reductionsにより，子ノードを通常より浅く幅0の窓で探索する．
+    /// # Params
+    /// 1. remaining_depth 子ノードからの残りの探索深さ．
+    /// 1. position 子ノードを調べる順番．
+    /// 1. next_actor 注目ノードで行動するプレイヤー．
+    /// 1. state 注目ノードの状態．
+    /// 1. child 子ノード．
+    /// 1. payoff_range 注目ノードの評価値の関心範囲．
+    /// 1. context 探索中に使用する情報．
+    ///
+    /// # Returns
+    /// 浅く探索した子ノードが，注目ノードの最善の評価値を上回らなかった場合は，その評価値．
+    /// 浅く探索しない子ノードや，上回った子ノードでは`None`を返すので，通常の深さで探索する必要がある．
+    #[allow(clippy::too_many_arguments)]
+    fn reduced_child_payoff<T>(
+        &self,
+        remaining_depth: N,
+        position: usize,
+        next_actor: Actor,
+        state: &S,
+        child: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let late_move_reduction = self.late_move_reduction.as_ref()?;
+        if position < late_move_reduction.start_after || remaining_depth <= N::one() {
+            return None;
+        }
+        let action = child.cause_action.as_ref()?;
+        if self
+            .reduction_exemption
+            .as_ref()
+            .is_some_and(|is_exempt| is_exempt(state, action))
+        {
+            return None;
+        }
+        // 浅くしても，子ノードから1手以上は探索する
+        let reduction = (late_move_reduction.reduction)(remaining_depth + N::one(), position)
+            .min(remaining_depth - N::one());
+        if reduction <= N::zero() {
+            return None;
+        }
+
+        let is_own_turn = next_actor == context.consideration_target;
+        let bound = if is_own_turn {
+            payoff_range.min
+        } else {
+            payoff_range.max
+        };
+        let payoff = self.construct_best_game_tree_alpha_beta(
+            remaining_depth - reduction,
+            child,
+            Range::new(bound, bound),
+            context,
+        );
+        child.payoff = None;
+        if context.aborted {
+            return None;
+        }
+        match payoff {
+            Some(e) if (is_own_turn && e <= bound) || (!is_own_turn && e >= bound) => {
+                child.payoff = payoff;
+                payoff
+            }
+            // 有望な子ノードは，通常の深さで探索し直す
+            Some(_) => {
+                context.stats.late_move_re_searches += 1;
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 指定した子ノードを順番に調べ，注目ノードにとって最善の子ノードを注目ノードの子とする．
+    /// 子ノードは`children`が列挙する順に探索されるので，有望な子ノードを先に列挙するほど枝刈りが起こりやすくなる．
+    ///
+    /// `extended_position`番目に列挙された子ノードは，1手深く探索する．
+    /// 末端ノードをまとめて評価する場合は，子ノードをすべて列挙してから調べる．
+    ///
+    /// # Returns
+    /// 最善と判断された子ノードが`children`の何番目に列挙されたか．
+    /// 評価値が決定した子ノードがない場合は`None`．
+    #[allow(clippy::too_many_arguments)]
+    fn select_best_child<'a, I, T>(
+        &self,
+        child_search: ChildSearch<N>,
+        extended_position: Option<usize>,
+        next_actor: Actor,
+        current_node: &mut GameTree<'a, S, A, E::Payoff>,
+        children: I,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<usize>
+    where
+        I: Iterator<Item = GameTree<'a, S, A, E::Payoff>>,
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let batches = self.batch_evaluation
+            && matches!(child_search, ChildSearch::Full(depth) if depth.is_zero())
+            && self.quiescence_depth.is_none();
+        if !batches {
+            return self.search_children(
+                child_search,
+                extended_position,
+                next_actor,
+                current_node,
+                children,
+                payoff_range,
+                context,
+            );
+        }
+        let mut children = children.collect::<Vec<_>>();
+        Self::evaluate_in_batch(&mut children, context.consideration_target);
+        self.search_children(
+            child_search,
+            extended_position,
+            next_actor,
+            current_node,
+            children.into_iter(),
+            payoff_range,
+            context,
+        )
+    }
+
+    /// 指定した子ノードのうち，ゲームが終了していないものの状態を`Evaluator::evaluate_batch`でまとめて評価し，
+    /// 各子ノードに記録する．
+    fn evaluate_in_batch(children: &mut [GameTree<S, A, E::Payoff>], consideration_target: Actor) {
+        let positions = children
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| R::terminal_kind(child.ref_state()).is_none())
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        let states = positions
+            .iter()
+            .map(|&position| children[position].ref_state())
+            .collect::<Vec<_>>();
+        let payoffs = E::evaluate_batch(consideration_target, &states);
+        debug_assert_eq!(positions.len(), payoffs.len());
+        for (position, payoff) in positions.into_iter().zip(payoffs) {
+            children[position].batched_payoff = Some(payoff);
+        }
+    }
+
+    /// 指定した子ノードを`children`が列挙する順に調べ，注目ノードにとって最善の子ノードを注目ノードの子とする．
+    /// 引数と返り値は`select_best_child`と同じである．
+    #[allow(clippy::too_many_arguments)]
+    fn search_children<'a, I, T>(
+        &self,
+        child_search: ChildSearch<N>,
+        extended_position: Option<usize>,
+        next_actor: Actor,
+        current_node: &mut GameTree<'a, S, A, E::Payoff>,
+        children: I,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> Option<usize>
+    where
+        I: Iterator<Item = GameTree<'a, S, A, E::Payoff>>,
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let consideration_target = context.consideration_target;
+        let mut current_payoff_range = payoff_range;
+        let mut best_position = None;
+
+        // 次の実現しうる状態をすべて列挙し，ひとつひとつ調べる
+        for (position, mut child) in children.enumerate() {
+            // 子ノードの評価値を再帰的に求める．
+            // ここでNoneが帰ってきた場合，その子ノードはゲーム終了でもなく，かつ取れる行動がないパターンなので，探索対象としない．
+            context.ply += 1;
+            let (search, extension) = match child_search {
+                ChildSearch::Full(depth) => {
+                    let single_reply = extended_position == Some(position);
+                    let extension = self.child_extension(single_reply, &child, context);
+                    (
+                        ChildSearch::Full(extended_depth(depth, extension)),
+                        extension,
+                    )
+                }
+                child_search => (child_search, 0),
+            };
+            context.line_extension += extension;
+            let reduced_payoff = match search {
+                ChildSearch::Full(depth) => {
+                    let state = current_node.ref_state();
+                    self.reduced_child_payoff(
+                        depth,
+                        position,
+                        next_actor,
+                        state,
+                        &mut child,
+                        current_payoff_range,
+                        context,
+                    )
+                }
+                ChildSearch::Quiescence(_) => None,
+            };
+            let child_payoff = match search {
+                _ if reduced_payoff.is_some() => reduced_payoff,
+                // 主要変化探索では，2番目以降の子ノードを幅0の窓で調べる．
+                // ただし，末端ノードは幅0の窓で調べても探索量が減らないので，通常通り調べる
+                ChildSearch::Full(depth)
+                    if self.search_algorithm == SearchAlgorithm::PrincipalVariation
+                        && best_position.is_some()
+                        && !depth.is_zero() =>
+                {
+                    self.scout_child(depth, next_actor, &mut child, current_payoff_range, context)
+                }
+                ChildSearch::Full(depth) => self.construct_best_game_tree_alpha_beta(
+                    depth,
+                    &mut child,
+                    current_payoff_range,
+                    context,
+                ),
+                ChildSearch::Quiescence(depth) => {
+                    self.quiescence(depth, &mut child, current_payoff_range, context)
+                }
+            };
+            context.ply -= 1;
+            context.line_extension -= extension;
+            let child_payoff = match child_payoff {
+                // 探索が打ち切られた場合，この子ノードの評価値は信頼できないので採用しない
+                _ if context.aborted => break,
+                Some(e) => e,
+                None => continue,
+            };
+            // ミニマックス法により，探索する必要がある枝だけを選択する
+            if let Some(e) = current_node.payoff {
+                let is_own_turn = next_actor == consideration_target;
+                // 勝敗の決まる評価値が等しい場合は，勝つ側は早く，負ける側は遅く勝敗が決まる行動を選択する
+                let prefers_line = e == child_payoff
+                    && self.prefers_terminal_ply(
+                        e,
+                        is_own_turn,
+                        current_node.terminal_ply,
+                        child.terminal_ply,
+                    );
+                if is_own_turn {
+                    // 自分の手番では，自分が有利になる行動を選択するので，
+                    // 自分が不利になる行動は候補から除外する
+                    if e >= child_payoff && !prefers_line {
+                        continue;
+                    }
+                } else {
+                    // 相手の手番では，相手は自分が不利になる行動を選択するので，
+                    // 自分が有利になる行動は候補から除外する．
+                    if e <= child_payoff && !prefers_line {
+                        continue;
+                    }
+                }
+            }
+            // 評価値の注目範囲を更新する．
+            // 可能なら，αβカットして探索量を減らす．
+            let maybe_next_range = if next_actor == consideration_target {
+                Range::try_new(child_payoff, current_payoff_range.max)
+            } else {
+                Range::try_new(current_payoff_range.min, child_payoff)
+            };
+            if maybe_next_range.is_none() {
+                if next_actor == consideration_target {
+                    context.stats.beta_cutoffs += 1;
+                    context.notify_cutoff(CutoffReason::Beta);
+                } else {
+                    context.stats.alpha_cutoffs += 1;
+                    context.notify_cutoff(CutoffReason::Alpha);
+                }
+                // 枝刈りを起こした行動は，他の局面でも先に調べる．
+                // 静止探索での枝刈りは，通常の探索の行動順には反映させない
+                if let (Some(action), ChildSearch::Full(depth)) =
+                    (child.cause_action.as_ref(), child_search)
+                {
+                    let remaining_depth = depth + N::one();
+                    if let Some(history) = context.history.as_deref_mut() {
+                        history.record_cutoff(remaining_depth, action);
+                    }
+                    if let Some(killers) = context.killers.as_mut() {
+                        if killers.is_killer(&remaining_depth, action) {
+                            context.stats.killer_hits += 1;
+                        }
+                        killers.record_cutoff(remaining_depth, action);
+                    }
+                    if let (Some(countermoves), Some(previous_action)) = (
+                        context.countermoves.as_mut(),
+                        current_node.cause_action.as_ref(),
+                    ) {
+                        countermoves.record_cutoff(previous_action, action);
+                    }
+                    context.enforce_memory_budget();
+                }
+            }
+            // ここに来たということは，より良い子ノードが見つかったということなので，子ノードの情報を入れ替える．
+            // また，注目ノードの評価値には，子ノードの値を反映させる．
+            if let (Some(hook), Some(action), true) = (
+                context.hook,
+                child.cause_action.as_ref(),
+                current_node.is_root(),
+            ) {
+                hook.on_best_action_updated(action, child_payoff);
+            }
+            current_node.terminal_ply = child.terminal_ply;
+            current_node.replace_child(child);
+            current_node.payoff = Some(child_payoff);
+            best_position = Some(position);
+            match maybe_next_range {
+                Some(range) => current_payoff_range = range,
+                None => break,
+            }
+        }
+
+        best_position
+    }
+
+    /// 指定した状態を根とするゲーム木をαβ法で探索し，根ノードを返す．
+    /// 根ノードの子をたどることで，最善と判断された手順を得られる．
+    fn search<'a>(&self, state: &'a S, actor: Actor) -> GameTree<'a, S, A, E::Payoff> {
+        self.advance_transposition_generation();
+        self.search_root(
+            state,
+            actor,
+            self.search_depth,
+            None,
+            SearchLimits::default(),
+            self.payoff_bounds.full_range(),
+        )
+        .root
+    }
+
+    /// 根ノードで最初に調べる行動を指定して，ゲーム木を指定した深さまでαβ法で探索する．
+    /// # Params
+    /// 1. search_depth 探索するゲーム木の深さ．
+    /// 1. first_action_index `MoveOrderer`で並べ替えた行動のうち，最初に調べる行動の番号．
+    /// 1. limits 探索に課す制限．
+    /// 1. payoff_range 根ノードの評価値の関心範囲．
+    ///
+    /// 評価値が関心範囲の下限を下回った場合，真の評価値はその値以下である．
+    /// 上限を上回った場合，真の評価値はその値以上である．
+    /// 関心範囲に収まった場合，真の評価値そのものである．
+    fn search_root<'a>(
+        &self,
+        state: &'a S,
+        actor: Actor,
+        search_depth: N,
+        first_action_index: Option<usize>,
+        limits: SearchLimits,
+        payoff_range: Range<E::Payoff>,
+    ) -> RootSearch<'a, S, A, E::Payoff> {
+        self.search_root_observed(
+            state,
+            actor,
+            search_depth,
+            first_action_index,
+            limits,
+            payoff_range,
+            None,
+        )
+    }
+
+    /// `search_root`と同様に探索し，その途中経過を`hook`に通知する．
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn search_root_observed<'a>(
+        &self,
+        state: &'a S,
+        actor: Actor,
+        search_depth: N,
+        first_action_index: Option<usize>,
+        limits: SearchLimits,
+        payoff_range: Range<E::Payoff>,
+        hook: Option<&dyn SearchHook<S, A, E::Payoff>>,
+    ) -> RootSearch<'a, S, A, E::Payoff> {
+        if let Some(hook) = hook {
+            hook.on_node_expanded(0, state, actor);
+        }
+        let terminal = R::terminal_kind(state);
+        if search_depth.is_zero() || terminal.is_some() {
+            let mut root = TreeNode::new(MinimaxNode::new(state.into(), None, None));
+            root.payoff = Some(self.evaluate_node(actor, state, terminal, 0));
+            return RootSearch {
+                root,
+                best_action_index: None,
+                aborted: false,
+                stats: SearchStats {
+                    nodes_evaluated: 1,
+                    ..SearchStats::default()
+                },
+            };
+        }
+
+        // 置換表は探索の間ずっと使うので，最初にロックしておく
+        let mut table = self.transposition_table.as_ref().map(|table| table.lock());
+        let mut history = self.history.as_ref().map(|history| history.lock());
+        let killers = self.killer_moves.map(|new_killers| new_killers());
+        let countermoves = self.countermoves.map(|new_countermoves| new_countermoves());
+        let mut context = SearchContext::new(
+            actor,
+            table.as_deref_mut(),
+            limits,
+            history.as_deref_mut(),
+            killers,
+            countermoves,
+        );
+        context.hook = hook;
+        context.memory_budget = self.memory_budget;
+        context.enforce_memory_budget();
+        self.search_root_in(
+            state,
+            search_depth,
+            first_action_index,
+            payoff_range,
+            &mut context,
+        )
+    }
+
+    /// 指定した探索中の情報を用いて，根ノードの子ノードを調べる．
+    /// 根ノードは終局していない状態でなければならず，`search_depth`は0であってはならない．
+    /// 根ノードで行動するプレイヤーは，`context`の評価値を計算する対象のプレイヤーである．
+    pub(crate) fn search_root_in<'a, T>(
+        &self,
+        state: &'a S,
+        search_depth: N,
+        first_action_index: Option<usize>,
+        payoff_range: Range<E::Payoff>,
+        context: &mut SearchContext<S, A, E::Payoff, N, T>,
+    ) -> RootSearch<'a, S, A, E::Payoff>
+    where
+        T: TranspositionStore<S, E::Payoff, N>,
+    {
+        let actor = context.consideration_target;
+        let mut root = TreeNode::new(MinimaxNode::new(state.into(), None, None));
+        let key = context
+            .transposition_table
+            .as_ref()
+            .map(|table| table.hash_state(state));
+        // 根ノードも訪れたノードに含める
+        context.stats.nodes_evaluated += 1;
+
+        let actions = self.ordered_actions(
+            state,
+            actor,
+            search_depth,
+            None,
+            first_action_index,
+            context,
+        );
+        let mut action_indices = vec![];
+        let children = actions.map(|(index, action)| {
+            action_indices.push(index);
+            self.child_node(state, 0, key, 0, action)
+        });
+        let best_position = self.select_best_child(
+            ChildSearch::Full(search_depth - N::one()),
+            None,
+            actor,
+            &mut root,
+            children,
+            payoff_range,
+            context,
+        );
+        RootSearch {
+            root,
+            best_action_index: best_position.map(|position| action_indices[position]),
+            aborted: context.aborted,
+            stats: context.stats,
+        }
+    }
+
+    /// 指定した時刻までに，指定した状態におけるエージェントの行動を選択する．
+    ///
+    /// 探索深さを1から順に増やしながら探索し，時刻を過ぎた時点で探索を打ち切る．
+    /// 打ち切られた深さの探索では，探索を終えた行動のうち最善のものが，それより浅い探索の結果よりも優先される．
+    /// 時刻の確認は一定数のノードを訪れるごとに行うので，指定した時刻をわずかに過ぎる場合がある．
+    #[cfg(feature = "std")]
+    pub fn select_action_with_deadline(
+        &self,
+        state: &S,
+        actor: Actor,
+        deadline: Instant,
+    ) -> Option<A> {
+        iterative_deepening::deepen(self, state, actor, Some(deadline), None, None, |_| true)
+            .into_best_action()
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).payoff
+    }
+
+    /// 指定した状態を，評価値の関心範囲`[min, max]`を指定して探索し，根ノードの評価値を返す．
+    ///
+    /// 評価値が関心範囲の外にある場合，その値は真の評価値の上界または下界でしかない．
+    /// 返り値の`ScoreBound`は，評価値がどちらであるかを表す．
+    /// `mode`が`WindowMode::FailHard`の場合，関心範囲の外にある評価値は関心範囲の端に切り詰められる．
+    /// 取れる行動がない場合は`None`を返す．
+    /// # Panics
+    /// `min > max`の場合
+    pub fn search_window(
+        &self,
+        state: &S,
+        actor: Actor,
+        min: E::Payoff,
+        max: E::Payoff,
+        mode: WindowMode,
+    ) -> Option<ScoreBound<E::Payoff>> {
+        assert!(min <= max, "search window must not be empty");
+        let payoff_range = Range::new(min, max);
+        let search = self.search_root(
+            state,
+            actor,
+            self.search_depth,
+            None,
+            SearchLimits::default(),
+            payoff_range,
+        );
+        search.root.payoff.map(|payoff| {
+            ScoreBound::classify(payoff, payoff_range).with_window_mode(mode, payoff_range)
+        })
+    }
+
+    /// 指定した状態における最善手順 (主要変化) と，その評価値を返す．
+    /// 主要変化の最初の行動は，`select_action`が選択する行動と等しい．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn select_action_with_pv(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> Option<PrincipalVariation<A, E::Payoff>> {
+        PrincipalVariation::from_game_tree(&mut self.search(state, actor))
+    }
+
+    /// 探索深さを1から順に増やしながら探索し，最善手順と各深さの探索の記録を返す．
+    /// 探索の結果を詳しく調べたい場合に用いる．
+    ///
+    /// 各深さは評価値がとりうる範囲全体を関心範囲として探索し，直前の深さで最善だった行動を最初に調べる．
+    /// 最善手順と評価値は，最も深い探索で得られたものとなる．
+    pub fn analyze(&self, state: &S, actor: Actor) -> AnalysisReport<A, E::Payoff> {
+        let stopwatch = Stopwatch::start();
+        self.advance_transposition_generation();
+        let payoff_range = self.payoff_bounds.full_range();
+        let mut depths = vec![];
+        let mut stats = SearchStats::default();
+        let mut best_action_index = None;
+        let mut principal_variation = None;
+        let mut depth = N::zero();
+        while depth < self.search_depth {
+            depth = depth + N::one();
+            let mut search = self.search_root(
+                state,
+                actor,
+                depth,
+                best_action_index,
+                SearchLimits::default(),
+                payoff_range,
+            );
+            stats.merge(&search.stats);
+            best_action_index = search.best_action_index;
+            depths.push(AnalysisDepth {
+                depth: depths.len() as u32 + 1,
+                nodes_evaluated: search.stats.nodes_evaluated,
+                alpha: payoff_range.min,
+                beta: payoff_range.max,
+                payoff: search.root.payoff,
+            });
+            principal_variation = PrincipalVariation::from_game_tree(&mut search.root);
+        }
+        stats.elapsed = stopwatch.elapsed();
+        AnalysisReport {
+            payoff: principal_variation.as_ref().map(|pv| pv.payoff),
+            principal_variation: principal_variation.map_or(vec![], |pv| pv.actions),
+            depths,
+            stats,
+        }
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    /// ノード数の上限に達したかどうかは，統計情報の`node_budget_exhausted`で確認できる．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その行動をとった場合に見込まれる評価値とともに返す．
+    /// 評価値は探索で得られた根ノードの評価値 (ミニマックス法による値) であり，行動をとった直後の状態の静的な評価値ではない．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn select_action_with_payoff(&self, state: &S, actor: Actor) -> Option<(A, E::Payoff)> {
+        let (scored, _) = self.select_scored_action_with_stats(state, actor);
+        scored.map(|scored| (scored.action, scored.payoff))
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        self.select_scored_action_with_hook(state, actor, None)
+    }
+
+    /// `select_scored_action_with_stats`と同様に行動を選択し，探索の途中経過を`hook`に通知する．
+    fn select_scored_action_with_hook(
+        &self,
+        state: &S,
+        actor: Actor,
+        hook: Option<&dyn SearchHook<S, A, E::Payoff>>,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let stopwatch = Stopwatch::start();
+        let (scored, mut stats) = if self.node_budget.is_some() {
+            let result =
+                iterative_deepening::deepen(self, state, actor, None, None, hook, |_| true);
+            let stats = *result.stats();
+            (result.into_scored_action(), stats)
+        } else {
+            self.advance_transposition_generation();
+            let RootSearch { root, stats, .. } = self.search_root_observed(
+                state,
+                actor,
+                self.search_depth,
+                None,
+                SearchLimits::default(),
+                self.payoff_bounds.full_range(),
+                hook,
+            );
+            (ScoredAction::from_game_tree(root), stats)
+        };
+        stats.elapsed = stopwatch.elapsed();
+        stats.depth_to_terminal = scored.as_ref().and_then(|scored| scored.depth_to_terminal);
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N, O> ScoredStrategy<S, A> for AlphaBetaStrategy<R, E, N, O>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+    O: MoveOrdering<S, A>,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
+
+impl<S, A, R, E, N, O> ObservableSearch<S, A> for AlphaBetaStrategy<R, E, N, O>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+    O: MoveOrdering<S, A>,
+{
+    fn select_scored_action_observed(
+        &self,
+        state: &S,
+        actor: Actor,
+        hook: &dyn SearchHook<S, A, E::Payoff>,
+    ) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_hook(state, actor, Some(hook))
+            .0
+    }
+}
+
+/// 2人ゲームにおける各プレイヤーを返す．
+#[deprecated(note = "use `Actor::ALL` instead")]
+pub fn actors() -> [Actor; 2] {
+    [Actor::First, Actor::Second]
+}
+
+/// 指定した深さまで探索する，αβ法で思考するエージェントを作る．
+/// `AlphaBetaStrategy::new`と同じだが，ルールと評価関数の型を戻り値の型から推論できる．
+/// 評価値の型の最小値から最大値までを，評価値がとりうる範囲とする．
+pub fn with_depth<R, E, N>(search_depth: N) -> AlphaBetaStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    AlphaBetaStrategy::new(search_depth)
+}
+
+/// αβ法で思考するエージェントを作る．
+/// 評価値の型の最小値から最大値までを，評価値がとりうる範囲とする．
+#[deprecated(since = "0.2.0", note = "use `AlphaBetaStrategy::new` instead")]
+pub fn construct_alpha_beta_strategy<R, E, N>(search_depth: N) -> AlphaBetaStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    AlphaBetaStrategy::new(search_depth)
+}
+
+/// 調べる順に並べた行動と，その`MoveOrderer`で並べ替えた時点での番号を列挙する．
+enum OrderedActions<I, A> {
+    /// 並べ替える必要がないので，行動を列挙するイテレータから必要な分だけ取り出す．
+    Lazy(Enumerate<I>),
+    /// 並べ替える必要がないので，`Rule::collect_available_actions`でバッファに格納した行動を順に取り出す．
+    Buffered(Enumerate<BufferedActions<A>>),
+    /// 並べ替えた行動．
+    Sorted(vec::IntoIter<(usize, A)>),
+}
+
+/// 探索中に使い回す，行動を格納するバッファ．
+/// 子ノードを調べている間は親ノードのバッファが使用中なので，使用中でないバッファは探索の深さの数までしか増えない．
+type ActionBuffers<A> = Rc<RefCell<Vec<Vec<A>>>>;
+
+/// バッファに格納した行動を，列挙された順に取り出す．
+/// 破棄されると，バッファを空にして`ActionBuffers`に戻す．
+struct BufferedActions<A> {
+    /// 取り出す順と逆順に並べた行動．
+    actions: Vec<A>,
+    /// バッファを戻す先．
+    buffers: ActionBuffers<A>,
+}
+
+impl<A> BufferedActions<A> {
+    /// 使用中でないバッファに，指定した状態で取れる行動を格納する．
+    fn collect<R>(state: &R::S, actor: Actor, buffers: &ActionBuffers<A>) -> Self
+    where
+        R: Rule<A = A>,
+    {
+        let mut actions = buffers.borrow_mut().pop().unwrap_or_default();
+        R::collect_available_actions(state, actor, &mut actions);
+        // 末尾から取り出すので，逆順に並べておく
+        actions.reverse();
+        Self {
+            actions,
+            buffers: Rc::clone(buffers),
+        }
+    }
+}
+
+impl<A> Iterator for BufferedActions<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        self.actions.pop()
+    }
+}
+
+impl<A> Drop for BufferedActions<A> {
+    fn drop(&mut self) {
+        let mut actions = core::mem::take(&mut self.actions);
+        actions.clear();
+        self.buffers.borrow_mut().push(actions);
+    }
+}
+
+impl<I, A> Iterator for OrderedActions<I, A>
+where
+    I: Iterator<Item = A>,
+{
+    type Item = (usize, A);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            OrderedActions::Lazy(actions) => actions.next(),
+            OrderedActions::Buffered(actions) => actions.next(),
+            OrderedActions::Sorted(actions) => actions.next(),
+        }
+    }
+}
+
+/// ミニマックス法で構築する，最善手順をたどるゲーム木．
+pub(crate) type GameTree<'a, S, A, E> = TreeNode<MinimaxNode<'a, S, A, E>>;
+
+/// ミニマックス法で利用するゲーム木のノード．
+#[derive(Debug)]
+pub(crate) struct MinimaxNode<'a, S, A, E> {
+    /// 現在の状態．
+    state: CowRef<'a, S>,
+    /// この状態に至る際に実行された行動．
+    pub(crate) cause_action: Option<A>,
+    /// エージェントにとっての現在状態の評価値．
+    pub(crate) payoff: Option<E>,
+    /// 差分更新により計算された，現在の状態のハッシュ値．
+    pub(crate) hash: Option<u64>,
+    /// パスによりこの状態に至った場合，パスしたプレイヤー．
+    pub(crate) null_move_actor: Option<Actor>,
+    /// この状態に至るまでに，`Action::is_pass`が`true`となる行動が続けてとられた回数．
+    pub(crate) consecutive_passes: u32,
+    /// 兄弟ノードとまとめて評価した，この状態の静的評価値．深さによる調整は行っていない．
+    pub(crate) batched_payoff: Option<E>,
+    /// 評価値が勝ちまたは負けである場合，最善の手順で勝敗が決まる末端ノードの，根ノードからの深さ．
+    pub(crate) terminal_ply: Option<u32>,
+}
+
+impl<'a, S, A, E> MinimaxNode<'a, S, A, E> {
+    pub(crate) fn new(state: CowRef<'a, S>, cause_action: Option<A>, payoff: Option<E>) -> Self {
+        Self {
+            state,
+            cause_action,
+            payoff,
+            hash: None,
+            null_move_actor: None,
+            consecutive_passes: 0,
+            batched_payoff: None,
+            terminal_ply: None,
+        }
+    }
+
+    pub(crate) fn ref_state(&self) -> &S {
+        self.state.as_ref()
+    }
+}
+
+impl<'a, S, A, E> MinimaxNode<'a, S, A, E>
+where
+    A: Action,
+{
+    /// 現在の状態で行動するプレイヤーを返す．
+    /// 根ノードでは，評価値を計算する対象のプレイヤーが行動する．
+    pub(crate) fn next_actor(&self, consideration_target: Actor) -> Actor {
+        match (self.cause_action.as_ref(), self.null_move_actor) {
+            (Some(action), _) => action.actor().opponent(),
+            (None, Some(actor)) => actor.opponent(),
+            (None, None) => consideration_target,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cmp {
+    #[test]
+    fn test_cmp() {
+        let v = vec![5, 2, 0, 9, 4, 3];
+        let max = v.into_iter().max_by(|left, right| left.cmp(right));
+        assert_eq!(Some(9), max);
+    }
+}
+
+#[cfg(test)]
+mod test_debug {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Step(Actor, u8);
+
+    impl Action for Step {
+        fn actor(&self) -> Actor {
+            self.0
+        }
+    }
+
+    impl State for u8 {}
+
+    /// 状態に1か2を加え，2以上になると終了するゲーム．
+    struct StepRule;
+
+    impl Rule for StepRule {
+        type S = u8;
+        type A = Step;
+        type ActionIterator = vec::IntoIter<Step>;
+
+        fn terminal_kind(state: &u8) -> Option<TerminalKind> {
+            if *state >= 2 {
+                Some(TerminalKind::Draw)
+            } else {
+                None
+            }
+        }
+
+        fn iterate_available_actions(_state: &u8, actor: Actor) -> Self::ActionIterator {
+            vec![Step(actor, 1), Step(actor, 2)].into_iter()
+        }
+
+        fn translate_state(state: &u8, action: &Step) -> u8 {
+            state + action.1
+        }
+    }
+
+    struct StepEvaluator;
+
+    impl Evaluator<u8> for StepEvaluator {
+        type Payoff = i32;
+
+        fn evaluate_payoff_for(_actor: Actor, state: &u8) -> i32 {
+            *state as i32 * 10
+        }
+    }
+
+    #[test]
+    fn test_debug_game_tree() {
+        let strategy = AlphaBetaStrategy::<StepRule, StepEvaluator, _>::new(1);
+        let search = strategy.search_root(
+            &0,
+            Actor::First,
+            1,
+            None,
+            SearchLimits::default(),
+            Range::new(i32::MIN, i32::MAX),
+        );
+        let formatted = format!("{:?}", search.root);
+        assert!(formatted.contains("payoff: Some(20)"), "{}", formatted);
+        assert!(formatted.contains("Borrowed(0)"), "{}", formatted);
+        assert!(formatted.contains("Step(First, 2)"), "{}", formatted);
     }
 }
@@ -0,0 +1,489 @@
+use crate::transposition::{deeper_or_same, TranspositionEntry};
+use crate::{Actor, BoundType, StateHasher, TranspositionStore};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::hash::{BuildHasherDefault, Hash};
+use core::mem;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::collections::hash_map::DefaultHasher;
+
+/// `LockFreeTranspositionTable`に記録するために，32ビットの整数に詰められる値．
+///
+/// 評価値と探索深さの型が実装する必要がある．
+/// 評価値は32ビットに，探索深さは16ビットに収まる場合にだけ記録される．
+pub trait AtomicPackable: Sized {
+    /// 値を32ビットの整数に変換する．収まらない場合は`None`を返す．
+    fn pack(&self) -> Option<u32>;
+
+    /// `pack`で変換した整数から値を復元する．
+    fn unpack(bits: u32) -> Self;
+}
+
+macro_rules! impl_atomic_packable_signed {
+    ($($t:ty),*) => {
+        $(impl AtomicPackable for $t {
+            fn pack(&self) -> Option<u32> {
+                i32::try_from(*self).ok().map(|value| value as u32)
+            }
+
+            fn unpack(bits: u32) -> Self {
+                bits as i32 as $t
+            }
+        })*
+    };
+}
+
+macro_rules! impl_atomic_packable_unsigned {
+    ($($t:ty),*) => {
+        $(impl AtomicPackable for $t {
+            fn pack(&self) -> Option<u32> {
+                u32::try_from(*self).ok()
+            }
+
+            fn unpack(bits: u32) -> Self {
+                bits as $t
+            }
+        })*
+    };
+}
+
+impl_atomic_packable_signed!(i8, i16, i32, i64, isize);
+impl_atomic_packable_unsigned!(u8, u16, u32, u64, usize);
+
+impl AtomicPackable for f32 {
+    fn pack(&self) -> Option<u32> {
+        Some(self.to_bits())
+    }
+
+    fn unpack(bits: u32) -> Self {
+        f32::from_bits(bits)
+    }
+}
+
+// `LockFreeTranspositionTable`が記録する64ビットの値の配置
+const DEPTH_SHIFT: u32 = 32;
+const DEPTH_MASK: u64 = 0xFFFF;
+const INDEX_SHIFT: u32 = 48;
+/// 行動の番号に1を加えた値を記録する．0は最善の行動が記録されていないことを表す．
+const INDEX_MASK: u64 = 0x7FF;
+const OCCUPIED_BIT: u64 = 1 << 59;
+const BOUND_SHIFT: u32 = 60;
+const TARGET_BIT: u64 = 1 << 62;
+const NEXT_ACTOR_BIT: u64 = 1 << 63;
+
+/// 探索結果を64ビットの値に詰める．
+/// 評価値か探索深さが収まらない場合は`None`を返す．
+/// 最善の行動の番号が収まらない場合は，その番号を記録しない．
+fn encode_entry<P, N>(entry: &TranspositionEntry<P, N>) -> Option<u64>
+where
+    P: AtomicPackable,
+    N: AtomicPackable,
+{
+    let payoff = u64::from(entry.payoff.pack()?);
+    let depth = u64::from(entry.depth.pack()?);
+    if depth > DEPTH_MASK {
+        return None;
+    }
+    let index = entry
+        .best_action_index
+        .map(|index| index as u64 + 1)
+        .filter(|&index| index <= INDEX_MASK)
+        .unwrap_or(0);
+    let bound: u64 = match entry.bound {
+        BoundType::Exact => 0,
+        BoundType::LowerBound => 1,
+        BoundType::UpperBound => 2,
+    };
+    let actor_bit = |actor, bit| if actor == Actor::Second { bit } else { 0 };
+    Some(
+        payoff
+            | depth << DEPTH_SHIFT
+            | index << INDEX_SHIFT
+            | OCCUPIED_BIT
+            | bound << BOUND_SHIFT
+            | actor_bit(entry.consideration_target, TARGET_BIT)
+            | actor_bit(entry.next_actor, NEXT_ACTOR_BIT),
+    )
+}
+
+/// `encode_entry`で詰めた値から探索結果を復元する．
+fn decode_entry<P, N>(key: u64, data: u64) -> TranspositionEntry<P, N>
+where
+    P: AtomicPackable,
+    N: AtomicPackable,
+{
+    let index = (data >> INDEX_SHIFT) & INDEX_MASK;
+    let bound = match (data >> BOUND_SHIFT) & 0b11 {
+        0 => BoundType::Exact,
+        1 => BoundType::LowerBound,
+        _ => BoundType::UpperBound,
+    };
+    let actor = |bit| {
+        if data & bit == 0 {
+            Actor::First
+        } else {
+            Actor::Second
+        }
+    };
+    TranspositionEntry {
+        key,
+        consideration_target: actor(TARGET_BIT),
+        next_actor: actor(NEXT_ACTOR_BIT),
+        depth: N::unpack(((data >> DEPTH_SHIFT) & DEPTH_MASK) as u32),
+        payoff: P::unpack(data as u32),
+        bound,
+        best_action_index: index.checked_sub(1).map(|index| index as usize),
+    }
+}
+
+/// `LockFreeTranspositionTable`の記録先．
+///
+/// 探索結果を詰めた値と，それとハッシュ値の排他的論理和を別々に記録する．
+/// 読み出した2つの値が別々の書き込みによるものである場合，復元したハッシュ値は記録したものと一致しないので，
+/// ハッシュ値を確かめることで，書き込みの途中の記録を読んだことを検出できる．
+#[derive(Default)]
+struct AtomicSlot {
+    /// ハッシュ値と`data`の排他的論理和．
+    check: AtomicU64,
+    /// 探索結果を詰めた値．記録されていない場合は0である．
+    data: AtomicU64,
+}
+
+impl AtomicSlot {
+    /// 記録されている状態のハッシュ値と，探索結果を詰めた値を返す．
+    fn load(&self) -> Option<(u64, u64)> {
+        let data = self.data.load(Ordering::Relaxed);
+        let check = self.check.load(Ordering::Relaxed);
+        if data & OCCUPIED_BIT == 0 {
+            None
+        } else {
+            Some((check ^ data, data))
+        }
+    }
+
+    fn save(&self, key: u64, data: u64) {
+        self.data.store(data, Ordering::Relaxed);
+        self.check.store(key ^ data, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        self.data.store(0, Ordering::Relaxed);
+        self.check.store(0, Ordering::Relaxed);
+    }
+}
+
+/// 排他制御を用いずに，複数のスレッドから同時に読み書きできる置換表．
+///
+/// 各記録先は2つの`AtomicU64`からなり，ハッシュ値と探索結果を詰めた値の排他的論理和により，
+/// 別のスレッドが書き込む途中の記録を読んでも，ハッシュ値の確認で捨てられる．
+/// 評価値と探索深さは`AtomicPackable`で詰めるので，それぞれ32ビットと16ビットに収まらない探索結果は記録されない．
+/// 容量は作成時に固定され，ハッシュ値が衝突した場合はより深く探索した結果を優先して残す．
+/// `ParallelAlphaBetaStrategy`とともに，`rayon`フィーチャが有効な場合にだけ使える．
+pub struct LockFreeTranspositionTable<S, P, N> {
+    entries: Vec<AtomicSlot>,
+    hasher: Arc<dyn StateHasher<S> + Send + Sync>,
+    /// 探索結果を64ビットの値に詰める関数．
+    encode: fn(&TranspositionEntry<P, N>) -> Option<u64>,
+    /// 詰めた値から探索結果を復元する関数．
+    decode: fn(u64, u64) -> TranspositionEntry<P, N>,
+}
+
+impl<S, P, N> LockFreeTranspositionTable<S, P, N>
+where
+    S: Hash,
+    P: AtomicPackable,
+    N: AtomicPackable,
+{
+    /// 指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, BuildHasherDefault::<DefaultHasher>::default())
+    }
+}
+
+impl<S, P, N> LockFreeTranspositionTable<S, P, N>
+where
+    P: AtomicPackable,
+    N: AtomicPackable,
+{
+    /// 状態のハッシュ値の計算方法を指定して，指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_hasher<H>(capacity: usize, hasher: H) -> Self
+    where
+        H: StateHasher<S> + Send + Sync + 'static,
+    {
+        assert!(
+            capacity > 0,
+            "capacity of transposition table must be positive"
+        );
+        let mut entries = Vec::with_capacity(capacity);
+        entries.resize_with(capacity, AtomicSlot::default);
+        Self {
+            entries,
+            hasher: Arc::new(hasher),
+            encode: encode_entry::<P, N>,
+            decode: decode_entry::<P, N>,
+        }
+    }
+}
+
+impl<S, P, N> LockFreeTranspositionTable<S, P, N> {
+    /// 記録できる状態の数を返す．
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 記録されている状態の数を返す．
+    pub fn len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|slot| slot.load().is_some())
+            .count()
+    }
+
+    /// 記録されている状態がないか．
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|slot| slot.load().is_none())
+    }
+
+    /// 記録をすべて消去する．
+    pub fn clear(&self) {
+        self.entries.iter().for_each(AtomicSlot::clear);
+    }
+
+    fn slot(&self, key: u64) -> &AtomicSlot {
+        &self.entries[(key % self.entries.len() as u64) as usize]
+    }
+}
+
+impl<S, P, N> LockFreeTranspositionTable<S, P, N>
+where
+    N: Ord,
+{
+    /// 指定した状態のハッシュ値を返す．
+    pub(crate) fn hash_state(&self, state: &S) -> u64 {
+        self.hasher.hash_state(state)
+    }
+
+    /// 指定した状態の記録を返す．
+    pub(crate) fn probe(
+        &self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        let (stored_key, data) = self.slot(key).load()?;
+        if stored_key != key {
+            return None;
+        }
+        let entry = (self.decode)(key, data);
+        if entry.consideration_target == consideration_target && entry.next_actor == next_actor {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// 探索結果を記録する．
+    /// 記録先にすでに別の状態が記録されている場合，より深く探索した結果を残す．
+    pub(crate) fn store(&self, entry: TranspositionEntry<P, N>) {
+        let data = match (self.encode)(&entry) {
+            Some(data) => data,
+            None => return,
+        };
+        let slot = self.slot(entry.key);
+        // 読み出してから書き込むまでに別のスレッドが書き込む場合があるが，その場合はどちらかの記録が残る
+        let replaces = slot.load().is_none_or(|(stored_key, stored_data)| {
+            deeper_or_same(&(self.decode)(stored_key, stored_data), &entry)
+        });
+        if replaces {
+            slot.save(entry.key, data);
+        }
+    }
+
+    /// 記録のために確保しているメモリのバイト数を返す．
+    fn memory_bytes(&self) -> usize {
+        self.entries.capacity() * mem::size_of::<AtomicSlot>()
+    }
+}
+
+impl<S, P, N> TranspositionStore<S, P, N> for LockFreeTranspositionTable<S, P, N>
+where
+    N: Ord,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        LockFreeTranspositionTable::hash_state(self, state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        LockFreeTranspositionTable::probe(self, key, consideration_target, next_actor)
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        LockFreeTranspositionTable::store(self, entry)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        LockFreeTranspositionTable::memory_bytes(self)
+    }
+}
+
+/// スレッド間で共有したまま読み書きする．
+impl<S, P, N> TranspositionStore<S, P, N> for &LockFreeTranspositionTable<S, P, N>
+where
+    N: Ord,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        LockFreeTranspositionTable::hash_state(self, state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        LockFreeTranspositionTable::probe(self, key, consideration_target, next_actor)
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        LockFreeTranspositionTable::store(self, entry)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        LockFreeTranspositionTable::memory_bytes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// ハッシュ値だけから決まる探索結果．
+    /// 置換表から読み出した記録がこれと異なれば，別の書き込みと混ざった記録を読んだことになる．
+    fn expected_entry(key: u64) -> TranspositionEntry<i32, u32> {
+        let mixed = key.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        TranspositionEntry {
+            key,
+            consideration_target: Actor::First,
+            next_actor: if key.is_multiple_of(2) {
+                Actor::First
+            } else {
+                Actor::Second
+            },
+            depth: (mixed >> 48) as u32 % 64,
+            payoff: (mixed >> 16) as i32,
+            bound: match key % 3 {
+                0 => BoundType::Exact,
+                1 => BoundType::LowerBound,
+                _ => BoundType::UpperBound,
+            },
+            best_action_index: Some((mixed % 1000) as usize).filter(|_| !key.is_multiple_of(5)),
+        }
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        for key in 0..1000 {
+            let entry = expected_entry(key);
+            let data = encode_entry(&entry).unwrap();
+            assert_eq!(entry, decode_entry(key, data));
+        }
+        // 収まらない評価値や探索深さは記録しない
+        let entry = TranspositionEntry {
+            key: 1,
+            consideration_target: Actor::First,
+            next_actor: Actor::First,
+            depth: 1u32,
+            payoff: i64::MAX,
+            bound: BoundType::Exact,
+            best_action_index: None,
+        };
+        assert_eq!(None, encode_entry(&entry));
+        let entry = TranspositionEntry {
+            depth: 1 << 16,
+            ..expected_entry(1)
+        };
+        assert_eq!(None, encode_entry(&entry));
+        // 収まらない行動の番号は記録しない
+        let entry = TranspositionEntry {
+            best_action_index: Some(1 << 20),
+            ..expected_entry(1)
+        };
+        let data = encode_entry(&entry).unwrap();
+        assert_eq!(None, decode_entry::<i32, u32>(1, data).best_action_index);
+    }
+
+    #[test]
+    fn test_store_and_probe() {
+        let table = LockFreeTranspositionTable::<u32, i32, u32>::new(8);
+        assert!(table.is_empty());
+        let shallow = TranspositionEntry {
+            depth: 2,
+            ..expected_entry(11)
+        };
+        let deep = TranspositionEntry {
+            depth: 4,
+            ..expected_entry(3)
+        };
+        table.store(deep);
+        // 同じ位置に記録される，より浅い探索結果は記録されない
+        table.store(shallow);
+        assert_eq!(1, table.len());
+        assert_eq!(
+            Some(deep),
+            table.probe(3, deep.consideration_target, deep.next_actor)
+        );
+        assert_eq!(
+            None,
+            table.probe(11, shallow.consideration_target, shallow.next_actor)
+        );
+        assert_eq!(
+            None,
+            table.probe(3, deep.consideration_target, deep.next_actor.opponent())
+        );
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(8, table.capacity());
+    }
+
+    #[test]
+    fn test_concurrent_access_never_yields_torn_entry() {
+        // 記録先が少ないので，同じ記録先への読み書きが頻繁に重なる
+        let table = LockFreeTranspositionTable::<u32, i32, u32>::new(16);
+        let keys = 256;
+        thread::scope(|scope| {
+            for thread in 0..8u64 {
+                let table = &table;
+                scope.spawn(move || {
+                    let mut random = thread.wrapping_mul(0x2545_F491_4F6C_DD1D) | 1;
+                    for _ in 0..200_000 {
+                        random ^= random << 13;
+                        random ^= random >> 7;
+                        random ^= random << 17;
+                        let expected = expected_entry(random % keys);
+                        if random & (1 << 40) == 0 {
+                            table.store(expected);
+                        } else if let Some(entry) = table.probe(
+                            expected.key,
+                            expected.consideration_target,
+                            expected.next_actor,
+                        ) {
+                            assert_eq!(expected, entry);
+                        }
+                    }
+                });
+            }
+        });
+        assert!(!table.is_empty());
+    }
+}
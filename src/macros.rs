@@ -0,0 +1,88 @@
+/// ルールと評価関数の型を名前付きで指定して，αβ法で思考するエージェントを作る．
+///
+/// `alpha_beta_strategy!(rule = R, evaluator = E, depth = d)`は`AlphaBetaStrategy::<R, E, _>::new(d)`に展開される．
+/// `rule`と`evaluator`は順不同で，どちらも省略できない．
+/// 探索深さの型は`depth`の値から推論される．
+///
+/// # Examples
+/// ```
+/// use minimax_strategy::{
+///     alpha_beta_strategy, closure_evaluator, Action, Actor, AlphaBetaStrategy, Rule, State,
+///     Strategy, TerminalKind,
+/// };
+///
+/// // 石の山から1個か2個の石を取り合うゲーム
+/// struct Pile(u32);
+///
+/// impl State for Pile {}
+///
+/// struct Take {
+///     actor: Actor,
+///     count: u32,
+/// }
+///
+/// impl Action for Take {
+///     fn actor(&self) -> Actor {
+///         self.actor
+///     }
+/// }
+///
+/// struct PileRule;
+///
+/// impl Rule for PileRule {
+///     type S = Pile;
+///     type A = Take;
+///     type ActionIterator = std::vec::IntoIter<Take>;
+///
+///     fn terminal_kind(pile: &Pile) -> Option<TerminalKind> {
+///         (pile.0 == 0).then_some(TerminalKind::Draw)
+///     }
+///
+///     fn iterate_available_actions(pile: &Pile, actor: Actor) -> Self::ActionIterator {
+///         (1..=pile.0.min(2))
+///             .map(|count| Take { actor, count })
+///             .collect::<Vec<_>>()
+///             .into_iter()
+///     }
+///
+///     fn translate_state(pile: &Pile, take: &Take) -> Pile {
+///         Pile(pile.0 - take.count)
+///     }
+/// }
+///
+/// closure_evaluator!(struct PileEvaluator: Pile => i32 = |_actor, pile| pile.0 as i32);
+///
+/// let strategy = alpha_beta_strategy!(rule = PileRule, evaluator = PileEvaluator, depth = 4);
+/// assert!(strategy.select_action(&Pile(5), Actor::First).is_some());
+///
+/// // 順不同で指定できる
+/// let _: AlphaBetaStrategy<PileRule, PileEvaluator, u8> =
+///     alpha_beta_strategy!(evaluator = PileEvaluator, rule = PileRule, depth = 4);
+/// ```
+///
+/// `rule`か`evaluator`を省略すると，コンパイルエラーとなる．
+/// ```compile_fail
+/// # use minimax_strategy::alpha_beta_strategy;
+/// # struct PileRule;
+/// let strategy = alpha_beta_strategy!(rule = PileRule, depth = 4);
+/// ```
+#[macro_export]
+macro_rules! alpha_beta_strategy {
+    (rule = $rule:ty, evaluator = $evaluator:ty, depth = $depth:expr $(,)?) => {
+        $crate::AlphaBetaStrategy::<$rule, $evaluator, _>::new($depth)
+    };
+    (evaluator = $evaluator:ty, rule = $rule:ty, depth = $depth:expr $(,)?) => {
+        $crate::AlphaBetaStrategy::<$rule, $evaluator, _>::new($depth)
+    };
+    (evaluator = $evaluator:ty, depth = $depth:expr $(,)?) => {
+        compile_error!("alpha_beta_strategy! requires `rule = <type implementing Rule>`")
+    };
+    (rule = $rule:ty, depth = $depth:expr $(,)?) => {
+        compile_error!("alpha_beta_strategy! requires `evaluator = <type implementing Evaluator>`")
+    };
+    ($($arguments:tt)*) => {
+        compile_error!(
+            "expected `alpha_beta_strategy!(rule = <Rule>, evaluator = <Evaluator>, depth = <search depth>)`"
+        )
+    };
+}
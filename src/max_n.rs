@@ -0,0 +1,226 @@
+use crate::SearchDepth;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Sub;
+
+/// 3人以上が参加しうるゲームにおけるプレイヤー．0から始まる手番の添字で表す．
+///
+/// 2人ゲームの`Actor`とは別に，`MultiRule`などのN人ゲーム用のトレイトで用いる．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MultiActor(pub usize);
+
+impl MultiActor {
+    /// 手番の添字を返す．
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// 指定した人数のゲームで，このプレイヤーの次に行動するプレイヤーを返す．
+    /// 最後のプレイヤーの次は，最初のプレイヤーに戻る．
+    pub fn next(&self, player_count: usize) -> Self {
+        MultiActor((self.0 + 1) % player_count)
+    }
+}
+
+/// N人ゲームにおける行動．
+pub trait MultiAction {
+    /// この行動の手番．
+    fn actor(&self) -> MultiActor;
+}
+
+/// N人ゲーム内の状態遷移条件を記述する．
+pub trait MultiRule {
+    /// このゲームルールが考慮するゲームの状態．
+    type S;
+    /// このゲームルールにおけるプレイヤーの行動．
+    type A: MultiAction;
+    /// ある状態において実行可能な行動を列挙する際に使用する型．
+    type ActionIterator: Iterator<Item = Self::A>;
+
+    /// ゲームに参加するプレイヤーの人数．
+    fn player_count() -> usize;
+
+    /// 指定した状態がすでにゲーム終了条件を満たしているか．
+    fn is_game_over(state: &Self::S) -> bool;
+
+    /// 指定された状態下で実行可能な行動を列挙する．
+    fn iterate_available_actions(state: &Self::S, actor: MultiActor) -> Self::ActionIterator;
+
+    /// 状態を遷移させる．
+    fn translate_state(state: &Self::S, action: &Self::A) -> Self::S;
+
+    /// 指定した状態で，指定したプレイヤーの次に行動するプレイヤーを返す．
+    /// 既定では手番の添字の順に行動する．
+    fn next_actor(_state: &Self::S, actor: MultiActor) -> MultiActor {
+        actor.next(Self::player_count())
+    }
+}
+
+/// N人ゲームの状態の評価関数．
+pub trait MultiEvaluator<S> {
+    /// プレイヤーの利得を表す型．
+    type Payoff;
+
+    /// 指定された状態について，各プレイヤーの利得を手番の添字の順に並べて返す．
+    /// 要素数は`MultiRule::player_count`と等しくなければならない．
+    fn evaluate_payoffs(state: &S) -> Vec<Self::Payoff>;
+}
+
+/// N人ゲームの戦略生成器．
+pub trait MultiStrategy<S, A> {
+    /// 指定した状態における，指定したプレイヤーの行動`a`を選択して`Some(a)`として返す．
+    /// 取れる行動がない場合は`None`を返す．
+    fn select_action(&self, state: &S, actor: MultiActor) -> Option<A>;
+}
+
+/// N人ゲームにおける行動を，Max^n法で思考するエージェント．
+///
+/// 各ノードで，行動するプレイヤーは自分の利得が最大になる子ノードを選び，その子ノードの利得の組をそのまま親ノードに伝える．
+/// 利得が等しい子ノードの間では，先に列挙されたものを選ぶ．
+/// 手番のプレイヤーに取れる行動がない状態は，ゲームが続く状態のまま静的に評価する．
+///
+/// `with_shallow_pruning`で利得の合計の上限を指定すると，浅い枝刈り (shallow pruning) を行う．
+pub struct MaxNStrategy<R, E, N>
+where
+    R: MultiRule,
+    E: MultiEvaluator<R::S>,
+{
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    /// 浅い枝刈りの設定．
+    shallow_pruning: Option<ShallowPruning<E::Payoff>>,
+    _r: PhantomData<fn() -> R>,
+    _e: PhantomData<fn() -> E>,
+}
+
+impl<R, E, N> MaxNStrategy<R, E, N>
+where
+    R: MultiRule,
+    E: MultiEvaluator<R::S>,
+{
+    /// Max^n法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            shallow_pruning: None,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+/// 浅い枝刈りの設定．
+struct ShallowPruning<P> {
+    /// すべてのプレイヤーの利得の合計の上限．
+    max_sum: P,
+    /// 利得の合計の上限から，あるプレイヤーの利得を引いた値を返す．
+    remainder: fn(P, P) -> P,
+}
+
+impl<R, E, N> MaxNStrategy<R, E, N>
+where
+    R: MultiRule,
+    E: MultiEvaluator<R::S>,
+{
+    /// 浅い枝刈りを行うようにする．
+    ///
+    /// 利得がすべて0以上であり，その合計が常に`max_sum`以下であるゲームでは，
+    /// あるプレイヤーの利得が`x`の子ノードが見つかった時点で，他のプレイヤーの利得は`max_sum - x`以下に限られる．
+    /// 親ノードのプレイヤーがすでにこれ以上の利得を得られる場合，残りの子ノードを調べない．
+    /// 枝刈りを行わない場合と同じ行動を選ぶが，条件を満たさない評価関数では誤った枝刈りが起こる．
+    pub fn with_shallow_pruning(self, max_sum: E::Payoff) -> Self
+    where
+        E::Payoff: Sub<Output = E::Payoff>,
+    {
+        Self {
+            shallow_pruning: Some(ShallowPruning {
+                max_sum,
+                remainder: |max_sum, payoff| max_sum - payoff,
+            }),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E, N> MaxNStrategy<R, E, N>
+where
+    A: MultiAction,
+    R: MultiRule<S = S, A = A>,
+    E: MultiEvaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+{
+    /// Max^n法により，指定したノードの利得の組と最善の行動を再帰的に計算する．
+    /// # Params
+    /// 1. state 注目ノードの状態．
+    /// 1. actor 注目ノードで行動するプレイヤー．
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. parent_best 親ノードで行動するプレイヤーと，そのプレイヤーがすでに調べた子ノードで得られる利得．
+    ///
+    /// # Returns
+    /// 利得の組と最善の行動．末端ノードでは行動は`None`である．
+    /// 枝刈りが起こった場合，利得の組は親ノードで選ばれないことが分かっているものである．
+    fn search(
+        &self,
+        state: &S,
+        actor: MultiActor,
+        remaining_depth: N,
+        parent_best: Option<(MultiActor, E::Payoff)>,
+    ) -> (Vec<E::Payoff>, Option<A>) {
+        if remaining_depth.is_zero() || R::is_game_over(state) {
+            return (E::evaluate_payoffs(state), None);
+        }
+
+        let me = actor.index();
+        let mut best: Option<(Vec<E::Payoff>, A)> = None;
+        for action in R::iterate_available_actions(state, actor) {
+            let next_state = R::translate_state(state, &action);
+            let next_actor = R::next_actor(&next_state, actor);
+            let my_best = best.as_ref().map(|(payoffs, _)| payoffs[me]);
+            let (payoffs, _) = self.search(
+                &next_state,
+                next_actor,
+                remaining_depth - N::one(),
+                my_best.map(|my_best| (actor, my_best)),
+            );
+            if my_best.is_none_or(|my_best| payoffs[me] > my_best) {
+                best = Some((payoffs, action));
+            }
+
+            // 自分の利得の分だけ，親ノードのプレイヤーの利得の上限が下がる
+            if let (Some(pruning), Some((parent, parent_best)), Some((payoffs, _))) =
+                (self.shallow_pruning.as_ref(), parent_best, best.as_ref())
+            {
+                if parent != actor
+                    && (pruning.remainder)(pruning.max_sum, payoffs[me]) <= parent_best
+                {
+                    break;
+                }
+            }
+        }
+
+        match best {
+            Some((payoffs, action)) => (payoffs, Some(action)),
+            None => (E::evaluate_payoffs(state), None),
+        }
+    }
+
+    /// 指定した状態で指定したプレイヤーが行動する場合の，各プレイヤーの利得の組を返す．
+    /// ゲームが終了している場合や取れる行動がない場合は，静的な評価値を返す．
+    pub fn root_payoffs(&self, state: &S, actor: MultiActor) -> Vec<E::Payoff> {
+        self.search(state, actor, self.search_depth, None).0
+    }
+}
+
+impl<S, A, R, E, N> MultiStrategy<S, A> for MaxNStrategy<R, E, N>
+where
+    A: MultiAction,
+    R: MultiRule<S = S, A = A>,
+    E: MultiEvaluator<S>,
+    E::Payoff: Copy + PartialOrd,
+    N: SearchDepth,
+{
+    fn select_action(&self, state: &S, actor: MultiActor) -> Option<A> {
+        self.search(state, actor, self.search_depth, None).1
+    }
+}
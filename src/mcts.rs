@@ -0,0 +1,672 @@
+use crate::{
+    Action, Actor, Evaluator, MemoryBudget, MemoryUsage, OutcomeRule, Rule, ScoredAction,
+    ScoredStrategy, State,
+};
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::mem;
+use core::num::NonZeroUsize;
+use num::Integer;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// UCB1における探索定数の既定値．
+const DEFAULT_EXPLORATION: f64 = core::f64::consts::SQRT_2;
+
+/// 2人ゲームにおける適切な行動を，モンテカルロ木探索で思考するエージェント．
+///
+/// 各シミュレーションでは，UCB1に従って子ノードを選び，未展開の行動があればノードを1つ展開した後，
+/// ゲームが終わるまで`PlayoutPolicy`に従って行動させて勝敗を調べる．
+/// 勝敗は`OutcomeRule::winner`で判定するので，評価関数を必要とせず，静的評価が難しいゲームでも利用できる．
+///
+/// シミュレーションは指定した回数だけ行う．
+/// `with_time_budget`で制限時間を指定した場合，回数に達する前でも制限時間を過ぎた時点でやめる．
+///
+/// `with_evaluator`で評価関数を指定すると，新たに展開したノードを静的に評価し，プレイアウトの結果と混ぜ合わせて報酬とする．
+///
+/// 探索したゲーム木は行動選択をまたいで保持され，
+/// 次に与えられた状態が前回の根ノードの子孫 (2手先まで) であれば，その部分木の統計を再利用する．
+///
+/// 探索はルート並列化により複数のスレッドで行う (`with_threads`)．
+/// 各スレッドは独立したゲーム木に対して指定した回数のシミュレーションを行い，
+/// 最後に根ノードの子ノードの統計をスレッドの順に足し合わせて行動を選ぶ．
+/// 各スレッドの乱数生成器のシード値はエージェントの乱数生成器から決まるので，
+/// 制限時間を指定しなければ，スレッド数とシード値が同じエージェントは常に同じ行動を選択する．
+/// 1つのゲーム木を複数のスレッドで共有する木並列化は行わない．
+pub struct MctsStrategy<R, N, P = UniformPlayout>
+where
+    R: Rule,
+{
+    /// 1回の行動選択で行うシミュレーションの回数．
+    simulations: N,
+    /// 1回の行動選択に使ってよい時間．
+    time_budget: Option<Duration>,
+    /// UCB1における探索定数．
+    exploration: f64,
+    /// 探索に用いるスレッドの数．
+    threads: usize,
+    /// 展開したノードの静的な評価．
+    leaf_evaluation: Option<LeafEvaluation<R::S>>,
+    /// ランダムな行動選択に用いる乱数生成器．
+    rng: Mutex<StdRng>,
+    /// 前回の行動選択で探索したゲーム木．
+    tree: Mutex<Option<MctsTree<R::S, R::A>>>,
+    /// 保持するゲーム木のメモリ使用量の上限．
+    memory_budget: Option<MemoryBudget>,
+    _r: PhantomData<R>,
+    _p: PhantomData<fn() -> P>,
+}
+
+impl<R, N> MctsStrategy<R, N>
+where
+    R: Rule,
+{
+    /// モンテカルロ木探索で思考するエージェントを作る．
+    /// `simulations`は1回の行動選択で行うシミュレーションの回数である．
+    /// プレイアウトでは一様ランダムに行動を選ぶ．
+    pub fn new(simulations: N) -> Self {
+        Self {
+            simulations,
+            time_budget: None,
+            exploration: DEFAULT_EXPLORATION,
+            threads: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            leaf_evaluation: None,
+            rng: Mutex::new(StdRng::from_entropy()),
+            tree: Mutex::new(None),
+            memory_budget: None,
+            _r: PhantomData,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<R, N, P> MctsStrategy<R, N, P>
+where
+    R: Rule,
+{
+    /// UCB1における探索定数を指定する．
+    /// 大きいほど，訪問回数の少ない行動を優先して調べる．
+    pub fn with_exploration(self, exploration: f64) -> Self {
+        Self {
+            exploration,
+            ..self
+        }
+    }
+
+    /// 乱数生成器のシード値を指定する．
+    /// 同じシード値を指定したエージェントは，同じ状態に対して同じ行動を選択する．
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            ..self
+        }
+    }
+
+    /// 1回の行動選択に使ってよい時間を指定する．
+    /// 制限時間を過ぎると，指定した回数に達していなくてもシミュレーションをやめる．
+    /// ただし，少なくとも1回はシミュレーションを行う．
+    pub fn with_time_budget(self, time_budget: Duration) -> Self {
+        Self {
+            time_budget: Some(time_budget),
+            ..self
+        }
+    }
+
+    /// 探索に用いるスレッドの数を指定する．
+    /// 既定値は`std::thread::available_parallelism`の値である．
+    /// `simulations`と制限時間はスレッドごとに適用されるので，スレッドが多いほど多くのシミュレーションを行う．
+    /// 保持するゲーム木は最初のスレッドのものだけであり，1スレッドの場合は並列化しない場合と同じ結果になる．
+    /// `rayon`フィーチャが無効な場合は，各スレッドの探索を1つのスレッドで順に行う．結果は有効な場合と変わらない．
+    /// # Panics
+    /// `threads`が0の場合
+    pub fn with_threads(self, threads: usize) -> Self {
+        assert!(threads > 0, "threads must be positive");
+        Self { threads, ..self }
+    }
+
+    /// プレイアウトで行動を選ぶ方針を指定する．
+    /// 保持しているゲーム木は引き継がない．
+    pub fn with_playout_policy<Q>(self) -> MctsStrategy<R, N, Q> {
+        MctsStrategy {
+            simulations: self.simulations,
+            time_budget: self.time_budget,
+            exploration: self.exploration,
+            threads: self.threads,
+            leaf_evaluation: self.leaf_evaluation,
+            rng: self.rng,
+            tree: Mutex::new(None),
+            memory_budget: self.memory_budget,
+            _r: PhantomData,
+            _p: PhantomData,
+        }
+    }
+
+    /// 展開したノードを指定した評価関数で静的に評価し，プレイアウトの結果と混ぜ合わせて報酬とする．
+    /// 報酬は`(1 - mixing) * プレイアウトの結果 + mixing * 静的な評価`である．
+    /// `mixing`が1の場合はプレイアウトを行わず，静的な評価だけを報酬とする．
+    /// ゲームが終了したノードでは，評価関数を用いずに勝敗を報酬とする．
+    /// # Panics
+    /// `mixing`が0以上1以下でない場合
+    pub fn with_evaluator<E>(self, mixing: f64) -> Self
+    where
+        E: Evaluator<R::S>,
+        E::Payoff: PayoffToScore,
+    {
+        assert!(
+            (0.0..=1.0).contains(&mixing),
+            "mixing must be between 0 and 1"
+        );
+        Self {
+            leaf_evaluation: Some(LeafEvaluation {
+                score: |state| E::evaluate_payoff_for(Actor::First, state).to_score(),
+                mixing,
+            }),
+            ..self
+        }
+    }
+
+    /// 保持しているゲーム木の根ノードで指定した行動をとった子ノードを，新たな根ノードとする．
+    /// それ以外の部分木は捨てる．
+    /// 自分と相手の行動を順に指定すれば，次の行動選択で，その状態までのシミュレーションの統計を再利用できる．
+    /// 指定した行動の子ノードがまだ展開されていない場合は，ゲーム木をすべて捨てる．
+    pub fn advance(&self, action: &R::A)
+    where
+        R::A: PartialEq,
+    {
+        let mut retained = self.tree.lock().unwrap_or_else(PoisonError::into_inner);
+        *retained = retained
+            .take()
+            .and_then(|tree| tree.advance(action))
+            .and_then(|tree| self.within_memory_budget(tree));
+    }
+
+    /// 行動選択をまたいで保持するゲーム木のメモリ使用量を，指定した上限に収める．
+    ///
+    /// 上限を超えたゲーム木は保持せずに捨てるので，次の行動選択は新たなゲーム木から探索する．
+    /// 1回の行動選択の間に作られるゲーム木の大きさは，シミュレーションの回数で制限する．
+    /// 使用量の数え方は`MemoryBudget`を参照．
+    pub fn with_memory_budget(self, memory_budget: MemoryBudget) -> Self {
+        Self {
+            memory_budget: Some(memory_budget),
+            ..self
+        }
+    }
+
+    /// 行動選択をまたいで保持しているゲーム木の，現在のメモリ使用量を返す．
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            retained_tree: self
+                .tree
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .as_ref()
+                .map_or(0, MctsTree::memory_bytes),
+            ..MemoryUsage::default()
+        }
+    }
+
+    /// 指定したゲーム木のメモリ使用量が上限に収まっていればそのまま返し，超えていれば捨てて`None`を返す．
+    fn within_memory_budget(&self, tree: MctsTree<R::S, R::A>) -> Option<MctsTree<R::S, R::A>> {
+        let usage = MemoryUsage {
+            retained_tree: tree.memory_bytes(),
+            ..MemoryUsage::default()
+        };
+        match self.memory_budget {
+            Some(memory_budget) if memory_budget.is_exceeded_by(&usage) => None,
+            _ => Some(tree),
+        }
+    }
+
+    /// 保持しているゲーム木の根ノードで行われたシミュレーションの回数を返す．
+    /// 再利用された部分木の統計も含む．
+    pub fn retained_simulations(&self) -> u64 {
+        self.tree
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map_or(0, |tree| tree.nodes[0].visits)
+    }
+}
+
+/// 1回の行動選択において，各スレッドに共通する探索の設定．
+struct SearchSettings<'a, S, N> {
+    /// 各スレッドで行うシミュレーションの回数．
+    simulations: N,
+    /// シミュレーションをやめる時刻．
+    deadline: Option<Instant>,
+    /// UCB1における探索定数．
+    exploration: f64,
+    /// 展開したノードの静的な評価．
+    leaf_evaluation: Option<&'a LeafEvaluation<S>>,
+}
+
+impl<'a, S, N> SearchSettings<'a, S, N>
+where
+    S: State + Clone + PartialEq,
+    N: Copy + Integer,
+{
+    /// 指定した回数に達するか制限時間を過ぎるまで，ゲーム木に対してシミュレーションを行う．
+    fn search<A, R, P>(&self, tree: &mut MctsTree<S, A>, rng: &mut StdRng)
+    where
+        A: Action + Clone,
+        R: OutcomeRule<S = S, A = A>,
+        P: PlayoutPolicy<S, A>,
+    {
+        let mut count = N::zero();
+        while count < self.simulations {
+            self.simulate::<A, R, P>(tree, rng);
+            count = count + N::one();
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                break;
+            }
+        }
+    }
+
+    /// 根ノードから1回のシミュレーションを行い，結果をゲーム木に反映させる．
+    fn simulate<A, R, P>(&self, tree: &mut MctsTree<S, A>, rng: &mut StdRng)
+    where
+        A: Action + Clone,
+        R: OutcomeRule<S = S, A = A>,
+        P: PlayoutPolicy<S, A>,
+    {
+        // 選択: 未展開の行動が残っているノードか，末端ノードに達するまで子ノードをたどる
+        let mut path = vec![0];
+        let mut current = 0;
+        while tree.nodes[current].untried_actions.is_empty() {
+            match tree.select_child(current, self.exploration) {
+                Some(child) => {
+                    path.push(child);
+                    current = child;
+                }
+                None => break,
+            }
+        }
+
+        // 展開: 未展開の行動を1つ選び，子ノードを追加する
+        if !tree.nodes[current].untried_actions.is_empty() {
+            let untried_actions = &mut tree.nodes[current].untried_actions;
+            let action = untried_actions.swap_remove(rng.gen_range(0, untried_actions.len()));
+            let child = tree.expand::<R>(current, action);
+            path.push(child);
+            current = child;
+        }
+
+        // プレイアウト: ゲームが終わるまで方針に従って行動させる．
+        // 評価関数を指定した場合は，静的な評価と混ぜ合わせる
+        let node = &tree.nodes[current];
+        let mut playout_reward = || {
+            let winner = playout::<R, P>(node.state.clone(), node.next_actor, rng);
+            first_reward(winner)
+        };
+        let reward = match self.leaf_evaluation {
+            Some(evaluation) if R::terminal_kind(&node.state).is_none() => {
+                let score = (evaluation.score)(&node.state);
+                if evaluation.mixing < 1.0 {
+                    (1.0 - evaluation.mixing) * playout_reward() + evaluation.mixing * score
+                } else {
+                    score
+                }
+            }
+            _ => playout_reward(),
+        };
+
+        // 逆伝播: 各ノードに至る行動をとったプレイヤーの立場で報酬を記録する
+        for &index in path.iter() {
+            let node = &mut tree.nodes[index];
+            node.visits += 1;
+            if let Some(action) = node.cause_action.as_ref() {
+                node.total_reward += match action.actor() {
+                    Actor::First => reward,
+                    Actor::Second => 1.0 - reward,
+                };
+            }
+        }
+    }
+}
+
+/// 選択した行動の評価値は，その行動を通ったシミュレーションにおける報酬の平均である．
+/// 報酬は勝ちを1，引き分けを0.5，負けを0とする．
+impl<S, A, R, N, P> ScoredStrategy<S, A> for MctsStrategy<R, N, P>
+where
+    S: State + Clone + PartialEq + Send + Sync,
+    A: Action + Clone + Send,
+    R: OutcomeRule<S = S, A = A>,
+    N: Copy + Integer + Sync,
+    P: PlayoutPolicy<S, A>,
+{
+    type Payoff = f64;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, f64>> {
+        let mut retained = self.tree.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut guard = self.rng.lock().unwrap_or_else(PoisonError::into_inner);
+        let rng = &mut *guard;
+
+        // 前回のゲーム木に指定した状態が含まれていれば，最初のスレッドはその部分木から探索を続ける
+        let mut tree = retained
+            .take()
+            .and_then(|tree| tree.reroot(state, actor))
+            .unwrap_or_else(|| MctsTree::new::<R>(state.clone(), actor));
+
+        let settings = SearchSettings {
+            simulations: self.simulations,
+            deadline: self.time_budget.map(|budget| Instant::now() + budget),
+            exploration: self.exploration,
+            leaf_evaluation: self.leaf_evaluation.as_ref(),
+        };
+        // 2番目以降のスレッドのシード値は，エージェントの乱数生成器から順に決める
+        let seeds = (1..self.threads).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let search_other = |seed| {
+            let mut tree = MctsTree::new::<R>(state.clone(), actor);
+            let mut rng = StdRng::seed_from_u64(seed);
+            settings.search::<A, R, P>(&mut tree, &mut rng);
+            tree
+        };
+        #[cfg(feature = "rayon")]
+        let (_, others) = rayon::join(
+            || settings.search::<A, R, P>(&mut tree, rng),
+            || seeds.into_par_iter().map(search_other).collect::<Vec<_>>(),
+        );
+        #[cfg(not(feature = "rayon"))]
+        let others = {
+            settings.search::<A, R, P>(&mut tree, rng);
+            seeds.into_iter().map(search_other).collect::<Vec<_>>()
+        };
+
+        // 根ノードの子ノードの統計を，行動ごとにスレッドの順で足し合わせる
+        let mut statistics: Vec<(usize, &A, u64, f64)> = Vec::new();
+        for searched in core::iter::once(&tree).chain(others.iter()) {
+            for &child in searched.nodes[0].children.iter() {
+                let node = &searched.nodes[child];
+                let action = match node.cause_action.as_ref() {
+                    Some(action) => action,
+                    None => continue,
+                };
+                match statistics
+                    .iter_mut()
+                    .find(|(index, ..)| *index == node.action_index)
+                {
+                    Some((_, _, visits, total_reward)) => {
+                        *visits += node.visits;
+                        *total_reward += node.total_reward;
+                    }
+                    None => {
+                        statistics.push((node.action_index, action, node.visits, node.total_reward))
+                    }
+                }
+            }
+        }
+
+        // 最も多くシミュレーションされた行動を選ぶ
+        let scored = statistics
+            .iter()
+            .max_by_key(|(_, _, visits, _)| *visits)
+            .map(|&(_, action, visits, total_reward)| {
+                ScoredAction::new(action.clone(), total_reward / visits as f64)
+            });
+        *retained = self.within_memory_budget(tree);
+        scored
+    }
+}
+
+/// モンテカルロ木探索のプレイアウトで，行動を選ぶ方針．
+/// ゲームの知識を用いて行動を選ぶと，少ないシミュレーションで精度の良い結果が得られることがある．
+pub trait PlayoutPolicy<S, A> {
+    /// 指定した状態で指定したプレイヤーが取れる行動のうち，実行するものの添字を返す．
+    /// `actions`は空でない．
+    fn choose_action<G>(state: &S, actor: Actor, actions: &[A], rng: &mut G) -> usize
+    where
+        G: Rng;
+}
+
+/// 取れる行動から一様ランダムに選ぶ．
+pub struct UniformPlayout;
+
+impl<S, A> PlayoutPolicy<S, A> for UniformPlayout {
+    fn choose_action<G>(_state: &S, _actor: Actor, actions: &[A], rng: &mut G) -> usize
+    where
+        G: Rng,
+    {
+        rng.gen_range(0, actions.len())
+    }
+}
+
+/// 評価値を，モンテカルロ木探索の報酬として扱える0以上1以下の値に変換する．
+/// 値が大きいほど，評価したプレイヤーにとって有利であることを表す．
+///
+/// 整数と浮動小数点数の評価値には，ロジスティック関数`1 / (1 + exp(-x))`で変換する実装がある．
+/// 評価値の1の差が勝率に大きく影響しない場合は，評価関数で値を縮めるか，独自の評価値の型を用いる．
+pub trait PayoffToScore {
+    /// 評価値を0以上1以下の値に変換する．
+    fn to_score(&self) -> f64;
+}
+
+macro_rules! impl_payoff_to_score {
+    ($($t:ty),*) => {
+        $(
+            impl PayoffToScore for $t {
+                fn to_score(&self) -> f64 {
+                    1.0 / (1.0 + (-(*self as f64)).exp())
+                }
+            }
+        )*
+    };
+}
+
+impl_payoff_to_score!(i8, i16, i32, i64, isize, f32, f64);
+
+/// 展開したノードの静的な評価の方法．
+struct LeafEvaluation<S> {
+    /// 先手にとっての評価値を，0以上1以下の値に変換したものを返す．
+    score: fn(&S) -> f64,
+    /// 静的な評価を報酬に混ぜる割合．
+    mixing: f64,
+}
+
+/// 指定した勝者に対する，先手にとっての報酬を返す．
+/// 勝ちを1，引き分けを0.5，負けを0とする．
+fn first_reward(winner: Option<Actor>) -> f64 {
+    match winner {
+        Some(Actor::First) => 1.0,
+        Some(Actor::Second) => 0.0,
+        None => 0.5,
+    }
+}
+
+/// 指定した状態からゲームが終わるまで方針に従って行動させ，勝者を返す．
+/// 途中で取れる行動がなくなった場合は引き分けとみなす．
+fn playout<R, P>(mut state: R::S, mut actor: Actor, rng: &mut StdRng) -> Option<Actor>
+where
+    R: OutcomeRule,
+    P: PlayoutPolicy<R::S, R::A>,
+{
+    while R::terminal_kind(&state).is_none() {
+        let actions = R::iterate_available_actions(&state, actor).collect::<Vec<_>>();
+        if actions.is_empty() {
+            return None;
+        }
+        let action = &actions[P::choose_action(&state, actor, &actions, rng)];
+        state = R::translate_state(&state, action);
+        actor = actor.opponent();
+    }
+    R::winner(&state)
+}
+
+/// モンテカルロ木探索で構築するゲーム木．
+/// ノードは配列に格納され，添字0が根ノードとなる．
+struct MctsTree<S, A> {
+    nodes: Vec<MctsNode<S, A>>,
+}
+
+/// モンテカルロ木探索で利用するゲーム木のノード．
+struct MctsNode<S, A> {
+    /// 現在の状態．
+    state: S,
+    /// この状態に至る際に実行された行動．
+    cause_action: Option<A>,
+    /// 親ノードで取れる行動のうち，この状態に至る行動の添字．
+    /// 異なるゲーム木の間で，同じ行動をとった子ノードを対応づけるのに用いる．
+    action_index: usize,
+    /// この状態で行動するプレイヤー．
+    next_actor: Actor,
+    /// 展開済みの子ノードの添字．
+    children: Vec<usize>,
+    /// まだ展開していない行動と，その添字．
+    untried_actions: Vec<(usize, A)>,
+    /// このノードを通ったシミュレーションの回数．
+    visits: u64,
+    /// この状態に至る行動をとったプレイヤーにとっての，報酬の合計．
+    total_reward: f64,
+}
+
+impl<S, A> MctsNode<S, A>
+where
+    A: Action,
+{
+    fn new<R>(state: S, cause_action: Option<A>, action_index: usize, next_actor: Actor) -> Self
+    where
+        R: Rule<S = S, A = A>,
+    {
+        let untried_actions = if R::terminal_kind(&state).is_some() {
+            vec![]
+        } else {
+            R::iterate_available_actions(&state, next_actor)
+                .enumerate()
+                .collect()
+        };
+        Self {
+            state,
+            cause_action,
+            action_index,
+            next_actor,
+            children: vec![],
+            untried_actions,
+            visits: 0,
+            total_reward: 0.0,
+        }
+    }
+}
+
+impl<S, A> MctsTree<S, A>
+where
+    S: PartialEq,
+    A: Action,
+{
+    fn new<R>(state: S, actor: Actor) -> Self
+    where
+        R: Rule<S = S, A = A>,
+    {
+        Self {
+            nodes: vec![MctsNode::new::<R>(state, None, 0, actor)],
+        }
+    }
+
+    /// 指定したノードの子ノードのうち，UCB1の値が最大のものを返す．
+    fn select_child(&self, parent: usize, exploration: f64) -> Option<usize> {
+        let log_visits = (self.nodes[parent].visits as f64).ln();
+        let ucb1 = |child: usize| {
+            let node = &self.nodes[child];
+            let visits = node.visits as f64;
+            node.total_reward / visits + exploration * (log_visits / visits).sqrt()
+        };
+        self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .max_by(|&left, &right| {
+                ucb1(left)
+                    .partial_cmp(&ucb1(right))
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+
+    /// 指定したノードで指定した行動をとった子ノードを追加し，その添字を返す．
+    fn expand<R>(&mut self, parent: usize, (action_index, action): (usize, A)) -> usize
+    where
+        R: Rule<S = S, A = A>,
+    {
+        let state = R::translate_state(&self.nodes[parent].state, &action);
+        let next_actor = action.actor().opponent();
+        let child = self.nodes.len();
+        self.nodes.push(MctsNode::new::<R>(
+            state,
+            Some(action),
+            action_index,
+            next_actor,
+        ));
+        self.nodes[parent].children.push(child);
+        child
+    }
+
+    /// 根ノードから2手先までの子孫のうち，指定した状態のノードを根とする部分木を返す．
+    /// 該当するノードがなければ`None`を返す．
+    fn reroot(self, state: &S, actor: Actor) -> Option<Self> {
+        let root = &self.nodes[0];
+        let new_root = core::iter::once(0)
+            .chain(root.children.iter().copied())
+            .chain(
+                root.children
+                    .iter()
+                    .flat_map(|&child| self.nodes[child].children.iter().copied()),
+            )
+            .find(|&index| {
+                let node = &self.nodes[index];
+                node.next_actor == actor && node.state == *state
+            })?;
+        Some(self.into_subtree(new_root))
+    }
+}
+
+impl<S, A> MctsTree<S, A> {
+    /// ノードと，各ノードの子ノードと未展開の行動を格納するために確保した領域のバイト数を返す．
+    fn memory_bytes(&self) -> usize {
+        let edges = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.children.capacity() * mem::size_of::<usize>()
+                    + node.untried_actions.capacity() * mem::size_of::<(usize, A)>()
+            })
+            .sum::<usize>();
+        self.nodes.capacity() * mem::size_of::<MctsNode<S, A>>() + edges
+    }
+
+    /// 根ノードで指定した行動をとった子ノードを根とする部分木を返す．
+    /// 該当するノードがなければ`None`を返す．
+    fn advance(self, action: &A) -> Option<Self>
+    where
+        A: PartialEq,
+    {
+        let new_root = self.nodes[0]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].cause_action.as_ref() == Some(action))?;
+        Some(self.into_subtree(new_root))
+    }
+
+    /// 指定したノードを根とする部分木を取り出す．
+    fn into_subtree(self, root: usize) -> Self {
+        let mut old_nodes = self.nodes.into_iter().map(Some).collect::<Vec<_>>();
+        let mut nodes = Vec::new();
+        // 新しいゲーム木での添字の順に並べた，元のゲーム木での添字
+        let mut order = vec![root];
+        while nodes.len() < order.len() {
+            let mut node = old_nodes[order[nodes.len()]]
+                .take()
+                .expect("each node is moved only once");
+            for child in node.children.iter_mut() {
+                order.push(*child);
+                *child = order.len() - 1;
+            }
+            nodes.push(node);
+        }
+        Self { nodes }
+    }
+}
@@ -0,0 +1,84 @@
+/// `AlphaBetaStrategy`と`MctsStrategy`が探索に用いる構造の，メモリ使用量の上限．
+///
+/// 使用量は次の構造ごとに，要素を格納するために確保した領域の大きさ (要素の大きさと確保した要素数の積) として数える．
+/// 構造自体の大きさや，状態や行動がヒープに確保する領域は数えない．
+/// - 置換表: 容量に比例し，記録の有無によらない
+/// - History Heuristicの実績: 記録した行動の番号の最大値に比例する
+/// - キラー手の表: キラー手を記録した深さの数に比例する
+/// - カウンター手の表: 記録した相手の行動の番号の最大値に比例する
+/// - 行動選択をまたいで保持するゲーム木 (`MctsStrategy`): ノード数と，各ノードの子ノードと未展開の行動の数に比例する
+///
+/// 使用量は各探索の開始時と，探索中に表が大きくなるたびに確認する．
+/// 保持するゲーム木は，行動選択を終えて保持する時点と，`MctsStrategy::advance`で部分木を取り出した時点で確認する．
+/// 上限を超えている場合は，上限に収まるまで次の順に記録を消去して領域を解放する．
+/// 1. 保持するゲーム木 (すべて捨て，次の行動選択では新たなゲーム木から探索する)
+/// 1. キラー手の表
+/// 1. カウンター手の表
+/// 1. History Heuristicの実績
+/// 1. 置換表 (容量を半分に減らし，収まらなければ繰り返す)
+///
+/// 1回の探索の間だけ作られるゲーム木は数えない．
+/// αβ法のゲーム木の大きさは`AlphaBetaStrategy::with_node_budget`で，
+/// `BestFirstStrategy`のゲーム木の大きさは`BestFirstStrategy::with_byte_budget`で制限する．
+/// これらは探索を終えると解放されるので，行動選択をまたいで残る使用量には影響しない．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryBudget {
+    bytes: usize,
+}
+
+impl MemoryBudget {
+    /// 指定したバイト数を上限とする．
+    pub fn new(bytes: usize) -> Self {
+        Self { bytes }
+    }
+
+    /// 上限のバイト数を返す．
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// 指定した使用量が上限を超えているか．
+    pub(crate) fn is_exceeded_by(&self, usage: &MemoryUsage) -> bool {
+        usage.total() > self.bytes
+    }
+}
+
+/// `AlphaBetaStrategy`と`MctsStrategy`が探索に用いる構造ごとの，メモリ使用量のバイト数．
+/// 数え方は`MemoryBudget`と同じである．
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryUsage {
+    /// 置換表．
+    pub transposition_table: usize,
+    /// History Heuristicの実績．
+    pub history: usize,
+    /// キラー手の表．
+    pub killers: usize,
+    /// カウンター手の表．
+    pub countermoves: usize,
+    /// 行動選択をまたいで保持するゲーム木．
+    pub retained_tree: usize,
+}
+
+impl MemoryUsage {
+    /// すべての構造の使用量の合計を返す．
+    pub fn total(&self) -> usize {
+        self.transposition_table
+            + self.history
+            + self.killers
+            + self.countermoves
+            + self.retained_tree
+    }
+
+    /// 構造ごとに，2つの使用量の大きい方をとる．
+    pub(crate) fn peak(&self, other: &MemoryUsage) -> MemoryUsage {
+        MemoryUsage {
+            transposition_table: self.transposition_table.max(other.transposition_table),
+            history: self.history.max(other.history),
+            killers: self.killers.max(other.killers),
+            countermoves: self.countermoves.max(other.countermoves),
+            retained_tree: self.retained_tree.max(other.retained_tree),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::node::TreeNode;
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, GameTree, MinimaxNode, Rule, ScoredAction,
+    ScoredStrategy, SearchDepth, State,
+};
+use core::fmt;
+use core::marker::PhantomData;
+
+/// 2人零和ゲームにおける適切な行動を，枝刈りを行わないミニマックス法で思考するエージェント．
+///
+/// 探索量は`AlphaBetaStrategy`より多くなるが，ゲーム木を指定した深さまですべて調べるので，
+/// 他の探索手法の結果を検証する際の基準として利用できる．
+pub struct MinimaxStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    _r: PhantomData<R>,
+    _e: PhantomData<E>,
+}
+
+impl<R, E, N> MinimaxStrategy<R, E, N> {
+    /// 指定した深さまで探索する，ミニマックス法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+// ルールと評価関数は型として指定するだけなので，探索深さの型にだけ制約を課す
+impl<R, E, N> Clone for MinimaxStrategy<R, E, N>
+where
+    N: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            search_depth: self.search_depth.clone(),
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<R, E, N> Copy for MinimaxStrategy<R, E, N> where N: Copy {}
+
+impl<R, E, N> fmt::Debug for MinimaxStrategy<R, E, N>
+where
+    N: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinimaxStrategy")
+            .field("search_depth", &self.search_depth)
+            .finish()
+    }
+}
+
+impl<R, E, N> PartialEq for MinimaxStrategy<R, E, N>
+where
+    N: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.search_depth == other.search_depth
+    }
+}
+
+impl<R, E, N> Eq for MinimaxStrategy<R, E, N> where N: Eq {}
+
+impl<S, A, R, E, N> MinimaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord,
+    N: SearchDepth,
+{
+    /// ミニマックス法により，指定したノードの評価値を再帰的に計算する．
+    /// # Params
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. current_node 注目ノード．
+    /// 1. ply 根ノードから注目ノードまでの深さ．
+    ///
+    /// # Returns
+    /// `Some(e)`: このノードの評価値`e`
+    ///
+    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
+    fn construct_best_game_tree_minimax(
+        &self,
+        remaining_depth: N,
+        consideration_target: Actor,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        ply: u32,
+    ) -> Option<E::Payoff> {
+        debug_assert!(current_node.payoff.is_none());
+
+        // 注目ノードが末端ノードなら，現在の状態に対する静的評価値をそのまま適用する
+        let terminal = R::terminal_kind(current_node.ref_state());
+        if remaining_depth.is_zero() || terminal.is_some() {
+            let payoff = evaluate_at_ply::<_, E>(
+                consideration_target,
+                current_node.ref_state(),
+                terminal,
+                ply,
+            );
+            current_node.payoff = Some(payoff);
+            return Some(payoff);
+        }
+
+        let next_actor = match current_node.cause_action.as_ref() {
+            Some(action) => action.actor().opponent(),
+            None => consideration_target,
+        };
+
+        // 最善の子ノードとその評価値．
+        // 注目ノードの状態を借用している間は注目ノードを変更できないので，探索が終わってから反映させる．
+        let mut best_child = None;
+        let mut best_payoff: Option<E::Payoff> = None;
+        let current_state = current_node.ref_state();
+
+        // 次の実現しうる状態をすべて列挙し，ひとつひとつ調べる
+        for action in R::iterate_available_actions(current_state, next_actor) {
+            let next_state = R::translate_state(current_state, &action);
+            let mut child =
+                current_node.new_child(MinimaxNode::new(next_state.into(), Some(action), None));
+            // 取れる行動がない子ノードは探索対象としない．
+            let child_payoff = match self.construct_best_game_tree_minimax(
+                remaining_depth - N::one(),
+                consideration_target,
+                &mut child,
+                ply + 1,
+            ) {
+                Some(e) => e,
+                None => continue,
+            };
+            let is_better = match best_payoff {
+                None => true,
+                // 自分の手番では自分が有利になる行動を，相手の手番では自分が不利になる行動を選択する
+                Some(e) if next_actor == consideration_target => child_payoff > e,
+                Some(e) => child_payoff < e,
+            };
+            if is_better {
+                best_child = Some(child);
+                best_payoff = Some(child_payoff);
+            }
+        }
+
+        // 最善の子ノードを注目ノードに反映させる．
+        // 取れる行動がなかった場合，注目ノードの評価値は決定しない．
+        if let Some(child) = best_child {
+            current_node.replace_child(child);
+            current_node.payoff = best_payoff;
+        }
+        current_node.payoff
+    }
+
+    /// 指定した状態を根とするゲーム木をミニマックス法で探索し，根ノードを返す．
+    fn search<'a>(&self, state: &'a S, actor: Actor) -> GameTree<'a, S, A, E::Payoff> {
+        let mut root = TreeNode::new(MinimaxNode::new(state.into(), None, None));
+        self.construct_best_game_tree_minimax(self.search_depth, actor, &mut root, 0);
+        root
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).payoff
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for MinimaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        ScoredAction::from_game_tree(self.search(state, actor))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::Actor;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// 探索前に，ある状態で取れる行動を調べる順に並べ替える．
+///
+/// αβ法では，有望な行動を先に調べるほど枝刈りが起こりやすくなる．
+/// 並べ替えは同じ状態と手番に対して常に同じ結果となる必要がある．
+///
+/// `Fn(&S, Actor, &mut Vec<A>)`を実装する型は，このトレイトを自動的に実装する．
+pub trait MoveOrderer<S, A> {
+    /// 指定した状態で指定したプレイヤーが取れる行動を，調べる順に並べ替える．
+    /// 行動を追加したり取り除いたりしてはならない．
+    fn order(&self, state: &S, actor: Actor, actions: &mut Vec<A>);
+}
+
+impl<S, A, F> MoveOrderer<S, A> for F
+where
+    F: Fn(&S, Actor, &mut Vec<A>),
+{
+    fn order(&self, state: &S, actor: Actor, actions: &mut Vec<A>) {
+        self(state, actor, actions)
+    }
+}
+
+/// 探索前に，ある状態で取れる行動を調べる順に並べ替える方法．
+///
+/// `AlphaBetaStrategy`の型引数として指定し，探索の実装を変えずに行動の調べる順を差し替えるために使う．
+/// `MoveOrderer`も指定した場合，このトレイトで並べ替えた後に`MoveOrderer`で並べ替える．
+/// 並べ替えは同じ状態と手番に対して常に同じ結果となる必要がある．
+pub trait MoveOrdering<S, A> {
+    /// `order_actions`が行動を並べ替えないか．
+    /// `true`の場合，行動は`Rule::iterate_available_actions`から必要な分だけ取り出され，`order_actions`は呼ばれない．
+    const PRESERVES_ORDER: bool = false;
+
+    /// 指定した状態で指定したプレイヤーが取れる行動を，調べる順に並べ替えて返す．
+    /// 行動を追加したり取り除いたりしてはならない．
+    fn order_actions(state: &S, actor: Actor, actions: Vec<A>) -> Vec<A>;
+}
+
+/// 行動を並べ替えない．
+pub struct NoOrdering;
+
+impl<S, A> MoveOrdering<S, A> for NoOrdering {
+    const PRESERVES_ORDER: bool = true;
+
+    fn order_actions(_state: &S, _actor: Actor, actions: Vec<A>) -> Vec<A> {
+        actions
+    }
+}
+
+/// `O`で並べ替えた行動を，逆順に調べる．
+pub struct ReverseOrdering<O>(PhantomData<fn() -> O>);
+
+impl<S, A, O> MoveOrdering<S, A> for ReverseOrdering<O>
+where
+    O: MoveOrdering<S, A>,
+{
+    fn order_actions(state: &S, actor: Actor, actions: Vec<A>) -> Vec<A> {
+        let mut actions = O::order_actions(state, actor, actions);
+        actions.reverse();
+        actions
+    }
+}
+
+/// `O1`の順に行動を調べ，`O1`で順位が等しい行動は`O2`の順に調べる．
+///
+/// 行動を`O2`で並べ替えた後，`O1`で並べ替える．
+/// したがって，`O1`が安定な並べ替え (`sort_by_key`など) である場合に，順位が等しい行動が`O2`の順に並ぶ．
+pub struct CombinedOrdering<O1, O2>(PhantomData<fn() -> (O1, O2)>);
+
+impl<S, A, O1, O2> MoveOrdering<S, A> for CombinedOrdering<O1, O2>
+where
+    O1: MoveOrdering<S, A>,
+    O2: MoveOrdering<S, A>,
+{
+    const PRESERVES_ORDER: bool = O1::PRESERVES_ORDER && O2::PRESERVES_ORDER;
+
+    fn order_actions(state: &S, actor: Actor, actions: Vec<A>) -> Vec<A> {
+        let actions = O2::order_actions(state, actor, actions);
+        O1::order_actions(state, actor, actions)
+    }
+}
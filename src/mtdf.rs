@@ -0,0 +1,179 @@
+use crate::{
+    Action, Actor, AlphaBetaStrategy, Evaluator, RootSearch, Rule, ScoreBound, ScoredAction,
+    ScoredStrategy, SearchDepth, SearchLimits, SearchStats, State,
+};
+use core::hash::Hash;
+use data_structure::Range;
+use num::Bounded;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+/// 2人零和ゲームにおける適切な行動を，MTD(f)で思考するエージェント．
+///
+/// 評価値の予想値`g`を幅0の関心範囲`[g, g]`としてαβ法で探索することを，
+/// 探索結果が予想値に一致するまで繰り返す．
+/// 探索結果が予想値を上回った場合，真の評価値はその結果以上なので，その結果を次の予想値とする．
+/// 下回った場合も同様である．関心範囲の端に一致した評価値は真の評価値そのものなので，
+/// 評価値の型に「次の値」を求める演算は必要ない．
+///
+/// 同じ局面を繰り返し探索するので，前回までの探索結果を再利用できるよう，常に置換表を用いる．
+///
+/// 各回の探索はfail-softでなければならない．
+/// fail-hardの探索では評価値が予想値に切り詰められ，予想値を更新できないためである．
+pub struct MtdfStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各回の探索に用いるαβ法のエージェント．
+    alpha_beta: AlphaBetaStrategy<R, E, N>,
+    /// 最初の行動選択で用いる評価値の予想値．
+    first_guess: E::Payoff,
+    /// 前回の行動選択で得られた，行動したプレイヤーとその評価値．
+    /// 同じプレイヤーの次の行動選択では，これを予想値とする．
+    previous_payoff: Mutex<Option<(Actor, E::Payoff)>>,
+}
+
+impl<R, E, N> MtdfStrategy<R, E, N>
+where
+    R: Rule,
+    R::S: Hash,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    /// MTD(f)で思考するエージェントを作る．
+    /// `first_guess`は最初の行動選択で用いる評価値の予想値で，以降は前回の行動選択で得られた評価値を予想値とする．
+    /// 置換表は`DEFAULT_TRANSPOSITION_TABLE_CAPACITY`個の状態を記録できる．
+    pub fn new(search_depth: N, first_guess: E::Payoff) -> Self {
+        Self {
+            alpha_beta: AlphaBetaStrategy::new(search_depth)
+                .with_transposition_table(DEFAULT_TRANSPOSITION_TABLE_CAPACITY),
+            first_guess,
+            previous_payoff: Mutex::new(None),
+        }
+    }
+}
+
+impl<R, E, N> MtdfStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 置換表を，指定した数の状態を記録できるものに置き換える．
+    /// 既定では`DEFAULT_TRANSPOSITION_TABLE_CAPACITY`個の状態を記録できる．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_transposition_table(self, capacity: usize) -> Self
+    where
+        R::S: Hash,
+    {
+        Self {
+            alpha_beta: self.alpha_beta.with_transposition_table(capacity),
+            ..self
+        }
+    }
+}
+
+impl<S, A, R, E, N> MtdfStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    /// 評価値が収束するまで幅0の関心範囲での探索を繰り返し，最後の探索結果を返す．
+    fn search<'a>(&self, state: &'a S, actor: Actor) -> RootSearch<'a, S, A, E::Payoff> {
+        let mut previous_payoff = self
+            .previous_payoff
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut guess = match *previous_payoff {
+            Some((previous_actor, payoff)) if previous_actor == actor => payoff,
+            _ => self.first_guess,
+        };
+        let mut stats = SearchStats::default();
+        self.alpha_beta.advance_transposition_generation();
+
+        loop {
+            let search = self.alpha_beta.search_root(
+                state,
+                actor,
+                self.alpha_beta.search_depth,
+                None,
+                SearchLimits::default(),
+                Range::new(guess, guess),
+            );
+            stats.merge(&search.stats);
+            let bound = search
+                .root
+                .payoff
+                .map(|payoff| ScoreBound::classify(payoff, Range::new(guess, guess)));
+            match bound {
+                // 探索結果が予想値を上回った (下回った) 場合，真の評価値はその値以上 (以下) である．
+                // 予想値は真の評価値に向かって単調に動くので，いずれ探索結果と一致する
+                Some(bound) if !bound.is_exact() => {
+                    guess = bound.value();
+                    stats.re_searches += 1;
+                }
+                // 予想値に一致した評価値は真の評価値である．
+                // 取れる行動がない場合も，探索を繰り返す必要はない
+                bound => {
+                    if let Some(bound) = bound {
+                        *previous_payoff = Some((actor, bound.value()));
+                    }
+                    return RootSearch { stats, ..search };
+                }
+            }
+        }
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).root.payoff
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    /// 統計情報の`re_searches`は，予想値を更新して探索し直した回数である．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    /// 統計情報の`re_searches`は，予想値を更新して探索し直した回数である．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let start = Instant::now();
+        let RootSearch {
+            root, mut stats, ..
+        } = self.search(state, actor);
+        let scored = ScoredAction::from_game_tree(root);
+        stats.elapsed = start.elapsed();
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for MtdfStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
+
+/// `MtdfStrategy`が既定で用いる置換表に記録できる状態の数．
+pub const DEFAULT_TRANSPOSITION_TABLE_CAPACITY: usize = 1 << 16;
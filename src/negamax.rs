@@ -0,0 +1,152 @@
+use crate::node::TreeNode;
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, GameTree, MinimaxNode, Rule, ScoredAction,
+    ScoredStrategy, SearchDepth, State,
+};
+use core::marker::PhantomData;
+use core::ops::Neg;
+use data_structure::Range;
+use num::Bounded;
+
+/// 2人零和ゲームにおける適切な行動を，ネガマックス法に基づくαβ法で思考するエージェント．
+///
+/// 各ノードの評価値をそのノードで行動するプレイヤーの視点で計算し，親ノードに伝える際に符号を反転させる．
+/// そのため，評価関数は一方のプレイヤーにとっての利得の符号を反転させたものが，
+/// もう一方のプレイヤーにとっての利得となるように実装されている必要がある．
+pub struct NegamaxStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    _r: PhantomData<R>,
+    _e: PhantomData<E>,
+}
+
+impl<R, E, N> NegamaxStrategy<R, E, N> {
+    /// 指定した深さまで探索する，ネガマックス法に基づくαβ法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<S, A, R, E, N> NegamaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Neg<Output = E::Payoff>,
+    N: SearchDepth,
+{
+    /// ネガマックス法により，指定したノードの評価値を再帰的に計算する．
+    /// 評価値は，注目ノードで行動するプレイヤーにとっての値である．
+    /// # Params
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. actor 注目ノードで行動するプレイヤー．
+    /// 1. current_node 注目ノード．
+    /// 1. payoff_range 評価値の関心範囲．
+    /// 1. ply 根ノードから注目ノードまでの深さ．
+    ///
+    /// # Returns
+    /// `Some(e)`: このノードの評価値`e`
+    ///
+    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
+    fn construct_best_game_tree_negamax(
+        &self,
+        remaining_depth: N,
+        actor: Actor,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        ply: u32,
+    ) -> Option<E::Payoff> {
+        debug_assert!(current_node.payoff.is_none());
+
+        // 注目ノードが末端ノードなら，行動するプレイヤーにとっての静的評価値をそのまま適用する
+        let terminal = R::terminal_kind(current_node.ref_state());
+        if remaining_depth.is_zero() || terminal.is_some() {
+            let payoff = evaluate_at_ply::<_, E>(actor, current_node.ref_state(), terminal, ply);
+            current_node.payoff = Some(payoff);
+            return Some(payoff);
+        }
+
+        let mut best_child = None;
+        let mut best_payoff: Option<E::Payoff> = None;
+        let mut current_payoff_range = payoff_range;
+        let current_state = current_node.ref_state();
+
+        for action in R::iterate_available_actions(current_state, actor) {
+            let next_state = R::translate_state(current_state, &action);
+            let mut child =
+                current_node.new_child(MinimaxNode::new(next_state.into(), Some(action), None));
+            // 子ノードでは相手が行動するので，関心範囲と評価値の符号を反転させる
+            let child_payoff_range =
+                Range::new(-current_payoff_range.max, -current_payoff_range.min);
+            let child_payoff = match self.construct_best_game_tree_negamax(
+                remaining_depth - N::one(),
+                actor.opponent(),
+                &mut child,
+                child_payoff_range,
+                ply + 1,
+            ) {
+                Some(e) => -e,
+                None => continue,
+            };
+            if let Some(e) = best_payoff {
+                if e >= child_payoff {
+                    continue;
+                }
+            }
+            best_child = Some(child);
+            best_payoff = Some(child_payoff);
+            // 可能なら，αβカットして探索量を減らす．
+            match Range::try_new(child_payoff, current_payoff_range.max) {
+                Some(range) => current_payoff_range = range,
+                None => break,
+            }
+        }
+
+        if let Some(child) = best_child {
+            current_node.replace_child(child);
+            current_node.payoff = best_payoff;
+        }
+        current_node.payoff
+    }
+
+    /// 指定した状態を根とするゲーム木をネガマックス法で探索し，根ノードを返す．
+    fn search<'a>(&self, state: &'a S, actor: Actor) -> GameTree<'a, S, A, E::Payoff> {
+        let mut root = TreeNode::new(MinimaxNode::new(state.into(), None, None));
+        // 最小値の符号を反転させるとオーバーフローする型があるので，関心範囲は最大値について対称にとる
+        self.construct_best_game_tree_negamax(
+            self.search_depth,
+            actor,
+            &mut root,
+            Range::new(-E::Payoff::max_value(), E::Payoff::max_value()),
+            0,
+        );
+        root
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor).payoff
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for NegamaxStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Neg<Output = E::Payoff>,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        ScoredAction::from_game_tree(self.search(state, actor))
+    }
+}
@@ -1,18 +1,47 @@
-use std::ops::{Deref, DerefMut};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
 
 /// 子をひとつ以下持つノードを表す．
-#[derive(Debug)]
 pub(crate) struct TreeNode<T> {
     /// このノードが保持する情報．
     item: T,
     /// 子ノード．
     child: Option<Box<Self>>,
+    /// 根ノードからの深さ．
+    depth: usize,
 }
 
 impl<T> TreeNode<T> {
-    /// 子を持たないノードを作成する．
+    /// 子を持たない根ノードを作成する．
     pub const fn new(item: T) -> Self {
-        Self { item, child: None }
+        Self::with_depth(item, 0)
+    }
+
+    /// 根ノードから指定した深さにある，子を持たないノードを作成する．
+    pub const fn with_depth(item: T, depth: usize) -> Self {
+        Self {
+            item,
+            child: None,
+            depth,
+        }
+    }
+
+    /// このノードの子となる，子を持たないノードを作成する．
+    /// 作成したノードはこのノードに接続されないので，必要に応じて`replace_child`で接続する．
+    pub const fn new_child(&self, item: T) -> Self {
+        Self::with_depth(item, self.depth + 1)
+    }
+
+    /// 根ノードからの深さを返す．根ノードの深さは0である．
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// このノードが根ノードか．
+    pub const fn is_root(&self) -> bool {
+        self.depth == 0
     }
 
     /// このノードが保持する情報を返す．
@@ -25,11 +54,97 @@ impl<T> TreeNode<T> {
         self.child.map(|c| *c)
     }
 
+    /// このノードが保持する情報と，子ノードに分解する．
+    pub fn into_parts(self) -> (T, Option<Self>) {
+        (self.item, self.child.map(|c| *c))
+    }
+
     /// このノードの子ノードを，指定したノードに置き換える．
     /// この処理の前にすでに子ノードが存在していた場合，その子は破棄される．
     pub fn replace_child(&mut self, new_child: Self) {
         self.child = Some(Box::new(new_child));
     }
+
+    /// このノードの子ノードを破棄する．
+    pub fn clear_child(&mut self) {
+        self.child = None;
+    }
+
+    /// このノードから子ノードを順にたどり，各ノードが保持する情報の参照を返すイテレータを作る．
+    pub fn iter_path(&self) -> PathIter<'_, T> {
+        PathIter { next: Some(self) }
+    }
+
+    /// このノードから子ノードを順にたどり，各ノードが保持する情報の可変参照を返すイテレータを作る．
+    pub fn iter_path_mut(&mut self) -> PathIterMut<'_, T> {
+        PathIterMut { next: Some(self) }
+    }
+
+    /// このノードから子ノードを順にたどり，各ノードが保持する情報を返すイテレータを作る．
+    pub fn into_iter_path(self) -> OwnedPathIter<T> {
+        OwnedPathIter { next: Some(self) }
+    }
+}
+
+/// ノードから子ノードを順にたどり，各ノードが保持する情報の参照を返すイテレータ．
+pub(crate) struct PathIter<'a, T> {
+    /// 次に返すノード．
+    next: Option<&'a TreeNode<T>>,
+}
+
+impl<'a, T> Iterator for PathIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.child.as_deref();
+        Some(&node.item)
+    }
+}
+
+/// ノードから子ノードを順にたどり，各ノードが保持する情報の可変参照を返すイテレータ．
+pub(crate) struct PathIterMut<'a, T> {
+    /// 次に返すノード．
+    next: Option<&'a mut TreeNode<T>>,
+}
+
+impl<'a, T> Iterator for PathIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.child.as_deref_mut();
+        Some(&mut node.item)
+    }
+}
+
+/// ノードから子ノードを順にたどり，各ノードが保持する情報を返すイテレータ．
+pub(crate) struct OwnedPathIter<T> {
+    /// 次に返すノード．
+    next: Option<TreeNode<T>>,
+}
+
+impl<T> Iterator for OwnedPathIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (item, child) = self.next.take()?.into_parts();
+        self.next = child;
+        Some(item)
+    }
+}
+
+impl<T> fmt::Debug for TreeNode<T>
+where
+    T: fmt::Debug,
+{
+    /// 子ノードを入れ子にせず，このノードから順にたどった情報を並べて表示する．
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreeNode")
+            .field("depth", &self.depth)
+            .field("path", &self.iter_path().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 impl<T> Deref for TreeNode<T> {
@@ -68,6 +183,15 @@ mod tests {
         assert_eq!(&vec![0, 1, 2, 3], node.deref());
     }
 
+    #[test]
+    fn test_into_parts() {
+        let mut node = TreeNode::new("root");
+        node.replace_child(TreeNode::new("child"));
+        let (item, child) = node.into_parts();
+        assert_eq!("root", item);
+        assert_eq!(Some("child"), child.map(|c| c.into_inner()));
+    }
+
     #[test]
     fn test_replace_child() {
         let node = TreeNode::new("root");
@@ -82,4 +206,80 @@ mod tests {
         node.replace_child(TreeNode::new("child2"));
         assert_eq!(Some("child2"), node.into_child().map(|c| *c.deref()));
     }
+
+    #[test]
+    fn test_depth() {
+        let mut root = TreeNode::new("root");
+        assert_eq!(0, root.depth());
+        assert!(root.is_root());
+
+        let mut child = root.new_child("child");
+        assert_eq!(1, child.depth());
+        assert!(!child.is_root());
+
+        let grandchild = child.new_child("grandchild");
+        assert_eq!(2, grandchild.depth());
+
+        child.replace_child(grandchild);
+        root.replace_child(child);
+        let child = root.into_child().unwrap();
+        assert_eq!(1, child.depth());
+        assert_eq!(Some(2), child.into_child().map(|c| c.depth()));
+    }
+
+    /// 根ノードから葉ノードまで3つのノードが連なった木を作る．
+    fn path() -> TreeNode<&'static str> {
+        let mut root = TreeNode::new("root");
+        let mut child = root.new_child("child");
+        child.replace_child(child.new_child("leaf"));
+        root.replace_child(child);
+        root
+    }
+
+    #[test]
+    fn test_iter_path() {
+        let root = path();
+        assert_eq!(
+            vec!["root", "child", "leaf"],
+            root.iter_path().copied().collect::<Vec<_>>()
+        );
+        // 途中のノードからもたどれる
+        let child = root.into_child().unwrap();
+        assert_eq!(
+            vec!["child", "leaf"],
+            child.iter_path().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(1, TreeNode::new("root").iter_path().count());
+    }
+
+    #[test]
+    fn test_iter_path_mut() {
+        let mut root = TreeNode::new(String::from("root"));
+        let mut child = root.new_child(String::from("child"));
+        child.replace_child(child.new_child(String::from("leaf")));
+        root.replace_child(child);
+        for (depth, item) in root.iter_path_mut().enumerate() {
+            item.push_str(&depth.to_string());
+        }
+        assert_eq!(
+            vec!["root0", "child1", "leaf2"],
+            root.iter_path().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_into_iter_path() {
+        assert_eq!(
+            vec!["root", "child", "leaf"],
+            path().into_iter_path().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_clear_child() {
+        let mut node = TreeNode::new("root");
+        node.replace_child(TreeNode::new("child"));
+        node.clear_child();
+        assert_eq!(None, node.into_child().map(|c| *c.deref()));
+    }
 }
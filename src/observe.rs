@@ -0,0 +1,177 @@
+use crate::sync::Lock;
+use crate::{Actor, ScoredAction, ScoredStrategy};
+use alloc::vec::Vec;
+use core::mem;
+
+/// 探索中に枝刈りが起こった理由．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CutoffReason {
+    /// 相手の手番のノードで，αβ法により残りの子ノードを調べなかった．
+    Alpha,
+    /// 自分の手番のノードで，αβ法により残りの子ノードを調べなかった．
+    Beta,
+    /// Null Move Pruningにより，ノードの探索を省略した．
+    NullMove,
+    /// Futility Pruningにより，行動を調べなかった．
+    Futility,
+    /// Razoringにより，浅い探索だけで評価値を決めた．
+    Razoring,
+}
+
+/// 探索の途中経過を受け取るフック．
+/// 既定ではいずれのメソッドも何もしないので，必要なものだけを実装すればよい．
+///
+/// `depth`は根ノードから注目ノードまでの深さである．
+pub trait SearchHook<S, A, P> {
+    /// ノードを訪れた際に呼ばれる．`actor`はそのノードで行動するプレイヤーである．
+    fn on_node_expanded(&self, _depth: u32, _state: &S, _actor: Actor) {}
+
+    /// 枝刈りが起こった際に呼ばれる．
+    fn on_cutoff(&self, _depth: u32, _reason: CutoffReason) {}
+
+    /// 根ノードで最善と判断された行動が更新された際に呼ばれる．
+    fn on_best_action_updated(&self, _action: &A, _payoff: P) {}
+}
+
+/// フックを渡して探索できるエージェント．
+pub trait ObservableSearch<S, A>: ScoredStrategy<S, A> {
+    /// 探索の途中経過を`hook`に通知しながら，指定した状態におけるエージェントの行動を選択する．
+    fn select_scored_action_observed(
+        &self,
+        state: &S,
+        actor: Actor,
+        hook: &dyn SearchHook<S, A, Self::Payoff>,
+    ) -> Option<ScoredAction<A, Self::Payoff>>;
+}
+
+/// 行動の選択を内部のエージェントに任せ，その探索の途中経過をフックに通知するエージェント．
+/// 選択する行動と評価値は，内部のエージェントが返すものと等しい．
+pub struct ObservableStrategy<T, H> {
+    /// 行動を選択するエージェント．
+    inner: T,
+    /// 途中経過を受け取るフック．
+    hook: H,
+}
+
+impl<T, H> ObservableStrategy<T, H> {
+    /// 指定したエージェントの探索の途中経過を，指定したフックに通知するエージェントを作る．
+    pub fn new(inner: T, hook: H) -> Self {
+        Self { inner, hook }
+    }
+
+    /// 行動を選択するエージェントを返す．
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// 途中経過を受け取るフックを返す．
+    pub fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    /// 内部のエージェントとフックに分解する．
+    pub fn into_parts(self) -> (T, H) {
+        (self.inner, self.hook)
+    }
+}
+
+impl<S, A, T, H> ScoredStrategy<S, A> for ObservableStrategy<T, H>
+where
+    T: ObservableSearch<S, A>,
+    H: SearchHook<S, A, T::Payoff>,
+{
+    type Payoff = T::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, T::Payoff>> {
+        self.inner
+            .select_scored_action_observed(state, actor, &self.hook)
+    }
+}
+
+/// 探索の途中経過を，標準エラー出力に書き出すフック．
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrintingHook;
+
+#[cfg(feature = "std")]
+impl<S, A, P> SearchHook<S, A, P> for PrintingHook
+where
+    S: core::fmt::Debug,
+    A: core::fmt::Debug,
+    P: core::fmt::Debug,
+{
+    fn on_node_expanded(&self, depth: u32, state: &S, actor: Actor) {
+        eprintln!("node depth={} actor={:?} state={:?}", depth, actor, state);
+    }
+
+    fn on_cutoff(&self, depth: u32, reason: CutoffReason) {
+        eprintln!("cutoff depth={} reason={:?}", depth, reason);
+    }
+
+    fn on_best_action_updated(&self, action: &A, payoff: P) {
+        eprintln!("best action={:?} payoff={:?}", action, payoff);
+    }
+}
+
+/// `CollectingHook`が記録する探索の途中経過．
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchEvent<S, A, P> {
+    /// ノードを訪れた．
+    NodeExpanded { depth: u32, state: S, actor: Actor },
+    /// 枝刈りが起こった．
+    Cutoff { depth: u32, reason: CutoffReason },
+    /// 根ノードで最善と判断された行動が更新された．
+    BestActionUpdated { action: A, payoff: P },
+}
+
+/// 探索の途中経過を，起こった順に記録するフック．
+pub struct CollectingHook<S, A, P> {
+    events: Lock<Vec<SearchEvent<S, A, P>>>,
+}
+
+impl<S, A, P> CollectingHook<S, A, P> {
+    /// 何も記録していないフックを作る．
+    pub fn new() -> Self {
+        Self {
+            events: Lock::new(Vec::new()),
+        }
+    }
+
+    /// 記録した途中経過を取り出し，記録を空にする．
+    pub fn take_events(&self) -> Vec<SearchEvent<S, A, P>> {
+        mem::take(&mut *self.events.lock())
+    }
+}
+
+impl<S, A, P> Default for CollectingHook<S, A, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, A, P> SearchHook<S, A, P> for CollectingHook<S, A, P>
+where
+    S: Clone,
+    A: Clone,
+{
+    fn on_node_expanded(&self, depth: u32, state: &S, actor: Actor) {
+        self.events.lock().push(SearchEvent::NodeExpanded {
+            depth,
+            state: state.clone(),
+            actor,
+        });
+    }
+
+    fn on_cutoff(&self, depth: u32, reason: CutoffReason) {
+        self.events
+            .lock()
+            .push(SearchEvent::Cutoff { depth, reason });
+    }
+
+    fn on_best_action_updated(&self, action: &A, payoff: P) {
+        self.events.lock().push(SearchEvent::BestActionUpdated {
+            action: action.clone(),
+            payoff,
+        });
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{
+    extended_depth, Action, Actor, AlphaBetaStrategy, AtomicPackable, ConcurrentTranspositionTable,
+    Evaluator, LockFreeTranspositionTable, Rule, ScoredAction, ScoredStrategy, SearchContext,
+    SearchDepth, SearchLimits, SearchStats, State, TranspositionStore,
+};
+use core::hash::Hash;
+use core::sync::atomic::{AtomicBool, Ordering};
+use data_structure::Range;
+use num::Bounded;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+/// 2人零和ゲームにおける適切な行動を，根ノードの子ノードを並列に調べるαβ法で思考するエージェント．
+///
+/// 最初の子ノードを関心範囲全体で調べた後，残りの子ノードを`rayon`のスレッドプールで並列に調べる．
+/// 各子ノードの探索には，その時点で判明している最善の評価値を関心範囲の下限として渡すので，
+/// 他のスレッドの探索結果による枝刈りも行われる．
+///
+/// `with_min_split_depth`を指定した場合は，根ノード以外のノードでも同様に子ノードを並列に調べる (Young Brothers Wait Concept)．
+///
+/// 置換表はスレッド間で共有される．
+/// `T`は共有する置換表の型であり，共有参照`&T`が`TranspositionStore`を実装する置換表を用いることができる．
+/// History Heuristicはスレッド間で共有できないので用いない．
+///
+/// `rayon`フィーチャが有効な場合にだけ使える．
+pub struct ParallelAlphaBetaStrategy<
+    R,
+    E,
+    N,
+    T = ConcurrentTranspositionTable<<R as Rule>::S, <E as Evaluator<<R as Rule>::S>>::Payoff, N>,
+> where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 各子ノードの探索に用いるαβ法のエージェント．
+    alpha_beta: AlphaBetaStrategy<R, E, N>,
+    /// スレッド間で共有する置換表．
+    /// 行動選択をまたいで再利用される．
+    transposition_table: Option<T>,
+    /// 根ノード以外で子ノードを並列に調べるノードの，残りの探索深さの下限．
+    /// `None`の場合，根ノードの子ノードだけを並列に調べる．
+    min_split_depth: Option<N>,
+    /// 探索に用いるスレッドプール．
+    /// `None`の場合，`rayon`の既定のスレッドプールを用いる．
+    thread_pool: Option<ThreadPool>,
+}
+
+impl<R, E, N> ParallelAlphaBetaStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded,
+{
+    /// 根ノードの子ノードを並列に調べるαβ法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        AlphaBetaStrategy::new(search_depth).into()
+    }
+}
+
+impl<R, E, N, T> ParallelAlphaBetaStrategy<R, E, N, T>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定した数の状態を記録できる置換表を，スレッド間で共有して探索するようにする．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_transposition_table(self, capacity: usize) -> ParallelAlphaBetaStrategy<R, E, N>
+    where
+        R::S: Hash,
+    {
+        self.with_custom_transposition_table(ConcurrentTranspositionTable::new(capacity))
+    }
+
+    /// 指定した置換表を，スレッド間で共有して探索するようにする．
+    /// 状態のハッシュ値の計算方法や，置換表の実装を変更したい場合に使用する．
+    /// 探索するには，共有参照`&U`が`TranspositionStore`を実装している必要がある．
+    pub fn with_custom_transposition_table<U>(
+        self,
+        table: U,
+    ) -> ParallelAlphaBetaStrategy<R, E, N, U> {
+        ParallelAlphaBetaStrategy {
+            alpha_beta: self.alpha_beta,
+            transposition_table: Some(table),
+            min_split_depth: self.min_split_depth,
+            thread_pool: self.thread_pool,
+        }
+    }
+
+    /// 指定した数の状態を記録できる，排他制御を用いない置換表をスレッド間で共有して探索するようにする．
+    /// 評価値と探索深さが`AtomicPackable`に収まらない探索結果は記録されない．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_lock_free_transposition_table(
+        self,
+        capacity: usize,
+    ) -> ParallelAlphaBetaStrategy<R, E, N, LockFreeTranspositionTable<R::S, E::Payoff, N>>
+    where
+        R::S: Hash,
+        E::Payoff: AtomicPackable,
+        N: AtomicPackable,
+    {
+        self.with_custom_transposition_table(LockFreeTranspositionTable::new(capacity))
+    }
+
+    /// 残りの探索深さが指定した値以上のノードでも，子ノードを並列に調べるようにする (Young Brothers Wait Concept)．
+    ///
+    /// 各ノードで最初の子ノード (長男) を調べて関心範囲を狭めた後，残りの子ノードを並列に調べる．
+    /// いずれかの子ノードで枝刈りが起こると，まだ調べている兄弟ノードの探索を打ち切る．
+    /// 浅いノードまで並列に調べるほど，スレッドに仕事を分けやすくなる代わりに，打ち切られる探索が増える．
+    ///
+    /// 子ノードを並列に調べるノードでは，置換表の参照やNull Move Pruningなどの枝刈りを行わない．
+    /// 枝刈りの設定によっては逐次探索と評価値が変わりうるが，枝刈りを行わない設定では根ノードの評価値は逐次探索と一致する．
+    pub fn with_min_split_depth(self, min_split_depth: N) -> Self {
+        Self {
+            min_split_depth: Some(min_split_depth),
+            ..self
+        }
+    }
+
+    /// 指定した数のスレッドを持つスレッドプールで探索するようにする．
+    /// 既定では`rayon`の既定のスレッドプールを用いる．
+    /// # Panics
+    /// `threads`が0の場合，またはスレッドプールを作れなかった場合
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            thread_pool: Some(build_thread_pool(threads)),
+            ..self
+        }
+    }
+}
+
+impl<R, E, N> From<AlphaBetaStrategy<R, E, N>> for ParallelAlphaBetaStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定したαβ法のエージェントと同じ設定で，根ノードの子ノードを並列に調べるようにする．
+    /// 元のエージェントの置換表とHistory Heuristicは用いない．
+    fn from(alpha_beta: AlphaBetaStrategy<R, E, N>) -> Self {
+        Self {
+            // 差分更新したハッシュ値は元の置換表のものなので，共有する置換表では使えない
+            alpha_beta: AlphaBetaStrategy {
+                incremental_hash: None,
+                ..alpha_beta
+            },
+            transposition_table: None,
+            min_split_depth: None,
+            thread_pool: None,
+        }
+    }
+}
+
+/// 指定した数のスレッドを持つスレッドプールを作る．
+/// # Panics
+/// `threads`が0の場合，またはスレッドプールを作れなかった場合
+pub(crate) fn build_thread_pool(threads: usize) -> ThreadPool {
+    assert!(threads > 0, "threads must be positive");
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("failed to build thread pool")
+}
+
+/// 最善と判断された子ノードの評価値，行動の番号と行動．
+type BestChild<P, A> = Option<(P, usize, A)>;
+
+/// 子ノードを並列に調べるノード．
+struct SplitNode<'s, S, N> {
+    state: &'s S,
+    /// 置換表を用いる場合は，状態のハッシュ値．
+    key: Option<u64>,
+    /// 根ノードからの深さ．
+    depth: usize,
+    /// 根ノードからの手数．
+    ply: u32,
+    /// 根ノードからこのノードまでの手順で，探索を延長した手数の合計．
+    line_extension: u32,
+    /// このノードで行動するプレイヤー．
+    next_actor: Actor,
+    /// このノードからの残りの探索深さ．
+    remaining_depth: N,
+    /// このノードに至るまでに，続けてパスが行われた回数．
+    consecutive_passes: u32,
+}
+
+/// 子ノードを並列に調べる間，兄弟ノードの探索で共有する情報 (分割点)．
+struct SplitPoint<'c, P, A> {
+    /// それまでに調べた子ノードのうち，最善のもの．
+    best: Mutex<BestChild<P, A>>,
+    /// 子ノードの探索を打ち切るか．
+    cancellation: Cancellation<'c>,
+    /// このノードの評価値の関心範囲．
+    payoff_range: Range<P>,
+    /// このノードで，評価値を最大化するプレイヤーが行動するか．
+    maximizes: bool,
+}
+
+impl<P, A> SplitPoint<'_, P, A>
+where
+    P: Copy + Ord,
+{
+    /// それまでの最善の評価値で狭めた，次に調べる子ノードの関心範囲を返す．
+    /// 枝刈りが起こり，残りの子ノードを調べる必要がない場合は`None`を返す．
+    fn child_range(&self) -> Option<Range<P>> {
+        let best = self
+            .best
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|&(payoff, _, _)| payoff);
+        let range = self.payoff_range;
+        match best {
+            Some(best) if self.maximizes => Range::try_new(range.min.max(best), range.max),
+            Some(best) => Range::try_new(range.min, range.max.min(best)),
+            None => Some(range),
+        }
+    }
+}
+
+/// 探索を打ち切るかを，スレッド間で共有する．
+/// 親の分割点で打ち切られた場合は，その子孫の分割点の探索もすべて打ち切る．
+struct Cancellation<'p> {
+    cancelled: AtomicBool,
+    parent: Option<&'p Cancellation<'p>>,
+}
+
+impl<'p> Cancellation<'p> {
+    fn new(parent: Option<&'p Cancellation<'p>>) -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+            parent,
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.parent.is_some_and(|parent| parent.is_cancelled())
+    }
+}
+
+impl<S, A, R, E, N, T> ParallelAlphaBetaStrategy<R, E, N, T>
+where
+    S: State + Sync,
+    A: Action + Send,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Send + Sync,
+    N: SearchDepth + Send + Sync,
+    T: Sync,
+    for<'t> &'t T: TranspositionStore<S, E::Payoff, N>,
+{
+    /// 根ノードの子ノードを並列に調べ，最善の子ノードと探索の統計情報を返す．
+    /// 取れる行動がない場合，最善の子ノードは`None`である．
+    fn search(&self, state: &S, actor: Actor) -> (BestChild<E::Payoff, A>, SearchStats) {
+        let search_depth = self.alpha_beta.search_depth;
+        if search_depth.is_zero() || R::terminal_kind(state).is_some() {
+            let stats = SearchStats {
+                nodes_evaluated: 1,
+                ..SearchStats::default()
+            };
+            return (None, stats);
+        }
+
+        let root = SplitNode {
+            state,
+            key: self.hash_state(state),
+            depth: 0,
+            ply: 0,
+            line_extension: 0,
+            next_actor: actor,
+            remaining_depth: search_depth,
+            consecutive_passes: 0,
+        };
+        let stats = Mutex::new(SearchStats {
+            nodes_evaluated: 1,
+            ..SearchStats::default()
+        });
+        let cancellation = Cancellation::new(None);
+        let full_range = self.alpha_beta.payoff_bounds.full_range();
+        let best = match self.thread_pool.as_ref() {
+            Some(pool) => {
+                pool.install(|| self.split(&root, full_range, actor, &cancellation, &stats))
+            }
+            None => self.split(&root, full_range, actor, &cancellation, &stats),
+        };
+        let stats = *Self::lock(&stats);
+        (best, stats)
+    }
+
+    /// 指定したノードの子ノードを調べ，最善の子ノードを返す．
+    /// 最初の子ノードを関心範囲全体で調べた後，残りの子ノードを並列に調べる．
+    /// 各子ノードの探索には，その時点で判明している最善の評価値で狭めた関心範囲を渡す．
+    /// 取れる行動がない場合や，探索が打ち切られた場合は`None`を返す．
+    fn split(
+        &self,
+        node: &SplitNode<'_, S, N>,
+        payoff_range: Range<E::Payoff>,
+        consideration_target: Actor,
+        cancellation: &Cancellation<'_>,
+        stats: &Mutex<SearchStats>,
+    ) -> BestChild<E::Payoff, A> {
+        let context = self.child_context(consideration_target);
+        let mut actions = self
+            .alpha_beta
+            .ordered_actions(
+                node.state,
+                node.next_actor,
+                node.remaining_depth,
+                None,
+                None,
+                &context,
+            )
+            .map(|(_, action)| action)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .enumerate();
+
+        // 兄弟ノードで枝刈りが起こったら，このノードの子ノードの探索を打ち切る
+        let split_point = SplitPoint {
+            best: Mutex::new(None),
+            cancellation: Cancellation::new(Some(cancellation)),
+            payoff_range,
+            maximizes: node.next_actor == consideration_target,
+        };
+        if let Some((index, action)) = actions.next() {
+            self.search_child(
+                node,
+                index,
+                action,
+                consideration_target,
+                &split_point,
+                stats,
+            );
+        }
+
+        let split_point = &split_point;
+        rayon::scope(|scope| {
+            for (index, action) in actions {
+                scope.spawn(move |_| {
+                    self.search_child(
+                        node,
+                        index,
+                        action,
+                        consideration_target,
+                        split_point,
+                        stats,
+                    );
+                });
+            }
+        });
+
+        if cancellation.is_cancelled() {
+            return None;
+        }
+        let best = Self::lock(&split_point.best).take();
+        best
+    }
+
+    /// 指定した行動をとった後の子ノードを調べ，分割点の最善の子ノードを更新する．
+    /// 子ノードの残りの探索深さが`min_split_depth`以上なら，その子ノードでも子ノードを並列に調べる．
+    fn search_child(
+        &self,
+        node: &SplitNode<'_, S, N>,
+        index: usize,
+        action: A,
+        consideration_target: Actor,
+        split_point: &SplitPoint<'_, E::Payoff, A>,
+        stats: &Mutex<SearchStats>,
+    ) {
+        let payoff_range = match split_point.child_range() {
+            Some(range) if !split_point.cancellation.is_cancelled() => range,
+            _ => return,
+        };
+        let is_cancelled = || split_point.cancellation.is_cancelled();
+        let mut context = self.child_context(consideration_target);
+        context.cancelled = Some(&is_cancelled);
+        context.ply = node.ply + 1;
+        context.line_extension = node.line_extension;
+        let mut child = self.alpha_beta.child_node(
+            node.state,
+            node.depth,
+            node.key,
+            node.consecutive_passes,
+            action,
+        );
+        let extension = self.alpha_beta.child_extension(false, &child, &mut context);
+        context.line_extension += extension;
+        let remaining_depth = extended_depth(node.remaining_depth - N::one(), extension);
+
+        let splits = self
+            .min_split_depth
+            .is_some_and(|min_split_depth| remaining_depth >= min_split_depth)
+            && R::terminal_kind(child.ref_state()).is_none()
+            && child.consecutive_passes < 2;
+        let payoff = if splits {
+            if context.visit_node() {
+                return;
+            }
+            Self::lock(stats).merge(&context.stats);
+            let child_node = SplitNode {
+                state: child.ref_state(),
+                key: self.hash_state(child.ref_state()),
+                depth: node.depth + 1,
+                ply: context.ply,
+                line_extension: context.line_extension,
+                next_actor: node.next_actor.opponent(),
+                remaining_depth,
+                consecutive_passes: child.consecutive_passes,
+            };
+            self.split(
+                &child_node,
+                payoff_range,
+                consideration_target,
+                &split_point.cancellation,
+                stats,
+            )
+            .map(|(payoff, _, _)| payoff)
+        } else {
+            let payoff = self.alpha_beta.construct_best_game_tree_alpha_beta(
+                remaining_depth,
+                &mut child,
+                payoff_range,
+                &mut context,
+            );
+            Self::lock(stats).merge(&context.stats);
+            payoff
+        };
+
+        // 関心範囲から外れた評価値は，真の評価値ではないが，最善の評価値を上回ることもない．
+        // 評価値が等しい場合は，逐次探索と同じ行動を選ぶよう，先に列挙された行動を優先する
+        if let Some(payoff) = payoff {
+            let mut best = Self::lock(&split_point.best);
+            let replaces = match *best {
+                Some((best_payoff, best_index, _)) => {
+                    let improves = if split_point.maximizes {
+                        payoff > best_payoff
+                    } else {
+                        payoff < best_payoff
+                    };
+                    improves || (payoff == best_payoff && index < best_index)
+                }
+                None => true,
+            };
+            if replaces {
+                let action = child.into_inner().cause_action.unwrap();
+                *best = Some((payoff, index, action));
+            }
+            drop(best);
+            if split_point.child_range().is_none() {
+                split_point.cancellation.cancel();
+            }
+        }
+    }
+
+    /// 置換表を用いる場合は，指定した状態のハッシュ値を返す．
+    fn hash_state(&self, state: &S) -> Option<u64> {
+        self.transposition_table
+            .as_ref()
+            .map(|table| table.hash_state(state))
+    }
+
+    /// 1つの子ノードの探索の間だけ使用する情報を作る．
+    fn child_context(&self, actor: Actor) -> SearchContext<'_, S, A, E::Payoff, N, &T> {
+        let killers = self
+            .alpha_beta
+            .killer_moves
+            .map(|new_killers| new_killers());
+        let countermoves = self
+            .alpha_beta
+            .countermoves
+            .map(|new_countermoves| new_countermoves());
+        SearchContext::new(
+            actor,
+            self.transposition_table.as_ref(),
+            SearchLimits::default(),
+            None,
+            killers,
+            countermoves,
+        )
+    }
+
+    fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        let search_depth = self.alpha_beta.search_depth;
+        let terminal = R::terminal_kind(state);
+        if search_depth.is_zero() || terminal.is_some() {
+            return Some(self.alpha_beta.evaluate_node(actor, state, terminal, 0));
+        }
+        self.search(state, actor).0.map(|(payoff, _, _)| payoff)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    /// 統計情報は，すべてのスレッドの探索を合計したものである．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    /// 統計情報は，すべてのスレッドの探索を合計したものである．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let start = Instant::now();
+        let (best, mut stats) = self.search(state, actor);
+        stats.elapsed = start.elapsed();
+        let scored = best.map(|(payoff, _, action)| ScoredAction::new(action, payoff));
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N, T> ScoredStrategy<S, A> for ParallelAlphaBetaStrategy<R, E, N, T>
+where
+    S: State + Sync,
+    A: Action + Send,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded + Send + Sync,
+    N: SearchDepth + Send + Sync,
+    T: Sync,
+    for<'t> &'t T: TranspositionStore<S, E::Payoff, N>,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
@@ -0,0 +1,27 @@
+use crate::GameTree;
+use alloc::vec::Vec;
+
+/// 探索で得られた最善手順 (主要変化)．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrincipalVariation<A, P> {
+    /// 根ノードの状態から順にとる行動．
+    /// 置換表による枝刈りが起こった場合など，探索深さより短くなる場合がある．
+    pub actions: Vec<A>,
+    /// 最善手順をたどった場合の，エージェントにとっての評価値．
+    pub payoff: P,
+}
+
+impl<A, P> PrincipalVariation<A, P> {
+    /// 探索済みのゲーム木の根ノードから最善の子ノードをたどり，主要変化を取り出す．
+    /// ゲーム木は破棄せず，各ノードの行動だけを取り出す．
+    /// 根ノードの評価値が存在しない場合は`None`を返す．
+    pub(crate) fn from_game_tree<S>(root: &mut GameTree<S, A, P>) -> Option<Self>
+    where
+        P: Copy,
+    {
+        let mut path = root.iter_path_mut();
+        let payoff = path.next()?.payoff?;
+        let actions = path.filter_map(|node| node.cause_action.take()).collect();
+        Some(Self { actions, payoff })
+    }
+}
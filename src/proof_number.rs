@@ -0,0 +1,362 @@
+use crate::{Action, Actor, OutcomeRule, Rule};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+/// 証明数・反証数の無限大を表す値．
+/// 証明 (反証) できないことが確定したノードの証明数 (反証数) に用いる．
+pub const INFINITE_PROOF_NUMBER: u64 = u64::MAX;
+
+/// 証明数探索の結果．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProofOutcome {
+    /// 攻め方が目標を必ず達成できることを証明した．
+    Proved,
+    /// 受け方が目標の達成を必ず防げることを証明した (反証した)．
+    Disproved,
+    /// ノード数の上限に達したため，証明も反証もできなかった．
+    Unknown,
+}
+
+/// 指定したプレイヤー (攻め方) が，ゲームの終了時に目標を必ず達成できるかを証明数探索で調べる．
+///
+/// 目標は，ゲームが終了した状態に対する述語で与える．
+/// 攻め方の手番のノードは，子ノードのいずれかが証明できれば証明できる (ORノード)．
+/// 受け方の手番のノードは，子ノードのすべてが証明できれば証明できる (ANDノード)．
+/// 各ノードで証明に必要な末端ノードの数の下界 (証明数) と，反証に必要な数の下界 (反証数) を保持し，
+/// 最も少ない展開で結論に近づく末端ノード (最有望証明ノード) を展開することを繰り返す．
+///
+/// 評価関数を必要とせず，勝ち負けのような二値の問いについては，αβ法より少ないノード数で結論を出せることが多い．
+pub struct ProofNumberSearch<R> {
+    /// 保持するノードの数の上限．
+    node_budget: usize,
+    _r: PhantomData<fn() -> R>,
+}
+
+impl<R> ProofNumberSearch<R> {
+    /// 保持するノードの数の上限を指定して作る．
+    pub fn new(node_budget: usize) -> Self {
+        Self {
+            node_budget,
+            _r: PhantomData,
+        }
+    }
+}
+
+impl<S, A, R> ProofNumberSearch<R>
+where
+    S: Clone,
+    A: Action,
+    R: Rule<S = S, A = A>,
+{
+    /// 指定した状態から`actor`が行動する場合に，`attacker`がゲームの終了時に`goal`を満たす状態に必ず至らせられるかを調べ，
+    /// 結果と探索したゲーム木を返す．
+    ///
+    /// `goal`はゲームが終了した状態と，取れる行動がない状態に対してだけ呼び出される．
+    pub fn solve<F>(
+        &self,
+        state: &S,
+        actor: Actor,
+        attacker: Actor,
+        goal: F,
+    ) -> (ProofOutcome, ProofTree<S, A>)
+    where
+        F: Fn(&S) -> bool,
+    {
+        let mut tree = ProofTree::new::<R, _>(state.clone(), actor, attacker, &goal);
+        while tree.outcome() == ProofOutcome::Unknown && tree.nodes.len() < self.node_budget {
+            let node = tree.most_proving_node();
+            tree.expand::<R, _>(node, &goal);
+            tree.update_ancestors(node);
+        }
+        (tree.outcome(), tree)
+    }
+}
+
+impl<S, A, R> ProofNumberSearch<R>
+where
+    S: Clone,
+    A: Action,
+    R: OutcomeRule<S = S, A = A>,
+{
+    /// 指定した状態から`actor`が行動する場合に，`attacker`が必ず勝てるかを調べ，結果と探索したゲーム木を返す．
+    /// 引き分けは勝ちとみなさない．
+    pub fn solve_win(
+        &self,
+        state: &S,
+        actor: Actor,
+        attacker: Actor,
+    ) -> (ProofOutcome, ProofTree<S, A>) {
+        self.solve(state, actor, attacker, |state| {
+            R::winner(state) == Some(attacker)
+        })
+    }
+}
+
+/// 証明数探索で構築したゲーム木．
+/// ノードは配列に格納され，添字0が根ノードとなる．
+#[derive(Debug, Clone)]
+pub struct ProofTree<S, A> {
+    nodes: Vec<ProofNode<S, A>>,
+    /// 目標の達成を目指すプレイヤー．
+    attacker: Actor,
+}
+
+/// 証明数探索で構築したゲーム木のノード．
+#[derive(Debug, Clone)]
+pub struct ProofNode<S, A> {
+    /// 現在の状態．
+    pub state: S,
+    /// この状態に至る際に実行された行動．根ノードでは`None`となる．
+    pub cause_action: Option<A>,
+    /// この状態で行動するプレイヤー．
+    pub next_actor: Actor,
+    /// このノードを証明するために，証明する必要のある末端ノードの数の下界．
+    /// 証明できた場合は0，反証できた場合は`INFINITE_PROOF_NUMBER`となる．
+    pub proof_number: u64,
+    /// このノードを反証するために，反証する必要のある末端ノードの数の下界．
+    /// 反証できた場合は0，証明できた場合は`INFINITE_PROOF_NUMBER`となる．
+    pub disproof_number: u64,
+    /// 親ノードの添字．根ノードでは`None`となる．
+    parent: Option<usize>,
+    /// 子ノードの添字の範囲．子ノードは連続して格納される．未展開のノードでは空となる．
+    children: Range<usize>,
+}
+
+impl<S, A> ProofNode<S, A> {
+    /// このノードが証明できたか．
+    pub fn is_proved(&self) -> bool {
+        self.proof_number == 0
+    }
+
+    /// このノードが反証できたか．
+    pub fn is_disproved(&self) -> bool {
+        self.disproof_number == 0
+    }
+}
+
+impl<S, A> ProofTree<S, A> {
+    /// 根ノードの証明数と反証数から，探索の結果を返す．
+    pub fn outcome(&self) -> ProofOutcome {
+        let root = self.root();
+        if root.is_proved() {
+            ProofOutcome::Proved
+        } else if root.is_disproved() {
+            ProofOutcome::Disproved
+        } else {
+            ProofOutcome::Unknown
+        }
+    }
+
+    /// 根ノードを返す．
+    pub fn root(&self) -> &ProofNode<S, A> {
+        &self.nodes[0]
+    }
+
+    /// 保持しているノードの数を返す．
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// ノードを保持していないか．根ノードは常に存在するので，常に`false`を返す．
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 保持しているすべてのノードを，展開した順に列挙する．
+    pub fn iter_nodes(&self) -> impl Iterator<Item = &ProofNode<S, A>> {
+        self.nodes.iter()
+    }
+
+    /// 指定したノードの子ノードを列挙する．
+    pub fn children<'a>(
+        &'a self,
+        node: &'a ProofNode<S, A>,
+    ) -> impl Iterator<Item = &'a ProofNode<S, A>> {
+        node.children.clone().map(move |child| &self.nodes[child])
+    }
+
+    /// 証明 (反証) できた場合，その根拠となる手順を根ノードから順に返す．
+    ///
+    /// 証明できた場合，攻め方の手番では証明できた子ノードのうち最初のものを，
+    /// 受け方の手番では証明に最も多くのノードを要したものを選んでたどる．
+    /// 反証できた場合は，攻め方と受け方を入れ替えて同様にたどる．
+    /// 結論が出ていない場合は空の手順を返す．
+    pub fn principal_line(&self) -> Vec<&A> {
+        let proved = match self.outcome() {
+            ProofOutcome::Proved => true,
+            ProofOutcome::Disproved => false,
+            ProofOutcome::Unknown => return vec![],
+        };
+        let mut line = vec![];
+        let mut current = 0;
+        loop {
+            let node = &self.nodes[current];
+            let is_winner_turn = (node.next_actor == self.attacker) == proved;
+            let resolved = node.children.clone().filter(|&child| {
+                let child = &self.nodes[child];
+                if proved {
+                    child.is_proved()
+                } else {
+                    child.is_disproved()
+                }
+            });
+            let next = if is_winner_turn {
+                resolved.min()
+            } else {
+                resolved.max_by_key(|&child| self.subtree_size(child))
+            };
+            match next {
+                Some(next) => {
+                    line.extend(self.nodes[next].cause_action.as_ref());
+                    current = next;
+                }
+                None => return line,
+            }
+        }
+    }
+
+    /// 指定したノードを根とする部分木のノードの数を返す．
+    fn subtree_size(&self, root: usize) -> usize {
+        let mut stack = vec![root];
+        let mut size = 0;
+        while let Some(index) = stack.pop() {
+            size += 1;
+            stack.extend(self.nodes[index].children.clone());
+        }
+        size
+    }
+
+    /// 指定したノードが，攻め方の手番のノード (ORノード) か．
+    fn is_or_node(&self, index: usize) -> bool {
+        self.nodes[index].next_actor == self.attacker
+    }
+
+    /// 根ノードから，ORノードでは証明数，ANDノードでは反証数の最も小さい子ノードをたどり，
+    /// 最有望証明ノードの添字を返す．
+    fn most_proving_node(&self) -> usize {
+        let mut current = 0;
+        loop {
+            let children = self.nodes[current].children.clone();
+            let next = if self.is_or_node(current) {
+                children.min_by_key(|&child| (self.nodes[child].proof_number, child))
+            } else {
+                children.min_by_key(|&child| (self.nodes[child].disproof_number, child))
+            };
+            match next {
+                Some(next) => current = next,
+                None => return current,
+            }
+        }
+    }
+
+    /// 指定したノードから根ノードに向かって，子ノードの証明数と反証数を逆伝播させる．
+    /// どちらも変わらなくなった時点で打ち切る．
+    fn update_ancestors(&mut self, from: usize) {
+        let mut current = Some(from);
+        while let Some(index) = current {
+            let children = self.nodes[index].children.clone();
+            if children.is_empty() {
+                return;
+            }
+            let proof_numbers = children.clone().map(|child| self.nodes[child].proof_number);
+            let disproof_numbers = children.map(|child| self.nodes[child].disproof_number);
+            let (proof_number, disproof_number) = if self.is_or_node(index) {
+                (
+                    proof_numbers.min().unwrap_or(INFINITE_PROOF_NUMBER),
+                    disproof_numbers.fold(0, saturating_sum),
+                )
+            } else {
+                (
+                    proof_numbers.fold(0, saturating_sum),
+                    disproof_numbers.min().unwrap_or(INFINITE_PROOF_NUMBER),
+                )
+            };
+            let node = &mut self.nodes[index];
+            if index != from
+                && node.proof_number == proof_number
+                && node.disproof_number == disproof_number
+            {
+                return;
+            }
+            node.proof_number = proof_number;
+            node.disproof_number = disproof_number;
+            current = node.parent;
+        }
+    }
+}
+
+impl<S, A> ProofTree<S, A>
+where
+    A: Action,
+{
+    fn new<R, F>(state: S, actor: Actor, attacker: Actor, goal: &F) -> Self
+    where
+        R: Rule<S = S, A = A>,
+        F: Fn(&S) -> bool,
+    {
+        let (proof_number, disproof_number) = initial_numbers::<R, _>(&state, actor, goal);
+        Self {
+            nodes: vec![ProofNode {
+                state,
+                cause_action: None,
+                next_actor: actor,
+                proof_number,
+                disproof_number,
+                parent: None,
+                children: 0..0,
+            }],
+            attacker,
+        }
+    }
+
+    /// 指定したノードで取れるすべての行動について子ノードを追加し，証明数と反証数の初期値を与える．
+    fn expand<R, F>(&mut self, parent: usize, goal: &F)
+    where
+        R: Rule<S = S, A = A>,
+        F: Fn(&S) -> bool,
+    {
+        debug_assert!(self.nodes[parent].children.is_empty());
+        let next_actor = self.nodes[parent].next_actor;
+        let children = R::iterate_available_actions(&self.nodes[parent].state, next_actor)
+            .map(|action| {
+                let state = R::translate_state(&self.nodes[parent].state, &action);
+                let (proof_number, disproof_number) =
+                    initial_numbers::<R, _>(&state, next_actor.opponent(), goal);
+                ProofNode {
+                    state,
+                    cause_action: Some(action),
+                    next_actor: next_actor.opponent(),
+                    proof_number,
+                    disproof_number,
+                    parent: Some(parent),
+                    children: 0..0,
+                }
+            })
+            .collect::<Vec<_>>();
+        let first_child = self.nodes.len();
+        self.nodes[parent].children = first_child..first_child + children.len();
+        self.nodes.extend(children);
+    }
+}
+
+/// 指定した状態のノードの，証明数と反証数の初期値を返す．
+/// ゲームが終了している状態と，取れる行動がない状態では，目標を満たすかどうかで結論を出す．
+pub(crate) fn initial_numbers<R, F>(state: &R::S, actor: Actor, goal: &F) -> (u64, u64)
+where
+    R: Rule,
+    F: Fn(&R::S) -> bool,
+{
+    let is_leaf = R::terminal_kind(state).is_some() || !R::has_available_actions(state, actor);
+    match (is_leaf, is_leaf && goal(state)) {
+        (true, true) => (0, INFINITE_PROOF_NUMBER),
+        (true, false) => (INFINITE_PROOF_NUMBER, 0),
+        (false, _) => (1, 1),
+    }
+}
+
+/// 証明数 (反証数) の和を，無限大を超えないように求める．
+pub(crate) fn saturating_sum(sum: u64, number: u64) -> u64 {
+    sum.saturating_add(number)
+}
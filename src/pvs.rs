@@ -0,0 +1,254 @@
+use crate::clock::Stopwatch;
+use crate::node::TreeNode;
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, GameTree, MinimaxNode, Rule, ScoredAction,
+    ScoredStrategy, SearchDepth, SearchStats, State,
+};
+use core::marker::PhantomData;
+use data_structure::Range;
+use num::Bounded;
+
+/// 2人零和ゲームにおける適切な行動を，主要変化探索 (Principal Variation Search) で思考するエージェント．
+///
+/// 各ノードで最初の子ノードだけを関心範囲全体で探索し，残りの子ノードは
+/// それまでの最善の評価値を上回るかどうかだけを幅0の窓で調べる．
+/// 上回ることが分かった子ノードは関心範囲全体で探索し直す．
+/// 最初の子ノードが最善であることが多いほど，`AlphaBetaStrategy`より探索量が少なくなる．
+pub struct PvsStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    _r: PhantomData<R>,
+    _e: PhantomData<E>,
+}
+
+impl<R, E, N> PvsStrategy<R, E, N> {
+    /// 指定した深さまで探索する，主要変化探索で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<S, A, R, E, N> PvsStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    /// 主要変化探索により，指定したノードの評価値を再帰的に計算する．
+    /// # Params
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. consideration_target 評価値を計算する対象のプレイヤー．
+    /// 1. current_node 注目ノード．
+    /// 1. payoff_range 評価値の関心範囲．
+    /// 1. ply 根ノードから注目ノードまでの深さ．
+    /// 1. stats 探索の統計情報．
+    ///
+    /// # Returns
+    /// `Some(e)`: このノードの評価値`e`
+    ///
+    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
+    fn construct_best_game_tree_pvs(
+        &self,
+        remaining_depth: N,
+        consideration_target: Actor,
+        current_node: &mut GameTree<S, A, E::Payoff>,
+        payoff_range: Range<E::Payoff>,
+        ply: u32,
+        stats: &mut SearchStats,
+    ) -> Option<E::Payoff> {
+        debug_assert!(current_node.payoff.is_none());
+
+        stats.nodes_evaluated += 1;
+        stats.max_depth_reached = stats.max_depth_reached.max(ply);
+
+        // 注目ノードが末端ノードなら，現在の状態に対する静的評価値をそのまま適用する
+        let terminal = R::terminal_kind(current_node.ref_state());
+        if remaining_depth.is_zero() || terminal.is_some() {
+            let payoff = evaluate_at_ply::<_, E>(
+                consideration_target,
+                current_node.ref_state(),
+                terminal,
+                ply,
+            );
+            current_node.payoff = Some(payoff);
+            return Some(payoff);
+        }
+
+        let next_actor = match current_node.cause_action.as_ref() {
+            Some(action) => action.actor().opponent(),
+            None => consideration_target,
+        };
+        let is_own_turn = next_actor == consideration_target;
+
+        let mut best_child = None;
+        let mut best_payoff: Option<E::Payoff> = None;
+        let mut current_payoff_range = payoff_range;
+        let current_state = current_node.ref_state();
+
+        for action in R::iterate_available_actions(current_state, next_actor) {
+            let next_state = R::translate_state(current_state, &action);
+            let mut child =
+                current_node.new_child(MinimaxNode::new(next_state.into(), Some(action), None));
+            // 子ノードが末端ノードなら，幅0の窓で調べても探索量は減らない
+            let is_leaf_child = (remaining_depth - N::one()).is_zero();
+            let child_payoff = match best_payoff {
+                // 2番目以降の子ノードは，関心範囲の下限 (相手の手番では上限) を超えるかだけを幅0の窓で調べる
+                Some(_) if !is_leaf_child => {
+                    let bound = if is_own_turn {
+                        current_payoff_range.min
+                    } else {
+                        current_payoff_range.max
+                    };
+                    let scout_payoff = self.construct_best_game_tree_pvs(
+                        remaining_depth - N::one(),
+                        consideration_target,
+                        &mut child,
+                        Range::new(bound, bound),
+                        ply + 1,
+                        stats,
+                    );
+                    // 窓を超え，かつ関心範囲に収まる場合は，真の評価値を得るために探索し直す．
+                    // 窓を超えた評価値は真の評価値の下界 (相手の手番では上界) なので，新たな関心範囲の端に使える．
+                    let re_search_range = scout_payoff.and_then(|e| {
+                        if is_own_turn && bound < e && e < current_payoff_range.max {
+                            Some(Range::new(e, current_payoff_range.max))
+                        } else if !is_own_turn && current_payoff_range.min < e && e < bound {
+                            Some(Range::new(current_payoff_range.min, e))
+                        } else {
+                            None
+                        }
+                    });
+                    match re_search_range {
+                        Some(range) => {
+                            stats.re_searches += 1;
+                            child.payoff = None;
+                            self.construct_best_game_tree_pvs(
+                                remaining_depth - N::one(),
+                                consideration_target,
+                                &mut child,
+                                range,
+                                ply + 1,
+                                stats,
+                            )
+                        }
+                        None => scout_payoff,
+                    }
+                }
+                // 最初の子ノードと末端ノードは，関心範囲全体で探索する
+                _ => self.construct_best_game_tree_pvs(
+                    remaining_depth - N::one(),
+                    consideration_target,
+                    &mut child,
+                    current_payoff_range,
+                    ply + 1,
+                    stats,
+                ),
+            };
+            // 取れる行動がない子ノードは探索対象としない．
+            let child_payoff = match child_payoff {
+                Some(e) => e,
+                None => continue,
+            };
+            // 自分の手番では自分が有利になる行動を，相手の手番では自分が不利になる行動を選択する
+            if let Some(e) = best_payoff {
+                if (is_own_turn && e >= child_payoff) || (!is_own_turn && e <= child_payoff) {
+                    continue;
+                }
+            }
+            best_child = Some(child);
+            best_payoff = Some(child_payoff);
+            // 可能なら，αβカットして探索量を減らす．
+            let maybe_next_range = if is_own_turn {
+                Range::try_new(child_payoff, current_payoff_range.max)
+            } else {
+                Range::try_new(current_payoff_range.min, child_payoff)
+            };
+            match maybe_next_range {
+                Some(range) => current_payoff_range = range,
+                None => {
+                    if is_own_turn {
+                        stats.beta_cutoffs += 1;
+                    } else {
+                        stats.alpha_cutoffs += 1;
+                    }
+                    break;
+                }
+            }
+        }
+
+        if let Some(child) = best_child {
+            current_node.replace_child(child);
+            current_node.payoff = best_payoff;
+        }
+        current_node.payoff
+    }
+
+    /// 指定した状態を根とするゲーム木を主要変化探索で探索し，根ノードを返す．
+    fn search<'a>(
+        &self,
+        state: &'a S,
+        actor: Actor,
+        stats: &mut SearchStats,
+    ) -> GameTree<'a, S, A, E::Payoff> {
+        let mut root = TreeNode::new(MinimaxNode::new(state.into(), None, None));
+        self.construct_best_game_tree_pvs(
+            self.search_depth,
+            actor,
+            &mut root,
+            Range::new(E::Payoff::min_value(), E::Payoff::max_value()),
+            0,
+            stats,
+        );
+        root
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor, &mut SearchStats::default())
+            .payoff
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let stopwatch = Stopwatch::start();
+        let mut stats = SearchStats::default();
+        let root = self.search(state, actor, &mut stats);
+        let scored = ScoredAction::from_game_tree(root);
+        stats.elapsed = stopwatch.elapsed();
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for PvsStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
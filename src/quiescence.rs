@@ -0,0 +1,26 @@
+use crate::{Actor, Rule};
+use alloc::vec::Vec;
+
+/// 静止探索で調べる行動を，すべての行動とは別に列挙できるゲームルール．
+///
+/// 駒を取るなどの局面を大きく変える行動だけを直接列挙できる場合，
+/// すべての行動を列挙して`Rule::is_loud_action`で絞り込むよりも軽い計算で静止探索を行える．
+pub trait QuiescenceRule: Rule {
+    /// 静止探索で調べる行動を列挙する際に使用する型．
+    type NoisyActions: IntoIterator<Item = Self::A>;
+
+    /// 指定された状態下で実行可能な行動のうち，局面を大きく変える行動を列挙する．
+    /// 列挙される行動がない状態は，静的評価値が信頼できる静かな局面とみなす．
+    fn iterate_noisy_actions(state: &Self::S, actor: Actor) -> Self::NoisyActions;
+}
+
+/// 静止探索で調べる行動を列挙する関数．
+pub(crate) type NoisyActionGenerator<S, A> = fn(&S, Actor) -> Vec<A>;
+
+/// `QuiescenceRule::iterate_noisy_actions`が列挙する行動を集める．
+pub(crate) fn collect_noisy_actions<R>(state: &R::S, actor: Actor) -> Vec<R::A>
+where
+    R: QuiescenceRule,
+{
+    R::iterate_noisy_actions(state, actor).into_iter().collect()
+}
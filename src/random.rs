@@ -0,0 +1,81 @@
+use crate::{Action, Actor, Rule, ScoredAction, ScoredStrategy, State};
+use core::fmt;
+use core::marker::PhantomData;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::{Mutex, PoisonError};
+
+/// 取れる行動から一様ランダムに行動を選択するエージェント．
+///
+/// 探索を行わないので，`Rule`の実装の検証や，他のエージェントの強さを測る対戦相手に用いる．
+pub struct RandomStrategy<R, G> {
+    /// 行動の選択に用いる乱数生成器．
+    rng: Mutex<G>,
+    _r: PhantomData<R>,
+}
+
+impl<R, G> RandomStrategy<R, G> {
+    /// 指定した乱数生成器で行動を選択するエージェントを作る．
+    pub fn new(rng: G) -> Self {
+        Self {
+            rng: Mutex::new(rng),
+            _r: PhantomData,
+        }
+    }
+}
+
+/// 乱数生成器の現在の状態を複製したエージェントを作る．
+/// 複製したエージェントは，複製元がそれ以降に選ぶのと同じ順に行動を選択する．
+impl<R, G> Clone for RandomStrategy<R, G>
+where
+    G: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(
+            self.rng
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+        )
+    }
+}
+
+impl<R, G> fmt::Debug for RandomStrategy<R, G>
+where
+    G: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RandomStrategy")
+            .field("rng", &self.rng)
+            .finish()
+    }
+}
+
+impl<R> RandomStrategy<R, StdRng> {
+    /// 指定したシード値で初期化した乱数生成器を用いるエージェントを作る．
+    /// 同じシード値からは，常に同じ順に行動が選択される．
+    pub fn seeded(seed: u64) -> Self {
+        Self::new(StdRng::seed_from_u64(seed))
+    }
+}
+
+/// 行動を評価しないので，選択した行動の評価値は`()`である．
+impl<S, A, R, G> ScoredStrategy<S, A> for RandomStrategy<R, G>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    G: Rng,
+{
+    type Payoff = ();
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, ()>> {
+        let mut actions = R::iterate_available_actions(state, actor).collect::<Vec<_>>();
+        if actions.is_empty() {
+            return None;
+        }
+        let mut rng = self.rng.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = rng.gen_range(0, actions.len());
+        Some(ScoredAction::new(actions.swap_remove(index), ()))
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{Action, Rule};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::iter::Map;
+use core::slice;
+#[cfg(feature = "serde")]
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// 1回のゲームで実現した状態と行動の記録．
+///
+/// 各行動と，その行動をとる直前の状態を順に保持し，最後に行動をとった後の状態を保持する．
+/// `serde`フィーチャを有効にすると，1行に1つのJSONを並べた形式で読み書きできる．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord<S, A> {
+    /// 各行動と，その行動をとる直前の状態．
+    transitions: Vec<(S, A)>,
+    /// 最後の行動をとった後の状態．
+    terminal: S,
+}
+
+/// 記録した行動列を再現できなかった理由．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// 指定した番号の行動をとる前に，ゲームが終了していた．
+    GameAlreadyOver {
+        /// 最初の行動を0とする，行動の番号．
+        ply: usize,
+    },
+    /// 指定した番号の行動が，その直前の状態では実行できない．
+    UnavailableAction {
+        /// 最初の行動を0とする，行動の番号．
+        ply: usize,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::GameAlreadyOver { ply } => {
+                write!(f, "game is already over before action {}", ply)
+            }
+            ReplayError::UnavailableAction { ply } => write!(f, "action {} is not available", ply),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl<S, A> GameRecord<S, A> {
+    /// 指定した状態から始まり，まだ行動が記録されていない記録を作る．
+    pub fn new(initial: S) -> Self {
+        Self {
+            transitions: vec![],
+            terminal: initial,
+        }
+    }
+
+    /// 最後の状態で指定した行動をとり，指定した状態に遷移したことを記録する．
+    pub fn record(&mut self, action: A, next_state: S) {
+        let state = core::mem::replace(&mut self.terminal, next_state);
+        self.transitions.push((state, action));
+    }
+
+    /// 最初の状態を返す．
+    pub fn initial(&self) -> &S {
+        self.transitions
+            .first()
+            .map_or(&self.terminal, |(state, _)| state)
+    }
+
+    /// 最後の行動をとった後の状態を返す．
+    pub fn terminal(&self) -> &S {
+        &self.terminal
+    }
+
+    /// 記録された行動の数を返す．
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// 行動が記録されていないか．
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// 記録された行動を順に列挙する．
+    pub fn actions(&self) -> impl Iterator<Item = &A> {
+        self.transitions.iter().map(|(_, action)| action)
+    }
+
+    /// 各行動と，その行動をとる直前の状態を順に列挙する．
+    pub fn iter(&self) -> GameRecordIter<'_, S, A> {
+        self.into_iter()
+    }
+}
+
+impl<S, A> GameRecord<S, A>
+where
+    A: Action + PartialEq,
+{
+    /// 指定した状態から指定した行動を順にとり，その過程を記録する．
+    ///
+    /// 各行動は，直前の状態で`Rule::iterate_available_actions`が列挙するものでなければならない．
+    /// ゲームが終了した後に行動が続く場合や，実行できない行動が含まれる場合はエラーを返す．
+    pub fn replay<R, I>(initial: S, actions: I) -> Result<Self, ReplayError>
+    where
+        R: Rule<S = S, A = A>,
+        I: IntoIterator<Item = A>,
+    {
+        let mut record = Self::new(initial);
+        for (ply, action) in actions.into_iter().enumerate() {
+            let state = record.terminal();
+            if R::terminal_kind(state).is_some() {
+                return Err(ReplayError::GameAlreadyOver { ply });
+            }
+            if !R::iterate_available_actions(state, action.actor()).any(|a| a == action) {
+                return Err(ReplayError::UnavailableAction { ply });
+            }
+            let next_state = R::translate_state(state, &action);
+            record.record(action, next_state);
+        }
+        Ok(record)
+    }
+}
+
+/// `GameRecord`に記録された各行動と，その行動をとる直前の状態を順に列挙するイテレータ．
+pub type GameRecordIter<'a, S, A> = Map<slice::Iter<'a, (S, A)>, fn(&'a (S, A)) -> (&'a S, &'a A)>;
+
+impl<'a, S, A> IntoIterator for &'a GameRecord<S, A> {
+    type Item = (&'a S, &'a A);
+    type IntoIter = GameRecordIter<'a, S, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let split: fn(&'a (S, A)) -> (&'a S, &'a A) = |(state, action)| (state, action);
+        self.transitions.iter().map(split)
+    }
+}
+
+/// 記録を読み書きする際の1行分の内容．
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum RecordLine<S, A> {
+    /// 行動と，その行動をとる直前の状態．
+    Transition { state: S, action: A },
+    /// 最後の行動をとった後の状態．
+    Terminal { state: S },
+}
+
+#[cfg(feature = "serde")]
+impl<S, A> GameRecord<S, A>
+where
+    S: serde::Serialize,
+    A: serde::Serialize,
+{
+    /// 記録を，1行に1つのJSONを並べた形式で書き込む．
+    /// 各行動とその直前の状態を1行ずつ書き込み，最後の行に最後の状態を書き込む．
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (state, action) in self {
+            serde_json::to_writer(&mut *w, &RecordLine::Transition { state, action })?;
+            writeln!(w)?;
+        }
+        let terminal = RecordLine::<_, &A>::Terminal {
+            state: &self.terminal,
+        };
+        serde_json::to_writer(&mut *w, &terminal)?;
+        writeln!(w)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, A> GameRecord<S, A>
+where
+    S: serde::de::DeserializeOwned,
+    A: serde::de::DeserializeOwned,
+{
+    /// `write_to`で書き込まれた記録を読み込む．
+    /// 形式が正しくない場合は，`io::ErrorKind::InvalidData`のエラーを返す．
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut transitions = vec![];
+        let mut terminal = None;
+        for line in BufReader::new(r).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if terminal.is_some() {
+                return Err(invalid_data("record continues after terminal state"));
+            }
+            match serde_json::from_str(&line)? {
+                RecordLine::Transition { state, action } => transitions.push((state, action)),
+                RecordLine::Terminal { state } => terminal = Some(state),
+            }
+        }
+        match terminal {
+            Some(terminal) => Ok(Self {
+                transitions,
+                terminal,
+            }),
+            None => Err(invalid_data("record has no terminal state")),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
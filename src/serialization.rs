@@ -0,0 +1,91 @@
+use crate::{
+    AlphaBetaStrategy, Evaluator, MemoryBudget, MoveOrdering, PayoffBounds, Rule, SearchAlgorithm,
+};
+use num::Bounded;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// `AlphaBetaStrategy`の設定のうち，値として保存できるもの．
+///
+/// 置換表やHistory Heuristicの実績などの探索中に蓄積される情報と，
+/// 行動の並べ替えなどの関数による設定は含まない．
+#[derive(Serialize, Deserialize)]
+struct AlphaBetaConfig<N, P> {
+    search_depth: N,
+    /// 省略した場合，評価値の型の最小値と最大値を用いる．
+    #[serde(default)]
+    payoff_bounds: Option<PayoffBounds<P>>,
+    #[serde(default)]
+    node_budget: Option<u64>,
+    #[serde(default)]
+    quiescence_depth: Option<N>,
+    #[serde(default)]
+    search_algorithm: SearchAlgorithm,
+    #[serde(default)]
+    null_move_reduction: Option<N>,
+    #[serde(default)]
+    extension_budget: Option<u32>,
+    #[serde(default)]
+    max_line_extension: Option<u32>,
+    #[serde(default)]
+    batch_evaluation: bool,
+    #[serde(default)]
+    memory_budget: Option<MemoryBudget>,
+}
+
+impl<R, E, N, O> Serialize for AlphaBetaStrategy<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Clone + Serialize,
+    N: Copy + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        AlphaBetaConfig {
+            search_depth: self.search_depth,
+            payoff_bounds: Some(self.payoff_bounds.clone()),
+            node_budget: self.node_budget,
+            quiescence_depth: self.quiescence_depth,
+            search_algorithm: self.search_algorithm,
+            null_move_reduction: self.null_move_reduction,
+            extension_budget: self.extension_budget,
+            max_line_extension: self.max_line_extension,
+            batch_evaluation: self.batch_evaluation,
+            memory_budget: self.memory_budget,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, R, E, N, O> Deserialize<'de> for AlphaBetaStrategy<R, E, N, O>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+    E::Payoff: Bounded + Deserialize<'de>,
+    N: Deserialize<'de>,
+    O: MoveOrdering<R::S, R::A>,
+{
+    /// 保存された設定を持つエージェントを作る．
+    /// 保存されない設定は，`AlphaBetaStrategy::new`で作った場合と同じになる．
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = AlphaBetaConfig::deserialize(deserializer)?;
+        let strategy = AlphaBetaStrategy::new(config.search_depth).with_move_ordering();
+        Ok(AlphaBetaStrategy {
+            payoff_bounds: config.payoff_bounds.unwrap_or(strategy.payoff_bounds),
+            node_budget: config.node_budget,
+            quiescence_depth: config.quiescence_depth,
+            search_algorithm: config.search_algorithm,
+            null_move_reduction: config.null_move_reduction,
+            extension_budget: config.extension_budget,
+            max_line_extension: config.max_line_extension,
+            batch_evaluation: config.batch_evaluation,
+            memory_budget: config.memory_budget,
+            ..strategy
+        })
+    }
+}
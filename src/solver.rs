@@ -0,0 +1,182 @@
+use crate::{Actor, GameOutcome, Rule, TerminalKind};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+
+/// ゲームが終了した状態の勝敗を判定する関数．
+/// 評価関数とは異なり，ゲームが終了していない状態は評価しない．
+pub trait TerminalOutcome<S> {
+    /// 指定した状態がゲーム終了条件を満たしていれば，その結果を返す．
+    /// 満たしていない場合は`None`を返す．
+    fn outcome(state: &S) -> Option<GameOutcome>;
+}
+
+/// `Rule::terminal_kind`から勝敗を判定する`TerminalOutcome`．
+/// ステイルメイトは引き分けとして扱う．
+pub struct RuleOutcome<R>(PhantomData<fn() -> R>);
+
+impl<R> TerminalOutcome<R::S> for RuleOutcome<R>
+where
+    R: Rule,
+{
+    fn outcome(state: &R::S) -> Option<GameOutcome> {
+        R::terminal_kind(state).map(|kind| match kind {
+            TerminalKind::Win(winner) => GameOutcome::Win(winner),
+            TerminalKind::Draw | TerminalKind::NoMovesAvailable => GameOutcome::Draw,
+        })
+    }
+}
+
+/// ゲームを完全に解いた結果．
+pub struct Solution<A> {
+    /// 双方が最善を尽くした場合のゲームの結果．
+    outcome: GameOutcome,
+    /// 調べた各状態における最善の行動．キーは`policy_key`で計算する．
+    policy: HashMap<u64, A>,
+}
+
+impl<A> Solution<A> {
+    /// 双方が最善を尽くした場合のゲームの結果を返す．
+    pub fn outcome(&self) -> GameOutcome {
+        self.outcome
+    }
+
+    /// 調べた各状態における最善の行動を返す．キーは`policy_key`で計算する．
+    pub fn policy(&self) -> &HashMap<u64, A> {
+        &self.policy
+    }
+
+    /// 指定した状態で，指定したプレイヤーがとるべき最善の行動を返す．
+    /// 探索で調べなかった状態や，ゲームが終了した状態では`None`を返す．
+    pub fn best_action<S>(&self, state: &S, actor: Actor) -> Option<&A>
+    where
+        S: Hash,
+    {
+        self.policy.get(&policy_key(state, actor))
+    }
+
+    /// 最善の行動の一覧を取り出す．
+    pub fn into_policy(self) -> HashMap<u64, A> {
+        self.policy
+    }
+}
+
+/// `Solution::policy`のキーとして用いる，状態と手番のハッシュ値を返す．
+pub fn policy_key<S>(state: &S, actor: Actor) -> u64
+where
+    S: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    actor.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 指定したプレイヤーにとって，結果`outcome`が`other`より好ましいか．
+fn is_better(actor: Actor, outcome: GameOutcome, other: GameOutcome) -> bool {
+    let rank = |outcome| match outcome {
+        GameOutcome::Win(winner) if winner == actor => 2,
+        GameOutcome::Draw => 1,
+        GameOutcome::Win(_) => 0,
+    };
+    rank(outcome) > rank(other)
+}
+
+/// 探索中に使用する情報．
+struct Solver<S, A> {
+    /// 解いた状態と手番ごとの結果．
+    outcomes: HashMap<(S, Actor), GameOutcome>,
+    /// 最善の行動を記録する場合は，その一覧．
+    policy: Option<HashMap<u64, A>>,
+}
+
+impl<S, A> Solver<S, A>
+where
+    S: Clone + Eq + Hash,
+    A: Clone,
+{
+    /// 指定した状態で指定したプレイヤーが行動する場合の，ゲームの結果を再帰的に求める．
+    fn solve<R, O>(&mut self, state: &S, actor: Actor) -> GameOutcome
+    where
+        R: Rule<S = S, A = A>,
+        O: TerminalOutcome<S>,
+    {
+        if let Some(outcome) = O::outcome(state) {
+            return outcome;
+        }
+        let key = (state.clone(), actor);
+        if let Some(&outcome) = self.outcomes.get(&key) {
+            return outcome;
+        }
+
+        let mut best: Option<(GameOutcome, A)> = None;
+        for action in R::iterate_available_actions(state, actor) {
+            let next = R::translate_state(state, &action);
+            let outcome = self.solve::<R, O>(&next, actor.opponent());
+            if best
+                .as_ref()
+                .is_none_or(|&(best, _)| is_better(actor, outcome, best))
+            {
+                best = Some((outcome, action));
+            }
+            // 勝ちより良い結果はないので，残りの行動は調べない
+            if outcome == GameOutcome::Win(actor) {
+                break;
+            }
+        }
+
+        // 取れる行動がない状態は，引き分けとして扱う
+        let outcome = match best {
+            Some((outcome, action)) => {
+                if let Some(policy) = self.policy.as_mut() {
+                    policy.insert(policy_key(state, actor), action);
+                }
+                outcome
+            }
+            None => GameOutcome::Draw,
+        };
+        self.outcomes.insert(key, outcome);
+        outcome
+    }
+}
+
+/// 指定した状態から，双方が最善を尽くした場合のゲームの結果を求める．
+///
+/// ゲーム終了までのすべての分岐を調べるので，状態の数が少ないゲームにだけ用いる．
+/// 同じ状態と手番の組は一度だけ調べる．
+/// ゲームの結果は`O`で判定し，評価関数は用いない．
+/// ゲームが終了しておらず，取れる行動もない状態は引き分けとして扱う．
+pub fn solve<R, O>(initial_state: &R::S, actor: Actor) -> GameOutcome
+where
+    R: Rule,
+    R::S: Clone + Eq + Hash,
+    R::A: Clone,
+    O: TerminalOutcome<R::S>,
+{
+    let mut solver = Solver {
+        outcomes: HashMap::new(),
+        policy: None,
+    };
+    solver.solve::<R, O>(initial_state, actor)
+}
+
+/// `solve`と同様にゲームを解き，調べた各状態における最善の行動とともに返す．
+/// 最善の行動をたどることで，最善の手順を再現できる．
+pub fn solve_with_policy<R, O>(initial_state: &R::S, actor: Actor) -> Solution<R::A>
+where
+    R: Rule,
+    R::S: Clone + Eq + Hash,
+    R::A: Clone,
+    O: TerminalOutcome<R::S>,
+{
+    let mut solver = Solver {
+        outcomes: HashMap::new(),
+        policy: Some(HashMap::new()),
+    };
+    let outcome = solver.solve::<R, O>(initial_state, actor);
+    Solution {
+        outcome,
+        policy: solver.policy.unwrap_or_default(),
+    }
+}
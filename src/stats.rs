@@ -0,0 +1,85 @@
+use crate::MemoryUsage;
+use core::time::Duration;
+
+/// 探索の統計情報．
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchStats {
+    /// 訪れたノードの数．
+    pub nodes_evaluated: u64,
+    /// 相手の手番のノードで起こった枝刈りの回数．
+    pub alpha_cutoffs: u64,
+    /// 自分の手番のノードで起こった枝刈りの回数．
+    pub beta_cutoffs: u64,
+    /// キラー手が枝刈りを起こした回数．
+    pub killer_hits: u64,
+    /// Null Move Pruningにより，ノードの探索を省略した回数．
+    pub null_move_cutoffs: u64,
+    /// Futility Pruningにより，調べなかった行動の数．
+    pub futility_prunes: u64,
+    /// Razoringにより，浅い探索だけで評価値を決めたノードの数．
+    pub razored_nodes: u64,
+    /// 探索を延長した子ノードの数．
+    pub extensions: u64,
+    /// 内部反復深化により，最初に調べる行動を見つけるための浅い探索を行った回数．
+    pub iid_searches: u64,
+    /// 主要変化探索において，幅0の窓での探索の後に探索し直した回数．
+    /// 行動の並びが良いほど少なくなる．
+    pub re_searches: u64,
+    /// Best Node Searchにおいて，子ノードを幅0の窓で探索した回数．
+    pub zero_window_searches: u64,
+    /// Late Move Reductionsにより浅く探索した子ノードが有望だったために，通常の深さで探索し直した回数．
+    pub late_move_re_searches: u64,
+    /// アスピレーションウィンドウによる探索で，評価値が窓の上限を上回ったために探索し直した回数．
+    pub aspiration_fail_highs: u64,
+    /// アスピレーションウィンドウによる探索で，評価値が窓の下限を下回ったために探索し直した回数．
+    pub aspiration_fail_lows: u64,
+    /// メモリ上に保持するノードの数を抑えるために，破棄したノードの数．
+    pub nodes_evicted: u64,
+    /// 探索中にメモリ上に同時に保持したノードの数の最大値．
+    /// ゲーム木を保持しない探索では0となる．
+    pub peak_nodes_retained: u64,
+    /// 根ノードから最も深く探索したノードまでの深さ．
+    pub max_depth_reached: u32,
+    /// 選択した行動の評価値が勝ちまたは負けである場合，その勝敗が決まる末端ノードまでの手数．
+    /// `ScoredAction::depth_to_terminal`と同じ値である．
+    pub depth_to_terminal: Option<u32>,
+    /// メモリ使用量の上限を指定した場合に，上限に収めた後の置換表と各表のメモリ使用量の最大値 (バイト数)．
+    /// 上限を指定しない場合は0となる．
+    pub peak_memory_bytes: u64,
+    /// メモリ使用量の上限を指定した場合に，上限に収めた後の使用量の構造ごとの最大値．
+    /// 各構造の最大値は異なる時点のものでありうるので，合計は`peak_memory_bytes`を超えることがある．
+    /// 上限を指定しない場合はすべて0となる．
+    pub peak_memory_usage: MemoryUsage,
+    /// 訪れたノード数が上限に達し，探索を打ち切ったか．
+    pub node_budget_exhausted: bool,
+    /// 探索にかかった時間．
+    pub elapsed: Duration,
+}
+
+impl SearchStats {
+    /// 別の探索の統計情報を合算する．
+    /// 探索にかかった時間は合算しない．
+    pub(crate) fn merge(&mut self, other: &SearchStats) {
+        self.nodes_evaluated += other.nodes_evaluated;
+        self.alpha_cutoffs += other.alpha_cutoffs;
+        self.beta_cutoffs += other.beta_cutoffs;
+        self.killer_hits += other.killer_hits;
+        self.null_move_cutoffs += other.null_move_cutoffs;
+        self.futility_prunes += other.futility_prunes;
+        self.razored_nodes += other.razored_nodes;
+        self.extensions += other.extensions;
+        self.iid_searches += other.iid_searches;
+        self.re_searches += other.re_searches;
+        self.zero_window_searches += other.zero_window_searches;
+        self.late_move_re_searches += other.late_move_re_searches;
+        self.aspiration_fail_highs += other.aspiration_fail_highs;
+        self.aspiration_fail_lows += other.aspiration_fail_lows;
+        self.nodes_evicted += other.nodes_evicted;
+        self.peak_nodes_retained = self.peak_nodes_retained.max(other.peak_nodes_retained);
+        self.max_depth_reached = self.max_depth_reached.max(other.max_depth_reached);
+        self.peak_memory_bytes = self.peak_memory_bytes.max(other.peak_memory_bytes);
+        self.peak_memory_usage = self.peak_memory_usage.peak(&other.peak_memory_usage);
+        self.node_budget_exhausted |= other.node_budget_exhausted;
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Actor, Evaluator};
+use core::marker::PhantomData;
+use core::ops::Neg;
+
+/// 先手にとっての利得だけを定める評価関数．
+///
+/// `SymmetricEvaluator`と組み合わせると，後手にとっての利得は先手の利得の符号を反転させたものとなる．
+pub trait FirstActorEvaluator<S> {
+    /// 先手の利得を表す型．
+    type Payoff;
+
+    /// 指定された状態について，先手の利得を評価する．
+    fn evaluate_for_first(state: &S) -> Self::Payoff;
+}
+
+/// 先手の利得を評価する`F`から，2人零和ゲームの評価関数を作る．
+///
+/// 先手には`F::evaluate_for_first`の値を，後手にはその符号を反転させた値を返す．
+/// 値を持たない型なので，探索するエージェントの型引数として`SymmetricEvaluator<F>`を指定する．
+pub struct SymmetricEvaluator<F> {
+    _f: PhantomData<F>,
+}
+
+impl<S, F> Evaluator<S> for SymmetricEvaluator<F>
+where
+    F: FirstActorEvaluator<S>,
+    F::Payoff: Neg<Output = F::Payoff>,
+{
+    type Payoff = F::Payoff;
+
+    fn evaluate_payoff_for(actor: Actor, state: &S) -> Self::Payoff {
+        match actor {
+            Actor::First => F::evaluate_for_first(state),
+            Actor::Second => -F::evaluate_for_first(state),
+        }
+    }
+}
+
+/// クロージャのような記法で，先手の利得を評価する式から2人零和ゲームの評価関数を宣言する．
+///
+/// 宣言したユニット構造体は`FirstActorEvaluator`を実装し，`Evaluator`としては`SymmetricEvaluator`と同じ値を返す．
+/// `closure_evaluator!`と同様，式から外側の変数を参照することはできない．
+///
+/// # Examples
+/// ```
+/// use minimax_strategy::{symmetric_evaluator, Actor, Evaluator};
+///
+/// symmetric_evaluator!(struct CountEvaluator: u32 => i64 = |state| *state as i64);
+///
+/// assert_eq!(3, CountEvaluator::evaluate_payoff_for(Actor::First, &3));
+/// assert_eq!(-3, CountEvaluator::evaluate_payoff_for(Actor::Second, &3));
+/// ```
+#[macro_export]
+macro_rules! symmetric_evaluator {
+    ($vis:vis struct $name:ident: $state:ty => $payoff:ty = |$state_pattern:pat| $body:expr) => {
+        $vis struct $name;
+
+        impl $crate::FirstActorEvaluator<$state> for $name {
+            type Payoff = $payoff;
+
+            fn evaluate_for_first($state_pattern: &$state) -> $payoff {
+                $body
+            }
+        }
+
+        impl $crate::Evaluator<$state> for $name {
+            type Payoff = $payoff;
+
+            fn evaluate_payoff_for(actor: $crate::Actor, state: &$state) -> $payoff {
+                <$crate::SymmetricEvaluator<$name> as $crate::Evaluator<$state>>::evaluate_payoff_for(
+                    actor, state,
+                )
+            }
+        }
+    };
+}
@@ -0,0 +1,64 @@
+use crate::{Rule, StateHasher};
+use core::marker::PhantomData;
+
+/// 盤面の回転や反転などで互いに移り合う状態のうち，辞書順で最小の状態．
+///
+/// 対称な状態はすべて同じ`CanonicalState`になるので，対称な状態を1つの状態として扱える．
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalState<S>(pub S);
+
+impl<S> CanonicalState<S>
+where
+    S: Clone + Ord,
+{
+    /// 指定した状態と，`Rule::symmetry_equivalents`が列挙する状態のうち，辞書順で最小のものを選ぶ．
+    pub fn new<R>(state: &S) -> Self
+    where
+        R: Rule<S = S>,
+    {
+        let smallest = R::symmetry_equivalents(state)
+            .filter(|equivalent| equivalent < state)
+            .min()
+            .unwrap_or_else(|| state.clone());
+        Self(smallest)
+    }
+}
+
+impl<S> CanonicalState<S> {
+    /// 選ばれた状態を取り出す．
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+/// 状態を`CanonicalState`に置き換えてからハッシュ値を計算する．
+///
+/// 置換表にこのハッシュ値の計算方法を指定すると，対称な状態の探索結果が1つの記録として共有される．
+/// 対称な状態は，同じプレイヤーの手番では同じ評価値を持たなければならない．
+/// 記録された最善の行動の番号は，対称な状態では別の行動を指すことがあるが，行動を調べる順序にしか影響しない．
+pub struct SymmetricStateHasher<R, H> {
+    inner: H,
+    _r: PhantomData<fn() -> R>,
+}
+
+impl<R, H> SymmetricStateHasher<R, H> {
+    /// 正規化した状態のハッシュ値を，指定した方法で計算するようにする．
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            _r: PhantomData,
+        }
+    }
+}
+
+impl<R, H> StateHasher<R::S> for SymmetricStateHasher<R, H>
+where
+    R: Rule,
+    R::S: Clone + Ord,
+    H: StateHasher<R::S>,
+{
+    fn hash_state(&self, state: &R::S) -> u64 {
+        let canonical = CanonicalState::new::<R>(state);
+        self.inner.hash_state(&canonical.0)
+    }
+}
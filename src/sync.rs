@@ -0,0 +1,60 @@
+#[cfg(not(feature = "std"))]
+use core::cell::{RefCell, RefMut};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// 行動選択をまたいで書き換える値．
+///
+/// `std`フィーチャが有効な場合は`Mutex`で保護し，エージェントをスレッド間で共有できるようにする．
+/// 無効な場合は`RefCell`で保護する．
+pub(crate) struct Lock<T> {
+    #[cfg(feature = "std")]
+    inner: Mutex<T>,
+    #[cfg(not(feature = "std"))]
+    inner: RefCell<T>,
+}
+
+/// `Lock`で保護された値への排他的な参照．
+#[cfg(feature = "std")]
+pub(crate) type LockGuard<'a, T> = MutexGuard<'a, T>;
+/// `Lock`で保護された値への排他的な参照．
+#[cfg(not(feature = "std"))]
+pub(crate) type LockGuard<'a, T> = RefMut<'a, T>;
+
+impl<T> Lock<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            inner: Mutex::new(value),
+            #[cfg(not(feature = "std"))]
+            inner: RefCell::new(value),
+        }
+    }
+
+    /// 値への排他的な参照を得る．
+    /// 他のスレッドが値を書き換える途中でパニックした場合も，その値をそのまま返す．
+    #[cfg(feature = "std")]
+    pub(crate) fn lock(&self) -> LockGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 値への排他的な参照を得る．
+    /// # Panics
+    /// 既に参照が得られている場合
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn lock(&self) -> LockGuard<'_, T> {
+        self.inner.borrow_mut()
+    }
+}
+
+/// 保護された値を複製した，新しい`Lock`を作る．
+/// # Panics
+/// `std`フィーチャが無効で，既に参照が得られている場合
+impl<T> Clone for Lock<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.lock().clone())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::iterative_deepening::deepen;
+use crate::{
+    Action, Actor, Evaluator, IterativeDeepeningStrategy, Rule, ScoredAction, ScoredStrategy,
+    SearchDepth, State,
+};
+use num::Bounded;
+use std::time::{Duration, Instant};
+
+/// 制限時間内で探索深さを増やしながら，αβ法で思考するエージェント．
+///
+/// 各深さの探索を終えるたびに経過時間を調べ，次の深さの探索が制限時間内に終わらないと見込まれる場合は探索をやめる．
+/// 探索中に制限時間を過ぎた場合も探索を打ち切り，それまでに探索を終えた行動のうち最善のものを選択する．
+/// 勝敗が決まる手順を読み切った場合や，深さを増やしても訪れるノード数が増えなかった場合は，
+/// それ以上深く探索しても結果が変わらないので，制限時間を残して探索をやめる．
+pub struct TimeLimitedStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 1回の行動選択に使ってよい時間．
+    budget: Duration,
+    inner: IterativeDeepeningStrategy<R, E, N>,
+}
+
+impl<R, E, N> TimeLimitedStrategy<R, E, N>
+where
+    R: Rule,
+    E: Evaluator<R::S>,
+{
+    /// 指定した制限時間内で思考するエージェントを作る．
+    /// 探索深さは，`N`で表現できる最大値まで増やされうる．
+    pub fn new(budget: Duration) -> Self
+    where
+        E::Payoff: Bounded,
+        N: Bounded,
+    {
+        Self {
+            budget,
+            inner: IterativeDeepeningStrategy::new(N::max_value()),
+        }
+    }
+
+    /// 1回の行動選択に使ってよい時間を返す．
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for TimeLimitedStrategy<R, E, N>
+where
+    S: State,
+    A: Action,
+    R: Rule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        let start = Instant::now();
+        let mut last_depth_start = start;
+        let mut last_nodes_evaluated = None;
+        deepen(
+            &self.inner.alpha_beta,
+            state,
+            actor,
+            Some(start + self.budget),
+            self.inner.aspiration.as_ref(),
+            None,
+            |last| {
+                // ゲーム木を末端まで読み切っていれば，探索を深くしても同じ木を探索し直すだけである
+                if last.completed {
+                    if last.depth_to_terminal.is_some()
+                        || last_nodes_evaluated == Some(last.nodes_evaluated)
+                    {
+                        return false;
+                    }
+                    last_nodes_evaluated = Some(last.nodes_evaluated);
+                }
+                // 次の深さの探索には，少なくとも直前の深さの探索と同じだけの時間がかかると見込む
+                let now = Instant::now();
+                let last_depth_elapsed = now - last_depth_start;
+                last_depth_start = now;
+                now - start + last_depth_elapsed <= self.budget
+            },
+        )
+        .into_scored_action()
+    }
+}
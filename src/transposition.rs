@@ -0,0 +1,849 @@
+use crate::sync::Lock;
+use crate::Actor;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::BuildHasherDefault;
+use core::hash::{BuildHasher, Hash};
+use core::mem;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "rayon")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// ゲームの状態からハッシュ値を計算する．
+///
+/// `BuildHasher`を実装する型は，`Hash`を実装する状態に対してこのトレイトを自動的に実装する．
+/// Zobristハッシュのように，ゲームに特化したハッシュ値を使う場合はこのトレイトを直接実装する．
+pub trait StateHasher<S> {
+    /// 指定した状態のハッシュ値を返す．
+    fn hash_state(&self, state: &S) -> u64;
+}
+
+impl<S, H> StateHasher<S> for H
+where
+    S: Hash,
+    H: BuildHasher,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        self.hash_one(state)
+    }
+}
+
+/// 置換表に記録された評価値が，真の評価値に対してどのような値であるか．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoundType {
+    /// 記録された値は真の評価値そのものである．
+    Exact,
+    /// 真の評価値は記録された値以上である．
+    LowerBound,
+    /// 真の評価値は記録された値以下である．
+    UpperBound,
+}
+
+/// 置換表に記録される，ある状態の探索結果．
+/// `TranspositionStore`を実装する置換表は，この値を記録し，読み出す．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranspositionEntry<P, N> {
+    /// 状態のハッシュ値．
+    pub key: u64,
+    /// 評価値を計算する対象のプレイヤー．
+    pub consideration_target: Actor,
+    /// この状態で行動するプレイヤー．
+    pub next_actor: Actor,
+    /// この状態から探索したゲーム木の深さ．
+    pub depth: N,
+    /// 探索で得られた評価値．
+    pub payoff: P,
+    /// 評価値の種類．
+    pub bound: BoundType,
+    /// 最善と判断された行動が，`MoveOrderer`で並べ替えた行動の何番目か．
+    pub best_action_index: Option<usize>,
+}
+
+/// 置換表に記録された探索結果と，それを記録した行動選択の世代．
+#[derive(Debug, Clone, Copy)]
+struct StoredEntry<P, N> {
+    entry: TranspositionEntry<P, N>,
+    generation: u32,
+}
+
+/// 置換表の記録先にすでに別の状態が記録されている場合に，どちらの探索結果を残すかの方針．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReplacementScheme {
+    /// 常に新しい探索結果を残す．
+    AlwaysReplace,
+    /// より深く探索した結果を残す．
+    /// ただし，以前の行動選択で記録された結果は深さによらず置き換える．
+    #[default]
+    DepthPreferred,
+    /// 記録先を2つずつの組にまとめ，一方には`DepthPreferred`と同じ方針で，もう一方には常に新しい探索結果を記録する．
+    /// 深く探索した結果を残しつつ，直近の探索結果も利用できる．
+    TwoTier,
+}
+
+impl ReplacementScheme {
+    /// 1つの状態を記録しうる記録先の数．
+    fn ways(self) -> usize {
+        match self {
+            ReplacementScheme::AlwaysReplace | ReplacementScheme::DepthPreferred => 1,
+            ReplacementScheme::TwoTier => 2,
+        }
+    }
+}
+
+/// 置換表の使用状況．
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TranspositionTableStats {
+    /// 記録できる状態の数．
+    pub capacity: usize,
+    /// 記録されている状態の数．
+    pub occupied: usize,
+    /// 記録を調べた回数．
+    pub probes: u64,
+    /// 調べた状態の記録が見つかった回数．
+    pub hits: u64,
+}
+
+impl TranspositionTableStats {
+    /// 記録できる状態の数に対する，記録されている状態の数の割合を返す．
+    pub fn occupancy(&self) -> f64 {
+        self.occupied as f64 / self.capacity as f64
+    }
+
+    /// 記録を調べた回数に対する，記録が見つかった回数の割合を返す．
+    /// 記録を調べていない場合は0を返す．
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.probes as f64
+        }
+    }
+}
+
+/// 探索済みの状態の評価値を記録する置換表が実装するトレイト．
+///
+/// αβ法による探索は，このトレイトを通して置換表を読み書きする．
+/// 複数のスレッドから共有する置換表は，共有参照`&T`に対してもこのトレイトを実装する．
+/// `ParallelAlphaBetaStrategy`と`LazySmpStrategy`は，共有参照を通して置換表を読み書きする．
+pub trait TranspositionStore<S, P, N> {
+    /// 指定した状態のハッシュ値を返す．
+    fn hash_state(&self, state: &S) -> u64;
+
+    /// 指定した状態の記録を返す．
+    /// 評価値を計算する対象のプレイヤーと，状態で行動するプレイヤーが一致する記録だけを返す．
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>>;
+
+    /// 探索結果を記録する．
+    /// 記録先にすでに別の状態が記録されている場合に，どちらを残すかは置換表が決める．
+    fn store(&mut self, entry: TranspositionEntry<P, N>);
+
+    /// 記録のために確保しているメモリのバイト数を返す．
+    /// `MemoryBudget`の使用量として数える．既定では0を返す．
+    fn memory_bytes(&self) -> usize {
+        0
+    }
+
+    /// 容量を減らし，確保していたメモリを解放する．
+    /// `MemoryBudget`の上限を超えた場合に呼び出される．
+    /// 容量をこれ以上減らせない場合は`false`を返す．既定では容量を減らさず，`false`を返す．
+    fn shrink(&mut self) -> bool {
+        false
+    }
+}
+
+impl<S, P, N, T> TranspositionStore<S, P, N> for &mut T
+where
+    T: TranspositionStore<S, P, N> + ?Sized,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        (**self).hash_state(state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        (**self).probe(key, consideration_target, next_actor)
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        (**self).store(entry)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        (**self).memory_bytes()
+    }
+
+    fn shrink(&mut self) -> bool {
+        (**self).shrink()
+    }
+}
+
+/// 複数の行動選択から共有される置換表．
+pub(crate) type SharedTranspositionTable<S, P, N> = Lock<TranspositionTable<S, P, N>>;
+
+/// 探索済みの状態の評価値を記録する置換表．
+///
+/// 容量は作成時に固定され，ハッシュ値が衝突した場合は`ReplacementScheme`に従ってどちらの探索結果を残すか決める．
+/// 既定では，より深く探索した結果を優先して残す．
+/// 行動選択ごとに世代を進めるので，以前の行動選択で記録された結果は置き換えられやすい．
+pub struct TranspositionTable<S, P, N> {
+    entries: Vec<Option<StoredEntry<P, N>>>,
+    hasher: Arc<dyn StateHasher<S> + Send + Sync>,
+    scheme: ReplacementScheme,
+    /// 現在の行動選択の世代．
+    generation: u32,
+    /// 記録を調べた回数．
+    probes: u64,
+    /// 調べた状態の記録が見つかった回数．
+    hits: u64,
+}
+
+/// 記録を複製した置換表を作る．ハッシュ値の計算方法は複製元と共有する．
+impl<S, P, N> Clone for TranspositionTable<S, P, N>
+where
+    P: Clone,
+    N: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            hasher: Arc::clone(&self.hasher),
+            scheme: self.scheme,
+            generation: self.generation,
+            probes: self.probes,
+            hits: self.hits,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, P, N> TranspositionTable<S, P, N>
+where
+    S: Hash,
+{
+    /// 指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, BuildHasherDefault::<DefaultHasher>::default())
+    }
+}
+
+impl<S, P, N> TranspositionTable<S, P, N> {
+    /// 状態のハッシュ値の計算方法を指定して，指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_hasher<H>(capacity: usize, hasher: H) -> Self
+    where
+        H: StateHasher<S> + Send + Sync + 'static,
+    {
+        assert!(
+            capacity > 0,
+            "capacity of transposition table must be positive"
+        );
+        let mut entries = Vec::with_capacity(capacity);
+        entries.resize_with(capacity, || None);
+        Self {
+            entries,
+            hasher: Arc::new(hasher),
+            scheme: ReplacementScheme::default(),
+            generation: 0,
+            probes: 0,
+            hits: 0,
+        }
+    }
+
+    /// 記録先に別の状態が記録されている場合に，どちらの探索結果を残すかの方針を指定する．
+    /// `ReplacementScheme::TwoTier`を指定した場合，容量は偶数に切り上げられる．
+    pub fn with_replacement_scheme(mut self, scheme: ReplacementScheme) -> Self {
+        let ways = scheme.ways();
+        let capacity = self.entries.len().div_ceil(ways) * ways;
+        self.entries.resize_with(capacity, || None);
+        self.scheme = scheme;
+        self
+    }
+
+    /// 記録先に別の状態が記録されている場合に，どちらの探索結果を残すかの方針を返す．
+    pub fn replacement_scheme(&self) -> ReplacementScheme {
+        self.scheme
+    }
+
+    /// 記録できる状態の数を返す．
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 記録されている状態の数を返す．
+    pub fn len(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.is_some()).count()
+    }
+
+    /// 記録されている状態がないか．
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|entry| entry.is_none())
+    }
+
+    /// 記録をすべて消去する．
+    /// 記録を調べた回数などの統計情報は消去しない．
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    /// 記録のために確保しているメモリのバイト数を返す．
+    /// 記録の有無によらず，容量に比例する．
+    pub fn memory_bytes(&self) -> usize {
+        self.entries.capacity() * mem::size_of::<Option<StoredEntry<P, N>>>()
+    }
+
+    /// 置換表の使用状況を返す．
+    pub fn stats(&self) -> TranspositionTableStats {
+        TranspositionTableStats {
+            capacity: self.capacity(),
+            occupied: self.len(),
+            probes: self.probes,
+            hits: self.hits,
+        }
+    }
+
+    /// 世代を進める．
+    /// 以降の探索結果を記録する際は，それまでに記録された結果を以前の行動選択のものとして扱う．
+    pub(crate) fn advance_generation(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// 指定したハッシュ値の状態を記録しうる記録先の範囲を返す．
+    fn bucket_of(&self, key: u64) -> Range<usize> {
+        let ways = self.scheme.ways();
+        let buckets = (self.entries.len() / ways) as u64;
+        let start = (key % buckets) as usize * ways;
+        start..start + ways
+    }
+}
+
+impl<S, P, N> TranspositionStore<S, P, N> for TranspositionTable<S, P, N>
+where
+    P: Copy,
+    N: Copy + Ord,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        self.hasher.hash_state(state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        self.probes += 1;
+        let bucket = self.bucket_of(key);
+        let found = self.entries[bucket]
+            .iter()
+            .flatten()
+            .map(|stored| stored.entry)
+            .find(|entry| {
+                entry.key == key
+                    && entry.consideration_target == consideration_target
+                    && entry.next_actor == next_actor
+            });
+        if found.is_some() {
+            self.hits += 1;
+        }
+        found
+    }
+
+    /// 記録先にすでに別の状態が記録されている場合，`ReplacementScheme`に従ってどちらを残すか決める．
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        let generation = self.generation;
+        let bucket = self.bucket_of(entry.key);
+        let slots = &mut self.entries[bucket];
+        let key = entry.key;
+        let stored = StoredEntry { entry, generation };
+        match self.scheme {
+            ReplacementScheme::AlwaysReplace => slots[0] = Some(stored),
+            ReplacementScheme::DepthPreferred => {
+                if prefers_new_entry(&slots[0], &stored.entry, generation) {
+                    slots[0] = Some(stored);
+                }
+            }
+            ReplacementScheme::TwoTier => {
+                let (deep, recent) = slots.split_at_mut(1);
+                let (deep, recent) = (&mut deep[0], &mut recent[0]);
+                if prefers_new_entry(deep, &stored.entry, generation) {
+                    // 深さを優先する記録先から押し出された別の状態は，常に上書きする記録先に移す．
+                    // 同じ状態の古い記録は残さない
+                    match deep.replace(stored) {
+                        Some(displaced) if displaced.entry.key != key => *recent = Some(displaced),
+                        _ => {
+                            if recent
+                                .as_ref()
+                                .is_some_and(|recent| recent.entry.key == key)
+                            {
+                                *recent = None;
+                            }
+                        }
+                    }
+                } else {
+                    *recent = Some(stored);
+                }
+            }
+        }
+    }
+
+    fn memory_bytes(&self) -> usize {
+        TranspositionTable::memory_bytes(self)
+    }
+
+    /// 容量を半分に減らす．
+    /// 記録は元の記録先の順に記録し直し，記録先が埋まっている場合は現在の世代のより深く探索した結果を残す．
+    fn shrink(&mut self) -> bool {
+        let ways = self.scheme.ways();
+        let capacity = self.entries.len() / 2 / ways * ways;
+        if capacity == 0 {
+            return false;
+        }
+        let mut entries = Vec::with_capacity(capacity);
+        entries.resize_with(capacity, || None);
+        let previous = mem::replace(&mut self.entries, entries);
+        let generation = self.generation;
+        for stored in previous.into_iter().flatten() {
+            let bucket = self.bucket_of(stored.entry.key);
+            let slots = &mut self.entries[bucket];
+            if let Some(vacant) = slots.iter_mut().find(|slot| slot.is_none()) {
+                *vacant = Some(stored);
+            } else if slots[0].as_ref().is_some_and(|current| {
+                if current.generation == stored.generation {
+                    current.entry.depth < stored.entry.depth
+                } else {
+                    stored.generation == generation
+                }
+            }) {
+                slots[0] = Some(stored);
+            }
+        }
+        true
+    }
+}
+
+/// 深さを優先する方針で，記録先の探索結果を新しい探索結果で置き換えるか判定する．
+/// 記録先が空の場合や，同じ状態，以前の行動選択の結果が記録されている場合は置き換える．
+fn prefers_new_entry<P, N>(
+    slot: &Option<StoredEntry<P, N>>,
+    entry: &TranspositionEntry<P, N>,
+    generation: u32,
+) -> bool
+where
+    N: Ord,
+{
+    match slot {
+        Some(current) => current.generation != generation || deeper_or_same(&current.entry, entry),
+        None => true,
+    }
+}
+
+/// 記録されている探索結果と新しい探索結果が同じ状態のものであるか，新しい探索結果の方が深く探索したものであるか．
+pub(crate) fn deeper_or_same<P, N>(
+    current: &TranspositionEntry<P, N>,
+    entry: &TranspositionEntry<P, N>,
+) -> bool
+where
+    N: Ord,
+{
+    current.key == entry.key || current.depth <= entry.depth
+}
+
+/// 複数のスレッドから同時に読み書きできる置換表．
+///
+/// 記録先ごとに排他制御するので，異なる記録先への読み書きは互いを待たない．
+/// 容量は作成時に固定され，ハッシュ値が衝突した場合はより深く探索した結果を優先して残す．
+/// `ParallelAlphaBetaStrategy`とともに，`rayon`フィーチャが有効な場合にだけ使える．
+#[cfg(feature = "rayon")]
+pub struct ConcurrentTranspositionTable<S, P, N> {
+    entries: Vec<Mutex<Option<TranspositionEntry<P, N>>>>,
+    hasher: Arc<dyn StateHasher<S> + Send + Sync>,
+}
+
+#[cfg(feature = "rayon")]
+impl<S, P, N> ConcurrentTranspositionTable<S, P, N>
+where
+    S: Hash,
+{
+    /// 指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, BuildHasherDefault::<DefaultHasher>::default())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<S, P, N> ConcurrentTranspositionTable<S, P, N> {
+    /// 状態のハッシュ値の計算方法を指定して，指定した数の状態を記録できる置換表を作る．
+    /// # Panics
+    /// `capacity`が0の場合
+    pub fn with_hasher<H>(capacity: usize, hasher: H) -> Self
+    where
+        H: StateHasher<S> + Send + Sync + 'static,
+    {
+        assert!(
+            capacity > 0,
+            "capacity of transposition table must be positive"
+        );
+        let mut entries = Vec::with_capacity(capacity);
+        entries.resize_with(capacity, || Mutex::new(None));
+        Self {
+            entries,
+            hasher: Arc::new(hasher),
+        }
+    }
+
+    /// 記録できる状態の数を返す．
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 記録されている状態の数を返す．
+    pub fn len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| Self::lock(entry).is_some())
+            .count()
+    }
+
+    /// 記録されている状態がないか．
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|entry| Self::lock(entry).is_none())
+    }
+
+    /// 記録をすべて消去する．
+    pub fn clear(&self) {
+        self.entries
+            .iter()
+            .for_each(|entry| *Self::lock(entry) = None);
+    }
+
+    fn slot(&self, key: u64) -> MutexGuard<'_, Option<TranspositionEntry<P, N>>> {
+        Self::lock(&self.entries[(key % self.entries.len() as u64) as usize])
+    }
+
+    fn lock(
+        entry: &Mutex<Option<TranspositionEntry<P, N>>>,
+    ) -> MutexGuard<'_, Option<TranspositionEntry<P, N>>> {
+        entry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<S, P, N> ConcurrentTranspositionTable<S, P, N>
+where
+    P: Copy,
+    N: Copy + Ord,
+{
+    /// 指定した状態のハッシュ値を返す．
+    pub(crate) fn hash_state(&self, state: &S) -> u64 {
+        self.hasher.hash_state(state)
+    }
+
+    /// 指定した状態の記録を返す．
+    pub(crate) fn probe(
+        &self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        (*self.slot(key)).filter(|entry| {
+            entry.key == key
+                && entry.consideration_target == consideration_target
+                && entry.next_actor == next_actor
+        })
+    }
+
+    /// 探索結果を記録する．
+    /// 記録先にすでに別の状態が記録されている場合，より深く探索した結果を残す．
+    pub(crate) fn store(&self, entry: TranspositionEntry<P, N>) {
+        let mut slot = self.slot(entry.key);
+        if slot
+            .as_ref()
+            .is_none_or(|current| deeper_or_same(current, &entry))
+        {
+            *slot = Some(entry);
+        }
+    }
+
+    /// 記録のために確保しているメモリのバイト数を返す．
+    fn memory_bytes(&self) -> usize {
+        self.entries.capacity() * mem::size_of::<Mutex<Option<TranspositionEntry<P, N>>>>()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<S, P, N> TranspositionStore<S, P, N> for ConcurrentTranspositionTable<S, P, N>
+where
+    P: Copy,
+    N: Copy + Ord,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        ConcurrentTranspositionTable::hash_state(self, state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        ConcurrentTranspositionTable::probe(self, key, consideration_target, next_actor)
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        ConcurrentTranspositionTable::store(self, entry)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        ConcurrentTranspositionTable::memory_bytes(self)
+    }
+}
+
+/// スレッド間で共有したまま読み書きする．
+#[cfg(feature = "rayon")]
+impl<S, P, N> TranspositionStore<S, P, N> for &ConcurrentTranspositionTable<S, P, N>
+where
+    P: Copy,
+    N: Copy + Ord,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        ConcurrentTranspositionTable::hash_state(self, state)
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<P, N>> {
+        ConcurrentTranspositionTable::probe(self, key, consideration_target, next_actor)
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<P, N>) {
+        ConcurrentTranspositionTable::store(self, entry)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        ConcurrentTranspositionTable::memory_bytes(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn entry(key: u64, depth: u32, payoff: i32) -> TranspositionEntry<i32, u32> {
+        TranspositionEntry {
+            key,
+            consideration_target: Actor::First,
+            next_actor: Actor::First,
+            depth,
+            payoff,
+            bound: BoundType::Exact,
+            best_action_index: None,
+        }
+    }
+
+    #[test]
+    fn test_store_and_probe() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(8);
+        assert!(table.is_empty());
+
+        table.store(entry(3, 2, 10));
+        assert_eq!(1, table.len());
+        assert_eq!(
+            Some(10),
+            table
+                .probe(3, Actor::First, Actor::First)
+                .map(|entry| entry.payoff)
+        );
+        assert!(table.probe(3, Actor::Second, Actor::First).is_none());
+        assert!(table.probe(11, Actor::First, Actor::First).is_none());
+    }
+
+    #[test]
+    fn test_depth_preferred_replacement() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(8);
+        table.store(entry(3, 4, 10));
+        // 同じ位置に記録される，より浅い探索結果は記録されない
+        table.store(entry(11, 2, 20));
+        assert!(table.probe(11, Actor::First, Actor::First).is_none());
+        // より深い探索結果は記録される
+        table.store(entry(11, 5, 30));
+        assert_eq!(
+            Some(30),
+            table
+                .probe(11, Actor::First, Actor::First)
+                .map(|entry| entry.payoff)
+        );
+        // 同じ状態の記録は，深さによらず更新される
+        table.store(entry(11, 1, 40));
+        assert_eq!(
+            Some(40),
+            table
+                .probe(11, Actor::First, Actor::First)
+                .map(|entry| entry.payoff)
+        );
+    }
+
+    fn probed_payoff(table: &mut TranspositionTable<u32, i32, u32>, key: u64) -> Option<i32> {
+        table
+            .probe(key, Actor::First, Actor::First)
+            .map(|entry| entry.payoff)
+    }
+
+    #[test]
+    fn test_tiny_depth_preferred_table_retains_deeper_entry() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(1)
+            .with_replacement_scheme(ReplacementScheme::DepthPreferred);
+        table.store(entry(1, 5, 10));
+        table.store(entry(2, 3, 20));
+        assert_eq!(Some(10), probed_payoff(&mut table, 1));
+        assert_eq!(None, probed_payoff(&mut table, 2));
+    }
+
+    #[test]
+    fn test_always_replace_scheme() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(1)
+            .with_replacement_scheme(ReplacementScheme::AlwaysReplace);
+        table.store(entry(1, 5, 10));
+        table.store(entry(2, 3, 20));
+        assert_eq!(None, probed_payoff(&mut table, 1));
+        assert_eq!(Some(20), probed_payoff(&mut table, 2));
+    }
+
+    #[test]
+    fn test_two_tier_scheme_keeps_deep_and_recent_entries() {
+        // 容量は偶数に切り上げられ，2つの記録先が1組になる
+        let mut table = TranspositionTable::<u32, i32, u32>::new(1)
+            .with_replacement_scheme(ReplacementScheme::TwoTier);
+        assert_eq!(2, table.capacity());
+        table.store(entry(1, 5, 10));
+        table.store(entry(2, 3, 20));
+        table.store(entry(3, 2, 30));
+        // 深く探索した結果と，最後に記録した結果が残る
+        assert_eq!(Some(10), probed_payoff(&mut table, 1));
+        assert_eq!(None, probed_payoff(&mut table, 2));
+        assert_eq!(Some(30), probed_payoff(&mut table, 3));
+        // より深い探索結果に押し出された記録は，常に上書きする記録先に移る
+        table.store(entry(4, 6, 40));
+        assert_eq!(Some(40), probed_payoff(&mut table, 4));
+        assert_eq!(Some(10), probed_payoff(&mut table, 1));
+        assert_eq!(None, probed_payoff(&mut table, 3));
+        // 同じ状態の古い記録は残らない
+        table.store(entry(1, 7, 50));
+        assert_eq!(Some(50), probed_payoff(&mut table, 1));
+        assert_eq!(Some(40), probed_payoff(&mut table, 4));
+        assert_eq!(2, table.len());
+    }
+
+    #[test]
+    fn test_entries_from_previous_generation_are_replaced() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(1);
+        table.store(entry(1, 5, 10));
+        table.advance_generation();
+        // 以前の行動選択の記録は，浅い探索結果でも置き換えられる
+        table.store(entry(2, 3, 20));
+        assert_eq!(Some(20), probed_payoff(&mut table, 2));
+        // 同じ世代の記録は，深さを優先して残す
+        table.store(entry(3, 1, 30));
+        assert_eq!(Some(20), probed_payoff(&mut table, 2));
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(4);
+        assert_eq!(0.0, table.stats().hit_rate());
+        table.store(entry(1, 1, 1));
+        assert_eq!(Some(1), probed_payoff(&mut table, 1));
+        assert_eq!(None, probed_payoff(&mut table, 2));
+        let stats = table.stats();
+        assert_eq!(
+            TranspositionTableStats {
+                capacity: 4,
+                occupied: 1,
+                probes: 2,
+                hits: 1,
+            },
+            stats
+        );
+        assert_eq!(0.25, stats.occupancy());
+        assert_eq!(0.5, stats.hit_rate());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(4);
+        table.store(entry(1, 1, 1));
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(4, table.capacity());
+    }
+
+    #[test]
+    fn test_shrink_keeps_deeper_entries() {
+        let mut table = TranspositionTable::<u32, i32, u32>::new(8);
+        table.store(entry(1, 2, 10));
+        table.store(entry(5, 4, 20));
+        table.store(entry(2, 1, 30));
+        let bytes = table.memory_bytes();
+
+        // 記録先が重なる1と5のうち，より深く探索した5の記録を残す
+        assert!(table.shrink());
+        assert_eq!(4, table.capacity());
+        assert_eq!(bytes / 2, table.memory_bytes());
+        assert_eq!(2, table.len());
+        assert!(table.probe(1, Actor::First, Actor::First).is_none());
+        assert!(table.probe(5, Actor::First, Actor::First).is_some());
+        assert!(table.probe(2, Actor::First, Actor::First).is_some());
+
+        assert!(table.shrink());
+        assert!(table.shrink());
+        assert_eq!(1, table.capacity());
+        assert!(!table.shrink());
+        assert_eq!(1, table.capacity());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_concurrent_store_and_probe() {
+        let table = ConcurrentTranspositionTable::<u32, i32, u32>::new(8);
+        assert!(table.is_empty());
+        table.store(entry(3, 4, 10));
+        // 同じ位置に記録される，より浅い探索結果は記録されない
+        table.store(entry(11, 2, 20));
+        assert_eq!(1, table.len());
+        assert_eq!(
+            Some(10),
+            table
+                .probe(3, Actor::First, Actor::First)
+                .map(|entry| entry.payoff)
+        );
+        assert!(table.probe(11, Actor::First, Actor::First).is_none());
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(8, table.capacity());
+    }
+
+    #[test]
+    fn test_hash_state_is_deterministic() {
+        let table = TranspositionTable::<u32, i32, u32>::new(4);
+        assert_eq!(table.hash_state(&42), table.hash_state(&42));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::clock::Stopwatch;
+use crate::{
+    evaluate_at_ply, Action, Actor, Evaluator, Rule, ScoredAction, ScoredStrategy, SearchDepth,
+    SearchStats, State,
+};
+use core::marker::PhantomData;
+use data_structure::Range;
+use num::Bounded;
+
+/// 状態を複製せずに，その場で書き換えて状態遷移できるゲームルール．
+///
+/// `Rule::translate_state`は遷移のたびに新しい状態を作るので，状態が大きなゲームでは複製の負担が大きい．
+/// このトレイトを実装すると，`UndoAlphaBetaStrategy`が1つの状態を書き換えながら探索できる．
+pub trait UndoRule: Rule {
+    /// 行動を取り消すために必要な情報．
+    type Undo;
+
+    /// 指定した状態を，指定した行動をとった後の状態に書き換える．
+    /// 書き換えた結果は，`Rule::translate_state`が返す状態と等しくなければならない．
+    /// # Returns
+    /// 行動を取り消すために必要な情報．
+    fn apply_action(state: &mut Self::S, action: &Self::A) -> Self::Undo;
+
+    /// `apply_action`で書き換えた状態を，行動をとる前の状態に戻す．
+    fn undo_action(state: &mut Self::S, undo: Self::Undo);
+}
+
+/// 2人零和ゲームにおける適切な行動を，状態を書き換えながらαβ法で思考するエージェント．
+///
+/// 探索中は1つの状態を`UndoRule::apply_action`で書き換えて子ノードへ進み，
+/// `UndoRule::undo_action`で元に戻してから次の行動を調べる．
+/// 状態の複製は行動選択ごとに1回だけなので，状態が大きなゲームでメモリ使用量が少なくなる．
+pub struct UndoAlphaBetaStrategy<R, E, N> {
+    /// 探索するゲーム木の深さ．
+    search_depth: N,
+    _r: PhantomData<R>,
+    _e: PhantomData<E>,
+}
+
+impl<R, E, N> UndoAlphaBetaStrategy<R, E, N> {
+    /// 指定した深さまで探索する，状態を書き換えながらαβ法で思考するエージェントを作る．
+    pub fn new(search_depth: N) -> Self {
+        Self {
+            search_depth,
+            _r: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<S, A, R, E, N> UndoAlphaBetaStrategy<R, E, N>
+where
+    S: State + Clone,
+    A: Action,
+    R: UndoRule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    /// αβ法により，指定した状態の評価値を再帰的に計算する．
+    /// 計算を終えた時点で，状態は呼び出し前と同じに戻っている．
+    /// # Params
+    /// 1. remaining_depth 残りの探索深さ．
+    /// 1. consideration_target 評価値を計算する対象のプレイヤー．
+    /// 1. next_actor 指定した状態で行動するプレイヤー．
+    /// 1. state 注目ノードの状態．
+    /// 1. payoff_range 評価値の関心範囲．
+    /// 1. ply 根ノードから注目ノードまでの深さ．
+    /// 1. stats 探索の統計情報．
+    ///
+    /// # Returns
+    /// `Some((e, a))`: このノードの評価値`e`と，最善と判断された行動`a`
+    ///
+    /// `None`: このノードがゲーム終了ノードではなく，かつ取れる行動がない場合
+    #[allow(clippy::too_many_arguments)]
+    fn alpha_beta(
+        &self,
+        remaining_depth: N,
+        consideration_target: Actor,
+        next_actor: Actor,
+        state: &mut S,
+        payoff_range: Range<E::Payoff>,
+        ply: u32,
+        stats: &mut SearchStats,
+    ) -> Option<(E::Payoff, Option<A>)> {
+        stats.nodes_evaluated += 1;
+        stats.max_depth_reached = stats.max_depth_reached.max(ply);
+
+        // 注目ノードが末端ノードなら，現在の状態に対する静的評価値をそのまま適用する
+        let terminal = R::terminal_kind(state);
+        if remaining_depth.is_zero() || terminal.is_some() {
+            let payoff = evaluate_at_ply::<_, E>(consideration_target, state, terminal, ply);
+            return Some((payoff, None));
+        }
+
+        let is_own_turn = next_actor == consideration_target;
+        let mut best: Option<(E::Payoff, Option<A>)> = None;
+        let mut current_payoff_range = payoff_range;
+
+        for action in R::iterate_available_actions(state, next_actor) {
+            // 状態を書き換えて子ノードを調べた後，元に戻す
+            let undo = R::apply_action(state, &action);
+            let child = self.alpha_beta(
+                remaining_depth - N::one(),
+                consideration_target,
+                next_actor.opponent(),
+                state,
+                current_payoff_range,
+                ply + 1,
+                stats,
+            );
+            R::undo_action(state, undo);
+
+            // 取れる行動がない子ノードは探索対象としない
+            let child_payoff = match child {
+                Some((e, _)) => e,
+                None => continue,
+            };
+            // 自分の手番では自分が有利になる行動を，相手の手番では自分が不利になる行動を選択する
+            if let Some((e, _)) = best {
+                if (is_own_turn && e >= child_payoff) || (!is_own_turn && e <= child_payoff) {
+                    continue;
+                }
+            }
+            best = Some((child_payoff, Some(action)));
+            // 可能なら，αβカットして探索量を減らす
+            let maybe_next_range = if is_own_turn {
+                Range::try_new(child_payoff, current_payoff_range.max)
+            } else {
+                Range::try_new(current_payoff_range.min, child_payoff)
+            };
+            match maybe_next_range {
+                Some(range) => current_payoff_range = range,
+                None => {
+                    if is_own_turn {
+                        stats.beta_cutoffs += 1;
+                    } else {
+                        stats.alpha_cutoffs += 1;
+                    }
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// 指定した状態を複製し，それを書き換えながら探索する．
+    fn search(
+        &self,
+        state: &S,
+        actor: Actor,
+        stats: &mut SearchStats,
+    ) -> Option<(E::Payoff, Option<A>)> {
+        let mut state = state.clone();
+        self.alpha_beta(
+            self.search_depth,
+            actor,
+            actor,
+            &mut state,
+            Range::new(E::Payoff::min_value(), E::Payoff::max_value()),
+            0,
+            stats,
+        )
+    }
+
+    /// 指定した状態における，指定したエージェントにとってのゲーム木の評価値を返す．
+    /// 取れる行動がない場合は`None`を返す．
+    pub fn root_payoff(&self, state: &S, actor: Actor) -> Option<E::Payoff> {
+        self.search(state, actor, &mut SearchStats::default())
+            .map(|(payoff, _)| payoff)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，探索の統計情報とともに返す．
+    pub fn select_action_with_stats(&self, state: &S, actor: Actor) -> (Option<A>, SearchStats) {
+        let (scored, stats) = self.select_scored_action_with_stats(state, actor);
+        (scored.map(|scored| scored.action), stats)
+    }
+
+    /// 指定した状態におけるエージェントの行動を選択し，その評価値と探索の統計情報とともに返す．
+    pub fn select_scored_action_with_stats(
+        &self,
+        state: &S,
+        actor: Actor,
+    ) -> (Option<ScoredAction<A, E::Payoff>>, SearchStats) {
+        let stopwatch = Stopwatch::start();
+        let mut stats = SearchStats::default();
+        let scored = self
+            .search(state, actor, &mut stats)
+            .and_then(|(payoff, action)| action.map(|action| ScoredAction::new(action, payoff)));
+        stats.elapsed = stopwatch.elapsed();
+        (scored, stats)
+    }
+}
+
+impl<S, A, R, E, N> ScoredStrategy<S, A> for UndoAlphaBetaStrategy<R, E, N>
+where
+    S: State + Clone,
+    A: Action,
+    R: UndoRule<S = S, A = A>,
+    E: Evaluator<S>,
+    E::Payoff: Copy + Ord + Bounded,
+    N: SearchDepth,
+{
+    type Payoff = E::Payoff;
+
+    fn select_scored_action(&self, state: &S, actor: Actor) -> Option<ScoredAction<A, E::Payoff>> {
+        self.select_scored_action_with_stats(state, actor).0
+    }
+}
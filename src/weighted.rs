@@ -0,0 +1,119 @@
+use crate::{Actor, Evaluator};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{AddAssign, Mul};
+use num::Zero;
+
+/// 指定されたプレイヤーにとっての状態の利得を評価する関数．
+type TermFn<S, P> = Box<dyn Fn(Actor, &S) -> P + Send + Sync>;
+
+/// 複数の評価関数の利得に重みを掛けて足し合わせる評価関数．
+///
+/// 駒の損得や配置，手番など，複数の観点の評価を組み合わせる場合に用いる．
+/// 評価関数と重みは実行時に指定するので，探索に渡すには`WeightedEvaluatorSource`と`Weighted`を用いる．
+pub struct WeightedEvaluator<S, P> {
+    /// 評価関数とその重みの組．
+    terms: Vec<(TermFn<S, P>, P)>,
+}
+
+impl<S, P> WeightedEvaluator<S, P> {
+    /// 評価関数と重みを順に追加して，`WeightedEvaluator`を作る．
+    pub fn builder() -> WeightedEvaluatorBuilder<S, P> {
+        WeightedEvaluatorBuilder { terms: Vec::new() }
+    }
+
+    /// 組み合わせる評価関数の数を返す．
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// 組み合わせる評価関数がないかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// 追加した順に，評価関数の重みを列挙する．
+    pub fn weights(&self) -> impl Iterator<Item = &P> {
+        self.terms.iter().map(|(_, weight)| weight)
+    }
+}
+
+impl<S, P> WeightedEvaluator<S, P>
+where
+    P: Clone + AddAssign + Mul<Output = P> + Zero,
+{
+    /// 指定された状態について，各評価関数の利得に重みを掛けた和を返す．
+    /// 評価関数がない場合は0を返す．
+    pub fn evaluate_payoff_for(&self, actor: Actor, state: &S) -> P {
+        let mut payoff = P::zero();
+        for (evaluate, weight) in self.terms.iter() {
+            payoff += evaluate(actor, state) * weight.clone();
+        }
+        payoff
+    }
+}
+
+impl<S, P> fmt::Debug for WeightedEvaluator<S, P>
+where
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedEvaluator")
+            .field("weights", &self.weights().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// `WeightedEvaluator`に評価関数と重みを追加していくビルダー．
+pub struct WeightedEvaluatorBuilder<S, P> {
+    terms: Vec<(TermFn<S, P>, P)>,
+}
+
+impl<S, P> WeightedEvaluatorBuilder<S, P> {
+    /// 評価関数とその重みを追加する．
+    pub fn add<F>(mut self, evaluate: F, weight: P) -> Self
+    where
+        F: Fn(Actor, &S) -> P + Send + Sync + 'static,
+    {
+        self.terms.push((Box::new(evaluate), weight));
+        self
+    }
+
+    /// 追加した評価関数を組み合わせた`WeightedEvaluator`を作る．
+    pub fn build(self) -> WeightedEvaluator<S, P> {
+        WeightedEvaluator { terms: self.terms }
+    }
+}
+
+/// 探索に用いる`WeightedEvaluator`を返す型．
+///
+/// `Evaluator`は値を持たずに呼び出されるので，`static`などに置いた`WeightedEvaluator`への参照を返すように実装する．
+pub trait WeightedEvaluatorSource<S> {
+    /// 利得を表す型．
+    type Payoff;
+
+    /// 探索に用いる`WeightedEvaluator`を返す．
+    fn weighted_evaluator() -> &'static WeightedEvaluator<S, Self::Payoff>;
+}
+
+/// `W`が返す`WeightedEvaluator`で利得を評価する評価関数．
+///
+/// 値を持たない型なので，探索するエージェントの型引数として`Weighted<W>`を指定する．
+pub struct Weighted<W> {
+    _w: PhantomData<W>,
+}
+
+impl<S, W> Evaluator<S> for Weighted<W>
+where
+    S: 'static,
+    W: WeightedEvaluatorSource<S>,
+    W::Payoff: Clone + AddAssign + Mul<Output = W::Payoff> + Zero + 'static,
+{
+    type Payoff = W::Payoff;
+
+    fn evaluate_payoff_for(actor: Actor, state: &S) -> Self::Payoff {
+        W::weighted_evaluator().evaluate_payoff_for(actor, state)
+    }
+}
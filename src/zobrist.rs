@@ -0,0 +1,268 @@
+use crate::transposition::StateHasher;
+use crate::Rule;
+use core::marker::PhantomData;
+use core::ops::BitXor;
+
+/// 状態遷移に合わせて，状態のハッシュ値を差分だけで更新できるゲームルール．
+///
+/// 置換表を使う際に状態全体からハッシュ値を計算し直す必要がなくなるので，盤面の大きなゲームで探索が速くなる．
+/// ハッシュ値は，同じ状態に対して常に同じ値となる必要がある．
+/// すなわち，`update_hash(zobrist_hash(s), s, a)`は`zobrist_hash(translate_state(s, a))`と等しくなければならない．
+pub trait ZobristHashable: Rule {
+    /// 指定した状態のハッシュ値を，状態全体から計算する．
+    fn zobrist_hash(state: &Self::S) -> u64;
+
+    /// 指定した状態で指定した行動をとった後の状態のハッシュ値を，行動前のハッシュ値から計算する．
+    /// # Params
+    /// 1. hash 行動前の状態のハッシュ値．
+    /// 1. state 行動前の状態．
+    /// 1. action 行動．
+    fn update_hash(hash: u64, state: &Self::S, action: &Self::A) -> u64;
+}
+
+/// 行動前の状態のハッシュ値から，行動後の状態のハッシュ値を計算する関数．
+pub(crate) type HashUpdater<S, A> = fn(u64, &S, &A) -> u64;
+
+/// Zobristハッシュで用いる乱数表．
+///
+/// 盤面の各マスと駒の組み合わせなどに1つずつ乱数を割り当て，
+/// 状態のハッシュ値をそれらの排他的論理和として表すことで，差分更新を可能にする．
+/// 同じシード値からは常に同じ乱数表が作られる．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZobristKeys<const N: usize> {
+    keys: [u64; N],
+}
+
+impl<const N: usize> ZobristKeys<N> {
+    /// 指定したシード値から乱数表を作る．
+    /// 定数として乱数表を定義できるよう，コンパイル時にも計算できる．
+    pub const fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut keys = [0; N];
+        let mut i = 0;
+        while i < N {
+            let (next, key) = split_mix64(state);
+            state = next;
+            keys[i] = key;
+            i += 1;
+        }
+        Self { keys }
+    }
+
+    /// 指定した番号の乱数を返す．
+    /// # Panics
+    /// `index`が`N`以上の場合
+    pub const fn key(&self, index: usize) -> u64 {
+        self.keys[index]
+    }
+
+    /// 乱数表に含まれる乱数の数を返す．
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// 乱数表が空か．
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+/// 盤面の各マスと，各マスがとりうる状態の組み合わせに乱数を割り当てた，Zobristハッシュの乱数表．
+///
+/// `SQUARES`はマスの数，`STATES`は1つのマスがとりうる状態 (駒の種類など) の数である．
+/// 空きマスには乱数を割り当てず，ハッシュ値は空きでないマスの乱数の排他的論理和とする．
+/// 同じシード値からは常に同じ乱数表が作られる．
+///
+/// # ハッシュ値の衝突
+/// 乱数は64ビットの一様乱数とみなせるので，異なる2つの状態のハッシュ値が一致する確率は約`2^-64`である．
+/// 探索中に`k`個の異なる状態を調べるとき，いずれかの組が衝突する確率は誕生日問題により約`k^2 / 2^65`となる．
+/// 例えば`10^8`個の状態を調べても約`2.7 * 10^-4`であり，
+/// 衝突しても置換表の1つの記録が誤って使われるだけなので，探索結果への影響は無視できる．
+///
+/// ただし，これは乱数が線形独立である場合の見積もりである．
+/// 乱数の数が64を大きく超えると，一部の乱数の排他的論理和が0になる組み合わせが必ず存在するが，
+/// そのような組み合わせが実際のゲームの状態の差として現れる確率は上の見積もりと同程度である．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZobristTable<const STATES: usize, const SQUARES: usize> {
+    keys: [[u64; STATES]; SQUARES],
+}
+
+impl<const STATES: usize, const SQUARES: usize> ZobristTable<STATES, SQUARES> {
+    /// 指定したシード値から乱数表を作る．
+    /// 定数として乱数表を定義できるよう，コンパイル時にも計算できる．
+    pub const fn new(seed: u64) -> Self {
+        let mut rng = seed;
+        let mut keys = [[0; STATES]; SQUARES];
+        let mut square = 0;
+        while square < SQUARES {
+            let mut state = 0;
+            while state < STATES {
+                let (next, key) = split_mix64(rng);
+                rng = next;
+                keys[square][state] = key;
+                state += 1;
+            }
+            square += 1;
+        }
+        Self { keys }
+    }
+
+    /// 指定したマスが指定した状態であることに割り当てられた乱数を返す．
+    /// # Panics
+    /// `square`が`SQUARES`以上，または`state`が`STATES`以上の場合
+    pub const fn key(&self, square: usize, state: usize) -> u64 {
+        self.keys[square][state]
+    }
+
+    /// 空きでないマスとその状態を列挙し，盤面全体のハッシュ値を計算する．
+    /// # Panics
+    /// `square`が`SQUARES`以上，または`state`が`STATES`以上の組が含まれる場合
+    pub fn hash<I>(&self, occupancies: I) -> ZobristHash
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        occupancies
+            .into_iter()
+            .fold(ZobristHash::default(), |hash, (square, state)| {
+                hash.toggle(self, square, state)
+            })
+    }
+}
+
+/// Zobristハッシュによる状態のハッシュ値．
+///
+/// 空の盤面のハッシュ値は`ZobristHash::default()`である．
+/// マスの状態が変わるたびに`toggle`で差分だけ更新できる．
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ZobristHash(pub u64);
+
+impl ZobristHash {
+    /// 指定したマスの指定した状態を，ハッシュ値に加える (または取り除く)．
+    ///
+    /// 排他的論理和で更新するので，同じマスと状態で2回呼び出すと元のハッシュ値に戻る．
+    /// 駒を動かす場合は，移動元のマスの状態と移動先のマスの状態をそれぞれ切り替える．
+    /// # Panics
+    /// `square`が`SQUARES`以上，または`state`が`STATES`以上の場合
+    pub fn toggle<const STATES: usize, const SQUARES: usize>(
+        self,
+        table: &ZobristTable<STATES, SQUARES>,
+        square: usize,
+        state: usize,
+    ) -> Self {
+        self ^ Self(table.key(square, state))
+    }
+}
+
+impl BitXor for ZobristHash {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl From<ZobristHash> for u64 {
+    fn from(hash: ZobristHash) -> u64 {
+        hash.0
+    }
+}
+
+/// Zobristハッシュでハッシュ値を計算できるゲームの状態．
+///
+/// `Hash`を実装しなくても，`ZobristStateHasher`を指定した置換表で状態を記録できる．
+/// 盤面が配列で表されるゲームでは，`ZobristTable::hash`を使えば容易に実装できる．
+/// 状態遷移のたびにハッシュ値を差分で更新したい場合は，ゲームルールに`ZobristHashable`を実装する．
+pub trait ZobristState {
+    /// 状態のハッシュ値を返す．
+    /// 同じ状態に対しては，常に同じ値を返さなければならない．
+    fn zobrist_hash(&self) -> ZobristHash;
+}
+
+/// `ZobristState::zobrist_hash`で状態のハッシュ値を計算する．
+///
+/// `TranspositionTable::with_hasher`に指定して使う．
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZobristStateHasher;
+
+impl<S> StateHasher<S> for ZobristStateHasher
+where
+    S: ZobristState,
+{
+    fn hash_state(&self, state: &S) -> u64 {
+        state.zobrist_hash().into()
+    }
+}
+
+/// SplitMix64により，乱数生成器の状態を進めて乱数を1つ生成する．
+/// # Returns
+/// 次の状態と，生成した乱数．
+const fn split_mix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+/// `ZobristHashable::zobrist_hash`で状態全体のハッシュ値を計算する．
+pub(crate) struct ZobristRuleHasher<R> {
+    _r: PhantomData<fn() -> R>,
+}
+
+impl<R> ZobristRuleHasher<R> {
+    pub(crate) fn new() -> Self {
+        Self { _r: PhantomData }
+    }
+}
+
+impl<R> StateHasher<R::S> for ZobristRuleHasher<R>
+where
+    R: ZobristHashable,
+{
+    fn hash_state(&self, state: &R::S) -> u64 {
+        R::zobrist_hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_deterministic() {
+        let keys = ZobristKeys::<16>::new(7);
+        assert_eq!(keys, ZobristKeys::<16>::new(7));
+        assert_ne!(keys, ZobristKeys::<16>::new(8));
+        assert_eq!(16, keys.len());
+    }
+
+    #[test]
+    fn test_keys_are_distinct() {
+        let keys = ZobristKeys::<64>::new(0);
+        for i in 0..keys.len() {
+            for j in 0..i {
+                assert_ne!(keys.key(i), keys.key(j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_matches_keys_with_same_seed() {
+        let keys = ZobristKeys::<12>::new(3);
+        let table = ZobristTable::<3, 4>::new(3);
+        for square in 0..4 {
+            for state in 0..3 {
+                assert_eq!(keys.key(square * 3 + state), table.key(square, state));
+            }
+        }
+    }
+
+    #[test]
+    fn test_toggle_is_involution() {
+        let table = ZobristTable::<2, 9>::new(0);
+        let hash = table.hash(vec![(0, 0), (4, 1)]);
+        assert_eq!(hash, hash.toggle(&table, 8, 1).toggle(&table, 8, 1));
+        assert_eq!(table.hash(vec![(0, 0)]), hash.toggle(&table, 4, 1));
+        assert_eq!(ZobristHash::default(), table.hash(vec![]));
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `CollectingRule::iterate_available_actions`が呼ばれた回数．
+static ITERATE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// `ReversiRule`と同じゲームで，行動をバッファに格納して列挙するルール．
+struct CollectingRule;
+
+impl Rule for CollectingRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = std::vec::IntoIter<Placement>;
+
+    const COLLECTS_ACTIONS: bool = true;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ITERATE_CALLS.fetch_add(1, Ordering::SeqCst);
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn collect_available_actions(state: &Board, actor: Actor, out: &mut Vec<Placement>) {
+        for row in 0..FIELD_SIZE {
+            for column in 0..FIELD_SIZE {
+                if state.at(column, row).is_none() {
+                    out.push(Placement::new(column, row, actor));
+                }
+            }
+        }
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+}
+
+#[test]
+fn test_default_collect_appends_iterated_actions() {
+    for (board, actor) in enumerate_positions(2) {
+        let expected = ReversiRule::iterate_available_actions(&board, actor).collect::<Vec<_>>();
+        // 既存の要素は残し，その後に追加する
+        let mut out = vec![Placement::new(0, 0, actor.opponent())];
+        ReversiRule::collect_available_actions(&board, actor, &mut out);
+        assert_eq!(Placement::new(0, 0, actor.opponent()), out[0]);
+        assert_eq!(expected, out[1..]);
+
+        let mut out = vec![];
+        CollectingRule::collect_available_actions(&board, actor, &mut out);
+        assert_eq!(expected, out);
+    }
+}
+
+#[test]
+fn test_search_with_action_buffers() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let iterating = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let collecting = AlphaBetaStrategy::<CollectingRule, ScoreEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                iterating.select_scored_action(&board, actor),
+                collecting.select_scored_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+            // 行動を調べる順序は同じなので，探索量も変わらない
+            let (_, expected) = iterating.select_action_with_stats(&board, actor);
+            let (_, stats) = collecting.select_action_with_stats(&board, actor);
+            assert_eq!(expected.nodes_evaluated, stats.nodes_evaluated);
+        }
+    }
+    // 並べ替えを行わない探索では，イテレータで行動を列挙しない
+    assert_eq!(0, ITERATE_CALLS.load(Ordering::SeqCst));
+
+    // 並べ替える場合はイテレータで列挙するが，結果は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let iterating =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+    let collecting =
+        AlphaBetaStrategy::<CollectingRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+    assert_eq!(
+        iterating.select_scored_action(&Board::new(), Actor::First),
+        collecting.select_scored_action(&Board::new(), Actor::First)
+    );
+    assert!(ITERATE_CALLS.load(Ordering::SeqCst) > 0);
+}
@@ -0,0 +1,37 @@
+use minimax_strategy::*;
+use std::convert::TryFrom;
+
+#[test]
+fn test_index_round_trip() {
+    for (index, &actor) in Actor::ALL.iter().enumerate() {
+        assert_eq!(index, actor.index());
+        assert_eq!(index, usize::from(actor));
+        assert_eq!(Some(actor), Actor::from_index(actor.index()));
+        assert_eq!(Ok(actor), Actor::try_from(actor.index()));
+    }
+    assert_eq!(0, Actor::First.index());
+    assert_eq!(1, Actor::Second.index());
+}
+
+#[test]
+fn test_out_of_range_index() {
+    assert_eq!(None, Actor::from_index(2));
+    assert_eq!(None, Actor::from_index(usize::MAX));
+    assert_eq!(Err(InvalidActorIndex(2)), Actor::try_from(2));
+    assert_eq!(
+        "actor index 2 is out of range",
+        InvalidActorIndex(2).to_string()
+    );
+}
+
+#[test]
+fn test_index_per_player_array() {
+    let mut scores = [0; 2];
+    scores[Actor::Second.index()] += 3;
+    scores[Actor::First.index()] += 1;
+    assert_eq!([1, 3], scores);
+    // 相手の添字は，もう一方の添字である
+    for &actor in Actor::ALL.iter() {
+        assert_eq!(1 - actor.index(), actor.opponent().index());
+    }
+}
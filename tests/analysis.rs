@@ -0,0 +1,90 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_analysis_pv_reaches_game_over() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+
+    for (board, actor) in enumerate_positions(1) {
+        let report = strategy.analyze(&board, actor);
+        assert_eq!(strategy.root_payoff(&board, actor), report.payoff);
+        assert_eq!(
+            report.principal_variation.first(),
+            report.best_action(),
+            "{}",
+            board
+        );
+
+        // 最善手順の行動を順にとるとゲームが終わり，その状態の評価値は報告された評価値と等しい
+        let mut state = board.clone();
+        let mut next_actor = actor;
+        for action in report.principal_variation.iter() {
+            assert!(ReversiRule::terminal_kind(&state).is_none(), "{}", state);
+            assert_eq!(next_actor, action.actor());
+            state = ReversiRule::translate_state(&state, action);
+            next_actor = next_actor.opponent();
+        }
+        assert!(ReversiRule::terminal_kind(&state).is_some(), "{}", state);
+        assert_eq!(
+            report.payoff,
+            Some(ScoreEvaluator::evaluate_payoff_for(actor, &state)),
+            "{}",
+            state
+        );
+    }
+}
+
+#[test]
+fn test_analysis_records_each_depth() {
+    let depth = 4;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let report = strategy.analyze(&Board::new(), Actor::First);
+
+    let depths = report
+        .depths
+        .iter()
+        .map(|depth| depth.depth)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 3, 4], depths);
+    assert_eq!(
+        report.stats.nodes_evaluated,
+        report
+            .depths
+            .iter()
+            .map(|depth| depth.nodes_evaluated)
+            .sum::<u64>()
+    );
+    for depth in report.depths.iter() {
+        assert!(depth.nodes_evaluated > 0);
+        assert_eq!(i32::MIN, depth.alpha);
+        assert_eq!(i32::MAX, depth.beta);
+    }
+    assert_eq!(report.payoff, report.depths.last().unwrap().payoff);
+    assert_eq!(depth, report.principal_variation.len());
+
+    // 最善手順の末端の状態を評価し直すと，報告された評価値と等しい
+    let state = report
+        .principal_variation
+        .iter()
+        .fold(Board::new(), |state, action| {
+            ReversiRule::translate_state(&state, action)
+        });
+    assert_eq!(
+        report.payoff,
+        Some(ScoreEvaluator::evaluate_payoff_for(Actor::First, &state))
+    );
+}
+
+#[test]
+fn test_analysis_display() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(2);
+    let report = strategy.analyze(&Board::new(), Actor::First);
+    let text = report.to_string();
+    assert!(text.starts_with("best action="), "{}", text);
+    assert!(text.contains("pv: "), "{}", text);
+    assert!(text.contains("depth=1 "), "{}", text);
+    assert!(text.contains("depth=2 "), "{}", text);
+}
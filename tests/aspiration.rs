@@ -0,0 +1,121 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_aspiration_window_keeps_root_payoff() {
+    let policies = [AspirationWidening::Double, AspirationWidening::FullWindow];
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        for &widening in policies.iter() {
+            for &delta in [0, 1, 5, i32::MAX].iter() {
+                let strategy =
+                    IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+                        .with_aspiration_window(delta, widening);
+                for (board, actor) in enumerate_positions(1) {
+                    assert_eq!(
+                        alpha_beta.root_payoff(&board, actor),
+                        strategy.root_payoff(&board, actor),
+                        "depth {} delta {} {:?}\n{}",
+                        depth,
+                        delta,
+                        widening,
+                        board
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aspiration_window_selects_same_action() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let strategy = IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_aspiration_window(2, AspirationWidening::Double);
+
+    for (board, actor) in enumerate_positions(2) {
+        let expected = alpha_beta.select_action(&board, actor).unwrap();
+        let action = strategy.select_action(&board, actor).unwrap();
+        let after_expected = ReversiRule::translate_state(&board, &expected);
+        let after_action = ReversiRule::translate_state(&board, &action);
+        assert_eq!(
+            alpha_beta.root_payoff(&after_expected, actor.opponent()),
+            alpha_beta.root_payoff(&after_action, actor.opponent()),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_aspiration_window_counts_re_searches() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    // 評価値は深さによって大きく変わるので，狭い窓では探索し直すことになる
+    let narrow = IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_aspiration_window(1, AspirationWidening::Double);
+    let result = narrow.search(&Board::new(), Actor::First);
+    let stats = result.stats();
+    assert!(stats.aspiration_fail_highs + stats.aspiration_fail_lows > 0);
+
+    // 窓が関心範囲全体を覆う場合は，探索し直さない
+    let wide = IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_aspiration_window(i32::MAX, AspirationWidening::Double);
+    let result = wide.search(&Board::new(), Actor::First);
+    assert_eq!(0, result.stats().aspiration_fail_highs);
+    assert_eq!(0, result.stats().aspiration_fail_lows);
+
+    // 窓を使わない場合も，探索し直さない
+    let plain = IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let result = plain.search(&Board::new(), Actor::First);
+    assert_eq!(0, result.stats().aspiration_fail_highs);
+    assert_eq!(0, result.stats().aspiration_fail_lows);
+}
+
+#[test]
+fn test_aspiration_strategy_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let strategy = AspirationStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_aspiration_delta(2);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                strategy.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_aspiration_strategy_reduces_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let plain = IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let strategy =
+        AspirationStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_aspiration_delta(8);
+    let expected = plain.search(&Board::new(), Actor::First);
+    let result = strategy.search(&Board::new(), Actor::First);
+    assert_eq!(expected.payoff(), result.payoff());
+    assert!(
+        result.stats().nodes_evaluated < expected.stats().nodes_evaluated,
+        "{} {}",
+        result.stats().nodes_evaluated,
+        expected.stats().nodes_evaluated
+    );
+    assert!(strategy
+        .select_action(&Board::new(), Actor::First)
+        .is_some());
+
+    // 幅を指定しない場合は，通常の反復深化と同じだけ探索する
+    let unbounded = AspirationStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let result = unbounded.search(&Board::new(), Actor::First);
+    assert_eq!(
+        expected.stats().nodes_evaluated,
+        result.stats().nodes_evaluated
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_default_count_matches_iteration() {
+    for (board, actor) in enumerate_positions(3) {
+        let expected = ReversiRule::iterate_available_actions(&board, actor).count();
+        assert_eq!(
+            expected,
+            ReversiRule::count_available_actions(&board, actor)
+        );
+        assert_eq!(
+            expected > 0,
+            ReversiRule::has_available_actions(&board, actor)
+        );
+    }
+    let full = Board::with_placements(
+        &(0..FIELD_SIZE * FIELD_SIZE)
+            .map(|i| (i % FIELD_SIZE, i / FIELD_SIZE, Actor::ALL[i % 2]))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(0, ReversiRule::count_available_actions(&full, Actor::First));
+    assert!(!ReversiRule::has_available_actions(&full, Actor::First));
+}
+
+/// 石の山から交互に1個か2個取り，取れなくなったプレイヤーが負けるゲームの状態．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Pile {
+    stones: u32,
+    next: Actor,
+}
+
+impl State for Pile {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Take {
+    actor: Actor,
+    stones: u32,
+}
+
+impl Action for Take {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+/// `count_available_actions`が呼ばれた回数．
+static COUNT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+struct PileRule;
+
+impl Rule for PileRule {
+    type S = Pile;
+    type A = Take;
+    type ActionIterator = std::vec::IntoIter<Take>;
+
+    fn terminal_kind(_state: &Pile) -> Option<TerminalKind> {
+        None
+    }
+
+    fn iterate_available_actions(state: &Pile, actor: Actor) -> Self::ActionIterator {
+        (1..=state.stones.min(2))
+            .map(|stones| Take { actor, stones })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn count_available_actions(state: &Pile, _actor: Actor) -> usize {
+        COUNT_CALLS.fetch_add(1, Ordering::Relaxed);
+        state.stones.min(2) as usize
+    }
+
+    fn translate_state(state: &Pile, action: &Take) -> Pile {
+        Pile {
+            stones: state.stones - action.stones,
+            next: action.actor.opponent(),
+        }
+    }
+}
+
+#[test]
+fn test_overridden_count_is_consistent() {
+    for stones in 0..5 {
+        let pile = Pile {
+            stones,
+            next: Actor::First,
+        };
+        assert_eq!(
+            PileRule::iterate_available_actions(&pile, Actor::First).count(),
+            PileRule::count_available_actions(&pile, Actor::First)
+        );
+        assert_eq!(
+            stones > 0,
+            PileRule::has_available_actions(&pile, Actor::First)
+        );
+    }
+}
+
+#[test]
+fn test_proof_number_search_uses_action_count() {
+    // 取れなくなった状態で手番のプレイヤーが負ける．石の数が3の倍数なら，手番のプレイヤーは負ける
+    let goal = |pile: &Pile| pile.next == Actor::Second;
+    let search = ProofNumberSearch::<PileRule>::new(1 << 12);
+    let before = COUNT_CALLS.load(Ordering::Relaxed);
+    for stones in 1..10 {
+        let pile = Pile {
+            stones,
+            next: Actor::First,
+        };
+        let (outcome, _) = search.solve(&pile, Actor::First, Actor::First, goal);
+        let expected = if stones % 3 == 0 {
+            ProofOutcome::Disproved
+        } else {
+            ProofOutcome::Proved
+        };
+        assert_eq!(expected, outcome, "{} stones", stones);
+    }
+    assert!(COUNT_CALLS.load(Ordering::Relaxed) > before);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `BatchCountingEvaluator`で，状態を1つずつ評価した回数．
+static SINGLE_EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+/// `BatchCountingEvaluator`で，まとめて評価した回数．
+static BATCHES: AtomicUsize = AtomicUsize::new(0);
+/// `BatchCountingEvaluator`で，まとめて評価した状態の数の合計．
+static BATCHED_STATES: AtomicUsize = AtomicUsize::new(0);
+
+/// `ScoreEvaluator`と同じ値を返し，評価関数の呼び出し方を記録する評価関数．
+struct BatchCountingEvaluator;
+
+impl Evaluator<Board> for BatchCountingEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> i32 {
+        SINGLE_EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+        ScoreEvaluator::evaluate_payoff_for(actor, state)
+    }
+
+    fn evaluate_batch(actor: Actor, states: &[&Board]) -> Vec<i32> {
+        BATCHES.fetch_add(1, Ordering::SeqCst);
+        BATCHED_STATES.fetch_add(states.len(), Ordering::SeqCst);
+        states
+            .iter()
+            .map(|state| ScoreEvaluator::evaluate_payoff_for(actor, state))
+            .collect()
+    }
+}
+
+#[test]
+fn test_default_batch_evaluates_each_state() {
+    let positions = enumerate_positions(2);
+    let states = positions.iter().map(|(board, _)| board).collect::<Vec<_>>();
+    for &actor in [Actor::First, Actor::Second].iter() {
+        let expected = states
+            .iter()
+            .map(|state| ScoreEvaluator::evaluate_payoff_for(actor, state))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, ScoreEvaluator::evaluate_batch(actor, &states));
+    }
+    assert!(ScoreEvaluator::evaluate_batch(Actor::First, &[]).is_empty());
+}
+
+#[test]
+fn test_batch_evaluation_keeps_search_result() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let batched = strategy.clone().with_batch_evaluation();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                strategy.select_scored_action(&board, actor),
+                batched.select_scored_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_leaves_are_evaluated_in_batches() {
+    let board = Board::new();
+    let strategy = AlphaBetaStrategy::<ReversiRule, BatchCountingEvaluator, _>::new(2);
+    strategy.select_action(&board, Actor::First);
+    // 既定ではまとめて評価しない
+    assert_eq!(0, BATCHES.load(Ordering::SeqCst));
+    assert!(SINGLE_EVALUATIONS.load(Ordering::SeqCst) > 0);
+
+    SINGLE_EVALUATIONS.store(0, Ordering::SeqCst);
+    let batched = strategy.with_batch_evaluation();
+    batched.select_action(&board, Actor::First);
+    // 深さ2の末端ノードはゲームが終了していないので，すべてまとめて評価される
+    assert_eq!(0, SINGLE_EVALUATIONS.load(Ordering::SeqCst));
+    let batches = BATCHES.load(Ordering::SeqCst);
+    assert!(batches > 0);
+    // 各バッチは，1手目の後に取れる8個の行動の子ノードからなる
+    assert_eq!(8 * batches, BATCHED_STATES.load(Ordering::SeqCst));
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 1手先の状態を評価し，評価値が最も高い行動のうち最初に列挙されたものを返す．
+fn greedy_action(board: &Board, actor: Actor) -> Option<Placement> {
+    let mut best: Option<(BoardEvaluation, Placement)> = None;
+    for action in ReversiRule::iterate_available_actions(board, actor) {
+        let next = ReversiRule::translate_state(board, &action);
+        let payoff = BoardEvaluator::evaluate_payoff_for(actor, &next);
+        if best.as_ref().is_none_or(|(best, _)| payoff > *best) {
+            best = Some((payoff, action));
+        }
+    }
+    best.map(|(_, action)| action)
+}
+
+#[test]
+fn test_beam_width_one_is_greedy_lookahead() {
+    for depth in 1..=4 {
+        let beam = BeamSearchStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, 1);
+        for (board, actor) in enumerate_positions(2) {
+            assert_eq!(
+                greedy_action(&board, actor),
+                beam.select_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_wide_beam_of_depth_one_matches_minimax() {
+    let beam = BeamSearchStrategy::<ReversiRule, BoardEvaluator, _>::new(1, usize::MAX);
+    let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(1);
+    for (board, actor) in enumerate_positions(3) {
+        let scored = beam.select_scored_action(&board, actor).unwrap();
+        assert_eq!(minimax.root_payoff(&board, actor), Some(scored.payoff));
+    }
+}
+
+#[test]
+fn test_beam_search_selects_winning_action() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let beam = BeamSearchStrategy::<ReversiRule, BoardEvaluator, _>::new(3, 4);
+    let action = beam.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+    assert_eq!(4, beam.beam_width());
+}
+
+#[test]
+fn test_beam_search_no_action_on_game_over() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let beam = BeamSearchStrategy::<ReversiRule, BoardEvaluator, _>::new(3, 4);
+    assert!(beam.select_action(&board, Actor::Second).is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_beam_width_must_be_positive() {
+    let _ = BeamSearchStrategy::<ReversiRule, BoardEvaluator, u32>::new(3, 0);
+}
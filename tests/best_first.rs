@@ -0,0 +1,155 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 指定した行動をとった後の状態を最後まで探索し，行動したプレイヤーにとっての評価値を返す．
+fn exact_payoff(board: &Board, actor: Actor, action: &Placement) -> Option<i32> {
+    let full = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE);
+    let after = ReversiRule::translate_state(board, action);
+    full.root_payoff(&after, actor.opponent())
+        .map(|payoff| -payoff)
+}
+
+#[test]
+fn test_best_first_matches_alpha_beta_at_comparable_node_count() {
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(6);
+    for (board, actor) in enumerate_positions(2) {
+        let (expected, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let budget = stats.nodes_evaluated as usize;
+        let best_first = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(budget);
+        let (scored, best_first_stats) = best_first.select_scored_action_with_stats(&board, actor);
+        // 1回の展開で追加するノードの数は，取れる行動の数以下である
+        assert!(best_first_stats.nodes_evaluated as usize <= budget + FIELD_SIZE * FIELD_SIZE);
+        assert_eq!(
+            exact_payoff(&board, actor, &expected.unwrap()),
+            exact_payoff(&board, actor, &scored.unwrap().action),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_best_first_resolves_whole_tree() {
+    let alpha_beta =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE);
+    let best_first = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(1 << 16);
+    for (board, actor) in enumerate_positions(4) {
+        let tree = best_first.search(&board, actor);
+        assert!(tree.root().resolved, "{}", board);
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            Some(tree.root().payoff),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_best_first_tree_inspection() {
+    let best_first = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(100);
+    let board = Board::new();
+    let tree = best_first.search(&board, Actor::First);
+    assert!(!tree.is_empty());
+    assert!(tree.len() >= 100);
+    assert_eq!(tree.len(), tree.iter_nodes().count());
+
+    // 根ノードはすべての行動について展開される
+    let root = tree.root();
+    assert_eq!(0, root.depth);
+    assert_eq!(None, root.cause_action);
+    assert_eq!(FIELD_SIZE * FIELD_SIZE, tree.children(root).count());
+    for child in tree.children(root) {
+        assert_eq!(1, child.depth);
+        assert_eq!(Actor::Second, child.next_actor);
+    }
+
+    // 主要変化は根ノードから1手ずつ深くなり，末端まで根ノードと同じ評価値を持つ
+    let pv = tree.principal_variation().collect::<Vec<_>>();
+    assert!(pv.len() > 2);
+    for (depth, node) in pv.iter().enumerate() {
+        assert_eq!(depth, node.depth);
+        assert_eq!(root.payoff, node.payoff);
+    }
+    let action = pv[1].cause_action;
+    assert_eq!(action, best_first.select_action(&board, Actor::First));
+}
+
+#[test]
+fn test_best_first_game_over() {
+    let best_first = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(100);
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    assert_eq!(None, best_first.select_action(&board, Actor::Second));
+    let tree = best_first.search(&board, Actor::Second);
+    assert_eq!(1, tree.len());
+    assert!(tree.root().terminal);
+    assert_eq!(-100, tree.root().payoff);
+}
+
+#[test]
+fn test_best_first_with_eviction_stays_within_budget() {
+    let budget = 60;
+    let bounded = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(budget).with_eviction(500);
+    assert_eq!(budget, bounded.node_budget());
+    let alpha_beta =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE);
+    let mut evicted = 0;
+    for (board, actor) in enumerate_positions(2) {
+        let (scored, stats) = bounded.select_scored_action_with_stats(&board, actor);
+        let action = scored.unwrap().action;
+        assert!(ReversiRule::iterate_available_actions(&board, actor).any(|a| a == action));
+        assert!(stats.peak_nodes_retained as usize <= budget, "{}", board);
+        // 上限が厳しくても，負けを避けられる局面では負ける行動を選ばない
+        let expected = exact_payoff(
+            &board,
+            actor,
+            &alpha_beta.select_action(&board, actor).unwrap(),
+        );
+        let payoff = exact_payoff(&board, actor, &action);
+        assert!(payoff >= expected.min(Some(0)), "{}", board);
+        evicted += stats.nodes_evicted;
+    }
+    assert!(evicted > 0);
+}
+
+#[test]
+fn test_best_first_eviction_statistics() {
+    let board = Board::new();
+    let bounded = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(40).with_eviction(200);
+    let tree = bounded.search(&board, Actor::First);
+    assert!(tree.nodes_evicted() > 0);
+    assert!(tree.peak_len() <= 40);
+    assert!(tree.len() <= tree.peak_len());
+    let (_, stats) = bounded.select_scored_action_with_stats(&board, Actor::First);
+    assert_eq!(tree.nodes_evicted(), stats.nodes_evicted);
+    assert_eq!(tree.peak_len() as u64, stats.peak_nodes_retained);
+    assert_eq!(
+        tree.len() as u64 + tree.nodes_evicted(),
+        stats.nodes_evaluated
+    );
+
+    // 上限がない場合は破棄しない
+    let (_, stats) = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(40)
+        .select_scored_action_with_stats(&board, Actor::First);
+    assert_eq!(0, stats.nodes_evicted);
+    assert_eq!(stats.nodes_evaluated, stats.peak_nodes_retained);
+}
+
+#[test]
+fn test_best_first_byte_budget() {
+    let node_size = std::mem::size_of::<BestFirstNode<Board, Placement, i32>>();
+    let bounded = BestFirstStrategy::<ReversiRule, ScoreEvaluator>::new(0)
+        .with_byte_budget(node_size * 50 + node_size / 2);
+    assert_eq!(50, bounded.node_budget());
+}
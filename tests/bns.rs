@@ -0,0 +1,98 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 選択した行動の価値が，αβ法で選択した行動の価値と等しいことを確かめる．
+fn assert_same_value_as_alpha_beta<S>(
+    alpha_beta: &AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize>,
+    strategy: &S,
+    board: &Board,
+    actor: Actor,
+) where
+    S: ScoredStrategy<Board, Placement, Payoff = i32>,
+{
+    let expected = alpha_beta.select_scored_action(board, actor).unwrap();
+    let scored = strategy.select_scored_action(board, actor).unwrap();
+    assert_eq!(expected.payoff, scored.payoff, "{}", board);
+    let after_expected = ReversiRule::translate_state(board, &expected.action);
+    let after_action = ReversiRule::translate_state(board, &scored.action);
+    assert_eq!(
+        alpha_beta.root_payoff(&after_expected, actor.opponent()),
+        alpha_beta.root_payoff(&after_action, actor.opponent()),
+        "{}",
+        board
+    );
+}
+
+#[test]
+fn test_bns_matches_alpha_beta() {
+    for depth in 1..=3 {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let bns = BnsStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(2) {
+            assert_same_value_as_alpha_beta(&alpha_beta, &bns, &board, actor);
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                bns.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bns_with_transposition_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let bns =
+        BnsStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_transposition_table(1 << 12);
+    for (board, actor) in enumerate_positions(3) {
+        assert_same_value_as_alpha_beta(&alpha_beta, &bns, &board, actor);
+    }
+}
+
+#[test]
+fn test_bns_reports_zero_window_searches() {
+    let bns = BnsStrategy::<ReversiRule, ScoreEvaluator, _>::new(2);
+    let (action, stats) = bns.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(action.is_some());
+    assert!(stats.zero_window_searches > 0);
+
+    // 取れる行動が1つしかない場合は，幅0の窓で探索するまでもない
+    // F S F
+    // S F S
+    // S F -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::Second),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::First),
+        (2, 1, Actor::Second),
+        (0, 2, Actor::Second),
+        (1, 2, Actor::First),
+    ]);
+    let (action, stats) = bns.select_action_with_stats(&board, Actor::First);
+    assert_eq!(Some(Placement::new(2, 2, Actor::First)), action);
+    assert_eq!(0, stats.zero_window_searches);
+}
+
+#[test]
+fn test_bns_game_over() {
+    let bns = BnsStrategy::<ReversiRule, ScoreEvaluator, _>::new(3);
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    assert_eq!(None, bns.select_action(&board, Actor::Second));
+    assert_eq!(Some(-100), bns.root_payoff(&board, Actor::Second));
+}
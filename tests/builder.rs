@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_builder_matches_chained_configuration() {
+    let built = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::builder()
+        .search_depth(4)
+        .transposition_table(1 << 10)
+        .move_ordering::<ReverseOrdering<NoOrdering>>()
+        .node_budget(500)
+        .build();
+    let chained = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4)
+        .with_transposition_table(1 << 10)
+        .with_move_ordering::<ReverseOrdering<NoOrdering>>()
+        .with_node_budget(500);
+    for (board, actor) in enumerate_positions(2) {
+        let (expected, expected_stats) = chained.select_action_with_stats(&board, actor);
+        let (action, stats) = built.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action, "{}", board);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+    }
+}
+
+#[test]
+fn test_builder_defaults_to_depth_one() {
+    let built = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, u32>::builder().build();
+    let depth_one = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, u32>::new(1);
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            depth_one.select_scored_action(&board, actor),
+            built.select_scored_action(&board, actor)
+        );
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_constructor_matches_new() {
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    let alpha_beta = construct_alpha_beta_strategy::<ReversiRule, ScoreEvaluator, _>(4);
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4)
+        .select_scored_action(&board, Actor::First);
+    assert_eq!(
+        expected,
+        alpha_beta.select_scored_action(&board, Actor::First)
+    );
+}
+
+#[test]
+fn test_with_depth_and_macro_match_new() {
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, u32>::new(4)
+        .select_scored_action(&board, Actor::First);
+    // ルールと評価関数の型は，変数の型から推論される
+    let inferred: AlphaBetaStrategy<ReversiRule, ScoreEvaluator, u32> = with_depth(4);
+    assert_eq!(
+        expected,
+        inferred.select_scored_action(&board, Actor::First)
+    );
+    let from_macro =
+        alpha_beta_strategy!(rule = ReversiRule, evaluator = ScoreEvaluator, depth = 4u32);
+    assert_eq!(
+        expected,
+        from_macro.select_scored_action(&board, Actor::First)
+    );
+    let reordered =
+        alpha_beta_strategy!(evaluator = ScoreEvaluator, rule = ReversiRule, depth = 4u32,);
+    assert_eq!(
+        expected,
+        reordered.select_scored_action(&board, Actor::First)
+    );
+}
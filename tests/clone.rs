@@ -0,0 +1,75 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_cloned_alpha_beta_selects_same_action() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4)
+        .with_transposition_table(1 << 10)
+        .with_killer_moves()
+        .with_move_orderer(|_: &Board, _: Actor, actions: &mut Vec<Placement>| actions.reverse());
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    // 置換表に記録が残った状態で複製する
+    let expected = strategy.select_action(&board, Actor::First);
+    let cloned = strategy.clone();
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            strategy.select_action(&board, actor),
+            cloned.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+    assert_eq!(expected, cloned.select_action(&board, Actor::First));
+}
+
+#[test]
+fn test_alpha_beta_debug_shows_configuration() {
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, u32>::new(3).with_node_budget(100);
+    let debug = format!("{:?}", strategy);
+    assert!(debug.starts_with("AlphaBetaStrategy"), "{}", debug);
+    assert!(debug.contains("search_depth: 3"), "{}", debug);
+    assert!(debug.contains("node_budget: Some(100)"), "{}", debug);
+    assert!(debug.contains("transposition_table: false"), "{}", debug);
+}
+
+#[test]
+fn test_copied_minimax_and_beam_search_select_same_action() {
+    let minimax = MinimaxStrategy::<ReversiRule, ScoreEvaluator, _>::new(3);
+    let beam = BeamSearchStrategy::<ReversiRule, ScoreEvaluator, _>::new(3, 4);
+    // 複製した後も元のエージェントを使える
+    let (copied_minimax, copied_beam) = (minimax, beam);
+    assert_eq!(minimax, copied_minimax);
+    assert_eq!(beam, copied_beam);
+    assert_ne!(beam, BeamSearchStrategy::new(3, 5));
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            minimax.select_action(&board, actor),
+            copied_minimax.select_action(&board, actor)
+        );
+        assert_eq!(
+            beam.select_action(&board, actor),
+            copied_beam.select_action(&board, actor)
+        );
+    }
+    assert_eq!(
+        "MinimaxStrategy { search_depth: 3 }",
+        format!("{:?}", minimax)
+    );
+}
+
+#[test]
+fn test_cloned_random_strategy_continues_same_sequence() {
+    let strategy = RandomStrategy::<ReversiRule, _>::seeded(7);
+    let board = Board::new();
+    let _ = strategy.select_action(&board, Actor::First);
+    let cloned = strategy.clone();
+    for _ in 0..10 {
+        assert_eq!(
+            strategy.select_action(&board, Actor::First),
+            cloned.select_action(&board, Actor::First)
+        );
+    }
+}
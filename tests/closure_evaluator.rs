@@ -0,0 +1,53 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+closure_evaluator!(struct CenterEvaluator: Board => i32 = |actor, state| {
+    match state.game_result() {
+        Some(GameResult::Win(winner)) if winner == actor => 100,
+        Some(GameResult::Win(_)) => -100,
+        _ => match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
+            Some(occupant) if occupant == &actor => 1,
+            Some(_) => -1,
+            None => 0,
+        },
+    }
+});
+
+#[test]
+fn test_evaluate_payoff() {
+    let board = Board::with_placements(&[(1, 1, Actor::First)]);
+    assert_eq!(
+        1,
+        CenterEvaluator::evaluate_payoff_for(Actor::First, &board)
+    );
+    assert_eq!(
+        -1,
+        CenterEvaluator::evaluate_payoff_for(Actor::Second, &board)
+    );
+    assert_eq!(
+        0,
+        CenterEvaluator::evaluate_payoff_for(Actor::First, &Board::new())
+    );
+}
+
+#[test]
+fn test_search_with_declared_evaluator() {
+    // 中央を取ると評価値が上がるので，1手読みでは中央に置く
+    let strategy = AlphaBetaStrategy::<ReversiRule, CenterEvaluator, _>::new(1);
+    let scored = strategy
+        .select_scored_action(&Board::new(), Actor::First)
+        .unwrap();
+    assert_eq!(Placement::new(1, 1, Actor::First), scored.action);
+    assert_eq!(1, scored.payoff);
+}
+
+#[test]
+fn test_declare_in_function_body() {
+    closure_evaluator!(struct ConstantEvaluator: Board => i32 = |_, _| 7);
+    assert_eq!(
+        7,
+        ConstantEvaluator::evaluate_payoff_for(Actor::First, &Board::new())
+    );
+}
@@ -0,0 +1,377 @@
+//! 結合テストで共通して使用する，3x3の三目並べ．
+//! `examples/reversi.rs`と同じルールと評価関数を持つ．
+#![allow(dead_code)]
+
+use minimax_strategy::*;
+use num::Bounded;
+use std::fmt;
+use std::ops::Neg;
+
+pub const FIELD_SIZE: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    Win(Actor),
+    Draw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Board {
+    occupancies: [[Option<Actor>; FIELD_SIZE]; FIELD_SIZE],
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self {
+            occupancies: [[None; FIELD_SIZE]; FIELD_SIZE],
+        }
+    }
+
+    pub fn at(&self, x: usize, y: usize) -> &Option<Actor> {
+        &self.occupancies[x][y]
+    }
+
+    pub fn at_mut(&mut self, x: usize, y: usize) -> &mut Option<Actor> {
+        &mut self.occupancies[x][y]
+    }
+
+    /// 指定した位置に石を置いた盤面を作る．
+    pub fn with_placements(placements: &[(usize, usize, Actor)]) -> Self {
+        let mut board = Self::new();
+        for &(x, y, actor) in placements {
+            *board.at_mut(x, y) = Some(actor);
+        }
+        board
+    }
+
+    pub fn game_result(&self) -> Option<GameResult> {
+        // 横方向に揃っているところがあるか
+        'row_check: for row in 0..FIELD_SIZE {
+            let start = self.at(0, row);
+            match start {
+                Some(actor) => {
+                    for column in 0..FIELD_SIZE {
+                        if self.at(column, row) != start {
+                            continue 'row_check;
+                        }
+                    }
+                    return Some(GameResult::Win(*actor));
+                }
+                None => continue,
+            }
+        }
+        // 縦方向に揃っているところがあるか
+        'column_check: for column in 0..FIELD_SIZE {
+            let start = self.at(column, 0);
+            match start {
+                Some(actor) => {
+                    for row in 0..FIELD_SIZE {
+                        if self.at(column, row) != start {
+                            continue 'column_check;
+                        }
+                    }
+                    return Some(GameResult::Win(*actor));
+                }
+                None => continue,
+            }
+        }
+        {
+            // 対角方向に揃っているところがあるか
+            let start = self.at(0, 0);
+            if let Some(actor) = start {
+                if (0..FIELD_SIZE).map(|i| self.at(i, i)).all(|a| a == start) {
+                    return Some(GameResult::Win(*actor));
+                }
+            }
+        }
+        {
+            let start = self.at(0, FIELD_SIZE - 1);
+            if let Some(actor) = start {
+                if (0..FIELD_SIZE)
+                    .map(|i| self.at(i, FIELD_SIZE - i - 1))
+                    .all(|a| a == start)
+                {
+                    return Some(GameResult::Win(*actor));
+                }
+            }
+        }
+        // まだ何も置かれていないマスがあれば，決着はついていない
+        for row in 0..FIELD_SIZE {
+            for column in 0..FIELD_SIZE {
+                if self.at(column, row).is_none() {
+                    return None;
+                }
+            }
+        }
+        // すべてのマスが埋まっているが，揃っているものがないので，勝負は引き分け
+        Some(GameResult::Draw)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..FIELD_SIZE {
+            for column in 0..FIELD_SIZE {
+                let displayed_item = match self.at(column, row) {
+                    Some(Actor::First) => "F",
+                    Some(Actor::Second) => "S",
+                    None => "-",
+                };
+                write!(f, "{} ", displayed_item)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl State for Board {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Placement {
+    pub x: usize,
+    pub y: usize,
+    pub actor: Actor,
+}
+
+impl Placement {
+    pub fn new(x: usize, y: usize, actor: Actor) -> Self {
+        Self { x, y, actor }
+    }
+}
+
+impl HistoryKey for Placement {
+    fn history_key(&self) -> usize {
+        let offset = match self.actor {
+            Actor::First => 0,
+            Actor::Second => FIELD_SIZE * FIELD_SIZE,
+        };
+        offset + self.y * FIELD_SIZE + self.x
+    }
+}
+
+impl fmt::Display for Placement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Player {:?} placed at ({}, {}).",
+            self.actor, self.x, self.y
+        )
+    }
+}
+
+impl Action for Placement {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+pub struct ReversiRule {}
+
+impl Rule for ReversiRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = std::vec::IntoIter<Placement>;
+
+    fn terminal_kind(state: &Self::S) -> Option<TerminalKind> {
+        state.game_result().map(|result| match result {
+            GameResult::Win(actor) => TerminalKind::Win(actor),
+            GameResult::Draw => TerminalKind::Draw,
+        })
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        let mut actions = vec![];
+        for row in 0..FIELD_SIZE {
+            for column in 0..FIELD_SIZE {
+                if state.at(column, row).is_none() {
+                    let action = Placement::new(column, row, actor);
+                    actions.push(action);
+                }
+            }
+        }
+        actions.into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        debug_assert!(state.at(action.x, action.y).is_none());
+        let mut next_state = state.clone();
+        *next_state.at_mut(action.x, action.y) = Some(action.actor);
+        next_state
+    }
+
+    /// 盤面を回転，反転させた7通りの盤面を列挙する．
+    fn symmetry_equivalents(state: &Board) -> impl Iterator<Item = Board> {
+        let state = state.clone();
+        (1..8).map(move |symmetry| {
+            let mut board = Board::new();
+            for x in 0..FIELD_SIZE {
+                for y in 0..FIELD_SIZE {
+                    let (x_to, y_to) = transform(symmetry, x, y);
+                    *board.at_mut(x_to, y_to) = *state.at(x, y);
+                }
+            }
+            board
+        })
+    }
+}
+
+/// 指定した番号の対称変換で，マスの位置を移す．
+/// 番号の下位2ビットは90度回転の回数，それ以外は左右反転の有無を表す．
+fn transform(symmetry: usize, x: usize, y: usize) -> (usize, usize) {
+    let last = FIELD_SIZE - 1;
+    let (x, y) = if symmetry >= 4 { (last - x, y) } else { (x, y) };
+    (0..symmetry % 4).fold((x, y), |(x, y), _| (last - y, x))
+}
+
+impl UndoRule for ReversiRule {
+    /// 石を置いたマスの位置．
+    type Undo = (usize, usize);
+
+    fn apply_action(state: &mut Board, action: &Placement) -> Self::Undo {
+        debug_assert!(state.at(action.x, action.y).is_none());
+        *state.at_mut(action.x, action.y) = Some(action.actor);
+        (action.x, action.y)
+    }
+
+    fn undo_action(state: &mut Board, (x, y): Self::Undo) {
+        *state.at_mut(x, y) = None;
+    }
+}
+
+/// Zobristハッシュの乱数表．各マスと各プレイヤーの組み合わせに1つずつ割り当てる．
+const ZOBRIST_KEYS: ZobristKeys<{ 2 * FIELD_SIZE * FIELD_SIZE }> = ZobristKeys::new(0);
+
+fn zobrist_index(x: usize, y: usize, actor: Actor) -> usize {
+    let player = match actor {
+        Actor::First => 0,
+        Actor::Second => 1,
+    };
+    (y * FIELD_SIZE + x) * 2 + player
+}
+
+impl ZobristHashable for ReversiRule {
+    fn zobrist_hash(state: &Board) -> u64 {
+        let mut hash = 0;
+        for y in 0..FIELD_SIZE {
+            for x in 0..FIELD_SIZE {
+                if let Some(actor) = state.at(x, y) {
+                    hash ^= ZOBRIST_KEYS.key(zobrist_index(x, y, *actor));
+                }
+            }
+        }
+        hash
+    }
+
+    fn update_hash(hash: u64, _state: &Board, action: &Placement) -> u64 {
+        hash ^ ZOBRIST_KEYS.key(zobrist_index(action.x, action.y, action.actor))
+    }
+}
+
+impl OutcomeRule for ReversiRule {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BoardEvaluation {
+    Lose,
+    OccupiedCenterMass,
+    Equal,
+    OccupyCenterMass,
+    Win,
+}
+
+impl Bounded for BoardEvaluation {
+    fn min_value() -> Self {
+        BoardEvaluation::Lose
+    }
+
+    fn max_value() -> Self {
+        BoardEvaluation::Win
+    }
+}
+
+impl Neg for BoardEvaluation {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            BoardEvaluation::Lose => BoardEvaluation::Win,
+            BoardEvaluation::OccupiedCenterMass => BoardEvaluation::OccupyCenterMass,
+            BoardEvaluation::Equal => BoardEvaluation::Equal,
+            BoardEvaluation::OccupyCenterMass => BoardEvaluation::OccupiedCenterMass,
+            BoardEvaluation::Win => BoardEvaluation::Lose,
+        }
+    }
+}
+
+pub struct BoardEvaluator;
+
+impl Evaluator<Board> for BoardEvaluator {
+    type Payoff = BoardEvaluation;
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> Self::Payoff {
+        match state.game_result() {
+            Some(GameResult::Win(a)) if a == actor => BoardEvaluation::Win,
+            Some(GameResult::Win(_)) => BoardEvaluation::Lose,
+            Some(GameResult::Draw) => BoardEvaluation::Equal,
+            _ => match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
+                Some(a) if a == &actor => BoardEvaluation::OccupyCenterMass,
+                Some(_) => BoardEvaluation::OccupiedCenterMass,
+                None => BoardEvaluation::Equal,
+            },
+        }
+    }
+}
+
+/// 整数の評価値を返す評価関数．
+/// 勝敗が決まっていない盤面では，中央と四隅の占有状況から評価値を計算する．
+pub struct ScoreEvaluator;
+
+impl Evaluator<Board> for ScoreEvaluator {
+    type Payoff = i32;
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> Self::Payoff {
+        match state.game_result() {
+            Some(GameResult::Win(a)) if a == actor => 100,
+            Some(GameResult::Win(_)) => -100,
+            Some(GameResult::Draw) => 0,
+            _ => {
+                let last = FIELD_SIZE - 1;
+                let squares = [
+                    (FIELD_SIZE / 2, FIELD_SIZE / 2, 4),
+                    (0, 0, 3),
+                    (last, 0, 2),
+                    (0, last, 2),
+                    (last, last, 1),
+                ];
+                squares
+                    .iter()
+                    .map(|&(x, y, score)| match state.at(x, y) {
+                        Some(a) if a == &actor => score,
+                        Some(_) => -score,
+                        None => 0,
+                    })
+                    .sum()
+            }
+        }
+    }
+}
+
+impl StaticEvaluator<Board> for ScoreEvaluator {}
+
+/// 空の盤面から指定した手数だけ進めた，ゲームが終了していない盤面と次の手番をすべて列挙する．
+pub fn enumerate_positions(plies: usize) -> Vec<(Board, Actor)> {
+    let mut positions = vec![(Board::new(), Actor::First)];
+    for _ in 0..plies {
+        positions = positions
+            .into_iter()
+            .flat_map(|(board, actor)| {
+                ReversiRule::iterate_available_actions(&board, actor)
+                    .map(|action| ReversiRule::translate_state(&board, &action))
+                    .filter(|next| ReversiRule::terminal_kind(next).is_none())
+                    .map(|next| (next, actor.opponent()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    positions
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 取れる行動のうち，最初に列挙されたものを選ぶエージェント．評価値は常に0とする．
+struct FirstActionStrategy;
+
+impl ScoredStrategy<Board, Placement> for FirstActionStrategy {
+    type Payoff = i32;
+
+    fn select_scored_action(
+        &self,
+        state: &Board,
+        actor: Actor,
+    ) -> Option<ScoredAction<Placement, i32>> {
+        let action = ReversiRule::iterate_available_actions(state, actor).next()?;
+        Some(ScoredAction::new(action, 0))
+    }
+}
+
+/// 取れる行動のうち，最後に列挙されたものを選ぶエージェント．評価値は常に0とする．
+struct LastActionStrategy;
+
+impl ScoredStrategy<Board, Placement> for LastActionStrategy {
+    type Payoff = i32;
+
+    fn select_scored_action(
+        &self,
+        state: &Board,
+        actor: Actor,
+    ) -> Option<ScoredAction<Placement, i32>> {
+        let action = ReversiRule::iterate_available_actions(state, actor).last()?;
+        Some(ScoredAction::new(action, 0))
+    }
+}
+
+/// 盤面に置かれた駒の数を返す．
+fn placed(board: &Board) -> usize {
+    (0..FIELD_SIZE)
+        .flat_map(|x| (0..FIELD_SIZE).map(move |y| (x, y)))
+        .filter(|&(x, y)| board.at(x, y).is_some())
+        .count()
+}
+
+#[test]
+fn test_switch_to_fallback_after_opening() {
+    let strategy = ComposedStrategy::new(
+        |board: &Board, _| placed(board) < 2,
+        FirstActionStrategy,
+        LastActionStrategy,
+    );
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    let mut ply = 0;
+    while ReversiRule::terminal_kind(&board).is_none() {
+        let action = strategy.select_action(&board, actor).unwrap();
+        let expected = if ply < 2 {
+            FirstActionStrategy.select_action(&board, actor)
+        } else {
+            LastActionStrategy.select_action(&board, actor)
+        };
+        assert_eq!(expected, Some(action), "ply {}", ply);
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+        ply += 1;
+    }
+    assert!(ply > 2);
+}
+
+#[test]
+fn test_nested_composition() {
+    // 先手は最初の行動，後手は序盤だけ最後の行動を選び，その後はαβ法で探索する
+    let strategy = ComposedStrategy::new(
+        |_: &Board, actor| actor == Actor::First,
+        FirstActionStrategy,
+        ComposedStrategy::new(
+            |board: &Board, _| placed(board) < 4,
+            LastActionStrategy,
+            AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE),
+        ),
+    );
+    let board = Board::new();
+    assert_eq!(
+        FirstActionStrategy.select_action(&board, Actor::First),
+        strategy.select_action(&board, Actor::First)
+    );
+    let board = Board::with_placements(&[(0, 0, Actor::First)]);
+    assert_eq!(
+        LastActionStrategy.select_action(&board, Actor::Second),
+        strategy.select_action(&board, Actor::Second)
+    );
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (2, 2, Actor::Second),
+    ]);
+    // 後手は先手の3目並びを防ぐ
+    let action = strategy.select_action(&board, Actor::Second).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+    assert_eq!(
+        strategy
+            .fallback()
+            .fallback()
+            .select_action(&board, Actor::Second),
+        Some(action)
+    );
+}
@@ -0,0 +1,121 @@
+use minimax_strategy::*;
+
+/// 先手が，すぐに引き分けるか，不利だが勝負を続けるかを選ぶゲームの局面．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Position {
+    Start,
+    Draw,
+    Gamble,
+    Balanced,
+}
+
+impl State for Position {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    actor: Actor,
+    to: Position,
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct ContemptRule;
+
+impl Rule for ContemptRule {
+    type S = Position;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Position) -> Option<TerminalKind> {
+        match state {
+            Position::Draw => Some(TerminalKind::Draw),
+            _ => None,
+        }
+    }
+
+    fn iterate_available_actions(state: &Position, actor: Actor) -> Self::ActionIterator {
+        let destinations = match state {
+            Position::Start => vec![Position::Draw, Position::Gamble],
+            _ => vec![Position::Balanced],
+        };
+        destinations
+            .into_iter()
+            .map(|to| Move { actor, to })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(_state: &Position, action: &Move) -> Position {
+        action.to
+    }
+}
+
+/// 勝負を続ける局面を先手にやや不利と評価する評価関数．
+struct ContemptEvaluator;
+
+impl Evaluator<Position> for ContemptEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Position) -> i32 {
+        let payoff = match state {
+            Position::Gamble => -1,
+            _ => 0,
+        };
+        match actor {
+            Actor::First => payoff,
+            Actor::Second => -payoff,
+        }
+    }
+}
+
+#[test]
+fn test_without_contempt_prefers_draw() {
+    let strategy = AlphaBetaStrategy::<ContemptRule, ContemptEvaluator, _>::new(1);
+    let scored = strategy
+        .select_scored_action(&Position::Start, Actor::First)
+        .unwrap();
+    assert_eq!(Position::Draw, scored.action.to);
+    assert_eq!(0, scored.payoff);
+}
+
+#[test]
+fn test_contempt_avoids_draw() {
+    let strategy = AlphaBetaStrategy::<ContemptRule, ContemptEvaluator, _>::new(1).with_contempt(2);
+    let scored = strategy
+        .select_scored_action(&Position::Start, Actor::First)
+        .unwrap();
+    assert_eq!(Position::Gamble, scored.action.to);
+    assert_eq!(-1, scored.payoff);
+}
+
+#[test]
+fn test_negative_contempt_prefers_draw() {
+    let strategy =
+        AlphaBetaStrategy::<ContemptRule, ContemptEvaluator, _>::new(1).with_contempt(-2);
+    let scored = strategy
+        .select_scored_action(&Position::Start, Actor::First)
+        .unwrap();
+    assert_eq!(Position::Draw, scored.action.to);
+    assert_eq!(2, scored.payoff);
+}
+
+#[test]
+fn test_contempt_ignores_balanced_heuristic() {
+    let strategy = AlphaBetaStrategy::<ContemptRule, ContemptEvaluator, _>::new(2).with_contempt(2);
+    assert_eq!(
+        Some(-2),
+        strategy.root_payoff(&Position::Draw, Actor::First)
+    );
+    assert_eq!(
+        Some(-2),
+        strategy.root_payoff(&Position::Draw, Actor::Second)
+    );
+    assert_eq!(
+        Some(0),
+        strategy.root_payoff(&Position::Balanced, Actor::First)
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_countermoves_keep_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let countermove =
+            AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_countermoves();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                countermove.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_countermoves_reduce_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let countermove =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_countermoves();
+
+    let mut nodes_without_countermove = 0;
+    let mut nodes_with_countermove = 0;
+    for (board, actor) in enumerate_positions(1) {
+        let (_, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_countermove += stats.nodes_evaluated;
+        let (_, stats) = countermove.select_action_with_stats(&board, actor);
+        nodes_with_countermove += stats.nodes_evaluated;
+    }
+    assert!(
+        nodes_with_countermove < nodes_without_countermove,
+        "{} {}",
+        nodes_with_countermove,
+        nodes_without_countermove
+    );
+}
+
+#[test]
+fn test_countermoves_with_killer_moves_and_move_orderer() {
+    // キラー手，カウンター手，MoveOrdererを組み合わせても評価値は変わらない
+    let reverse = |_: &Board, _: Actor, actions: &mut Vec<Placement>| actions.reverse();
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let combined = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_orderer(reverse)
+            .with_killer_moves()
+            .with_countermoves()
+            .with_history_heuristic();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                combined.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
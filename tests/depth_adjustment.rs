@@ -0,0 +1,113 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 勝敗が決まった盤面の評価値を，根ノードからの深さに応じて調整する評価関数．
+struct DepthAdjustedEvaluator;
+
+impl Evaluator<Board> for DepthAdjustedEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> i32 {
+        ScoreEvaluator::evaluate_payoff_for(actor, state)
+    }
+
+    fn adjust_for_depth(payoff: i32, ply: u32) -> i32 {
+        let ply = ply as i32;
+        if payoff >= 100 {
+            payoff - ply
+        } else if payoff <= -100 {
+            payoff + ply
+        } else {
+            payoff
+        }
+    }
+}
+
+/// 先手が(2, 2)に置けばすぐに勝てるが，(1, 0)に置いても後で勝てる盤面．
+///
+/// F - -
+/// S F -
+/// S - -
+fn winning_board() -> Board {
+    Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ])
+}
+
+#[test]
+fn test_unadjusted_payoff_may_delay_win() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let scored = strategy
+        .select_scored_action(&winning_board(), Actor::First)
+        .unwrap();
+
+    // 勝つまでの手数を区別しないので，すぐに勝てる行動を選ぶとは限らない
+    assert_eq!(Placement::new(1, 0, Actor::First), scored.action);
+    assert_eq!(100, scored.payoff);
+}
+
+#[test]
+fn test_adjusted_payoff_prefers_faster_win() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+    let minimax = MinimaxStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+    let negamax = NegamaxStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+    let pvs = PvsStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+
+    let expected = Some(ScoredAction::new(Placement::new(2, 2, Actor::First), 99));
+    let board = winning_board();
+    assert_eq!(
+        expected,
+        alpha_beta.select_scored_action(&board, Actor::First)
+    );
+    assert_eq!(expected, minimax.select_scored_action(&board, Actor::First));
+    assert_eq!(expected, negamax.select_scored_action(&board, Actor::First));
+    assert_eq!(expected, pvs.select_scored_action(&board, Actor::First));
+}
+
+#[test]
+fn test_adjusted_payoff_prefers_slower_loss() {
+    // F S -
+    // - F -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (1, 0, Actor::Second),
+    ]);
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let plain = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let adjusted = AlphaBetaStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+
+    // 後手はどう置いても負けるので，負けるまでの手数を区別しなければ，すぐに負ける行動を選びうる
+    let scored = plain.select_scored_action(&board, Actor::Second).unwrap();
+    assert_eq!(Placement::new(2, 0, Actor::Second), scored.action);
+    assert_eq!(-100, scored.payoff);
+
+    // (2, 2)を塞げば，負けを2手遅らせられる
+    let scored = adjusted
+        .select_scored_action(&board, Actor::Second)
+        .unwrap();
+    assert_eq!(Placement::new(2, 2, Actor::Second), scored.action);
+    assert_eq!(-96, scored.payoff);
+}
+
+#[test]
+fn test_adjusted_payoff_keeps_game_outcome() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let plain = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let adjusted = AlphaBetaStrategy::<ReversiRule, DepthAdjustedEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(2) {
+        // 勝敗の符号は，深さに応じた調整で変わらない
+        let plain = plain.root_payoff(&board, actor).unwrap();
+        let adjusted = adjusted.root_payoff(&board, actor).unwrap();
+        assert_eq!(plain.signum(), adjusted.signum(), "{}", board);
+        assert!((plain - adjusted).abs() < FIELD_SIZE as i32 * FIELD_SIZE as i32);
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+fn strategy() -> AlphaBetaStrategy<ReversiRule, BoardEvaluator, usize> {
+    AlphaBetaStrategy::new(FIELD_SIZE * FIELD_SIZE)
+}
+
+#[test]
+fn test_immediate_win() {
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let scored = strategy()
+        .select_scored_action(&board, Actor::First)
+        .unwrap();
+    assert_eq!(Placement::new(2, 0, Actor::First), scored.action);
+    assert_eq!(BoardEvaluation::Win, scored.payoff);
+    assert_eq!(Some(1), scored.depth_to_terminal);
+}
+
+#[test]
+fn test_forced_win_prefers_shortest_line() {
+    // 先手は3手目で勝てる．より遅く勝つ手順もあるが，最短の手順の手数が返る
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (0, 1, Actor::First),
+        (0, 2, Actor::Second),
+        (1, 0, Actor::Second),
+    ]);
+    let (action, stats) = strategy().select_action_with_stats(&board, Actor::First);
+    assert_eq!(Some(3), stats.depth_to_terminal);
+
+    let scored = strategy()
+        .select_scored_action(&board, Actor::First)
+        .unwrap();
+    assert_eq!(action, Some(scored.action));
+    assert_eq!(BoardEvaluation::Win, scored.payoff);
+    assert_eq!(Some(3), scored.depth_to_terminal);
+}
+
+#[test]
+fn test_forced_loss_prefers_longest_line() {
+    // 後手はどう打っても負けるが，4手目まで負けを遅らせられる
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (0, 1, Actor::First),
+        (1, 2, Actor::First),
+        (1, 0, Actor::Second),
+        (2, 0, Actor::Second),
+    ]);
+    let scored = strategy()
+        .select_scored_action(&board, Actor::Second)
+        .unwrap();
+    assert_eq!(BoardEvaluation::Lose, scored.payoff);
+    assert_eq!(Some(4), scored.depth_to_terminal);
+}
+
+#[test]
+fn test_draw_has_no_depth_to_terminal() {
+    let (_, stats) = strategy().select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(None, stats.depth_to_terminal);
+    let scored = strategy()
+        .select_scored_action(&Board::new(), Actor::First)
+        .unwrap();
+    assert_eq!(BoardEvaluation::Equal, scored.payoff);
+    assert_eq!(None, scored.depth_to_terminal);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_first_cannot_force_win_from_start() {
+    let search = DfpnSearch::<ReversiRule>::new(1 << 16);
+    let (outcome, proof) = search.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+    assert_eq!(INFINITE_PROOF_NUMBER, proof.proof_number);
+    assert_eq!(0, proof.disproof_number);
+
+    // 後手の反証手順をたどると，先手が勝てないまま終局する
+    let mut board = Board::new();
+    let line = proof.principal_line();
+    assert!(!line.is_empty());
+    for action in line {
+        board = ReversiRule::translate_state(&board, action);
+    }
+    assert!(ReversiRule::terminal_kind(&board).is_some(), "{}", board);
+    assert_ne!(Some(Actor::First), ReversiRule::winner(&board), "{}", board);
+}
+
+#[test]
+fn test_agrees_with_proof_number_search() {
+    let pns = ProofNumberSearch::<ReversiRule>::new(1 << 20);
+    let dfpn = DfpnSearch::<ReversiRule>::new(1 << 16);
+    let goal = |board: &Board| ReversiRule::winner(board) != Some(Actor::Second);
+    let (expected, _) = pns.solve(&Board::new(), Actor::First, Actor::First, goal);
+    let (outcome, _) = dfpn.solve(&Board::new(), Actor::First, Actor::First, goal);
+    assert_eq!(ProofOutcome::Proved, expected);
+    assert_eq!(expected, outcome);
+}
+
+#[test]
+fn test_prove_forced_win_and_extract_line() {
+    // F - -
+    // - F -
+    // S S -
+    // 先手は(2,2)で勝つ
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 2, Actor::Second),
+        (1, 2, Actor::Second),
+    ]);
+    let search = DfpnSearch::<ReversiRule>::new(1 << 10);
+    let (outcome, proof) = search.solve_win(&board, Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Proved, outcome);
+    assert_eq!(0, proof.proof_number);
+    assert_eq!(
+        vec![&Placement::new(2, 2, Actor::First)],
+        proof.principal_line()
+    );
+}
+
+#[test]
+fn test_solves_with_fewer_entries_than_proof_number_search_needs() {
+    // 証明数探索では解けないノード数の上限を，df-pnでは置換表の容量として与えても解ける
+    let budget = 1 << 8;
+    let pns = ProofNumberSearch::<ReversiRule>::new(budget);
+    let (outcome, tree) = pns.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Unknown, outcome);
+    assert!(tree.len() >= budget);
+
+    let dfpn = DfpnSearch::<ReversiRule>::new(budget);
+    let (outcome, _) = dfpn.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+}
+
+#[test]
+fn test_unknown_when_expansion_limit_reached() {
+    let search = DfpnSearch::<ReversiRule>::new(1 << 10).with_expansion_limit(10);
+    let (outcome, proof) = search.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Unknown, outcome);
+    assert!(proof.principal_line().is_empty());
+    assert!(proof.expansions >= 10);
+}
+
+#[test]
+fn test_solve_terminal_state() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let search = DfpnSearch::<ReversiRule>::new(1);
+    let (outcome, proof) = search.solve_win(&board, Actor::Second, Actor::First);
+    assert_eq!(ProofOutcome::Proved, outcome);
+    assert_eq!(0, proof.expansions);
+    assert!(proof.principal_line().is_empty());
+    let (outcome, _) = search.solve_win(&board, Actor::Second, Actor::Second);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+}
+
+/// 後手が逃げ続ければ同じ局面が繰り返されるゲームの局面．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Position {
+    Home,
+    Away,
+    Goal,
+}
+
+impl State for Position {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    actor: Actor,
+    to: Position,
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct ChaseRule;
+
+impl Rule for ChaseRule {
+    type S = Position;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Position) -> Option<TerminalKind> {
+        match state {
+            Position::Goal => Some(TerminalKind::Win(Actor::First)),
+            _ => None,
+        }
+    }
+
+    fn iterate_available_actions(state: &Position, actor: Actor) -> Self::ActionIterator {
+        let destinations = match state {
+            Position::Home => vec![Position::Away],
+            Position::Away => vec![Position::Goal, Position::Home],
+            Position::Goal => vec![],
+        };
+        destinations
+            .into_iter()
+            .map(|to| Move { actor, to })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(_state: &Position, action: &Move) -> Position {
+        action.to
+    }
+}
+
+impl OutcomeRule for ChaseRule {}
+
+#[test]
+fn test_cycle_policy() {
+    let search = DfpnSearch::<ChaseRule>::new(16);
+    let (outcome, _) = search.solve_win(&Position::Home, Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+
+    let search = search.with_cycle_policy(CyclePolicy::RepetitionIsProof);
+    let (outcome, proof) = search.solve_win(&Position::Home, Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Proved, outcome);
+    let line = proof.principal_line();
+    assert_eq!(Position::Away, line[0].to);
+}
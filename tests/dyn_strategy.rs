@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::Arc;
+
+/// 先手が1手で勝てる盤面．
+///
+/// F - -
+/// S F -
+/// S - -
+fn winning_board() -> Board {
+    Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ])
+}
+
+/// `Strategy`を実装した型だけを受け取り，行動を選ばせる．
+fn select<T>(strategy: &T, state: &Board, actor: Actor) -> Option<Placement>
+where
+    T: Strategy<Board, Placement>,
+{
+    strategy.select_action(state, actor)
+}
+
+#[test]
+fn test_boxed_strategies_in_vec() {
+    // 難易度ごとに異なるエージェントを実行時に選べる
+    let strategies: Vec<Box<dyn Strategy<Board, Placement>>> = vec![
+        Box::new(AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(1)),
+        Box::new(MinimaxStrategy::<ReversiRule, ScoreEvaluator, _>::new(2)),
+    ];
+    let board = winning_board();
+    for strategy in strategies.iter() {
+        assert_eq!(
+            Some(Placement::new(2, 2, Actor::First)),
+            select(strategy, &board, Actor::First)
+        );
+    }
+
+    let expected = [
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(1)
+            .select_action(&Board::new(), Actor::First),
+        MinimaxStrategy::<ReversiRule, ScoreEvaluator, _>::new(2)
+            .select_action(&Board::new(), Actor::First),
+    ];
+    for (strategy, expected) in strategies.iter().zip(expected.iter()) {
+        assert_eq!(*expected, select(strategy, &Board::new(), Actor::First));
+    }
+}
+
+#[test]
+fn test_borrowed_and_shared_strategies() {
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(2);
+    let expected = alpha_beta.select_action(&Board::new(), Actor::Second);
+
+    let borrowed: &dyn Strategy<Board, Placement> = &alpha_beta;
+    assert_eq!(expected, select(&borrowed, &Board::new(), Actor::Second));
+
+    let shared: Arc<dyn Strategy<Board, Placement>> = Arc::new(alpha_beta);
+    assert_eq!(expected, select(&shared, &Board::new(), Actor::Second));
+    // 評価値は返さない
+    let scored = shared
+        .select_scored_action(&Board::new(), Actor::Second)
+        .unwrap();
+    assert_eq!((), scored.payoff);
+}
+
+#[test]
+fn test_no_available_action() {
+    let strategy: Box<dyn Strategy<Board, Placement>> =
+        Box::new(AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3));
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+    ]);
+    assert_eq!(None, select(&strategy, &board, Actor::Second));
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+/// ゴールに先に着いた方が勝つすごろくのゴールの位置．
+const GOAL: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Race {
+    positions: [u8; 2],
+}
+
+impl State for Race {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// 確実に1マス進む．
+    Walk,
+    /// 硬貨を投げ，表なら2マス進み，裏なら進まない．
+    Gamble,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move {
+    kind: Kind,
+    actor: Actor,
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+fn advance(state: &Race, actor: Actor, steps: u8) -> Race {
+    let mut positions = state.positions;
+    positions[actor.index()] = (positions[actor.index()] + steps).min(GOAL);
+    Race { positions }
+}
+
+struct RaceRule;
+
+impl Rule for RaceRule {
+    type S = Race;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Race) -> Option<TerminalKind> {
+        Actor::ALL
+            .iter()
+            .find(|actor| state.positions[actor.index()] == GOAL)
+            .map(|&actor| TerminalKind::Win(actor))
+    }
+
+    fn iterate_available_actions(_state: &Race, actor: Actor) -> Self::ActionIterator {
+        vec![
+            Move {
+                kind: Kind::Walk,
+                actor,
+            },
+            Move {
+                kind: Kind::Gamble,
+                actor,
+            },
+        ]
+        .into_iter()
+    }
+
+    fn translate_state(state: &Race, action: &Move) -> Race {
+        match action.kind {
+            Kind::Walk => advance(state, action.actor, 1),
+            Kind::Gamble => advance(state, action.actor, 2),
+        }
+    }
+}
+
+impl StochasticRule for RaceRule {
+    fn chance_outcomes(state: &Race, action: &Move) -> Vec<(Race, Probability)> {
+        match action.kind {
+            Kind::Walk => vec![(advance(state, action.actor, 1), 1.0)],
+            Kind::Gamble => vec![
+                (advance(state, action.actor, 2), 0.5),
+                (advance(state, action.actor, 0), 0.5),
+            ],
+        }
+    }
+}
+
+fn race_payoff(actor: Actor, state: &Race) -> f64 {
+    match RaceRule::terminal_kind(state) {
+        Some(TerminalKind::Win(winner)) if winner == actor => 1.0,
+        Some(_) => -1.0,
+        None => {
+            let mine = state.positions[actor.index()] as f64;
+            let theirs = state.positions[actor.opponent().index()] as f64;
+            (mine - theirs) / GOAL as f64 * 0.5
+        }
+    }
+}
+
+/// 勝ちを1，負けを-1とし，それ以外は位置の差に応じて-0.5から0.5の値を返す．
+struct RaceEvaluator;
+
+impl Evaluator<Race> for RaceEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Race) -> f64 {
+        race_payoff(actor, state)
+    }
+}
+
+static COUNTED_EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// 評価した回数を数える`RaceEvaluator`．
+struct CountingRaceEvaluator;
+
+impl Evaluator<Race> for CountingRaceEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Race) -> f64 {
+        COUNTED_EVALUATIONS.fetch_add(1, AtomicOrdering::Relaxed);
+        race_payoff(actor, state)
+    }
+}
+
+fn all_races() -> Vec<Race> {
+    let mut races = vec![];
+    for first in 0..GOAL {
+        for second in 0..GOAL {
+            races.push(Race {
+                positions: [first, second],
+            });
+        }
+    }
+    races
+}
+
+#[test]
+fn test_expectimax_takes_expectation_at_chance_nodes() {
+    // 歩けば位置3で0.375，賭ければ確率1/2で勝ち，確率1/2で位置2のまま0.25となり期待値は0.625
+    let strategy = ExpectimaxStrategy::<RaceRule, RaceEvaluator, _>::new(1);
+    let state = Race { positions: [2, 0] };
+    let scored = strategy.select_scored_action(&state, Actor::First).unwrap();
+    assert_eq!(Kind::Gamble, scored.action.kind);
+    assert_eq!(0.625, scored.payoff);
+}
+
+#[test]
+fn test_expectimax_prefers_certain_win() {
+    let strategy = ExpectimaxStrategy::<RaceRule, RaceEvaluator, _>::new(3);
+    let state = Race { positions: [3, 3] };
+    let scored = strategy
+        .select_scored_action(&state, Actor::Second)
+        .unwrap();
+    assert_eq!(Kind::Walk, scored.action.kind);
+    assert_eq!(1.0, scored.payoff);
+}
+
+#[test]
+fn test_star1_pruning_keeps_payoffs_and_reduces_evaluations() {
+    let depth = 6;
+    let plain = ExpectimaxStrategy::<RaceRule, CountingRaceEvaluator, _>::new(depth);
+    let pruned = ExpectimaxStrategy::<RaceRule, CountingRaceEvaluator, _>::new(depth)
+        .with_payoff_bounds(-1.0, 1.0);
+
+    let mut plain_evaluations = 0;
+    let mut pruned_evaluations = 0;
+    for state in all_races() {
+        for &actor in Actor::ALL.iter() {
+            COUNTED_EVALUATIONS.store(0, AtomicOrdering::Relaxed);
+            let expected = plain.select_scored_action(&state, actor).unwrap();
+            plain_evaluations += COUNTED_EVALUATIONS.swap(0, AtomicOrdering::Relaxed);
+            let scored = pruned.select_scored_action(&state, actor).unwrap();
+            pruned_evaluations += COUNTED_EVALUATIONS.swap(0, AtomicOrdering::Relaxed);
+            assert!(
+                (expected.payoff - scored.payoff).abs() < 1e-9,
+                "{:?} {:?}: {} != {}",
+                state,
+                actor,
+                expected.payoff,
+                scored.payoff
+            );
+        }
+    }
+    assert!(
+        pruned_evaluations < plain_evaluations,
+        "{} >= {}",
+        pruned_evaluations,
+        plain_evaluations
+    );
+}
+
+#[test]
+fn test_expectimax_no_action_on_game_over() {
+    let strategy = ExpectimaxStrategy::<RaceRule, RaceEvaluator, _>::new(3);
+    let state = Race {
+        positions: [GOAL, 0],
+    };
+    assert!(strategy.select_action(&state, Actor::Second).is_none());
+    assert_eq!(-1.0, strategy.root_payoff(&state, Actor::Second));
+}
+
+#[test]
+#[should_panic]
+fn test_payoff_bounds_must_be_ordered() {
+    let _ =
+        ExpectimaxStrategy::<RaceRule, RaceEvaluator, u32>::new(3).with_payoff_bounds(1.0, -1.0);
+}
+
+/// 偶然事象のない三目並べでは，すべての遷移が確率1で起こる．
+impl StochasticRule for ReversiRule {}
+
+/// 勝ちを1，負けを-1，それ以外を0とする三目並べの評価関数．
+struct OutcomeEvaluator;
+
+impl Evaluator<Board> for OutcomeEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> f64 {
+        match ReversiRule::winner(state) {
+            Some(winner) if winner == actor => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        }
+    }
+}
+
+#[test]
+fn test_deterministic_rule_reduces_to_minimax() {
+    let strategy = ExpectimaxStrategy::<ReversiRule, OutcomeEvaluator, _>::new(9)
+        .with_payoff_bounds(-1.0, 1.0);
+    // 三目並べは，互いに最善を尽くせば引き分けになる
+    assert_eq!(0.0, strategy.root_payoff(&Board::new(), Actor::First));
+
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let scored = strategy.select_scored_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (scored.action.x, scored.action.y));
+    assert_eq!(1.0, scored.payoff);
+}
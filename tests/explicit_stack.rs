@@ -0,0 +1,176 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_explicit_stack_matches_recursive_alpha_beta() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let explicit_stack =
+            ExplicitStackAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(2) {
+            assert_eq!(
+                alpha_beta.select_scored_action(&board, actor),
+                explicit_stack.select_scored_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+            // 枝刈りの条件が同じなら，訪れるノードも同じになる
+            let (_, expected) = alpha_beta.select_action_with_stats(&board, actor);
+            let (_, stats) = explicit_stack.select_action_with_stats(&board, actor);
+            assert_eq!(expected.nodes_evaluated, stats.nodes_evaluated);
+            assert_eq!(expected.alpha_cutoffs, stats.alpha_cutoffs);
+            assert_eq!(expected.beta_cutoffs, stats.beta_cutoffs);
+        }
+    }
+}
+
+#[test]
+fn test_explicit_stack_matches_recursive_alpha_beta_with_bounded_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let explicit_stack =
+            ExplicitStackAlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(2) {
+            // 勝敗が決まるまでの手数も含めて一致する
+            assert_eq!(
+                alpha_beta.select_scored_action(&board, actor),
+                explicit_stack.select_scored_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_explicit_stack_prefers_faster_win() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    // 深く探索すると遅れて勝つ手順も見つかるが，すぐに勝てる行動を選ぶ
+    let strategy = ExplicitStackAlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(5);
+    let scored = strategy.select_scored_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (scored.action.x, scored.action.y));
+    assert_eq!(BoardEvaluation::Win, scored.payoff);
+    assert_eq!(Some(1), scored.depth_to_terminal);
+}
+
+#[test]
+fn test_explicit_stack_at_game_end() {
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+    ]);
+    let strategy = ExplicitStackAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3);
+    assert_eq!(None, strategy.select_action(&board, Actor::Second));
+    assert_eq!(Some(-100), strategy.root_payoff(&board, Actor::Second));
+}
+
+/// 石の山から石を取り合い，最後の石を取ったプレイヤーが勝つゲームの状態．
+/// 石が3個以上ある間は1個ずつしか取れないので，ゲーム木はほとんど分岐しない．
+struct Countdown {
+    stones: u32,
+    last_taker: Option<Actor>,
+}
+
+impl State for Countdown {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Take {
+    count: u32,
+    actor: Actor,
+}
+
+impl Action for Take {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct CountdownRule;
+
+impl Rule for CountdownRule {
+    type S = Countdown;
+    type A = Take;
+    type ActionIterator = std::vec::IntoIter<Take>;
+
+    fn terminal_kind(state: &Countdown) -> Option<TerminalKind> {
+        match (state.stones, state.last_taker) {
+            (0, Some(taker)) => Some(TerminalKind::Win(taker)),
+            _ => None,
+        }
+    }
+
+    fn iterate_available_actions(state: &Countdown, actor: Actor) -> Self::ActionIterator {
+        let max_count = if state.stones <= 2 { state.stones } else { 1 };
+        (1..=max_count)
+            .map(|count| Take { count, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Countdown, action: &Take) -> Countdown {
+        Countdown {
+            stones: state.stones - action.count,
+            last_taker: Some(action.actor),
+        }
+    }
+}
+
+closure_evaluator!(struct WinEvaluator: Countdown => i32 = |actor, state| {
+    match CountdownRule::terminal_kind(state) {
+        Some(TerminalKind::Win(winner)) if winner == actor => 1,
+        Some(TerminalKind::Win(_)) => -1,
+        _ => 0,
+    }
+});
+
+#[test]
+fn test_explicit_stack_searches_very_deep_tree() {
+    // 再帰呼び出しで探索するとスレッドのスタックを使い果たす深さ
+    let stones = 100_000;
+    let state = Countdown {
+        stones,
+        last_taker: None,
+    };
+    let strategy =
+        ExplicitStackAlphaBetaStrategy::<CountdownRule, WinEvaluator, _>::new(stones as usize);
+    // 石の数が偶数なら，石が2個のときに先手の手番となり，2個とも取って勝つ
+    let (action, stats) = strategy.select_action_with_stats(&state, Actor::First);
+    assert_eq!(
+        Some(Take {
+            count: 1,
+            actor: Actor::First
+        }),
+        action
+    );
+    assert_eq!(Some(1), strategy.root_payoff(&state, Actor::First));
+    assert!(stats.nodes_evaluated >= stones as u64);
+
+    // 浅いゲーム木では，再帰呼び出しで探索した結果と一致する
+    for stones in 1..40 {
+        let state = Countdown {
+            stones,
+            last_taker: None,
+        };
+        let depth = stones as usize;
+        let alpha_beta = AlphaBetaStrategy::<CountdownRule, WinEvaluator, _>::new(depth);
+        let explicit_stack =
+            ExplicitStackAlphaBetaStrategy::<CountdownRule, WinEvaluator, _>::new(depth);
+        assert_eq!(
+            alpha_beta.select_scored_action(&state, Actor::Second),
+            explicit_stack.select_scored_action(&state, Actor::Second)
+        );
+    }
+}
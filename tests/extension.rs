@@ -0,0 +1,213 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::cmp::Ordering;
+
+/// 最初の行動を選んだ後は，取れる行動が1つしかないゲームの局面．
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Trap {
+    ply: u32,
+    /// 先手が最初に選んだ数．
+    choice: i32,
+    /// 先手にとっての得点．
+    score: i32,
+}
+
+impl State for Trap {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pick {
+    actor: Actor,
+    value: i32,
+}
+
+impl Action for Pick {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+/// 先手が1か2を選んで得点とし，後手が何もしない1手を挟んだ後，
+/// 2を選んでいた場合は先手が唯一の行動で5点を失うゲーム．
+struct TrapRule;
+
+impl Rule for TrapRule {
+    type S = Trap;
+    type A = Pick;
+    type ActionIterator = std::vec::IntoIter<Pick>;
+
+    fn terminal_kind(state: &Trap) -> Option<TerminalKind> {
+        if state.ply < 3 {
+            return None;
+        }
+        Some(match state.score.cmp(&0) {
+            Ordering::Greater => TerminalKind::Win(Actor::First),
+            Ordering::Less => TerminalKind::Win(Actor::Second),
+            Ordering::Equal => TerminalKind::Draw,
+        })
+    }
+
+    fn iterate_available_actions(state: &Trap, actor: Actor) -> Self::ActionIterator {
+        let values = match state.ply {
+            0 => vec![2, 1],
+            2 if state.choice == 2 => vec![-5],
+            _ => vec![0],
+        };
+        values
+            .into_iter()
+            .map(|value| Pick { actor, value })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Trap, action: &Pick) -> Trap {
+        Trap {
+            ply: state.ply + 1,
+            choice: if state.ply == 0 {
+                action.value
+            } else {
+                state.choice
+            },
+            score: state.score + action.value,
+        }
+    }
+}
+
+struct TrapEvaluator;
+
+impl Evaluator<Trap> for TrapEvaluator {
+    type Payoff = i32;
+    fn evaluate_payoff_for(actor: Actor, state: &Trap) -> i32 {
+        match actor {
+            Actor::First => state.score,
+            Actor::Second => -state.score,
+        }
+    }
+}
+
+fn trap() -> Trap {
+    Trap {
+        ply: 0,
+        choice: 0,
+        score: 0,
+    }
+}
+
+#[test]
+fn test_single_reply_extends_search() {
+    let alpha_beta = AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2);
+    let extended = AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2).with_extensions(10);
+
+    // 深さ2では，2を選んだ後に失う得点が見えない
+    let action = alpha_beta.select_action(&trap(), Actor::First).unwrap();
+    assert_eq!(2, action.value);
+
+    // 取れる行動が1つしかないノードを延長すれば，失う得点まで読める
+    let (action, stats) = extended.select_action_with_stats(&trap(), Actor::First);
+    assert_eq!(1, action.unwrap().value);
+    assert_eq!(Some(1), extended.root_payoff(&trap(), Actor::First));
+    // 後手の何もしない行動と，先手の最後の行動を，最初の行動ごとに延長する
+    assert_eq!(4, stats.extensions);
+    assert_eq!(3, stats.max_depth_reached);
+}
+
+#[test]
+fn test_extension_budget_limits_extensions() {
+    for budget in 0..=5 {
+        let strategy =
+            AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2).with_extensions(budget);
+        let (_, stats) = strategy.select_action_with_stats(&trap(), Actor::First);
+        assert_eq!(u64::from(budget.min(4)), stats.extensions);
+    }
+}
+
+#[test]
+fn test_no_extensions_by_default() {
+    let strategy =
+        AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2).with_singular_extensions(1);
+    let (action, stats) = strategy.select_action_with_stats(&trap(), Actor::First);
+
+    assert_eq!(2, action.unwrap().value);
+    assert_eq!(0, stats.extensions);
+    assert_eq!(2, stats.max_depth_reached);
+}
+
+#[test]
+fn test_extension_policy_extends_requested_nodes() {
+    // 後手の何もしない行動の後を，1手深く探索する
+    let policy = |_: &Trap, pick: &Pick| (pick.actor == Actor::Second) as u8;
+    let strategy =
+        AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2).with_extension_policy(policy, 4);
+    let (action, stats) = strategy.select_action_with_stats(&trap(), Actor::First);
+
+    assert_eq!(1, action.unwrap().value);
+    assert_eq!(2, stats.extensions);
+    assert_eq!(3, stats.max_depth_reached);
+}
+
+#[test]
+fn test_max_line_extension_limits_policy() {
+    let strategy = AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2)
+        .with_extension_policy(|_: &Trap, _: &Pick| u8::MAX, 0);
+    let (action, stats) = strategy.select_action_with_stats(&trap(), Actor::First);
+
+    assert_eq!(2, action.unwrap().value);
+    assert_eq!(0, stats.extensions);
+    assert_eq!(2, stats.max_depth_reached);
+}
+
+#[test]
+fn test_max_line_extension_applies_to_single_reply() {
+    let strategy = AlphaBetaStrategy::<TrapRule, TrapEvaluator, _>::new(2)
+        .with_extensions(10)
+        .with_extension_policy(|_: &Trap, _: &Pick| 0, 1);
+    let (action, stats) = strategy.select_action_with_stats(&trap(), Actor::First);
+
+    // 後手の何もしない行動だけを延長し，先手の最後の行動は延長しない
+    assert_eq!(1, action.unwrap().value);
+    assert_eq!(2, stats.extensions);
+    assert_eq!(3, stats.max_depth_reached);
+}
+
+#[test]
+fn test_singular_extension_with_transposition_table() {
+    let depth = 6;
+    let single_reply: IterativeDeepeningStrategy<_, _, _> =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_transposition_table(1 << 12)
+            .with_extensions(u32::MAX)
+            .into();
+    let singular: IterativeDeepeningStrategy<_, _, _> =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_transposition_table(1 << 12)
+            .with_extensions(u32::MAX)
+            .with_singular_extensions(1)
+            .into();
+
+    let single_reply = single_reply.search(&Board::new(), Actor::First);
+    let singular = singular.search(&Board::new(), Actor::First);
+
+    // 置換表に記録された最善の行動が際立って良いノードでも延長する
+    assert!(singular.best_action().is_some());
+    assert!(singular.stats().extensions > single_reply.stats().extensions);
+}
+
+#[test]
+fn test_extensions_keep_winning_payoff() {
+    // F - -
+    // S F -
+    // S - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ]);
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3)
+        .with_transposition_table(1 << 10)
+        .with_extensions(16)
+        .with_singular_extensions(5);
+
+    assert_eq!(Some(100), strategy.root_payoff(&board, Actor::First));
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_build_expands_every_action() {
+    let builder = GameTreeBuilder::<ReversiRule>::new();
+
+    let tree = builder.build(&Board::new(), 0, Actor::First);
+    assert_eq!(Board::new(), tree.state);
+    assert!(tree.children.is_empty());
+    assert_eq!(None, tree.game_result);
+
+    let tree = builder.build(&Board::new(), 2, Actor::First);
+    assert_eq!(9, tree.children.len());
+    for (action, child) in tree.children.iter() {
+        assert_eq!(Actor::First, action.actor);
+        assert_eq!(
+            ReversiRule::translate_state(&Board::new(), action),
+            child.state
+        );
+        assert_eq!(8, child.children.len());
+        for (action, grandchild) in child.children.iter() {
+            assert_eq!(Actor::Second, action.actor);
+            assert!(grandchild.children.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_build_stops_at_game_over() {
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(&board, 3, Actor::Second);
+    assert!(tree.children.is_empty());
+    assert_eq!(Some(GameOutcome::Win(Actor::First)), tree.game_result);
+}
+
+#[test]
+fn test_full_game_tree_of_tic_tac_toe() {
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(
+        &Board::new(),
+        FIELD_SIZE * FIELD_SIZE,
+        Actor::First,
+    );
+    // 三目並べの終局までの手順は255168通りある
+    let finished = tree
+        .iter_nodes()
+        .filter(|(_, state, _)| ReversiRule::terminal_kind(state).is_some())
+        .count();
+    assert_eq!(255_168, finished);
+    assert_eq!(549_946, tree.iter_nodes().count());
+}
+
+#[test]
+fn test_iter_nodes_is_breadth_first() {
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(&Board::new(), 2, Actor::First);
+    let nodes = tree.iter_nodes().collect::<Vec<_>>();
+    assert_eq!(1 + 9 + 9 * 8, nodes.len());
+
+    let (depth, state, action) = nodes[0];
+    assert_eq!(0, depth);
+    assert_eq!(&Board::new(), state);
+    assert_eq!(None, action);
+    // 浅いノードから順に列挙される
+    assert!(nodes.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    for (depth, state, action) in nodes.into_iter().skip(1) {
+        let action = action.unwrap();
+        let expected_actor = if depth == 1 {
+            Actor::First
+        } else {
+            Actor::Second
+        };
+        assert_eq!(expected_actor, action.actor);
+        assert_eq!(&Some(action.actor), state.at(action.x, action.y));
+    }
+}
+
+/// 引用符で囲まれた文字列の外で，波括弧の対応がとれているか．
+fn braces_are_balanced(dot: &str) -> bool {
+    let mut depth = 0i32;
+    let mut in_quote = false;
+    let mut escaped = false;
+    for c in dot.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quote => escaped = true,
+            '"' => in_quote = !in_quote,
+            '{' if !in_quote => depth += 1,
+            '}' if !in_quote => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    depth == 0 && !in_quote
+}
+
+#[test]
+fn test_to_dot_is_valid_digraph() {
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(&Board::new(), 2, Actor::First);
+    let dot = tree.to_dot();
+    assert!(dot.starts_with("digraph"));
+    assert!(braces_are_balanced(&dot));
+    assert_eq!(9 + 9 * 8, dot.matches(" -> ").count());
+    let nodes = dot
+        .lines()
+        .filter(|line| line.contains("[label=") && !line.contains(" -> "))
+        .count();
+    assert_eq!(1 + 9 + 9 * 8, nodes);
+    assert!(!dot.contains("color=red"));
+}
+
+#[test]
+fn test_to_dot_limits_depth() {
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(&Board::new(), 3, Actor::First);
+    let config = DotConfig {
+        max_depth: 1,
+        highlight_pv: false,
+    };
+    let dot = tree.to_dot_with(&config);
+    assert!(braces_are_balanced(&dot));
+    assert_eq!(9, dot.matches(" -> ").count());
+}
+
+#[test]
+fn test_to_dot_highlights_principal_variation() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let tree = GameTreeBuilder::<ReversiRule>::new().build(&board, 5, Actor::First);
+    let config = DotConfig {
+        highlight_pv: true,
+        ..DotConfig::default()
+    };
+    let dot = tree.to_dot_with(&config);
+    assert!(braces_are_balanced(&dot));
+    // 先手はすぐに勝つので，最善手順は1手だけ
+    let highlighted = dot
+        .lines()
+        .filter(|line| line.contains("penwidth=2"))
+        .collect::<Vec<_>>();
+    assert_eq!(1, highlighted.len());
+    assert!(highlighted[0].contains("x: 2, y: 0"), "{}", highlighted[0]);
+    assert!(dot.contains("First wins"));
+}
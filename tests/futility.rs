@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 置くとゲームが終わる行動か．
+fn is_decisive(state: &Board, action: &Placement) -> bool {
+    ReversiRule::terminal_kind(&ReversiRule::translate_state(state, action)).is_some()
+}
+
+fn positions() -> Vec<(Board, Actor)> {
+    (0..=3).flat_map(enumerate_positions).collect()
+}
+
+#[test]
+fn test_futility_pruning_is_off_by_default() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4);
+    let (_, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(0, stats.futility_prunes);
+}
+
+#[test]
+fn test_futility_pruning_reduces_visited_nodes() {
+    let depth = 4;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let futility = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_futility_pruning(vec![8, 12])
+        .with_futility_exemption(is_decisive);
+
+    let mut nodes_without_futility = 0;
+    let mut nodes_with_futility = 0;
+    let mut futility_prunes = 0;
+    for (board, actor) in positions() {
+        let (_, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_futility += stats.nodes_evaluated;
+        let (_, stats) = futility.select_action_with_stats(&board, actor);
+        nodes_with_futility += stats.nodes_evaluated;
+        futility_prunes += stats.futility_prunes;
+        // 余裕が十分なら，評価値は変わらない
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            futility.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+    assert!(futility_prunes > 0);
+    assert!(
+        nodes_with_futility < nodes_without_futility,
+        "{} {}",
+        nodes_with_futility,
+        nodes_without_futility
+    );
+}
+
+#[test]
+fn test_futility_exemption_protects_decisive_actions() {
+    // 余裕が小さいと，勝敗が決まる行動を見落として評価値が変わる．
+    // そのような行動を除外すれば，評価値は変わらない
+    let depth = 3;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let unsound = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_futility_pruning(vec![3]);
+    let exempted = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_futility_pruning(vec![3])
+        .with_futility_exemption(is_decisive);
+
+    let mut mismatches = 0;
+    for (board, actor) in positions() {
+        let expected = alpha_beta.root_payoff(&board, actor);
+        if unsound.root_payoff(&board, actor) != expected {
+            mismatches += 1;
+        }
+        assert_eq!(expected, exempted.root_payoff(&board, actor), "{}", board);
+    }
+    assert!(mismatches > 0);
+}
+
+#[test]
+fn test_exempt_actions_are_never_pruned() {
+    let depth = 4;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let exempted = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_futility_pruning(vec![0, 0])
+        .with_futility_exemption(|_: &Board, _: &Placement| true);
+    for (board, actor) in enumerate_positions(2) {
+        let (expected, expected_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (action, stats) = exempted.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+        assert_eq!(0, stats.futility_prunes);
+    }
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_history_heuristic_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let history = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_history_heuristic();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                history.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_history_heuristic_reduces_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let history =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_history_heuristic();
+
+    let mut nodes_without_history = 0;
+    let mut nodes_with_history = 0;
+    for (board, actor) in enumerate_positions(1) {
+        let (_, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_history += stats.nodes_evaluated;
+        let (_, stats) = history.select_action_with_stats(&board, actor);
+        nodes_with_history += stats.nodes_evaluated;
+    }
+    assert!(
+        nodes_with_history < nodes_without_history,
+        "{} {}",
+        nodes_with_history,
+        nodes_without_history
+    );
+}
+
+#[test]
+fn test_history_persists_until_cleared() {
+    let depth = 6;
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_history_heuristic();
+    let (_, first_stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+
+    // 前回の行動選択の実績を使うので，探索順が変わる
+    let (_, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+    assert_ne!(first_stats.nodes_evaluated, stats.nodes_evaluated);
+
+    // 実績を消去すれば，最初の行動選択と同じだけ探索する
+    strategy.clear_history();
+    let (_, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(first_stats.nodes_evaluated, stats.nodes_evaluated);
+}
+
+#[test]
+fn test_history_heuristic_with_killer_moves() {
+    // History HeuristicとKiller Moveは独立に有効化でき，組み合わせても評価値は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let both = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_history_heuristic()
+        .with_killer_moves();
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            both.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_internal_iterative_deepening_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let iid = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_transposition_table(1 << 12)
+            .with_internal_iterative_deepening(3, 2);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                iid.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_internal_iterative_deepening_keeps_action() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let table = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12);
+    let iid = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12)
+        .with_internal_iterative_deepening(3, 2);
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            table.select_action(&board, actor),
+            iid.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_internal_iterative_deepening_searches_are_counted() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let iid = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12)
+        .with_internal_iterative_deepening(3, 2);
+    let (_, stats) = iid.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(stats.iid_searches > 0);
+
+    // 置換表を用いない場合は，浅い探索の結果を取り出せないので行わない
+    let without_table = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_internal_iterative_deepening(3, 2);
+    let (_, stats) = without_table.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(0, stats.iid_searches);
+}
+
+#[test]
+#[should_panic]
+fn test_internal_iterative_deepening_requires_positive_reduction() {
+    AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(4)
+        .with_internal_iterative_deepening(3, 0);
+}
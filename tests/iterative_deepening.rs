@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_iterative_deepening_root_payoff_matches_alpha_beta() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let iterative = IterativeDeepeningStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                iterative.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_iterative_deepening_selects_winning_action() {
+    // F - -
+    // S F -
+    // S - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ]);
+    let strategy = IterativeDeepeningStrategy::<ReversiRule, BoardEvaluator, _>::new(5);
+
+    let action = strategy.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 2), (action.x, action.y));
+}
+
+#[test]
+fn test_iterative_deepening_zero_depth() {
+    let strategy = IterativeDeepeningStrategy::<ReversiRule, BoardEvaluator, _>::new(0);
+    assert!(strategy
+        .select_action(&Board::new(), Actor::First)
+        .is_none());
+    assert!(strategy.root_payoff(&Board::new(), Actor::First).is_none());
+}
+
+#[test]
+fn test_iterative_deepening_reports_each_depth() {
+    let strategy = IterativeDeepeningStrategy::<ReversiRule, BoardEvaluator, _>::new(4);
+    let result = strategy.search(&Board::new(), Actor::First);
+
+    assert_eq!(Some(4), result.completed_depth());
+    let depths = result
+        .iterations()
+        .iter()
+        .map(|iteration| iteration.depth)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 3, 4], depths);
+    assert!(result
+        .iterations()
+        .iter()
+        .all(|iteration| iteration.best_action.is_some()));
+    // 最も深い探索の結果が，最終的な結果となる
+    let last = result.iterations().last().unwrap();
+    assert_eq!(last.best_action.as_ref(), result.best_action());
+    assert_eq!(last.payoff, result.payoff());
+}
+
+#[test]
+fn test_iterative_deepening_can_stop_early() {
+    let strategy = IterativeDeepeningStrategy::<ReversiRule, BoardEvaluator, _>::new(9);
+    let result =
+        strategy.search_while(&Board::new(), Actor::First, |iteration| iteration.depth < 2);
+
+    assert_eq!(Some(2), result.completed_depth());
+    assert!(result.best_action().is_some());
+}
+
+#[test]
+fn test_iterative_deepening_from_alpha_beta() {
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+    let strategy = IterativeDeepeningStrategy::from(alpha_beta);
+    assert_eq!(3, strategy.max_depth());
+}
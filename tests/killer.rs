@@ -0,0 +1,81 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_killer_moves_keep_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let killer =
+            AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                killer.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_killer_moves_reduce_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let killer =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+
+    let mut nodes_without_killer = 0;
+    let mut nodes_with_killer = 0;
+    for (board, actor) in enumerate_positions(1) {
+        let (_, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_killer += stats.nodes_evaluated;
+        let (_, stats) = killer.select_action_with_stats(&board, actor);
+        nodes_with_killer += stats.nodes_evaluated;
+    }
+    assert!(
+        nodes_with_killer < nodes_without_killer,
+        "{} {}",
+        nodes_with_killer,
+        nodes_without_killer
+    );
+}
+
+#[test]
+fn test_killer_hits_are_counted() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let killer =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+    let (_, stats) = killer.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(stats.killer_hits > 0);
+    assert!(stats.killer_hits <= stats.alpha_cutoffs + stats.beta_cutoffs);
+
+    // キラー手を用いない場合は数えない
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let (_, stats) = alpha_beta.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(0, stats.killer_hits);
+}
+
+#[test]
+fn test_killer_moves_with_move_orderer() {
+    // キラー手の後は，指定した順に調べる
+    let reverse = |_: &Board, _: Actor, actions: &mut Vec<Placement>| actions.reverse();
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let killer = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_orderer(reverse)
+            .with_killer_moves();
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                killer.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 深いノードほど大きく浅くする．
+fn reduction(remaining_depth: usize, _position: usize) -> usize {
+    if remaining_depth >= 6 {
+        2
+    } else {
+        1
+    }
+}
+
+fn positions() -> Vec<(Board, Actor)> {
+    (0..=2).flat_map(enumerate_positions).collect()
+}
+
+#[test]
+fn test_late_move_reductions_match_unreduced_search() {
+    // 終局まで読む場合，浅く探索しても評価値と行動の価値は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_killer_moves();
+    let reduced = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_killer_moves()
+        .with_late_move_reductions(2, reduction);
+
+    let mut nodes_without_reduction = 0;
+    let mut nodes_with_reduction = 0;
+    let mut late_move_re_searches = 0;
+    for (board, actor) in positions() {
+        let (expected, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_reduction += stats.nodes_evaluated;
+        assert_eq!(0, stats.late_move_re_searches);
+        let (action, stats) = reduced.select_action_with_stats(&board, actor);
+        nodes_with_reduction += stats.nodes_evaluated;
+        late_move_re_searches += stats.late_move_re_searches;
+
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            reduced.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+        let after_expected = ReversiRule::translate_state(&board, &expected.unwrap());
+        let after_action = ReversiRule::translate_state(&board, &action.unwrap());
+        assert_eq!(
+            alpha_beta.root_payoff(&after_expected, actor.opponent()),
+            alpha_beta.root_payoff(&after_action, actor.opponent()),
+            "{}",
+            board
+        );
+    }
+    assert!(late_move_re_searches > 0);
+    assert!(
+        nodes_with_reduction < nodes_without_reduction,
+        "{} {}",
+        nodes_with_reduction,
+        nodes_without_reduction
+    );
+}
+
+#[test]
+fn test_reduction_exemption() {
+    // すべての行動を除外すれば，浅く探索しない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let exempted = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_late_move_reductions(0, reduction)
+        .with_reduction_exemption(|_: &Board, _: &Placement| true);
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, expected_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (action, stats) = exempted.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+        assert_eq!(0, stats.late_move_re_searches);
+    }
+}
+
+#[test]
+fn test_late_move_reductions_start_after() {
+    // 調べる行動の数より後から浅くする場合，探索は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let reduced = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_late_move_reductions(FIELD_SIZE * FIELD_SIZE, reduction);
+    let (_, expected_stats) = alpha_beta.select_action_with_stats(&Board::new(), Actor::First);
+    let (_, stats) = reduced.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+}
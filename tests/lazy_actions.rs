@@ -0,0 +1,134 @@
+use minimax_strategy::*;
+use num::Bounded;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// 先手と後手が0か1を1回ずつ選ぶゲームの状態．
+/// 選ばれた数の列で表す．
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Path(Vec<u8>);
+
+impl State for Path {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Choice {
+    value: u8,
+    actor: Actor,
+}
+
+impl Action for Choice {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+thread_local! {
+    /// 各状態で，行動が何個取り出されたか．
+    static PULLED_ACTIONS: RefCell<HashMap<Path, usize>> = RefCell::new(HashMap::new());
+}
+
+/// 取り出された行動の数を記録しながら，行動を1つずつ列挙する．
+struct CountingActions {
+    path: Path,
+    actor: Actor,
+    next: u8,
+}
+
+impl Iterator for CountingActions {
+    type Item = Choice;
+
+    fn next(&mut self) -> Option<Choice> {
+        if self.next > 1 {
+            return None;
+        }
+        PULLED_ACTIONS
+            .with(|pulled| *pulled.borrow_mut().entry(self.path.clone()).or_insert(0) += 1);
+        let choice = Choice {
+            value: self.next,
+            actor: self.actor,
+        };
+        self.next += 1;
+        Some(choice)
+    }
+}
+
+struct ChoiceRule;
+
+impl Rule for ChoiceRule {
+    type S = Path;
+    type A = Choice;
+    type ActionIterator = CountingActions;
+
+    fn terminal_kind(state: &Path) -> Option<TerminalKind> {
+        if state.0.len() >= 2 {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Path, actor: Actor) -> CountingActions {
+        CountingActions {
+            path: state.clone(),
+            actor,
+            next: 0,
+        }
+    }
+
+    fn translate_state(state: &Path, action: &Choice) -> Path {
+        let mut next = state.clone();
+        next.0.push(action.value);
+        next
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Score(i32);
+
+impl Bounded for Score {
+    fn min_value() -> Self {
+        Score(i32::MIN)
+    }
+
+    fn max_value() -> Self {
+        Score(i32::MAX)
+    }
+}
+
+/// 先手にとっての評価値．
+/// 先手が0を選ぶと後手の応答によらず5以上となり，1を選ぶと後手が0を選べば3となる．
+struct ChoiceEvaluator;
+
+impl Evaluator<Path> for ChoiceEvaluator {
+    type Payoff = Score;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Path) -> Score {
+        let score = match state.0.as_slice() {
+            [0, 0] => 5,
+            [0, 1] => 6,
+            [1, 0] => 3,
+            [1, 1] => 9,
+            _ => 0,
+        };
+        match actor {
+            Actor::First => Score(score),
+            Actor::Second => Score(-score),
+        }
+    }
+}
+
+#[test]
+fn test_actions_after_cutoff_are_not_pulled() {
+    PULLED_ACTIONS.with(|pulled| pulled.borrow_mut().clear());
+    let strategy = AlphaBetaStrategy::<ChoiceRule, ChoiceEvaluator, _>::new(2);
+    let action = strategy.select_action(&Path(vec![]), Actor::First).unwrap();
+    assert_eq!(0, action.value);
+
+    PULLED_ACTIONS.with(|pulled| {
+        let pulled = pulled.borrow();
+        assert_eq!(Some(&2), pulled.get(&Path(vec![])));
+        assert_eq!(Some(&2), pulled.get(&Path(vec![0])));
+        // 先手が1を選んだ後，後手の最初の応答で枝刈りが起こるので，2番目の応答は取り出されない
+        assert_eq!(Some(&1), pulled.get(&Path(vec![1])));
+    });
+}
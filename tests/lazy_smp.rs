@@ -0,0 +1,85 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_lazy_smp_is_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<LazySmpStrategy<ReversiRule, ScoreEvaluator, usize>>();
+}
+
+#[test]
+fn test_lazy_smp_with_four_threads_keeps_root_payoff() {
+    // 終局まで読む深さでは，より深い探索の評価値を用いても根ノードの評価値は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let lazy_smp = LazySmpStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12)
+        .with_threads(4);
+    assert_eq!(&[1, 0, 1], lazy_smp.depth_offsets());
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            lazy_smp.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+        // 選んだ行動を先に調べる逐次探索でも同じ行動が選ばれれば，その行動は最善である
+        let chosen = lazy_smp.select_action(&board, actor).unwrap();
+        let root = board.clone();
+        let sequential = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_orderer(
+                move |state: &Board, _: Actor, actions: &mut Vec<Placement>| {
+                    if *state == root {
+                        actions.sort_by_key(|action| *action != chosen);
+                    }
+                },
+            );
+        assert_eq!(
+            Some(chosen),
+            sequential.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_lazy_smp_with_deeper_helpers() {
+    let board = Board::new();
+    let lazy_smp =
+        LazySmpStrategy::<ReversiRule, ScoreEvaluator, _>::new(2).with_depth_offsets(vec![7, 7, 7]);
+    let (action, stats) = lazy_smp.select_action_with_stats(&board, Actor::First);
+    assert!(action.is_some());
+    assert!(stats.nodes_evaluated > 1);
+
+    // 終局した状態では行動を選ばない
+    let finished = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+    ]);
+    assert_eq!(None, lazy_smp.select_action(&finished, Actor::Second));
+    assert_eq!(Some(-100), lazy_smp.root_payoff(&finished, Actor::Second));
+}
+
+#[test]
+fn test_lazy_smp_with_lock_free_table_keeps_root_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    // 衝突が頻発する小さな置換表を，8つのスレッドで共有する
+    let lazy_smp = LazySmpStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_lock_free_transposition_table(64)
+        .with_threads(8);
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            lazy_smp.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use minimax_strategy::*;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+const PLAYER_COUNT: usize = 3;
+
+/// 3人が順に0か1を1回ずつ選ぶゲームの状態．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Picks {
+    /// これまでに選ばれた値を，先に選ばれたものを上位の桁として並べたもの．
+    code: usize,
+    count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pick {
+    bit: usize,
+    actor: MultiActor,
+}
+
+impl MultiAction for Pick {
+    fn actor(&self) -> MultiActor {
+        self.actor
+    }
+}
+
+struct PickRule;
+
+impl MultiRule for PickRule {
+    type S = Picks;
+    type A = Pick;
+    type ActionIterator = std::vec::IntoIter<Pick>;
+
+    fn player_count() -> usize {
+        PLAYER_COUNT
+    }
+
+    fn is_game_over(state: &Picks) -> bool {
+        state.count == PLAYER_COUNT
+    }
+
+    fn iterate_available_actions(_state: &Picks, actor: MultiActor) -> Self::ActionIterator {
+        vec![Pick { bit: 0, actor }, Pick { bit: 1, actor }].into_iter()
+    }
+
+    fn translate_state(state: &Picks, action: &Pick) -> Picks {
+        Picks {
+            code: state.code * 2 + action.bit,
+            count: state.count + 1,
+        }
+    }
+}
+
+/// 選ばれた値の組ごとの利得．
+const PICK_PAYOFFS: [[u32; PLAYER_COUNT]; 8] = [
+    [3, 1, 2],
+    [0, 3, 1],
+    [1, 2, 4],
+    [4, 0, 3],
+    [2, 2, 2],
+    [0, 0, 5],
+    [2, 3, 3],
+    [3, 1, 1],
+];
+
+struct PickEvaluator;
+
+impl MultiEvaluator<Picks> for PickEvaluator {
+    type Payoff = u32;
+
+    fn evaluate_payoffs(state: &Picks) -> Vec<u32> {
+        PICK_PAYOFFS[state.code].to_vec()
+    }
+}
+
+#[test]
+fn test_max_n_each_player_maximizes_own_payoff() {
+    // 3人目は各組で自分の利得が大きい方を選び，(0, 0)では[3, 1, 2]，(0, 1)では[1, 2, 4]，(1, 0)では[0, 0, 5]，(1, 1)では[2, 3, 3]となる．
+    // 2人目は0を選んだ後は[1, 2, 4]を，1を選んだ後は[2, 3, 3]を選ぶので，1人目は1を選ぶ
+    let root = Picks { code: 0, count: 0 };
+    for strategy in [
+        MaxNStrategy::<PickRule, PickEvaluator, _>::new(3),
+        MaxNStrategy::<PickRule, PickEvaluator, _>::new(3).with_shallow_pruning(8),
+    ] {
+        assert_eq!(vec![2, 3, 3], strategy.root_payoffs(&root, MultiActor(0)));
+        let action = strategy.select_action(&root, MultiActor(0)).unwrap();
+        assert_eq!(1, action.bit);
+    }
+}
+
+#[test]
+fn test_max_n_no_action_on_game_over() {
+    let strategy = MaxNStrategy::<PickRule, PickEvaluator, _>::new(3);
+    let state = Picks { code: 5, count: 3 };
+    assert!(strategy.select_action(&state, MultiActor(0)).is_none());
+    assert_eq!(vec![0, 0, 5], strategy.root_payoffs(&state, MultiActor(0)));
+}
+
+#[test]
+fn test_multi_actor_next_wraps_around() {
+    assert_eq!(MultiActor(1), MultiActor(0).next(3));
+    assert_eq!(MultiActor(0), MultiActor(2).next(3));
+    assert_eq!(2, MultiActor(2).index());
+}
+
+/// 3人で石を置き，自分の石を縦横斜めに3つ並べたプレイヤーが勝つ3x3の盤面．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Board {
+    cells: [Option<MultiActor>; 9],
+}
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+impl Board {
+    fn winner(&self) -> Option<MultiActor> {
+        LINES.iter().find_map(|line| {
+            let first = self.cells[line[0]]?;
+            line.iter()
+                .all(|&cell| self.cells[cell] == Some(first))
+                .then_some(first)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    cell: usize,
+    actor: MultiActor,
+}
+
+impl MultiAction for Placement {
+    fn actor(&self) -> MultiActor {
+        self.actor
+    }
+}
+
+struct ThreePlayerRule;
+
+impl MultiRule for ThreePlayerRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = std::vec::IntoIter<Placement>;
+
+    fn player_count() -> usize {
+        PLAYER_COUNT
+    }
+
+    fn is_game_over(state: &Board) -> bool {
+        state.winner().is_some() || state.cells.iter().all(Option::is_some)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: MultiActor) -> Self::ActionIterator {
+        (0..9)
+            .filter(|&cell| state.cells[cell].is_none())
+            .map(|cell| Placement { cell, actor })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        let mut next = *state;
+        next.cells[action.cell] = Some(action.actor);
+        next
+    }
+}
+
+static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// 勝者の利得を1とし，それ以外は置いた石の数に応じて合計1を分け合う．評価した回数を数える．
+struct CountingShareEvaluator;
+
+impl MultiEvaluator<Board> for CountingShareEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoffs(state: &Board) -> Vec<f64> {
+        EVALUATIONS.fetch_add(1, AtomicOrdering::Relaxed);
+        let mut payoffs = vec![0.0; PLAYER_COUNT];
+        if let Some(winner) = state.winner() {
+            payoffs[winner.index()] = 1.0;
+            return payoffs;
+        }
+        let stones = state.cells.iter().flatten().count();
+        if stones == 0 {
+            return vec![1.0 / PLAYER_COUNT as f64; PLAYER_COUNT];
+        }
+        for actor in state.cells.iter().flatten() {
+            payoffs[actor.index()] += 1.0 / stones as f64;
+        }
+        payoffs
+    }
+}
+
+#[test]
+fn test_shallow_pruning_keeps_actions_and_reduces_evaluations() {
+    let plain = MaxNStrategy::<ThreePlayerRule, CountingShareEvaluator, _>::new(5);
+    let pruned = MaxNStrategy::<ThreePlayerRule, CountingShareEvaluator, _>::new(5)
+        .with_shallow_pruning(1.0);
+
+    let mut positions = vec![(Board { cells: [None; 9] }, MultiActor(0))];
+    for cell in [4, 0, 8] {
+        let (board, actor) = *positions.last().unwrap();
+        let action = Placement { cell, actor };
+        let next = ThreePlayerRule::translate_state(&board, &action);
+        positions.push((next, actor.next(PLAYER_COUNT)));
+    }
+
+    let mut plain_evaluations = 0;
+    let mut pruned_evaluations = 0;
+    for (board, actor) in positions {
+        EVALUATIONS.store(0, AtomicOrdering::Relaxed);
+        let expected = plain.select_action(&board, actor);
+        let expected_payoffs = plain.root_payoffs(&board, actor);
+        plain_evaluations += EVALUATIONS.swap(0, AtomicOrdering::Relaxed);
+        let action = pruned.select_action(&board, actor);
+        let payoffs = pruned.root_payoffs(&board, actor);
+        pruned_evaluations += EVALUATIONS.swap(0, AtomicOrdering::Relaxed);
+        assert_eq!(expected, action, "{:?}", board);
+        assert_eq!(expected_payoffs, payoffs, "{:?}", board);
+    }
+    assert!(
+        pruned_evaluations < plain_evaluations,
+        "{} >= {}",
+        pruned_evaluations,
+        plain_evaluations
+    );
+}
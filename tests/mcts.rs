@@ -0,0 +1,369 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use rand::Rng;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_mcts_selects_winning_action() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let mcts = MctsStrategy::<ReversiRule, _>::new(1000).with_seed(0);
+
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+}
+
+#[test]
+fn test_mcts_blocks_opponent_win() {
+    // S S -
+    // F - -
+    // - - F
+    let board = Board::with_placements(&[
+        (0, 0, Actor::Second),
+        (1, 0, Actor::Second),
+        (0, 1, Actor::First),
+        (2, 2, Actor::First),
+    ]);
+    let mcts = MctsStrategy::<ReversiRule, _>::new(5000).with_seed(1);
+
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+}
+
+#[test]
+fn test_mcts_no_action_on_game_over() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let mcts = MctsStrategy::<ReversiRule, _>::new(100).with_seed(0);
+
+    assert!(mcts.select_action(&board, Actor::Second).is_none());
+}
+
+#[test]
+fn test_mcts_is_reproducible_with_seed() {
+    for (board, actor) in enumerate_positions(2) {
+        let first = MctsStrategy::<ReversiRule, _>::new(200).with_seed(42);
+        let second = MctsStrategy::<ReversiRule, _>::new(200).with_seed(42);
+        assert_eq!(
+            first.select_action(&board, actor),
+            second.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_mcts_reuses_tree() {
+    let simulations = 500;
+    let mcts = MctsStrategy::<ReversiRule, _>::new(simulations).with_seed(0);
+    let board = Board::new();
+
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    assert_eq!(simulations, mcts.retained_simulations());
+
+    // 自分と相手が1手ずつ進めた状態は前回のゲーム木に含まれるので，その統計が引き継がれる
+    let board = ReversiRule::translate_state(&board, &action);
+    let reply = ReversiRule::iterate_available_actions(&board, Actor::Second)
+        .next()
+        .unwrap();
+    let board = ReversiRule::translate_state(&board, &reply);
+    mcts.select_action(&board, Actor::First).unwrap();
+    assert!(mcts.retained_simulations() > simulations);
+
+    // 無関係な状態では，ゲーム木を作り直す
+    let unrelated = Board::with_placements(&[(2, 2, Actor::Second), (0, 2, Actor::First)]);
+    mcts.select_action(&unrelated, Actor::Second).unwrap();
+    assert_eq!(simulations, mcts.retained_simulations());
+}
+
+/// 常に最初の行動を選ぶプレイアウト方針．呼ばれた回数を数える．
+struct FirstActionPlayout;
+
+static FIRST_ACTION_PLAYOUT_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+impl<S, A> PlayoutPolicy<S, A> for FirstActionPlayout {
+    fn choose_action<G>(_state: &S, _actor: Actor, _actions: &[A], _rng: &mut G) -> usize
+    where
+        G: Rng,
+    {
+        FIRST_ACTION_PLAYOUT_CALLS.fetch_add(1, AtomicOrdering::Relaxed);
+        0
+    }
+}
+
+#[test]
+fn test_mcts_uses_playout_policy() {
+    let mcts = MctsStrategy::<ReversiRule, _>::new(100)
+        .with_seed(0)
+        .with_playout_policy::<FirstActionPlayout>();
+    assert!(mcts.select_action(&Board::new(), Actor::First).is_some());
+    assert!(FIRST_ACTION_PLAYOUT_CALLS.load(AtomicOrdering::Relaxed) > 0);
+}
+
+#[test]
+fn test_mcts_stops_at_time_budget() {
+    let mcts = MctsStrategy::<ReversiRule, _>::new(u64::MAX)
+        .with_seed(0)
+        .with_time_budget(Duration::from_millis(50));
+    let start = Instant::now();
+    assert!(mcts.select_action(&Board::new(), Actor::First).is_some());
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(mcts.retained_simulations() > 0);
+}
+
+/// 指定した行動をとった後の状態を最後まで探索し，行動したプレイヤーにとっての評価値を返す．
+fn exact_payoff(board: &Board, actor: Actor, action: &Placement) -> i32 {
+    let full = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE);
+    let after = ReversiRule::translate_state(board, action);
+    full.root_payoff(&after, actor.opponent())
+        .map(|payoff| -payoff)
+        .unwrap()
+}
+
+/// 各列について，相手の駒がなく自分の駒がある列を数え，相手の分を引いた評価関数．
+/// 駒が2つ並んだ列は大きく数える．
+struct LineEvaluator;
+
+impl Evaluator<Board> for LineEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> f64 {
+        let mut lines = vec![];
+        for i in 0..FIELD_SIZE {
+            lines.push((0..FIELD_SIZE).map(|j| (i, j)).collect::<Vec<_>>());
+            lines.push((0..FIELD_SIZE).map(|j| (j, i)).collect::<Vec<_>>());
+        }
+        lines.push((0..FIELD_SIZE).map(|i| (i, i)).collect());
+        lines.push((0..FIELD_SIZE).map(|i| (i, FIELD_SIZE - 1 - i)).collect());
+        let score = lines
+            .iter()
+            .map(|line| {
+                let count = |owner| {
+                    line.iter()
+                        .filter(|&&(x, y)| *state.at(x, y) == Some(owner))
+                        .count()
+                };
+                let (own, opponent) = (count(actor), count(actor.opponent()));
+                let weight = |count| match count {
+                    0 => 0.0,
+                    1 => 1.0,
+                    2 => 5.0,
+                    _ => 100.0,
+                };
+                match (own, opponent) {
+                    (_, 0) => weight(own),
+                    (0, _) => -weight(opponent),
+                    _ => 0.0,
+                }
+            })
+            .sum::<f64>();
+        score / 4.0
+    }
+}
+
+#[test]
+fn test_mcts_with_evaluator_plays_better_than_random_playouts() {
+    // 同じシミュレーション回数で，最善の行動を選べた局面の数を比べる
+    let count_best = |evaluate: bool| {
+        enumerate_positions(2)
+            .into_iter()
+            .filter(|(board, actor)| {
+                let mcts = MctsStrategy::<ReversiRule, _>::new(30)
+                    .with_seed(0)
+                    .with_threads(1);
+                let mcts = if evaluate {
+                    mcts.with_evaluator::<LineEvaluator>(0.5)
+                } else {
+                    mcts
+                };
+                let action = mcts.select_action(board, *actor).unwrap();
+                let best = ReversiRule::iterate_available_actions(board, *actor)
+                    .map(|action| exact_payoff(board, *actor, &action))
+                    .max()
+                    .unwrap();
+                exact_payoff(board, *actor, &action) == best
+            })
+            .count()
+    };
+    let random = count_best(false);
+    let evaluated = count_best(true);
+    assert!(evaluated > random, "{} <= {}", evaluated, random);
+}
+
+#[test]
+fn test_mcts_with_evaluator_only() {
+    // 静的な評価だけを報酬とする場合も，勝ちに至る行動を選ぶ
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let mcts = MctsStrategy::<ReversiRule, _>::new(200)
+        .with_seed(0)
+        .with_evaluator::<LineEvaluator>(1.0);
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+}
+
+#[test]
+fn test_payoff_to_score() {
+    assert_eq!(0.5, 0i32.to_score());
+    assert!(3i32.to_score() > 0.9);
+    assert!((-3i32).to_score() < 0.1);
+    assert!((2.0f64.to_score() + (-2.0f64).to_score() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_mcts_advance_keeps_subtree() {
+    let mcts = MctsStrategy::<ReversiRule, _>::new(500).with_seed(0);
+    let board = Board::new();
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    mcts.advance(&action);
+    let after = ReversiRule::translate_state(&board, &action);
+    // 選んだ行動は最も多くシミュレーションされている
+    let retained = mcts.retained_simulations();
+    assert!(retained > 500 / 9, "{}", retained);
+
+    let reply = ReversiRule::iterate_available_actions(&after, Actor::Second)
+        .next()
+        .unwrap();
+    mcts.advance(&reply);
+    let retained = mcts.retained_simulations();
+    assert!(retained > 0);
+    // 続けて行動を選択すると，再利用した統計に加えてシミュレーションを行う
+    let next = ReversiRule::translate_state(&after, &reply);
+    mcts.select_action(&next, Actor::First).unwrap();
+    assert_eq!(retained + 500, mcts.retained_simulations());
+}
+
+#[test]
+fn test_mcts_advance_to_unexpanded_action_drops_tree() {
+    let mcts = MctsStrategy::<ReversiRule, _>::new(1).with_seed(0);
+    let board = Board::new();
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    let other = ReversiRule::iterate_available_actions(&board, Actor::First)
+        .find(|candidate| candidate != &action)
+        .unwrap();
+    mcts.advance(&other);
+    assert_eq!(0, mcts.retained_simulations());
+}
+
+#[test]
+fn test_mcts_reuses_tree_throughout_game() {
+    // 両者の行動を伝えながら対戦すると，各行動選択は前回までの統計を引き継ぐ
+    let simulations = 200;
+    let mcts = MctsStrategy::<ReversiRule, _>::new(simulations).with_seed(0);
+    let opponent = RandomStrategy::<ReversiRule, _>::seeded(0);
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    let mut reused_moves = 0;
+    while ReversiRule::terminal_kind(&board).is_none() {
+        let action = match actor {
+            Actor::First => {
+                let before = mcts.retained_simulations();
+                let action = mcts.select_action(&board, actor).unwrap();
+                assert_eq!(before + simulations, mcts.retained_simulations());
+                if before > 0 {
+                    reused_moves += 1;
+                }
+                action
+            }
+            Actor::Second => opponent.select_action(&board, actor).unwrap(),
+        };
+        mcts.advance(&action);
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+    assert!(reused_moves > 0);
+}
+
+#[test]
+fn test_parallel_mcts_is_reproducible_with_seed() {
+    for (board, actor) in enumerate_positions(2) {
+        let first = MctsStrategy::<ReversiRule, _>::new(50)
+            .with_seed(42)
+            .with_threads(4);
+        let second = MctsStrategy::<ReversiRule, _>::new(50)
+            .with_seed(42)
+            .with_threads(4);
+        let first = first.select_scored_action(&board, actor).unwrap();
+        let second = second.select_scored_action(&board, actor).unwrap();
+        assert_eq!(first.action, second.action, "{}", board);
+        assert_eq!(first.payoff, second.payoff, "{}", board);
+    }
+}
+
+#[test]
+fn test_parallel_mcts_selects_winning_action() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let mcts = MctsStrategy::<ReversiRule, _>::new(250)
+        .with_seed(0)
+        .with_threads(4);
+
+    let action = mcts.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+    // 保持するのは最初のスレッドのゲーム木だけ
+    assert_eq!(250, mcts.retained_simulations());
+}
+
+#[test]
+#[should_panic]
+fn test_mcts_rejects_zero_threads() {
+    let _ = MctsStrategy::<ReversiRule, u64>::new(100).with_threads(0);
+}
+
+#[test]
+fn test_mcts_memory_budget_discards_retained_tree() {
+    let board = Board::new();
+    let unlimited = MctsStrategy::<ReversiRule, _>::new(500).with_seed(0);
+    unlimited.select_action(&board, Actor::First).unwrap();
+    let usage = unlimited.memory_usage();
+    assert!(usage.retained_tree > 0);
+    assert_eq!(usage.retained_tree, usage.total());
+
+    // 上限に収まるゲーム木は保持される
+    let generous = MctsStrategy::<ReversiRule, _>::new(500)
+        .with_seed(0)
+        .with_memory_budget(MemoryBudget::new(usage.retained_tree));
+    generous.select_action(&board, Actor::First).unwrap();
+    assert_eq!(usage, generous.memory_usage());
+    assert_eq!(500, generous.retained_simulations());
+
+    // 上限を超えるゲーム木は捨てられ，次の行動選択は新たなゲーム木から探索する
+    let tight = MctsStrategy::<ReversiRule, _>::new(500)
+        .with_seed(0)
+        .with_memory_budget(MemoryBudget::new(usage.retained_tree - 1));
+    let action = tight.select_action(&board, Actor::First).unwrap();
+    assert_eq!(0, tight.memory_usage().total());
+    assert_eq!(0, tight.retained_simulations());
+    tight.advance(&action);
+    assert_eq!(0, tight.retained_simulations());
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+const MEGABYTE: usize = 1 << 20;
+
+fn budgeted_strategy(budget: usize) -> AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize> {
+    AlphaBetaStrategy::new(FIELD_SIZE * FIELD_SIZE)
+        .with_transposition_table(1 << 16)
+        .with_history_heuristic()
+        .with_killer_moves()
+        .with_countermoves()
+        .with_memory_budget(MemoryBudget::new(budget))
+}
+
+#[test]
+fn test_memory_usage_stays_within_budget() {
+    let strategy = budgeted_strategy(MEGABYTE);
+    // 置換表だけで上限を超えている
+    assert!(strategy.memory_usage().transposition_table > MEGABYTE);
+
+    let board = Board::new();
+    let (action, stats) = strategy.select_action_with_stats(&board, Actor::First);
+    let action = action.unwrap();
+    assert!(ReversiRule::iterate_available_actions(&board, Actor::First).any(|a| a == action));
+
+    let usage = strategy.memory_usage();
+    assert!(usage.total() <= MEGABYTE, "{:?}", usage);
+    assert!(usage.transposition_table > 0);
+    assert!(0 < stats.peak_memory_bytes);
+    assert!(stats.peak_memory_bytes <= MEGABYTE as u64);
+
+    // 探索中は，すべての構造が上限の範囲で使われる
+    let peak = stats.peak_memory_usage;
+    assert!(0 < peak.transposition_table, "{:?}", peak);
+    assert!(0 < peak.history, "{:?}", peak);
+    assert!(0 < peak.killers, "{:?}", peak);
+    assert!(0 < peak.countermoves, "{:?}", peak);
+    assert_eq!(0, peak.retained_tree);
+    for bytes in [
+        peak.transposition_table,
+        peak.history,
+        peak.killers,
+        peak.countermoves,
+    ] {
+        assert!(bytes <= MEGABYTE, "{:?}", peak);
+    }
+}
+
+#[test]
+fn test_tiny_budget_evicts_deterministically() {
+    let board = Board::new();
+    let first = budgeted_strategy(0);
+    let second = budgeted_strategy(0);
+    let (action, stats) = first.select_action_with_stats(&board, Actor::First);
+    let (expected_action, expected_stats) = second.select_action_with_stats(&board, Actor::First);
+    assert_eq!(expected_action, action);
+    assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+
+    // 置換表は最小の容量まで減らされるが，探索は合法な行動を返す
+    let action = action.unwrap();
+    assert!(ReversiRule::iterate_available_actions(&board, Actor::First).any(|a| a == action));
+    assert_eq!(1, first.transposition_table_stats().unwrap().capacity);
+    assert_eq!(0, first.memory_usage().history);
+}
+
+#[test]
+fn test_generous_budget_keeps_search_unchanged() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let unlimited = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 10)
+        .with_history_heuristic();
+    let budgeted = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 10)
+        .with_history_heuristic()
+        .with_memory_budget(MemoryBudget::new(MEGABYTE));
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, expected_stats) = unlimited.select_action_with_stats(&board, actor);
+        let (action, stats) = budgeted.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action, "{}", board);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+        assert_eq!(0, expected_stats.peak_memory_bytes);
+        assert_eq!(MemoryUsage::default(), expected_stats.peak_memory_usage);
+        assert!(0 < stats.peak_memory_usage.transposition_table);
+        assert!(stats.peak_memory_usage.total() >= stats.peak_memory_bytes as usize);
+    }
+    assert_eq!(
+        unlimited.memory_usage(),
+        budgeted.memory_usage(),
+        "{:?}",
+        budgeted.memory_usage()
+    );
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_minimax_root_payoff_matches_alpha_beta() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let board = Board::new();
+
+    for &actor in Actor::ALL.iter() {
+        assert_eq!(
+            minimax.root_payoff(&board, actor),
+            alpha_beta.root_payoff(&board, actor)
+        );
+    }
+    // 三目並べは，互いに最善を尽くせば引き分けになる
+    assert_eq!(
+        Some(BoardEvaluation::Equal),
+        minimax.root_payoff(&board, Actor::First)
+    );
+}
+
+#[test]
+fn test_minimax_selects_winning_action() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+
+    let action = minimax.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+    assert_eq!(
+        Some(BoardEvaluation::Win),
+        minimax.root_payoff(&board, Actor::First)
+    );
+}
+
+#[test]
+fn test_minimax_no_action_on_game_over() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+
+    assert!(minimax.select_action(&board, Actor::Second).is_none());
+    assert_eq!(
+        Some(BoardEvaluation::Lose),
+        minimax.root_payoff(&board, Actor::Second)
+    );
+}
+
+#[test]
+fn test_alpha_beta_and_minimax_select_actions_with_equal_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+
+    for (board, actor) in enumerate_positions(2) {
+        let expected = minimax.root_payoff(&board, actor);
+        assert_eq!(expected, alpha_beta.root_payoff(&board, actor), "{}", board);
+
+        // 選択された行動の後の盤面を相手側から評価し，両者が同じ価値の行動を選んだことを確かめる
+        let minimax_action = minimax.select_action(&board, actor).unwrap();
+        let alpha_beta_action = alpha_beta.select_action(&board, actor).unwrap();
+        let after_minimax = ReversiRule::translate_state(&board, &minimax_action);
+        let after_alpha_beta = ReversiRule::translate_state(&board, &alpha_beta_action);
+        assert_eq!(
+            minimax.root_payoff(&after_minimax, actor.opponent()),
+            minimax.root_payoff(&after_alpha_beta, actor.opponent()),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_alpha_beta_and_minimax_agree_at_shallow_depth() {
+    // 探索が途中で打ち切られ，静的評価値が使われる場合も結果は一致する
+    for depth in 1..4 {
+        let minimax = MinimaxStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                minimax.root_payoff(&board, actor),
+                alpha_beta.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 中央，四隅，辺の順に並べる．
+fn sort_center_first(actions: &mut [Placement]) {
+    let center = FIELD_SIZE / 2;
+    actions.sort_by_key(|action| {
+        let is_edge = |v: usize| v == 0 || v == FIELD_SIZE - 1;
+        if action.x == center && action.y == center {
+            0
+        } else if is_edge(action.x) && is_edge(action.y) {
+            1
+        } else {
+            2
+        }
+    });
+}
+
+/// 辺，四隅，中央の順に調べる．
+struct CenterLast;
+
+impl MoveOrderer<Board, Placement> for CenterLast {
+    fn order(&self, _state: &Board, _actor: Actor, actions: &mut Vec<Placement>) {
+        sort_center_first(actions);
+        actions.reverse();
+    }
+}
+
+#[test]
+fn test_move_orderer_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let ordered = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_orderer(|_: &Board, _: Actor, actions: &mut Vec<Placement>| {
+                sort_center_first(actions)
+            })
+            .with_transposition_table(1 << 12);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                ordered.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_good_move_orderer_reduces_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let good = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth).with_move_orderer(
+        |_: &Board, _: Actor, actions: &mut Vec<Placement>| sort_center_first(actions),
+    );
+    let bad = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_orderer(CenterLast);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+
+    let (_, good_stats) = good.select_action_with_stats(&Board::new(), Actor::First);
+    let (_, bad_stats) = bad.select_action_with_stats(&Board::new(), Actor::First);
+    let (_, stats) = alpha_beta.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(
+        good_stats.nodes_evaluated < stats.nodes_evaluated,
+        "{} {}",
+        good_stats.nodes_evaluated,
+        stats.nodes_evaluated
+    );
+    assert!(
+        stats.nodes_evaluated < bad_stats.nodes_evaluated,
+        "{} {}",
+        stats.nodes_evaluated,
+        bad_stats.nodes_evaluated
+    );
+}
+
+/// 中央，四隅，辺の順に並べる．
+struct CenterFirst;
+
+impl MoveOrdering<Board, Placement> for CenterFirst {
+    fn order_actions(_state: &Board, _actor: Actor, mut actions: Vec<Placement>) -> Vec<Placement> {
+        sort_center_first(&mut actions);
+        actions
+    }
+}
+
+#[test]
+fn test_move_ordering_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let ordered = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_ordering::<CenterFirst>();
+        let reversed = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_move_ordering::<ReverseOrdering<CenterFirst>>()
+            .with_quiescence_depth(2);
+        let plain = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_quiescence_depth(2);
+        for (board, actor) in enumerate_positions(1) {
+            let expected = alpha_beta.root_payoff(&board, actor);
+            assert_eq!(expected, ordered.root_payoff(&board, actor), "{}", board);
+            assert_eq!(
+                plain.root_payoff(&board, actor),
+                reversed.root_payoff(&board, actor),
+                "{}",
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_move_ordering_matches_move_orderer() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let ordering = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_ordering::<CenterFirst>();
+    let orderer = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_orderer(|_: &Board, _: Actor, actions: &mut Vec<Placement>| {
+            sort_center_first(actions)
+        });
+    let reversed = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_ordering::<ReverseOrdering<CenterFirst>>();
+    let bad = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_orderer(CenterLast);
+
+    let stats_of = |(_, stats): (Option<Placement>, SearchStats)| stats.nodes_evaluated;
+    assert_eq!(
+        stats_of(orderer.select_action_with_stats(&Board::new(), Actor::First)),
+        stats_of(ordering.select_action_with_stats(&Board::new(), Actor::First))
+    );
+    assert_eq!(
+        stats_of(bad.select_action_with_stats(&Board::new(), Actor::First)),
+        stats_of(reversed.select_action_with_stats(&Board::new(), Actor::First))
+    );
+}
+
+#[test]
+fn test_combined_ordering_breaks_ties() {
+    let board = Board::new();
+    let actions = ReversiRule::iterate_available_actions(&board, Actor::First).collect::<Vec<_>>();
+
+    // 中央と四隅の順位が等しい行動は，逆順に並ぶ
+    let combined = CombinedOrdering::<CenterFirst, ReverseOrdering<NoOrdering>>::order_actions(
+        &board,
+        Actor::First,
+        actions.clone(),
+    );
+    let positions = combined
+        .iter()
+        .map(|action| (action.x, action.y))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (1, 1),
+            (2, 2),
+            (0, 2),
+            (2, 0),
+            (0, 0),
+            (1, 2),
+            (2, 1),
+            (0, 1),
+            (1, 0)
+        ],
+        positions
+    );
+
+    let identity = CombinedOrdering::<NoOrdering, NoOrdering>::order_actions(
+        &board,
+        Actor::First,
+        actions.clone(),
+    );
+    assert_eq!(actions, identity);
+}
+
+/// `ReversiRule`と同じゲームで，中央，四隅，辺の順に大きな点数を行動に与えるルール．
+struct ScoringRule;
+
+impl Rule for ScoringRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = std::vec::IntoIter<Placement>;
+
+    const SCORES_ACTIONS: bool = true;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn heuristic_action_score(_state: &Board, action: &Placement) -> i32 {
+        let center = FIELD_SIZE / 2;
+        let is_edge = |v: usize| v == 0 || v == FIELD_SIZE - 1;
+        if action.x == center && action.y == center {
+            2
+        } else if is_edge(action.x) && is_edge(action.y) {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+}
+
+#[test]
+fn test_default_heuristic_action_score_is_zero() {
+    for (board, actor) in enumerate_positions(1) {
+        for action in ReversiRule::iterate_available_actions(&board, actor) {
+            assert_eq!(0, ReversiRule::heuristic_action_score(&board, &action));
+        }
+    }
+}
+
+#[test]
+fn test_heuristic_action_score_matches_move_orderer() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let scoring = AlphaBetaStrategy::<ScoringRule, ScoreEvaluator, _>::new(depth);
+    let orderer = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_move_orderer(|_: &Board, _: Actor, actions: &mut Vec<Placement>| {
+            sort_center_first(actions)
+        });
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            scoring.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+        // 点数の大きい順に調べるので，同じ順に並べ替える`MoveOrderer`と探索量が一致する
+        let (expected_action, expected) = orderer.select_action_with_stats(&board, actor);
+        let (action, stats) = scoring.select_action_with_stats(&board, actor);
+        assert_eq!(expected_action, action, "{}", board);
+        assert_eq!(expected.nodes_evaluated, stats.nodes_evaluated, "{}", board);
+    }
+
+    let (_, stats) = alpha_beta.select_action_with_stats(&Board::new(), Actor::First);
+    let (_, scoring_stats) = scoring.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(scoring_stats.nodes_evaluated < stats.nodes_evaluated);
+}
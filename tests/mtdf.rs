@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_mtdf_root_payoff_matches_alpha_beta() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for &first_guess in [
+            BoardEvaluation::Lose,
+            BoardEvaluation::Equal,
+            BoardEvaluation::Win,
+        ]
+        .iter()
+        {
+            let mtdf = MtdfStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, first_guess);
+            for (board, actor) in enumerate_positions(1) {
+                assert_eq!(
+                    alpha_beta.root_payoff(&board, actor),
+                    mtdf.root_payoff(&board, actor),
+                    "depth {} guess {:?}\n{}",
+                    depth,
+                    first_guess,
+                    board
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mtdf_with_transposition_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let mtdf = MtdfStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, BoardEvaluation::Win)
+        .with_transposition_table(1 << 12);
+
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            mtdf.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+        // 選択した行動の価値も，αβ法で選択した行動と等しい
+        let expected = alpha_beta.select_action(&board, actor).unwrap();
+        let action = mtdf.select_action(&board, actor).unwrap();
+        let after_expected = ReversiRule::translate_state(&board, &expected);
+        let after_action = ReversiRule::translate_state(&board, &action);
+        assert_eq!(
+            alpha_beta.root_payoff(&after_expected, actor.opponent()),
+            alpha_beta.root_payoff(&after_action, actor.opponent()),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_mtdf_converges_immediately_with_correct_guess() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    // 三目並べの評価値は引き分けなので，正しい予想値からは探索し直さない
+    let mtdf = MtdfStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, BoardEvaluation::Equal);
+    let (action, stats) = mtdf.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(action.is_some());
+    assert_eq!(0, stats.re_searches);
+
+    let mtdf = MtdfStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, BoardEvaluation::Lose);
+    let (_, stats) = mtdf.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(stats.re_searches > 0);
+    // 前回の評価値が次の予想値となる
+    let (_, stats) = mtdf.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(0, stats.re_searches);
+}
+
+#[test]
+fn test_mtdf_reuses_transposition_table() {
+    // 置換表は常に用いられ，行動選択をまたいで再利用される
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let mtdf = MtdfStrategy::<ReversiRule, BoardEvaluator, _>::new(depth, BoardEvaluation::Equal);
+    let (first_action, first_stats) = mtdf.select_action_with_stats(&Board::new(), Actor::First);
+    let (action, stats) = mtdf.select_action_with_stats(&Board::new(), Actor::First);
+    assert_eq!(first_action, action);
+    assert!(
+        stats.nodes_evaluated < first_stats.nodes_evaluated,
+        "{} {}",
+        stats.nodes_evaluated,
+        first_stats.nodes_evaluated
+    );
+}
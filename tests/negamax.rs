@@ -0,0 +1,63 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_negamax_selects_same_actions_as_alpha_beta() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let negamax = NegamaxStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            negamax.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+        assert_eq!(
+            alpha_beta.select_action(&board, actor),
+            negamax.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_negamax_matches_alpha_beta_at_shallow_depth() {
+    for depth in 1..4 {
+        let negamax = NegamaxStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                negamax.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+            assert_eq!(
+                alpha_beta.select_action(&board, actor),
+                negamax.select_action(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_negamax_plays_full_game_to_draw() {
+    let strategy = NegamaxStrategy::<ReversiRule, BoardEvaluator, _>::new(9);
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    while ReversiRule::terminal_kind(&board).is_none() {
+        let action = strategy.select_action(&board, actor).unwrap();
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+    assert_eq!(Some(GameResult::Draw), board.game_result());
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// パスを認める三目並べ．
+/// 三目並べでは駒を置いて不利になることはないので，パスは常に最善の行動以下の価値しか持たない．
+struct PassRule;
+
+impl Rule for PassRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = <ReversiRule as Rule>::ActionIterator;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+
+    fn try_null_move(state: &Board) -> Option<Board> {
+        Some(state.clone())
+    }
+}
+
+#[test]
+fn test_null_move_pruning_reduces_visited_nodes() {
+    // パスした後の探索にもノードを費やすので，十分に深く探索する場合にだけ効果がある
+    let depth = 6;
+    let alpha_beta = AlphaBetaStrategy::<PassRule, ScoreEvaluator, _>::new(depth);
+    let null_move =
+        AlphaBetaStrategy::<PassRule, ScoreEvaluator, _>::new(depth).with_null_move_pruning(2);
+
+    let mut nodes_without_null_move = 0;
+    let mut nodes_with_null_move = 0;
+    let mut null_move_cutoffs = 0;
+    for (board, actor) in enumerate_positions(2) {
+        let (_, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_null_move += stats.nodes_evaluated;
+        assert_eq!(0, stats.null_move_cutoffs);
+        let (_, stats) = null_move.select_action_with_stats(&board, actor);
+        nodes_with_null_move += stats.nodes_evaluated;
+        null_move_cutoffs += stats.null_move_cutoffs;
+        // パスが不利なゲームでは，枝刈りしても評価値は変わらない
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            null_move.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+    assert!(null_move_cutoffs > 0);
+    assert!(
+        nodes_with_null_move < nodes_without_null_move,
+        "{} {}",
+        nodes_with_null_move,
+        nodes_without_null_move
+    );
+}
+
+#[test]
+fn test_null_move_pruning_requires_null_move() {
+    // パスを認めないルールでは，探索は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let null_move =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth).with_null_move_pruning(1);
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, expected_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (action, stats) = null_move.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+        assert_eq!(0, stats.null_move_cutoffs);
+    }
+}
+
+#[test]
+fn test_null_move_pruning_plays_full_game() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let null_move =
+        AlphaBetaStrategy::<PassRule, BoardEvaluator, _>::new(depth).with_null_move_pruning(2);
+    let alpha_beta = AlphaBetaStrategy::<PassRule, BoardEvaluator, _>::new(depth);
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    while PassRule::terminal_kind(&board).is_none() {
+        let action = match actor {
+            Actor::First => null_move.select_action(&board, actor),
+            Actor::Second => alpha_beta.select_action(&board, actor),
+        }
+        .unwrap();
+        board = PassRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+    assert_eq!(Some(GameResult::Draw), board.game_result());
+}
+
+#[test]
+fn test_null_move_pruning_keeps_selected_action() {
+    let depth = 6;
+    let alpha_beta = AlphaBetaStrategy::<PassRule, ScoreEvaluator, _>::new(depth);
+    let null_move =
+        AlphaBetaStrategy::<PassRule, ScoreEvaluator, _>::new(depth).with_null_move_pruning(2);
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.select_action(&board, actor),
+            null_move.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+/// 終盤ではパスを認めない三目並べ．
+/// 空きマスが少ない局面をツークツワンクが起こりうる局面とみなす．
+struct EndgamePassRule;
+
+/// パスを認める最小の空きマスの数．
+const MIN_EMPTY_SQUARES_FOR_PASS: usize = 5;
+
+impl Rule for EndgamePassRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = <ReversiRule as Rule>::ActionIterator;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+
+    fn try_null_move(state: &Board) -> Option<Board> {
+        let empty_squares = ReversiRule::iterate_available_actions(state, Actor::First).count();
+        if empty_squares >= MIN_EMPTY_SQUARES_FOR_PASS {
+            Some(state.clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_null_move_pruning_disabled_by_rule() {
+    // パスを認めない局面だけを探索する場合，探索は変わらない
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<EndgamePassRule, ScoreEvaluator, _>::new(depth);
+    let null_move = AlphaBetaStrategy::<EndgamePassRule, ScoreEvaluator, _>::new(depth)
+        .with_null_move_pruning(1);
+    let plies = FIELD_SIZE * FIELD_SIZE - MIN_EMPTY_SQUARES_FOR_PASS + 1;
+    for (board, actor) in enumerate_positions(plies) {
+        let (expected, expected_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (action, stats) = null_move.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action);
+        assert_eq!(expected_stats.nodes_evaluated, stats.nodes_evaluated);
+        assert_eq!(0, stats.null_move_cutoffs);
+    }
+
+    // パスを認める序盤では枝刈りが起こる
+    let (_, stats) = null_move.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(stats.null_move_cutoffs > 0);
+}
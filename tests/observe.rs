@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+type Event = SearchEvent<Board, Placement, BoardEvaluation>;
+
+fn count_nodes(events: &[Event]) -> u64 {
+    events
+        .iter()
+        .filter(|event| matches!(event, SearchEvent::NodeExpanded { .. }))
+        .count() as u64
+}
+
+fn count_cutoffs(events: &[Event], expected: CutoffReason) -> u64 {
+    events
+        .iter()
+        .filter(|event| matches!(event, SearchEvent::Cutoff { reason, .. } if *reason == expected))
+        .count() as u64
+}
+
+#[test]
+fn test_hook_counts_match_search_stats() {
+    for (board, actor) in enumerate_positions(2) {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(4);
+        let (expected, stats) = alpha_beta.select_scored_action_with_stats(&board, actor);
+        let observable = ObservableStrategy::new(alpha_beta, CollectingHook::new());
+        let scored = observable.select_scored_action(&board, actor);
+        assert_eq!(expected, scored, "{}", board);
+
+        let events = observable.hook().take_events();
+        assert_eq!(stats.nodes_evaluated, count_nodes(&events), "{}", board);
+        assert_eq!(
+            stats.alpha_cutoffs,
+            count_cutoffs(&events, CutoffReason::Alpha)
+        );
+        assert_eq!(
+            stats.beta_cutoffs,
+            count_cutoffs(&events, CutoffReason::Beta)
+        );
+    }
+}
+
+#[test]
+fn test_hook_reports_root_and_best_action() {
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+    let observable = ObservableStrategy::new(alpha_beta, CollectingHook::new());
+    let scored = observable
+        .select_scored_action(&board, Actor::First)
+        .unwrap();
+
+    let events = observable.hook().take_events();
+    assert_eq!(
+        Some(&SearchEvent::NodeExpanded {
+            depth: 0,
+            state: board.clone(),
+            actor: Actor::First,
+        }),
+        events.first()
+    );
+    assert!(events.iter().all(|event| match event {
+        SearchEvent::NodeExpanded { depth, .. } | SearchEvent::Cutoff { depth, .. } => *depth <= 3,
+        SearchEvent::BestActionUpdated { .. } => true,
+    }));
+    let last_best = events.iter().rev().find_map(|event| match event {
+        SearchEvent::BestActionUpdated { action, payoff } => Some((*action, *payoff)),
+        _ => None,
+    });
+    assert_eq!(Some((scored.action, scored.payoff)), last_best);
+    // 取り出した後は記録が空になる
+    assert!(observable.hook().take_events().is_empty());
+}
+
+#[test]
+fn test_hook_counts_match_search_stats_with_node_budget() {
+    let board = Board::new();
+    let alpha_beta =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(5).with_node_budget(300);
+    let (_, stats) = alpha_beta.select_scored_action_with_stats(&board, Actor::First);
+    let observable = ObservableStrategy::new(alpha_beta, CollectingHook::new());
+    observable.select_action(&board, Actor::First);
+
+    let events = observable.hook().take_events();
+    assert_eq!(stats.nodes_evaluated, count_nodes(&events));
+}
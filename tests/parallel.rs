@@ -0,0 +1,203 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_alpha_beta_strategy_is_send_sync() {
+    assert_send_sync::<AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize>>();
+    assert_send_sync::<ParallelAlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize>>();
+    assert_send_sync::<ConcurrentTranspositionTable<Board, i32, usize>>();
+    assert_send_sync::<LockFreeTranspositionTable<Board, i32, usize>>();
+}
+
+#[test]
+fn test_parallel_search_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let parallel = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let shared = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_transposition_table(1 << 12);
+        let lock_free = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_lock_free_transposition_table(1 << 12);
+        for (board, actor) in enumerate_positions(2) {
+            let expected = alpha_beta.root_payoff(&board, actor);
+            assert_eq!(expected, parallel.root_payoff(&board, actor), "{}", board);
+            assert_eq!(expected, shared.root_payoff(&board, actor), "{}", board);
+            assert_eq!(expected, lock_free.root_payoff(&board, actor), "{}", board);
+        }
+    }
+}
+
+/// 記録先の衝突を起こさず，すべての探索結果を記録する置換表．
+/// 複製した置換表とは記録を共有する．
+#[derive(Clone, Default)]
+struct MapTable {
+    entries: Arc<Mutex<HashMap<u64, TranspositionEntry<i32, usize>>>>,
+}
+
+impl TranspositionStore<Board, i32, usize> for &MapTable {
+    fn hash_state(&self, state: &Board) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn probe(
+        &mut self,
+        key: u64,
+        consideration_target: Actor,
+        next_actor: Actor,
+    ) -> Option<TranspositionEntry<i32, usize>> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(&key).copied().filter(|entry| {
+            entry.consideration_target == consideration_target && entry.next_actor == next_actor
+        })
+    }
+
+    fn store(&mut self, entry: TranspositionEntry<i32, usize>) {
+        self.entries.lock().unwrap().insert(entry.key, entry);
+    }
+}
+
+#[test]
+fn test_parallel_search_with_custom_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let table = MapTable::default();
+    let parallel = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_custom_transposition_table(table.clone());
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            parallel.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+    assert!(!table.entries.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_parallel_search_selects_same_action() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let parallel: ParallelAlphaBetaStrategy<_, _, _> =
+        AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_killer_moves()
+            .into();
+    let parallel = parallel.with_transposition_table(1 << 12);
+
+    for (board, actor) in enumerate_positions(1) {
+        assert_eq!(
+            alpha_beta.select_action(&board, actor),
+            parallel.select_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_parallel_search_at_game_end() {
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+    ]);
+    let parallel = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3);
+    assert_eq!(None, parallel.select_action(&board, Actor::Second));
+    assert_eq!(Some(-100), parallel.root_payoff(&board, Actor::Second));
+
+    let (action, stats) = parallel.select_action_with_stats(&Board::new(), Actor::First);
+    assert!(action.is_some());
+    assert!(stats.nodes_evaluated > 1);
+}
+
+#[test]
+fn test_parallel_search_is_deterministic() {
+    // 初期状態ではすべての行動が引き分けとなるので，評価値の等しい行動から先に列挙されたものを選ぶ必要がある
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let board = Board::new();
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .select_scored_action(&board, Actor::First);
+    let parallel = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for _ in 0..10 {
+        assert_eq!(
+            expected,
+            parallel.select_scored_action(&board, Actor::First)
+        );
+    }
+}
+
+#[test]
+fn test_young_brothers_wait_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let split = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_min_split_depth(2)
+            .with_threads(4);
+        let shared = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_min_split_depth(1)
+            .with_threads(2)
+            .with_transposition_table(1 << 12);
+        for (board, actor) in enumerate_positions(2) {
+            let expected = alpha_beta.root_payoff(&board, actor);
+            assert_eq!(expected, split.root_payoff(&board, actor), "{}", board);
+            assert_eq!(expected, shared.root_payoff(&board, actor), "{}", board);
+        }
+    }
+}
+
+#[test]
+fn test_young_brothers_wait_selects_equally_valued_action() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let split = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_min_split_depth(2)
+        .with_threads(4);
+    for (board, actor) in enumerate_positions(1) {
+        let expected = alpha_beta.select_scored_action(&board, actor);
+        let scored = split.select_scored_action(&board, actor);
+        assert_eq!(
+            expected.as_ref().map(|scored| scored.payoff),
+            scored.as_ref().map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+        // 評価値の等しい行動のうち，どれを選んでもよい．
+        // 選んだ行動を先に調べる逐次探索でも同じ行動が選ばれれば，その行動は最善である
+        if let Some(scored) = scored {
+            let chosen = scored.action;
+            let root = board.clone();
+            let sequential = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+                .with_move_orderer(
+                    move |state: &Board, _: Actor, actions: &mut Vec<Placement>| {
+                        if *state == root {
+                            actions.sort_by_key(|action| *action != chosen);
+                        }
+                    },
+                );
+            assert_eq!(
+                Some(chosen),
+                sequential.select_action(&board, actor),
+                "{}",
+                board
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_zero_threads_panics() {
+    let _ = ParallelAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3).with_threads(0);
+}
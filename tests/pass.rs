@@ -0,0 +1,176 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 各プレイヤーが自分の山から石を取り，山が空になったプレイヤーはパスするしかないゲームの状態．
+#[derive(Debug, Clone, PartialEq)]
+struct Piles {
+    /// 先手と後手の山に残っている石の数．
+    stones: [u32; 2],
+    /// 直前まで続けてパスが行われた回数．
+    passes: u32,
+}
+
+impl State for Piles {}
+
+fn index_of(actor: Actor) -> usize {
+    match actor {
+        Actor::First => 0,
+        Actor::Second => 1,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    Take(Actor, u32),
+    Pass(Actor),
+}
+
+impl Action for Move {
+    fn actor(&self) -> Actor {
+        match *self {
+            Move::Take(actor, _) | Move::Pass(actor) => actor,
+        }
+    }
+
+    fn is_pass(&self) -> bool {
+        matches!(self, Move::Pass(_))
+    }
+}
+
+fn available_moves(state: &Piles, actor: Actor) -> std::vec::IntoIter<Move> {
+    let stones = state.stones[index_of(actor)];
+    if stones == 0 {
+        return vec![Move::Pass(actor)].into_iter();
+    }
+    (1..=stones.min(2))
+        .map(|count| Move::Take(actor, count))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+fn translate(state: &Piles, action: &Move) -> Piles {
+    let mut next = state.clone();
+    match *action {
+        Move::Take(actor, count) => {
+            next.stones[index_of(actor)] -= count;
+            next.passes = 0;
+        }
+        Move::Pass(_) => next.passes += 1,
+    }
+    next
+}
+
+/// 両者が続けてパスするとゲームが終わるルール．
+struct PilesRule;
+
+impl Rule for PilesRule {
+    type S = Piles;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(state: &Piles) -> Option<TerminalKind> {
+        if state.passes >= 2 {
+            Some(TerminalKind::Draw)
+        } else {
+            None
+        }
+    }
+
+    fn iterate_available_actions(state: &Piles, actor: Actor) -> Self::ActionIterator {
+        available_moves(state, actor)
+    }
+
+    fn translate_state(state: &Piles, action: &Move) -> Piles {
+        translate(state, action)
+    }
+}
+
+/// ゲームの終わりを判定せず，パスを際限なく繰り返せるルール．
+struct EndlessPilesRule;
+
+impl Rule for EndlessPilesRule {
+    type S = Piles;
+    type A = Move;
+    type ActionIterator = std::vec::IntoIter<Move>;
+
+    fn terminal_kind(_state: &Piles) -> Option<TerminalKind> {
+        None
+    }
+
+    fn iterate_available_actions(state: &Piles, actor: Actor) -> Self::ActionIterator {
+        available_moves(state, actor)
+    }
+
+    fn translate_state(state: &Piles, action: &Move) -> Piles {
+        translate(state, action)
+    }
+}
+
+/// 自分の山に残っている石が少ないほど有利と評価する．
+struct PilesEvaluator;
+
+impl Evaluator<Piles> for PilesEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Piles) -> i32 {
+        state.stones[index_of(actor.opponent())] as i32 - state.stones[index_of(actor)] as i32
+    }
+}
+
+#[test]
+fn test_forced_passes_reach_game_over() {
+    let strategy = AlphaBetaStrategy::<PilesRule, PilesEvaluator, _>::new(4);
+    let mut state = Piles {
+        stones: [1, 6],
+        passes: 0,
+    };
+    let mut actor = Actor::First;
+    let mut passes = 0;
+    for _ in 0..20 {
+        if PilesRule::terminal_kind(&state).is_some() {
+            break;
+        }
+        let action = strategy.select_action(&state, actor).unwrap();
+        if action.is_pass() {
+            passes += 1;
+        }
+        state = PilesRule::translate_state(&state, &action);
+        actor = actor.opponent();
+    }
+    assert_eq!(Some(TerminalKind::Draw), PilesRule::terminal_kind(&state));
+    assert_eq!([0, 0], state.stones);
+    // 先手は石を取り終えた後，後手が石を取り終えるまでパスし続ける
+    assert!(passes >= 3, "{}", passes);
+}
+
+#[test]
+fn test_double_pass_is_terminal_in_search() {
+    let state = Piles {
+        stones: [0, 0],
+        passes: 0,
+    };
+    // ルールが終わりを判定しなくても，両者がパスした時点で探索を止める
+    let strategy = AlphaBetaStrategy::<EndlessPilesRule, PilesEvaluator, _>::new(1000u32);
+    let (action, stats) = strategy.select_action_with_stats(&state, Actor::First);
+    assert_eq!(Some(Move::Pass(Actor::First)), action);
+    assert_eq!(3, stats.nodes_evaluated);
+    assert_eq!(Some(0), strategy.root_payoff(&state, Actor::First));
+
+    // 間に石を取る行動が挟まれば，パスが続いたとはみなさない
+    let state = Piles {
+        stones: [0, 1],
+        passes: 0,
+    };
+    let (action, stats) = strategy.select_action_with_stats(&state, Actor::First);
+    assert_eq!(Some(Move::Pass(Actor::First)), action);
+    assert_eq!(5, stats.nodes_evaluated);
+    // 後手が石を取り終えてから両者がパスするので，残りの石の差はなくなる
+    assert_eq!(Some(0), strategy.root_payoff(&state, Actor::First));
+}
+
+#[test]
+fn test_actions_are_not_passes_by_default() {
+    assert!(!Placement::new(0, 0, Actor::First).is_pass());
+}
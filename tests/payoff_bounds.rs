@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// `ScoreEvaluator`の評価値を浮動小数点数にした評価関数．
+struct FloatScoreEvaluator;
+
+impl Evaluator<Board> for FloatScoreEvaluator {
+    type Payoff = f64;
+
+    fn evaluate_payoff_for(actor: Actor, state: &Board) -> f64 {
+        f64::from(ScoreEvaluator::evaluate_payoff_for(actor, state))
+    }
+}
+
+fn float_bounds() -> PayoffBounds<f64> {
+    PayoffBounds::new(f64::NEG_INFINITY, f64::INFINITY)
+}
+
+#[test]
+fn test_float_payoffs_match_integer_payoffs() {
+    for depth in 1..=4 {
+        let integer = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let float =
+            AlphaBetaStrategy::<ReversiRule, FloatScoreEvaluator, _>::new_with_payoff_bounds(
+                depth,
+                float_bounds(),
+            );
+        for (board, actor) in enumerate_positions(2) {
+            let expected = integer.select_scored_action(&board, actor).unwrap();
+            let scored = float.select_scored_action(&board, actor).unwrap();
+            assert_eq!(expected.action, scored.action, "depth {}\n{}", depth, board);
+            assert_eq!(f64::from(expected.payoff), scored.payoff);
+        }
+    }
+}
+
+#[test]
+fn test_float_payoffs_with_pruning_options() {
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(5)
+        .root_payoff(&board, Actor::First)
+        .map(f64::from);
+    let float = AlphaBetaStrategy::<ReversiRule, FloatScoreEvaluator, _>::new_with_payoff_bounds(
+        5,
+        float_bounds(),
+    )
+    .with_transposition_table(1 << 10)
+    .with_killer_moves()
+    .with_search_algorithm(SearchAlgorithm::PrincipalVariation);
+    assert_eq!(expected, float.root_payoff(&board, Actor::First));
+}
+
+#[test]
+fn test_signed_and_narrow_search_depths() {
+    let board = Board::new();
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3usize)
+        .select_action(&board, Actor::First);
+    let isize_depth = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3isize);
+    let i8_depth = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3i8);
+    assert_eq!(expected, isize_depth.select_action(&board, Actor::First));
+    assert_eq!(expected, i8_depth.select_action(&board, Actor::First));
+}
+
+#[test]
+fn test_narrow_payoff_bounds_cap_root_window() {
+    // 評価値の範囲を指定しても，範囲内に収まる評価値は変わらない
+    let board = Board::with_placements(&[(0, 0, Actor::First), (1, 1, Actor::Second)]);
+    let default = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4);
+    let bounded = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(4)
+        .with_payoff_bounds(PayoffBounds::new(-100, 100));
+    assert_eq!(
+        default.root_payoff(&board, Actor::First),
+        bounded.root_payoff(&board, Actor::First)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_empty_payoff_bounds_panics() {
+    let _ = PayoffBounds::new(1.0, -1.0);
+}
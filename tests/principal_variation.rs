@@ -0,0 +1,45 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_pv_reaches_game_over() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+
+    for (board, actor) in enumerate_positions(1) {
+        let pv = strategy.select_action_with_pv(&board, actor).unwrap();
+        assert_eq!(
+            strategy.select_action(&board, actor),
+            pv.actions.first().cloned()
+        );
+        assert_eq!(strategy.root_payoff(&board, actor), Some(pv.payoff));
+
+        // 主要変化の行動を順にとると，ゲームが終わり，その状態の評価値は主要変化の評価値と等しい
+        let mut state = board.clone();
+        let mut next_actor = actor;
+        for action in pv.actions.iter() {
+            assert!(ReversiRule::terminal_kind(&state).is_none(), "{}", state);
+            assert_eq!(next_actor, action.actor());
+            state = ReversiRule::translate_state(&state, action);
+            next_actor = next_actor.opponent();
+        }
+        assert!(ReversiRule::terminal_kind(&state).is_some(), "{}", state);
+        assert_eq!(
+            pv.payoff,
+            BoardEvaluator::evaluate_payoff_for(actor, &state),
+            "{}",
+            state
+        );
+    }
+}
+
+#[test]
+fn test_pv_length_is_limited_by_depth() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+    let pv = strategy
+        .select_action_with_pv(&Board::new(), Actor::First)
+        .unwrap();
+    assert_eq!(3, pv.actions.len());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_first_cannot_force_win_from_start() {
+    let search = ProofNumberSearch::<ReversiRule>::new(1 << 20);
+    let (outcome, tree) = search.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+    assert_eq!(ProofOutcome::Disproved, tree.outcome());
+    assert!(tree.root().is_disproved());
+    assert_eq!(INFINITE_PROOF_NUMBER, tree.root().proof_number);
+
+    // 後手の反証手順をたどると，先手が勝てないまま終局する
+    let mut board = Board::new();
+    let line = tree.principal_line();
+    assert!(!line.is_empty());
+    for action in line {
+        board = ReversiRule::translate_state(&board, action);
+    }
+    assert!(ReversiRule::terminal_kind(&board).is_some(), "{}", board);
+    assert_ne!(Some(Actor::First), ReversiRule::winner(&board), "{}", board);
+}
+
+#[test]
+fn test_first_can_avoid_losing_from_start() {
+    // 「後手が勝てない」ことは，先手が負けないことを意味する
+    let search = ProofNumberSearch::<ReversiRule>::new(1 << 20);
+    let (outcome, _) = search.solve(&Board::new(), Actor::First, Actor::First, |board| {
+        ReversiRule::winner(board) != Some(Actor::Second)
+    });
+    assert_eq!(ProofOutcome::Proved, outcome);
+}
+
+#[test]
+fn test_prove_forced_win_and_extract_line() {
+    // F - -
+    // - F -
+    // S S -
+    // 先手は(2,2)で勝つ
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 2, Actor::Second),
+        (1, 2, Actor::Second),
+    ]);
+    let search = ProofNumberSearch::<ReversiRule>::new(1 << 16);
+    let (outcome, tree) = search.solve_win(&board, Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Proved, outcome);
+    assert_eq!(0, tree.root().proof_number);
+
+    let line = tree.principal_line();
+    assert_eq!(vec![&Placement::new(2, 2, Actor::First)], line);
+
+    // 証明できた子ノードは勝ちに至る行動のものだけである
+    let proved = tree
+        .children(tree.root())
+        .filter(|child| child.is_proved())
+        .map(|child| child.cause_action.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Placement::new(2, 2, Actor::First)], proved);
+}
+
+#[test]
+fn test_unknown_when_budget_exhausted() {
+    let search = ProofNumberSearch::<ReversiRule>::new(10);
+    let (outcome, tree) = search.solve_win(&Board::new(), Actor::First, Actor::First);
+    assert_eq!(ProofOutcome::Unknown, outcome);
+    assert!(tree.principal_line().is_empty());
+    assert!(tree.len() >= 10);
+    assert_eq!(tree.len(), tree.iter_nodes().count());
+}
+
+#[test]
+fn test_solve_terminal_state() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let search = ProofNumberSearch::<ReversiRule>::new(1);
+    let (outcome, tree) = search.solve_win(&board, Actor::Second, Actor::First);
+    assert_eq!(ProofOutcome::Proved, outcome);
+    assert_eq!(1, tree.len());
+    let (outcome, _) = search.solve_win(&board, Actor::Second, Actor::Second);
+    assert_eq!(ProofOutcome::Disproved, outcome);
+}
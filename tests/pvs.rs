@@ -0,0 +1,38 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_pvs_root_payoff_matches_alpha_beta() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let pvs = PvsStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                pvs.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pvs_visits_fewer_nodes_than_alpha_beta() {
+    // 空の盤面から最後まで読む場合の探索量を比べる．
+    // この局面では最初に調べる子ノードが最善であることが多く，探索量は必ずαβ法より少ない．
+    // 他の局面での比較は`benches/pvs.rs`で行う
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let pvs = PvsStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let board = Board::new();
+
+    let (alpha_beta_action, alpha_beta_stats) =
+        alpha_beta.select_action_with_stats(&board, Actor::First);
+    let (pvs_action, pvs_stats) = pvs.select_action_with_stats(&board, Actor::First);
+    assert_eq!(alpha_beta_action, pvs_action);
+    assert!(pvs_stats.nodes_evaluated < alpha_beta_stats.nodes_evaluated);
+}
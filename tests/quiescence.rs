@@ -0,0 +1,223 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// すべての行動を静止探索の対象とする三目並べ．
+struct LoudRule;
+
+impl Rule for LoudRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = <ReversiRule as Rule>::ActionIterator;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+
+    fn is_loud_action(_action: &Placement) -> bool {
+        true
+    }
+}
+
+/// 後手が(2, 2)に置けば勝つ盤面．
+fn threatened_board() -> Board {
+    // - - F
+    // - F -
+    // S S -
+    Board::with_placements(&[
+        (2, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 2, Actor::Second),
+        (1, 2, Actor::Second),
+    ])
+}
+
+#[test]
+fn test_quiescence_mitigates_horizon_effect() {
+    let board = threatened_board();
+
+    // 深さ1の探索では，相手の勝ちを見落とす
+    let shallow = AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(1);
+    let action = shallow.select_action(&board, Actor::First).unwrap();
+    assert_ne!((2, 2), (action.x, action.y));
+
+    // 静止探索により，相手の勝ちを防ぐ行動を選ぶ
+    let quiescence =
+        AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(1).with_quiescence_depth(1);
+    let action = quiescence.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 2), (action.x, action.y));
+}
+
+#[test]
+fn test_quiescence_without_loud_actions_keeps_payoff() {
+    // 静止探索の対象となる行動がなければ，結果は変わらない
+    for depth in 1..4 {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let quiescence = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+            .with_quiescence_depth(3);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.select_action_with_stats(&board, actor).0,
+                quiescence.select_action_with_stats(&board, actor).0,
+                "depth {}\n{}",
+                depth,
+                board
+            );
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                quiescence.root_payoff(&board, actor)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_quiescence_is_pruned() {
+    let depth = 2;
+    let quiescence_depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(depth)
+        .with_quiescence_depth(quiescence_depth);
+    let (action, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+
+    assert!(action.is_some());
+    // 静止探索は探索深さを超えて行われ，その中でも枝刈りが起こる
+    assert!(stats.max_depth_reached > depth as u32);
+    assert!(stats.alpha_cutoffs + stats.beta_cutoffs > 0);
+    let minimax_nodes: u64 = (0..=quiescence_depth as u64)
+        .map(|plies| (0..plies).map(|i| 9 - i).product::<u64>())
+        .sum();
+    assert!(stats.nodes_evaluated < minimax_nodes);
+}
+
+impl QuiescenceRule for LoudRule {
+    type NoisyActions = <ReversiRule as Rule>::ActionIterator;
+
+    fn iterate_noisy_actions(state: &Board, actor: Actor) -> Self::NoisyActions {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+}
+
+/// 静止探索の対象となる行動がない三目並べ．
+impl QuiescenceRule for ReversiRule {
+    type NoisyActions = Option<Placement>;
+
+    fn iterate_noisy_actions(_state: &Board, _actor: Actor) -> Self::NoisyActions {
+        None
+    }
+}
+
+#[test]
+fn test_quiescence_search_matches_loud_actions() {
+    for depth in 1..4 {
+        for quiescence_depth in 1..4 {
+            let loud = AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(depth)
+                .with_quiescence_depth(quiescence_depth);
+            let noisy = AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(depth)
+                .with_quiescence_search(quiescence_depth);
+            for (board, actor) in enumerate_positions(2) {
+                let (loud_action, loud_stats) = loud.select_action_with_stats(&board, actor);
+                let (noisy_action, noisy_stats) = noisy.select_action_with_stats(&board, actor);
+                assert_eq!(loud_action, noisy_action, "{}", board);
+                assert_eq!(loud_stats.nodes_evaluated, noisy_stats.nodes_evaluated);
+            }
+        }
+    }
+
+    let quiescence =
+        AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(1).with_quiescence_search(1);
+    let action = quiescence
+        .select_action(&threatened_board(), Actor::First)
+        .unwrap();
+    assert_eq!((2, 2), (action.x, action.y));
+}
+
+#[test]
+fn test_quiescence_search_stops_at_quiet_position() {
+    // 静止探索で調べる行動がなければ，探索深さで評価する
+    for depth in 1..4 {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let quiescence = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+            .with_quiescence_search(5);
+        for (board, actor) in enumerate_positions(1) {
+            let (action, stats) = alpha_beta.select_action_with_stats(&board, actor);
+            let (quiescence_action, quiescence_stats) =
+                quiescence.select_action_with_stats(&board, actor);
+            assert_eq!(action, quiescence_action, "depth {}\n{}", depth, board);
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                quiescence.root_payoff(&board, actor)
+            );
+            assert_eq!(stats.max_depth_reached, quiescence_stats.max_depth_reached);
+        }
+    }
+}
+
+/// すべての行動を静止探索の対象とし，どちらのプレイヤーも1手で勝てない局面を静かな局面とする三目並べ．
+struct ThreatAwareRule;
+
+impl Rule for ThreatAwareRule {
+    type S = Board;
+    type A = Placement;
+    type ActionIterator = <ReversiRule as Rule>::ActionIterator;
+
+    fn terminal_kind(state: &Board) -> Option<TerminalKind> {
+        ReversiRule::terminal_kind(state)
+    }
+
+    fn iterate_available_actions(state: &Board, actor: Actor) -> Self::ActionIterator {
+        ReversiRule::iterate_available_actions(state, actor)
+    }
+
+    fn translate_state(state: &Board, action: &Placement) -> Board {
+        ReversiRule::translate_state(state, action)
+    }
+
+    fn is_loud_action(_action: &Placement) -> bool {
+        true
+    }
+
+    fn is_quiet_position(state: &Board) -> bool {
+        Actor::ALL.iter().all(|&actor| {
+            ReversiRule::iterate_available_actions(state, actor).all(|action| {
+                let next = ReversiRule::translate_state(state, &action);
+                ReversiRule::terminal_kind(&next).is_none()
+            })
+        })
+    }
+}
+
+#[test]
+fn test_quiescence_stops_at_quiet_position_before_depth() {
+    // 脅威のある局面では静止探索を続けるので，相手の勝ちを防ぐ行動を選ぶ
+    let quiet =
+        AlphaBetaStrategy::<ThreatAwareRule, BoardEvaluator, _>::new(1).with_quiescence_depth(3);
+    let action = quiet
+        .select_action(&threatened_board(), Actor::First)
+        .unwrap();
+    assert_eq!((2, 2), (action.x, action.y));
+
+    // 静かな局面に達した時点で打ち切るので，すべての行動を最後まで調べる静止探索より探索量が少ない
+    let quiescence_depth = FIELD_SIZE * FIELD_SIZE;
+    let loud = AlphaBetaStrategy::<LoudRule, BoardEvaluator, _>::new(1)
+        .with_quiescence_depth(quiescence_depth);
+    let quiet = AlphaBetaStrategy::<ThreatAwareRule, BoardEvaluator, _>::new(1)
+        .with_quiescence_depth(quiescence_depth);
+    let mut total = (0, 0);
+    for (board, actor) in enumerate_positions(2) {
+        let (_, loud_stats) = loud.select_action_with_stats(&board, actor);
+        let (_, quiet_stats) = quiet.select_action_with_stats(&board, actor);
+        assert!(quiet_stats.max_depth_reached <= loud_stats.max_depth_reached);
+        total.0 += quiet_stats.nodes_evaluated;
+        total.1 += loud_stats.nodes_evaluated;
+    }
+    assert!(total.0 < total.1);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const GAMES: usize = 1000;
+
+/// 指定したエージェント同士で対戦し，結果を返す．
+fn play<F, S>(first: &F, second: &S) -> GameResult
+where
+    F: Strategy<Board, Placement>,
+    S: Strategy<Board, Placement>,
+{
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    loop {
+        if let Some(result) = board.game_result() {
+            return result;
+        }
+        let action = match actor {
+            Actor::First => first.select_action(&board, actor),
+            Actor::Second => second.select_action(&board, actor),
+        }
+        .unwrap();
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+}
+
+#[test]
+fn test_random_strategy_selects_available_action() {
+    let strategy = RandomStrategy::<ReversiRule, _>::seeded(0);
+    for (board, actor) in enumerate_positions(3) {
+        let action = strategy.select_action(&board, actor).unwrap();
+        assert_eq!(actor, action.actor);
+        assert!(board.at(action.x, action.y).is_none());
+    }
+
+    // 取れる行動がなければ，行動を選択しない
+    let full = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::Second),
+        (2, 0, Actor::First),
+        (0, 1, Actor::First),
+        (1, 1, Actor::Second),
+        (2, 1, Actor::Second),
+        (0, 2, Actor::Second),
+        (1, 2, Actor::First),
+        (2, 2, Actor::First),
+    ]);
+    assert_eq!(None, strategy.select_action(&full, Actor::First));
+}
+
+#[test]
+fn test_random_strategy_is_reproducible() {
+    let first = RandomStrategy::<ReversiRule, _>::seeded(7);
+    let second = RandomStrategy::<ReversiRule, _>::new(StdRng::seed_from_u64(7));
+    for _ in 0..100 {
+        assert_eq!(
+            first.select_action(&Board::new(), Actor::First),
+            second.select_action(&Board::new(), Actor::First)
+        );
+    }
+}
+
+#[test]
+fn test_random_strategy_chooses_uniformly() {
+    // 空の盤面では，9マスがほぼ同じ回数ずつ選ばれる
+    let strategy = RandomStrategy::<ReversiRule, _>::seeded(1);
+    let mut counts = [0; FIELD_SIZE * FIELD_SIZE];
+    for _ in 0..GAMES * FIELD_SIZE * FIELD_SIZE {
+        let action = strategy.select_action(&Board::new(), Actor::First).unwrap();
+        counts[action.y * FIELD_SIZE + action.x] += 1;
+    }
+    for &count in counts.iter() {
+        assert!(800 < count && count < 1200, "{:?}", counts);
+    }
+}
+
+#[test]
+fn test_random_games_outcome_distribution() {
+    // 互いにランダムに打つ三目並べでは，先手の勝ち，後手の勝ち，引き分けの確率は
+    // それぞれ約58.5%，28.8%，12.7%である
+    let first = RandomStrategy::<ReversiRule, _>::seeded(2);
+    let second = RandomStrategy::<ReversiRule, _>::seeded(3);
+    let (mut first_wins, mut second_wins, mut draws) = (0i32, 0, 0);
+    for _ in 0..GAMES {
+        match play(&first, &second) {
+            GameResult::Win(Actor::First) => first_wins += 1,
+            GameResult::Win(Actor::Second) => second_wins += 1,
+            GameResult::Draw => draws += 1,
+        }
+    }
+    let expected = [(first_wins, 585), (second_wins, 288), (draws, 127)];
+    for &(count, expected) in expected.iter() {
+        assert!(
+            (count - expected).abs() < 60,
+            "{} {} {}",
+            first_wins,
+            second_wins,
+            draws
+        );
+    }
+}
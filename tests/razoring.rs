@@ -0,0 +1,159 @@
+use minimax_strategy::*;
+use std::cmp::Ordering;
+
+/// 1手で評価値が変わりうる最大の量．
+const MAX_STEP: i32 = 5;
+/// ゲームが終わるまでの手数．
+const HORIZON: u32 = 6;
+
+/// 乱数から作られるゲーム木の局面．
+/// 各行動は評価値を`-MAX_STEP`から`MAX_STEP`の範囲で変化させる．
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Walk {
+    id: u64,
+    ply: u32,
+    score: i32,
+}
+
+impl State for Walk {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    actor: Actor,
+    id: u64,
+    delta: i32,
+}
+
+impl Action for Step {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+/// SplitMix64により，指定した値から乱数を作る．
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+struct WalkRule;
+
+impl Rule for WalkRule {
+    type S = Walk;
+    type A = Step;
+    type ActionIterator = std::vec::IntoIter<Step>;
+
+    fn terminal_kind(state: &Walk) -> Option<TerminalKind> {
+        if state.ply < HORIZON {
+            return None;
+        }
+        Some(match state.score.cmp(&0) {
+            Ordering::Greater => TerminalKind::Win(Actor::First),
+            Ordering::Less => TerminalKind::Win(Actor::Second),
+            Ordering::Equal => TerminalKind::Draw,
+        })
+    }
+
+    fn iterate_available_actions(state: &Walk, actor: Actor) -> Self::ActionIterator {
+        let branching = 2 + mix(state.id) % 3;
+        (0..branching)
+            .map(|i| {
+                let id = mix(state.id ^ (i + 1).wrapping_mul(0x1234_5678_9ABC_DEF1));
+                let delta = (id % (2 * MAX_STEP as u64 + 1)) as i32 - MAX_STEP;
+                Step { actor, id, delta }
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn translate_state(state: &Walk, action: &Step) -> Walk {
+        Walk {
+            id: action.id,
+            ply: state.ply + 1,
+            score: state.score + action.delta,
+        }
+    }
+}
+
+struct WalkEvaluator;
+
+impl Evaluator<Walk> for WalkEvaluator {
+    type Payoff = i32;
+    fn evaluate_payoff_for(actor: Actor, state: &Walk) -> i32 {
+        match actor {
+            Actor::First => state.score,
+            Actor::Second => -state.score,
+        }
+    }
+}
+
+impl StaticEvaluator<Walk> for WalkEvaluator {}
+
+fn root(seed: u64) -> Walk {
+    Walk {
+        id: mix(seed),
+        ply: 0,
+        score: 0,
+    }
+}
+
+#[test]
+fn test_razoring_with_generous_margins_keeps_payoff() {
+    // 残りの探索深さがdのノードの評価値は，静的評価値から高々d * MAX_STEPしか変わらないので，
+    // その値を余裕とすれば，Razoringは評価値を変えない
+    let margins = (1..=3).map(|depth| depth * MAX_STEP).collect::<Vec<_>>();
+    let mut razored_nodes = 0;
+    for depth in 1..=HORIZON {
+        let alpha_beta = AlphaBetaStrategy::<WalkRule, WalkEvaluator, _>::new(depth);
+        let razoring = AlphaBetaStrategy::<WalkRule, WalkEvaluator, _>::new(depth)
+            .with_razoring(margins.clone());
+        for seed in 0..200 {
+            for &actor in Actor::ALL.iter() {
+                let state = root(seed);
+                assert_eq!(
+                    alpha_beta.root_payoff(&state, actor),
+                    razoring.root_payoff(&state, actor),
+                    "depth {} seed {}",
+                    depth,
+                    seed
+                );
+                let (_, stats) = razoring.select_action_with_stats(&state, actor);
+                razored_nodes += stats.razored_nodes;
+            }
+        }
+    }
+    assert!(razored_nodes > 0);
+}
+
+#[test]
+fn test_razoring_is_off_by_default() {
+    let strategy = AlphaBetaStrategy::<WalkRule, WalkEvaluator, _>::new(HORIZON);
+    let (_, stats) = strategy.select_action_with_stats(&root(0), Actor::First);
+    assert_eq!(0, stats.razored_nodes);
+}
+
+#[test]
+fn test_razoring_reduces_visited_nodes() {
+    let depth = HORIZON;
+    let margins = (1..=3).map(|depth| depth * MAX_STEP).collect::<Vec<_>>();
+    let alpha_beta = AlphaBetaStrategy::<WalkRule, WalkEvaluator, _>::new(depth);
+    let razoring =
+        AlphaBetaStrategy::<WalkRule, WalkEvaluator, _>::new(depth).with_razoring(margins);
+
+    let mut nodes_without_razoring = 0;
+    let mut nodes_with_razoring = 0;
+    for seed in 0..200 {
+        let (_, stats) = alpha_beta.select_action_with_stats(&root(seed), Actor::First);
+        nodes_without_razoring += stats.nodes_evaluated;
+        let (_, stats) = razoring.select_action_with_stats(&root(seed), Actor::First);
+        nodes_with_razoring += stats.nodes_evaluated;
+    }
+    assert!(
+        nodes_with_razoring < nodes_without_razoring,
+        "{} {}",
+        nodes_with_razoring,
+        nodes_without_razoring
+    );
+}
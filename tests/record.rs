@@ -0,0 +1,98 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 先手が左の列を揃えて勝つまでの行動列．
+fn winning_actions() -> Vec<Placement> {
+    vec![
+        Placement::new(0, 0, Actor::First),
+        Placement::new(1, 0, Actor::Second),
+        Placement::new(0, 1, Actor::First),
+        Placement::new(1, 1, Actor::Second),
+        Placement::new(0, 2, Actor::First),
+    ]
+}
+
+#[test]
+fn test_replay_records_transitions() {
+    let actions = winning_actions();
+    let record = GameRecord::replay::<ReversiRule, _>(Board::new(), actions.clone()).unwrap();
+
+    assert_eq!(actions.len(), record.len());
+    assert_eq!(&Board::new(), record.initial());
+    assert_eq!(actions, record.actions().copied().collect::<Vec<_>>());
+    assert_eq!(
+        Some(GameResult::Win(Actor::First)),
+        record.terminal().game_result()
+    );
+
+    // 各状態で記録された行動をとると，次の状態になる
+    let mut expected = Board::new();
+    for (state, action) in &record {
+        assert_eq!(&expected, state);
+        expected = ReversiRule::translate_state(state, action);
+    }
+    assert_eq!(&expected, record.terminal());
+}
+
+#[test]
+fn test_replay_detects_invalid_actions() {
+    // すでに石が置かれたマスには置けない
+    let mut actions = winning_actions();
+    actions[1] = Placement::new(0, 0, Actor::Second);
+    assert_eq!(
+        Err(ReplayError::UnavailableAction { ply: 1 }),
+        GameRecord::replay::<ReversiRule, _>(Board::new(), actions)
+    );
+
+    // 勝敗が決まった後は行動できない
+    let mut actions = winning_actions();
+    actions.push(Placement::new(2, 2, Actor::Second));
+    assert_eq!(
+        Err(ReplayError::GameAlreadyOver { ply: 5 }),
+        GameRecord::replay::<ReversiRule, _>(Board::new(), actions)
+    );
+}
+
+#[test]
+fn test_record_played_game() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(9);
+    let mut record = GameRecord::new(Board::new());
+    let mut actor = Actor::First;
+    while ReversiRule::terminal_kind(record.terminal()).is_none() {
+        let action = strategy.select_action(record.terminal(), actor).unwrap();
+        let next_state = ReversiRule::translate_state(record.terminal(), &action);
+        record.record(action, next_state);
+        actor = actor.opponent();
+    }
+    assert!(!record.is_empty());
+
+    let replayed =
+        GameRecord::replay::<ReversiRule, _>(Board::new(), record.actions().copied()).unwrap();
+    assert_eq!(record, replayed);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_record_round_trip() {
+    let record = GameRecord::replay::<ReversiRule, _>(Board::new(), winning_actions()).unwrap();
+    let mut buffer = vec![];
+    record.write_to(&mut buffer).unwrap();
+    // 各行動とその直前の状態を1行ずつ書き込み，最後の状態を最後の行に書き込む
+    assert_eq!(
+        record.len() + 1,
+        String::from_utf8(buffer.clone()).unwrap().lines().count()
+    );
+    let restored = GameRecord::<Board, Placement>::read_from(&mut buffer.as_slice()).unwrap();
+    assert_eq!(record, restored);
+
+    // 最後の状態がない記録は読み込めない
+    let truncated = buffer
+        .split(|&b| b == b'\n')
+        .take(record.len())
+        .collect::<Vec<_>>()
+        .join(&b'\n');
+    let error = GameRecord::<Board, Placement>::read_from(&mut truncated.as_slice()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 関心範囲の幅を変えながら試す窓．
+const WINDOWS: [(i32, i32); 6] = [(-100, 100), (0, 0), (-5, 5), (3, 4), (-4, -3), (50, 60)];
+
+#[test]
+fn test_fail_soft_bounds_true_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(1) {
+        let payoff = strategy.root_payoff(&board, actor).unwrap();
+        for &(min, max) in WINDOWS.iter() {
+            let bound = strategy
+                .search_window(&board, actor, min, max, WindowMode::FailSoft)
+                .unwrap();
+            match bound {
+                ScoreBound::Exact(e) => assert_eq!(payoff, e, "{}", board),
+                ScoreBound::Lower(e) => assert!(max < e && e <= payoff, "{}", board),
+                ScoreBound::Upper(e) => assert!(payoff <= e && e < min, "{}", board),
+            }
+            // 真の評価値が関心範囲に収まる場合だけ，真の評価値そのものが得られる
+            assert_eq!(min <= payoff && payoff <= max, bound.is_exact());
+        }
+    }
+}
+
+#[test]
+fn test_fail_hard_clamps_to_window() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(1) {
+        let payoff = strategy.root_payoff(&board, actor).unwrap();
+        for &(min, max) in WINDOWS.iter() {
+            let bound = strategy
+                .search_window(&board, actor, min, max, WindowMode::FailHard)
+                .unwrap();
+            let expected = if payoff < min {
+                ScoreBound::Upper(min)
+            } else if payoff > max {
+                ScoreBound::Lower(max)
+            } else {
+                ScoreBound::Exact(payoff)
+            };
+            assert_eq!(expected, bound, "{}", board);
+        }
+    }
+}
+
+#[test]
+fn test_fail_soft_is_at_least_as_tight_as_fail_hard() {
+    let depth = 4;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12);
+    let board = Board::new();
+    for &(min, max) in WINDOWS.iter() {
+        let soft = strategy.search_window(&board, Actor::First, min, max, WindowMode::FailSoft);
+        let hard = strategy.search_window(&board, Actor::First, min, max, WindowMode::FailHard);
+        match (soft.unwrap(), hard.unwrap()) {
+            (ScoreBound::Exact(s), ScoreBound::Exact(h)) => assert_eq!(s, h),
+            (ScoreBound::Lower(s), ScoreBound::Lower(h)) => assert!(s > h),
+            (ScoreBound::Upper(s), ScoreBound::Upper(h)) => assert!(s < h),
+            (soft, hard) => panic!("{:?} {:?}", soft, hard),
+        }
+    }
+}
+
+#[test]
+fn test_score_bound_accessors() {
+    assert_eq!(3, ScoreBound::Lower(3).value());
+    assert_eq!(BoundType::Exact, ScoreBound::Exact(0).bound_type());
+    assert_eq!(BoundType::LowerBound, ScoreBound::Lower(0).bound_type());
+    assert_eq!(BoundType::UpperBound, ScoreBound::Upper(0).bound_type());
+    assert!(ScoreBound::Exact(1).is_exact());
+    assert!(!ScoreBound::Upper(1).is_exact());
+    assert_eq!(WindowMode::FailSoft, WindowMode::default());
+}
+
+#[test]
+#[should_panic]
+fn test_empty_window_panics() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(2);
+    strategy.search_window(&Board::new(), Actor::First, 1, 0, WindowMode::FailSoft);
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 先手が1手で勝てる盤面．
+///
+/// F - -
+/// S F -
+/// S - -
+fn winning_board() -> Board {
+    Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ])
+}
+
+#[test]
+fn test_scored_action_of_winning_position() {
+    // 1手先までしか読まないので，すぐに勝てる行動だけが勝ちと評価される
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(1);
+    let scored = strategy
+        .select_scored_action(&winning_board(), Actor::First)
+        .unwrap();
+
+    assert_eq!(Placement::new(2, 2, Actor::First), scored.action);
+    assert_eq!(100, scored.payoff);
+}
+
+#[test]
+fn test_scored_payoff_equals_minimax_value() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let minimax = MinimaxStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let pvs = PvsStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let negamax = NegamaxStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let iterative_deepening =
+        IterativeDeepeningStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(1) {
+        let expected = minimax.root_payoff(&board, actor);
+        let scored = minimax.select_scored_action(&board, actor);
+        assert_eq!(expected, scored.map(|scored| scored.payoff), "{}", board);
+        assert_eq!(
+            expected,
+            alpha_beta
+                .select_scored_action(&board, actor)
+                .map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+        assert_eq!(
+            expected,
+            pvs.select_scored_action(&board, actor)
+                .map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+        assert_eq!(
+            expected,
+            negamax
+                .select_scored_action(&board, actor)
+                .map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+        assert_eq!(
+            expected,
+            iterative_deepening
+                .select_scored_action(&board, actor)
+                .map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_scored_action_equals_selected_action() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12);
+    for (board, actor) in enumerate_positions(2) {
+        let scored = strategy.select_scored_action(&board, actor);
+        assert_eq!(
+            strategy.select_action(&board, actor),
+            scored.map(|scored| scored.action),
+            "{}",
+            board
+        );
+        assert_eq!(
+            strategy.root_payoff(&board, actor),
+            scored.map(|scored| scored.payoff),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_no_scored_action_when_game_is_over() {
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 1, Actor::First),
+        (2, 2, Actor::First),
+        (0, 1, Actor::Second),
+        (0, 2, Actor::Second),
+    ]);
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(3);
+    assert_eq!(None, strategy.select_scored_action(&board, Actor::Second));
+}
+
+#[test]
+fn test_mcts_scored_payoff_is_average_reward() {
+    let mcts = MctsStrategy::<ReversiRule, _>::new(1000).with_seed(0);
+    let scored = mcts
+        .select_scored_action(&winning_board(), Actor::First)
+        .unwrap();
+
+    assert_eq!(Placement::new(2, 2, Actor::First), scored.action);
+    assert!((0.0..=1.0).contains(&scored.payoff));
+    assert!(scored.payoff > 0.9, "{}", scored.payoff);
+}
+
+#[test]
+fn test_payoff_of_forced_win_is_backed_up() {
+    // 先手は3手目で勝てるが，1手目の直後にはまだ勝敗が決まらない
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (0, 1, Actor::First),
+        (0, 2, Actor::Second),
+        (1, 0, Actor::Second),
+    ]);
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(FIELD_SIZE * FIELD_SIZE);
+    let (action, payoff) = strategy
+        .select_action_with_payoff(&board, Actor::First)
+        .unwrap();
+
+    assert_eq!(BoardEvaluation::Win, payoff);
+    assert_eq!(strategy.select_action(&board, Actor::First), Some(action));
+    let next_board = ReversiRule::translate_state(&board, &action);
+    assert!(ReversiRule::terminal_kind(&next_board).is_none());
+    assert_ne!(
+        BoardEvaluation::Win,
+        BoardEvaluator::evaluate_payoff_for(Actor::First, &next_board)
+    );
+}
+
+#[test]
+fn test_payoff_equals_root_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            strategy.root_payoff(&board, actor),
+            strategy
+                .select_action_with_payoff(&board, actor)
+                .map(|(_, payoff)| payoff),
+            "{}",
+            board
+        );
+    }
+    let finished = winning_board();
+    let finished = ReversiRule::translate_state(&finished, &Placement::new(2, 2, Actor::First));
+    assert_eq!(
+        None,
+        strategy.select_action_with_payoff(&finished, Actor::Second)
+    );
+}
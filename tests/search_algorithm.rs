@@ -0,0 +1,81 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_principal_variation_keeps_root_payoff() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+        let pvs = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+            .with_search_algorithm(SearchAlgorithm::PrincipalVariation);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                pvs.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_principal_variation_selects_action_with_equal_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let pvs = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_search_algorithm(SearchAlgorithm::PrincipalVariation);
+
+    for (board, actor) in enumerate_positions(2) {
+        let expected = alpha_beta.select_action(&board, actor).unwrap();
+        let action = pvs.select_action(&board, actor).unwrap();
+        let after_expected = ReversiRule::translate_state(&board, &expected);
+        let after_action = ReversiRule::translate_state(&board, &action);
+        assert_eq!(
+            alpha_beta.root_payoff(&after_expected, actor.opponent()),
+            alpha_beta.root_payoff(&after_action, actor.opponent()),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_principal_variation_with_transposition_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let pvs = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_search_algorithm(SearchAlgorithm::PrincipalVariation)
+        .with_transposition_table(1 << 12);
+    let strategy = IterativeDeepeningStrategy::from(pvs);
+
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            strategy.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_principal_variation_counts_re_searches() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let pvs = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_search_algorithm(SearchAlgorithm::PrincipalVariation);
+    let board = Board::new();
+
+    let (_, alpha_beta_stats) = alpha_beta.select_action_with_stats(&board, Actor::First);
+    let (_, pvs_stats) = pvs.select_action_with_stats(&board, Actor::First);
+    assert_eq!(0, alpha_beta_stats.re_searches);
+    assert!(pvs_stats.re_searches > 0);
+    // 独立したエージェントとしての主要変化探索と，同じ量の探索を行う
+    let (_, standalone_stats) = PvsStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .select_action_with_stats(&board, Actor::First);
+    assert_eq!(standalone_stats.nodes_evaluated, pvs_stats.nodes_evaluated);
+    assert_eq!(standalone_stats.re_searches, pvs_stats.re_searches);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_alpha_beta_config_round_trip() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(4)
+        .with_search_algorithm(SearchAlgorithm::PrincipalVariation)
+        .with_quiescence_depth(2);
+    let json = serde_json::to_string(&strategy).unwrap();
+    let restored: AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(json, serde_json::to_string(&restored).unwrap());
+
+    let board = Board::with_placements(&[(1, 1, Actor::First), (0, 0, Actor::Second)]);
+    assert_eq!(
+        strategy.select_action(&board, Actor::First),
+        restored.select_action(&board, Actor::First)
+    );
+    assert_eq!(
+        strategy
+            .select_action_with_stats(&board, Actor::First)
+            .1
+            .nodes_evaluated,
+        restored
+            .select_action_with_stats(&board, Actor::First)
+            .1
+            .nodes_evaluated
+    );
+}
+
+#[test]
+fn test_alpha_beta_limits_round_trip() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(4)
+        .with_payoff_bounds(PayoffBounds::new(-100, 100))
+        .with_extensions(3)
+        .with_memory_budget(MemoryBudget::new(1 << 20));
+    let json = serde_json::to_string(&strategy).unwrap();
+    let restored: AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(json, serde_json::to_string(&restored).unwrap());
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["payoff_bounds"]["min"], -100);
+    assert_eq!(value["payoff_bounds"]["max"], 100);
+    assert_eq!(value["extension_budget"], 3);
+    assert_eq!(value["memory_budget"]["bytes"], 1 << 20);
+
+    let budget = MemoryBudget::new(4096);
+    let json = serde_json::to_string(&budget).unwrap();
+    assert_eq!(budget, serde_json::from_str::<MemoryBudget>(&json).unwrap());
+}
+
+#[test]
+fn test_alpha_beta_config_defaults() {
+    // 探索深さ以外の設定は省略できる
+    let restored: AlphaBetaStrategy<ReversiRule, ScoreEvaluator, usize> =
+        serde_json::from_str(r#"{"search_depth":9}"#).unwrap();
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(9);
+    assert_eq!(
+        strategy.select_action(&Board::new(), Actor::First),
+        restored.select_action(&Board::new(), Actor::First)
+    );
+}
+
+#[test]
+fn test_stats_and_actor_round_trip() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, usize>::new(5);
+    let (_, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(stats, serde_json::from_str::<SearchStats>(&json).unwrap());
+
+    for &actor in Actor::ALL.iter() {
+        let json = serde_json::to_string(&actor).unwrap();
+        assert_eq!(actor, serde_json::from_str::<Actor>(&json).unwrap());
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+type Outcome = RuleOutcome<ReversiRule>;
+
+/// 一方が最善の行動の一覧に従い，他方が指定したエージェントに従って対局した結果を返す．
+fn play_against<T>(solution: &Solution<Placement>, solver_actor: Actor, opponent: &T) -> GameOutcome
+where
+    T: Strategy<Board, Placement>,
+{
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    loop {
+        if let Some(outcome) = Outcome::outcome(&board) {
+            return outcome;
+        }
+        let action = if actor == solver_actor {
+            *solution.best_action(&board, actor).unwrap()
+        } else {
+            opponent.select_action(&board, actor).unwrap()
+        };
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+}
+
+#[test]
+fn test_start_position_is_draw() {
+    assert_eq!(
+        GameOutcome::Draw,
+        solve::<ReversiRule, Outcome>(&Board::new(), Actor::First)
+    );
+}
+
+#[test]
+fn test_solver_finds_forced_win() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let solution = solve_with_policy::<ReversiRule, Outcome>(&board, Actor::First);
+    assert_eq!(GameOutcome::Win(Actor::First), solution.outcome());
+    let action = solution.best_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+    // 手番が入れ替わると，後手が先に勝つ
+    assert_eq!(
+        GameOutcome::Win(Actor::Second),
+        solve::<ReversiRule, Outcome>(&board, Actor::Second)
+    );
+}
+
+#[test]
+fn test_solver_policy_never_loses_against_alpha_beta() {
+    // 最善を尽くせば負けないので，相手が勝ちを読み切って探索を省いた局面には進まない．
+    // そのため，初期状態から解いた一覧だけで先手と後手のどちらも持てる
+    let solution = solve_with_policy::<ReversiRule, Outcome>(&Board::new(), Actor::First);
+    for depth in 1..=4 {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let outcome = play_against(&solution, Actor::First, &alpha_beta);
+        assert_ne!(GameOutcome::Win(Actor::Second), outcome, "depth {}", depth);
+        let outcome = play_against(&solution, Actor::Second, &alpha_beta);
+        assert_ne!(GameOutcome::Win(Actor::First), outcome, "depth {}", depth);
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_stats_action_matches_select_action() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(4);
+    for (board, actor) in enumerate_positions(1) {
+        let (action, stats) = strategy.select_action_with_stats(&board, actor);
+        assert_eq!(strategy.select_action(&board, actor), action);
+        assert!(stats.nodes_evaluated > 0);
+        assert_eq!(4, stats.max_depth_reached);
+    }
+}
+
+#[test]
+fn test_stats_count_cutoffs() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let (action, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+
+    assert!(action.is_some());
+    assert!(stats.alpha_cutoffs > 0);
+    assert!(stats.beta_cutoffs > 0);
+    assert_eq!(depth as u32, stats.max_depth_reached);
+}
+
+#[test]
+fn test_stats_on_game_over() {
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(3);
+    let (action, stats) = strategy.select_action_with_stats(&board, Actor::Second);
+
+    assert!(action.is_none());
+    assert_eq!(1, stats.nodes_evaluated);
+    assert_eq!(0, stats.alpha_cutoffs + stats.beta_cutoffs);
+    assert_eq!(0, stats.max_depth_reached);
+}
+
+#[test]
+fn test_node_budget_limits_search() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth).with_node_budget(500);
+    let (action, stats) = strategy.select_action_with_stats(&Board::new(), Actor::First);
+
+    // 上限に達しても，それまでに探索を終えた結果から行動を選ぶ
+    assert!(action.is_some());
+    assert!(stats.node_budget_exhausted);
+    assert!(stats.nodes_evaluated <= 500);
+    assert!(stats.max_depth_reached < depth as u32);
+}
+
+#[test]
+fn test_node_budget_is_deterministic() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    for &budget in [1, 10, 100, 1000].iter() {
+        let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+            .with_node_budget(budget);
+        let (first_action, first_stats) =
+            strategy.select_action_with_stats(&Board::new(), Actor::First);
+        let (second_action, second_stats) =
+            strategy.select_action_with_stats(&Board::new(), Actor::First);
+
+        assert_eq!(first_action, second_action);
+        assert_eq!(first_stats.nodes_evaluated, second_stats.nodes_evaluated);
+        assert!(first_stats.nodes_evaluated <= budget);
+        assert_eq!(
+            first_action,
+            strategy.select_action(&Board::new(), Actor::First)
+        );
+    }
+}
+
+#[test]
+fn test_depth_limit_is_reached_before_node_budget() {
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(2).with_node_budget(u64::MAX);
+    let unlimited = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(2);
+    let verifier = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(1);
+    for (board, actor) in enumerate_positions(1) {
+        let (action, stats) = strategy.select_action_with_stats(&board, actor);
+        assert!(!stats.node_budget_exhausted);
+        assert_eq!(2, stats.max_depth_reached);
+        // 上限に達しなければ，上限を指定しない場合と同じ価値の行動を選ぶ
+        let expected = unlimited.select_action(&board, actor).unwrap();
+        let after = ReversiRule::translate_state(&board, &action.unwrap());
+        let expected_after = ReversiRule::translate_state(&board, &expected);
+        assert_eq!(
+            verifier.root_payoff(&expected_after, actor.opponent()),
+            verifier.root_payoff(&after, actor.opponent())
+        );
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// `ScoreEvaluator`と同じ評価を，先手の利得だけで定める．
+struct FirstScore;
+
+impl FirstActorEvaluator<Board> for FirstScore {
+    type Payoff = i32;
+
+    fn evaluate_for_first(state: &Board) -> i32 {
+        ScoreEvaluator::evaluate_payoff_for(Actor::First, state)
+    }
+}
+
+symmetric_evaluator!(struct CenterEvaluator: Board => i32 = |state| {
+    match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
+        Some(Actor::First) => 1,
+        Some(Actor::Second) => -1,
+        None => 0,
+    }
+});
+
+#[test]
+fn test_symmetric_evaluator_negates_for_second() {
+    for (board, _) in enumerate_positions(3) {
+        for actor in [Actor::First, Actor::Second] {
+            assert_eq!(
+                ScoreEvaluator::evaluate_payoff_for(actor, &board),
+                SymmetricEvaluator::<FirstScore>::evaluate_payoff_for(actor, &board),
+                "{}",
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_symmetric_evaluator_searches_like_hand_written_evaluator() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let symmetric = AlphaBetaStrategy::<ReversiRule, SymmetricEvaluator<FirstScore>, _>::new(depth);
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            expected.select_scored_action(&board, actor),
+            symmetric.select_scored_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_declared_symmetric_evaluator() {
+    let board = Board::with_placements(&[(1, 1, Actor::Second)]);
+    assert_eq!(
+        -1,
+        CenterEvaluator::evaluate_payoff_for(Actor::First, &board)
+    );
+    assert_eq!(
+        1,
+        CenterEvaluator::evaluate_payoff_for(Actor::Second, &board)
+    );
+    assert_eq!(-1, CenterEvaluator::evaluate_for_first(&board));
+
+    // 中央を取ると評価値が上がるので，1手読みでは中央に置く
+    let strategy = AlphaBetaStrategy::<ReversiRule, CenterEvaluator, _>::new(1);
+    let action = strategy.select_action(&Board::new(), Actor::Second);
+    assert_eq!(Some(Placement::new(1, 1, Actor::Second)), action);
+}
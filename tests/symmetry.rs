@@ -0,0 +1,51 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_symmetric_boards_share_canonical_state() {
+    let corner = Board::with_placements(&[(0, 0, Actor::First), (1, 0, Actor::Second)]);
+    let canonical = CanonicalState::new::<ReversiRule>(&corner);
+    let equivalents = ReversiRule::symmetry_equivalents(&corner).collect::<Vec<_>>();
+    assert_eq!(7, equivalents.len());
+    for equivalent in equivalents.iter() {
+        assert_eq!(canonical, CanonicalState::new::<ReversiRule>(equivalent));
+        assert!(canonical.0 <= *equivalent);
+    }
+    assert!(canonical.0 <= corner);
+
+    let other = Board::with_placements(&[(1, 1, Actor::First), (1, 0, Actor::Second)]);
+    assert_ne!(canonical, CanonicalState::new::<ReversiRule>(&other));
+}
+
+#[test]
+fn test_symmetric_transposition_table_reduces_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let board = Board::new();
+    let plain = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 14);
+    let symmetric = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth)
+        .with_symmetric_transposition_table(1 << 14);
+
+    // 終局まで読む深さでは，評価値は終局した盤面だけで決まり，盤面の対称性を保つ
+    let (plain_action, plain_stats) = plain.select_action_with_stats(&board, Actor::First);
+    let (symmetric_action, symmetric_stats) =
+        symmetric.select_action_with_stats(&board, Actor::First);
+    assert!(plain_action.is_some() && symmetric_action.is_some());
+    assert!(
+        symmetric_stats.nodes_evaluated < plain_stats.nodes_evaluated,
+        "symmetric: {}, plain: {}",
+        symmetric_stats.nodes_evaluated,
+        plain_stats.nodes_evaluated
+    );
+
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            plain.root_payoff(&board, actor),
+            symmetric.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+/// 先手が1手だけ行動し，引き分けかステイルメイトで終わるゲームの局面．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Ending {
+    Start,
+    Draw,
+    Stalemate,
+}
+
+impl State for Ending {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Choose {
+    actor: Actor,
+    ending: Ending,
+}
+
+impl Action for Choose {
+    fn actor(&self) -> Actor {
+        self.actor
+    }
+}
+
+struct EndingRule;
+
+impl Rule for EndingRule {
+    type S = Ending;
+    type A = Choose;
+    type ActionIterator = std::vec::IntoIter<Choose>;
+
+    fn terminal_kind(state: &Ending) -> Option<TerminalKind> {
+        match state {
+            Ending::Start => None,
+            Ending::Draw => Some(TerminalKind::Draw),
+            Ending::Stalemate => Some(TerminalKind::NoMovesAvailable),
+        }
+    }
+
+    fn iterate_available_actions(_state: &Ending, actor: Actor) -> Self::ActionIterator {
+        vec![
+            Choose {
+                actor,
+                ending: Ending::Draw,
+            },
+            Choose {
+                actor,
+                ending: Ending::Stalemate,
+            },
+        ]
+        .into_iter()
+    }
+
+    fn translate_state(_state: &Ending, action: &Choose) -> Ending {
+        action.ending
+    }
+}
+
+impl OutcomeRule for EndingRule {}
+
+/// すべての状態を同じ値で評価する評価関数．
+struct FlatEvaluator;
+
+impl Evaluator<Ending> for FlatEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(_actor: Actor, _state: &Ending) -> i32 {
+        0
+    }
+}
+
+/// 相手をステイルメイトに追い込むことを，引き分けより高く評価する評価関数．
+struct StalemateEvaluator;
+
+impl Evaluator<Ending> for StalemateEvaluator {
+    type Payoff = i32;
+
+    fn evaluate_payoff_for(_actor: Actor, _state: &Ending) -> i32 {
+        0
+    }
+
+    fn evaluate_terminal_payoff_for(actor: Actor, _state: &Ending, kind: TerminalKind) -> i32 {
+        match kind {
+            TerminalKind::Win(winner) if winner == actor => 100,
+            TerminalKind::Win(_) => -100,
+            TerminalKind::Draw => 0,
+            // 先手の行動で終わるので，行動できないのは後手である
+            TerminalKind::NoMovesAvailable if actor == Actor::First => 1,
+            TerminalKind::NoMovesAvailable => -1,
+        }
+    }
+}
+
+#[test]
+fn test_evaluator_distinguishes_stalemate() {
+    let flat = AlphaBetaStrategy::<EndingRule, FlatEvaluator, _>::new(2);
+    let scored = flat.select_scored_action(&Ending::Start, Actor::First);
+    assert_eq!(Some(0), scored.map(|scored| scored.payoff));
+
+    let strategy = AlphaBetaStrategy::<EndingRule, StalemateEvaluator, _>::new(2);
+    let scored = strategy
+        .select_scored_action(&Ending::Start, Actor::First)
+        .unwrap();
+    assert_eq!(Ending::Stalemate, scored.action.ending);
+    assert_eq!(1, scored.payoff);
+    assert_eq!(
+        Some(-1),
+        strategy.root_payoff(&Ending::Stalemate, Actor::Second)
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_is_game_over_follows_terminal_kind() {
+    assert!(!EndingRule::is_game_over(&Ending::Start));
+    assert!(EndingRule::is_game_over(&Ending::Draw));
+    assert!(EndingRule::is_game_over(&Ending::Stalemate));
+
+    for (board, _) in enumerate_positions(2) {
+        assert_eq!(
+            ReversiRule::terminal_kind(&board).is_some(),
+            ReversiRule::is_game_over(&board)
+        );
+    }
+}
+
+#[test]
+fn test_winner_follows_terminal_kind() {
+    assert_eq!(None, EndingRule::winner(&Ending::Stalemate));
+
+    // F F F
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (2, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    assert_eq!(
+        Some(TerminalKind::Win(Actor::First)),
+        ReversiRule::terminal_kind(&board)
+    );
+    assert_eq!(Some(Actor::First), ReversiRule::winner(&board));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_time_limited_strategy_with_short_budget_selects_action() {
+    let strategy =
+        TimeLimitedStrategy::<ReversiRule, BoardEvaluator, usize>::new(Duration::from_millis(1));
+    let board = Board::new();
+
+    // 時刻の確認は一定数のノードごとなので，所要時間は環境に依存する．
+    // 制限時間が短くても，最初の深さの探索結果から合法な行動が選択される
+    let action = strategy.select_action(&board, Actor::First).unwrap();
+    assert!(ReversiRule::iterate_available_actions(&board, Actor::First).any(|a| a == action));
+}
+
+#[test]
+fn test_time_limited_strategy_stops_after_solving_game() {
+    // 盤面が小さいのでゲーム木は短時間で末端まで読み切れ，制限時間を使い切らない
+    let budget = Duration::from_secs(60);
+    let strategy = TimeLimitedStrategy::<ReversiRule, BoardEvaluator, usize>::new(budget);
+
+    let start = Instant::now();
+    let action = strategy.select_action(&Board::new(), Actor::First);
+    let elapsed = start.elapsed();
+
+    assert!(action.is_some());
+    assert!(elapsed < budget / 4, "{:?}", elapsed);
+}
+
+#[test]
+fn test_time_limited_strategy_selects_winning_action() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let strategy =
+        TimeLimitedStrategy::<ReversiRule, BoardEvaluator, u8>::new(Duration::from_millis(50));
+
+    let action = strategy.select_action(&board, Actor::First).unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+}
+
+#[test]
+fn test_select_action_with_near_deadline_returns_action() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(9);
+    let board = Board::new();
+    let deadline = Instant::now() + Duration::from_millis(2);
+
+    // 時刻の確認は一定数のノードごとなので，所要時間は環境に依存する．
+    // 打ち切られても，浅い探索の結果から合法な行動が選択される
+    let action = strategy
+        .select_action_with_deadline(&board, Actor::First, deadline)
+        .unwrap();
+    assert!(ReversiRule::iterate_available_actions(&board, Actor::First).any(|a| a == action));
+}
+
+#[test]
+fn test_select_action_with_expired_deadline() {
+    // F F -
+    // S S -
+    // - - -
+    let board = Board::with_placements(&[
+        (0, 0, Actor::First),
+        (1, 0, Actor::First),
+        (0, 1, Actor::Second),
+        (1, 1, Actor::Second),
+    ]);
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(5);
+
+    // 時刻の確認は一定数のノードごとなので，深さ1の探索は打ち切られない
+    let action = strategy
+        .select_action_with_deadline(&board, Actor::First, Instant::now())
+        .unwrap();
+    assert_eq!((2, 0), (action.x, action.y));
+}
+
+#[test]
+fn test_select_action_with_distant_deadline_matches_select_action() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(9);
+    let deadline = Instant::now() + Duration::from_secs(60);
+    for (board, actor) in enumerate_positions(2) {
+        let expected = strategy.root_payoff(&board, actor);
+        let action = strategy
+            .select_action_with_deadline(&board, actor, deadline)
+            .unwrap();
+        let next = ReversiRule::translate_state(&board, &action);
+        // 相手側から見た評価値が一致すれば，同じ価値の行動を選択している
+        let opponent_payoff = strategy.root_payoff(&next, actor.opponent());
+        assert_eq!(expected.map(|e| -e), opponent_payoff, "{}", board);
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_transposition_table_keeps_root_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    // 置換表は行動選択をまたいで再利用されるので，すべての局面で同じエージェントを使う
+    let with_table = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 12);
+
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            with_table.root_payoff(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
+
+#[test]
+fn test_transposition_table_with_iterative_deepening() {
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(6)
+        .with_transposition_table(1 << 10);
+    let strategy = IterativeDeepeningStrategy::from(alpha_beta);
+
+    for (board, actor) in enumerate_positions(1) {
+        let expected =
+            AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(6).root_payoff(&board, actor);
+        assert_eq!(expected, strategy.root_payoff(&board, actor), "{}", board);
+    }
+}
+
+#[test]
+fn test_tiny_transposition_table_plays_full_game() {
+    // 容量が小さく衝突が頻発しても，正しく行動を選択できる
+    let strategy =
+        AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(9).with_transposition_table(1);
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    while ReversiRule::terminal_kind(&board).is_none() {
+        let action = strategy.select_action(&board, actor).unwrap();
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+    assert_eq!(Some(GameResult::Draw), board.game_result());
+}
+
+#[test]
+fn test_transposition_table_reduces_visited_nodes() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let with_table = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 16);
+
+    let mut nodes_without_table = 0;
+    let mut nodes_with_table = 0;
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, stats) = alpha_beta.select_action_with_stats(&board, actor);
+        nodes_without_table += stats.nodes_evaluated;
+        let (action, stats) = with_table.select_action_with_stats(&board, actor);
+        nodes_with_table += stats.nodes_evaluated;
+        assert_eq!(expected, action, "{}", board);
+    }
+    // 同じ局面に異なる手順で到達することが多いので，探索量は大きく減る
+    assert!(
+        nodes_with_table * 2 < nodes_without_table,
+        "{} {}",
+        nodes_with_table,
+        nodes_without_table
+    );
+}
+
+#[test]
+fn test_replacement_schemes_keep_root_payoff() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    for scheme in [
+        ReplacementScheme::AlwaysReplace,
+        ReplacementScheme::DepthPreferred,
+        ReplacementScheme::TwoTier,
+    ] {
+        // 衝突が頻発する小さな置換表でも，探索結果は変わらない
+        let table = TranspositionTable::new(64).with_replacement_scheme(scheme);
+        let with_table = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+            .with_custom_transposition_table(table);
+        for (board, actor) in enumerate_positions(2) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                with_table.root_payoff(&board, actor),
+                "{:?} {}",
+                scheme,
+                board
+            );
+        }
+        let stats = with_table.transposition_table_stats().unwrap();
+        assert_eq!(64, stats.capacity);
+        assert!(stats.hits > 0 && stats.hits <= stats.probes, "{:?}", stats);
+        assert!(stats.occupied <= stats.capacity, "{:?}", stats);
+    }
+}
+
+#[test]
+fn test_transposition_table_stats_without_table() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(2);
+    assert_eq!(None, strategy.transposition_table_stats());
+}
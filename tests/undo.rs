@@ -0,0 +1,50 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_apply_and_undo_restore_state() {
+    for (board, actor) in enumerate_positions(2) {
+        for action in ReversiRule::iterate_available_actions(&board, actor) {
+            let mut state = board.clone();
+            let undo = ReversiRule::apply_action(&mut state, &action);
+            assert_eq!(ReversiRule::translate_state(&board, &action), state);
+            ReversiRule::undo_action(&mut state, undo);
+            assert_eq!(board, state);
+        }
+    }
+}
+
+#[test]
+fn test_undo_root_payoff_matches_alpha_beta() {
+    for depth in 1..=FIELD_SIZE * FIELD_SIZE {
+        let alpha_beta = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        let undo = UndoAlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+        for (board, actor) in enumerate_positions(1) {
+            assert_eq!(
+                alpha_beta.root_payoff(&board, actor),
+                undo.root_payoff(&board, actor),
+                "depth {}\n{}",
+                depth,
+                board
+            );
+        }
+    }
+}
+
+#[test]
+fn test_undo_selects_same_action_as_alpha_beta() {
+    // 同じ順序で行動を調べるので，選択する行動も探索量もαβ法と一致する
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let undo = UndoAlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let board = Board::new();
+
+    let (alpha_beta_action, alpha_beta_stats) =
+        alpha_beta.select_action_with_stats(&board, Actor::First);
+    let (undo_action, undo_stats) = undo.select_action_with_stats(&board, Actor::First);
+    assert_eq!(alpha_beta_action, undo_action);
+    assert_eq!(alpha_beta_stats.nodes_evaluated, undo_stats.nodes_evaluated);
+    assert!(undo.select_action(&board, Actor::First).is_some());
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+use std::sync::OnceLock;
+
+/// 自分の石の数から相手の石の数を引いた値．
+fn material(actor: Actor, state: &Board) -> f32 {
+    let mut count = 0.0;
+    for x in 0..FIELD_SIZE {
+        for y in 0..FIELD_SIZE {
+            match state.at(x, y) {
+                Some(a) if *a == actor => count += 1.0,
+                Some(_) => count -= 1.0,
+                None => {}
+            }
+        }
+    }
+    count
+}
+
+/// 中央のマスを占めているかどうか．
+fn center(actor: Actor, state: &Board) -> f32 {
+    match state.at(FIELD_SIZE / 2, FIELD_SIZE / 2) {
+        Some(a) if *a == actor => 1.0,
+        Some(_) => -1.0,
+        None => 0.0,
+    }
+}
+
+#[test]
+fn test_unit_weights_sum_evaluators() {
+    let evaluator = WeightedEvaluator::builder()
+        .add(material, 1.0f32)
+        .add(center, 1.0)
+        .build();
+    assert_eq!(2, evaluator.len());
+    let board = Board::with_placements(&[
+        (1, 1, Actor::First),
+        (0, 0, Actor::Second),
+        (2, 0, Actor::First),
+    ]);
+    for actor in [Actor::First, Actor::Second] {
+        assert_eq!(
+            material(actor, &board) + center(actor, &board),
+            evaluator.evaluate_payoff_for(actor, &board)
+        );
+    }
+}
+
+#[test]
+fn test_weights_scale_evaluators() {
+    let evaluator = WeightedEvaluator::builder()
+        .add(material, 1.0f32)
+        .add(center, 0.5)
+        .build();
+    assert_eq!(vec![&1.0, &0.5], evaluator.weights().collect::<Vec<_>>());
+    let board = Board::with_placements(&[(1, 1, Actor::First), (0, 0, Actor::First)]);
+    assert_eq!(2.5, evaluator.evaluate_payoff_for(Actor::First, &board));
+    assert_eq!(-2.5, evaluator.evaluate_payoff_for(Actor::Second, &board));
+}
+
+#[test]
+fn test_empty_weighted_evaluator_returns_zero() {
+    let evaluator = WeightedEvaluator::<Board, i32>::builder().build();
+    assert!(evaluator.is_empty());
+    assert_eq!(
+        0,
+        evaluator.evaluate_payoff_for(Actor::First, &Board::new())
+    );
+}
+
+/// `ScoreEvaluator`だけを重み1で組み合わせる．
+struct WeightedScore;
+
+impl WeightedEvaluatorSource<Board> for WeightedScore {
+    type Payoff = i32;
+
+    fn weighted_evaluator() -> &'static WeightedEvaluator<Board, i32> {
+        static EVALUATOR: OnceLock<WeightedEvaluator<Board, i32>> = OnceLock::new();
+        EVALUATOR.get_or_init(|| {
+            WeightedEvaluator::builder()
+                .add(ScoreEvaluator::evaluate_payoff_for, 1)
+                .build()
+        })
+    }
+}
+
+#[test]
+fn test_weighted_searches_like_single_evaluator() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let expected = AlphaBetaStrategy::<ReversiRule, ScoreEvaluator, _>::new(depth);
+    let weighted = AlphaBetaStrategy::<ReversiRule, Weighted<WeightedScore>, _>::new(depth);
+    for (board, actor) in enumerate_positions(2) {
+        assert_eq!(
+            expected.select_scored_action(&board, actor),
+            weighted.select_scored_action(&board, actor),
+            "{}",
+            board
+        );
+    }
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::*;
+use minimax_strategy::*;
+
+#[test]
+fn test_incremental_hash_matches_full_hash() {
+    for (board, actor) in enumerate_positions(3) {
+        let hash = ReversiRule::zobrist_hash(&board);
+        for action in ReversiRule::iterate_available_actions(&board, actor) {
+            let next = ReversiRule::translate_state(&board, &action);
+            assert_eq!(
+                ReversiRule::zobrist_hash(&next),
+                ReversiRule::update_hash(hash, &board, &action),
+                "{}",
+                next
+            );
+        }
+    }
+}
+
+#[test]
+fn test_zobrist_table_matches_full_hash_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let full_hash = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_transposition_table(1 << 16);
+    let zobrist = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_zobrist_transposition_table(1 << 16);
+
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, plain_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (full_hash_action, full_hash_stats) = full_hash.select_action_with_stats(&board, actor);
+        let (zobrist_action, zobrist_stats) = zobrist.select_action_with_stats(&board, actor);
+        assert_eq!(expected, full_hash_action, "{}", board);
+        assert_eq!(expected, zobrist_action, "{}", board);
+        assert_eq!(
+            alpha_beta.root_payoff(&board, actor),
+            zobrist.root_payoff(&board, actor)
+        );
+        assert!(zobrist_stats.nodes_evaluated <= plain_stats.nodes_evaluated);
+        assert!(full_hash_stats.nodes_evaluated <= plain_stats.nodes_evaluated);
+    }
+}
+
+#[test]
+fn test_tiny_zobrist_table_plays_full_game() {
+    let strategy = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(9)
+        .with_zobrist_transposition_table(1);
+    let mut board = Board::new();
+    let mut actor = Actor::First;
+    while ReversiRule::terminal_kind(&board).is_none() {
+        let action = strategy.select_action(&board, actor).unwrap();
+        board = ReversiRule::translate_state(&board, &action);
+        actor = actor.opponent();
+    }
+    assert_eq!(Some(GameResult::Draw), board.game_result());
+}
+
+const ZOBRIST_TABLE: ZobristTable<2, { FIELD_SIZE * FIELD_SIZE }> = ZobristTable::new(0);
+
+impl ZobristState for Board {
+    fn zobrist_hash(&self) -> ZobristHash {
+        let occupancies = (0..FIELD_SIZE * FIELD_SIZE).filter_map(|square| {
+            let actor = self.at(square % FIELD_SIZE, square / FIELD_SIZE).as_ref()?;
+            let state = match actor {
+                Actor::First => 0,
+                Actor::Second => 1,
+            };
+            Some((square, state))
+        });
+        ZOBRIST_TABLE.hash(occupancies)
+    }
+}
+
+#[test]
+fn test_zobrist_state_hash_is_incremental() {
+    for (board, actor) in enumerate_positions(3) {
+        let hash = board.zobrist_hash();
+        for action in ReversiRule::iterate_available_actions(&board, actor) {
+            let next = ReversiRule::translate_state(&board, &action);
+            let square = action.y * FIELD_SIZE + action.x;
+            let state = match action.actor {
+                Actor::First => 0,
+                Actor::Second => 1,
+            };
+            assert_eq!(
+                next.zobrist_hash(),
+                hash.toggle(&ZOBRIST_TABLE, square, state),
+                "{}",
+                next
+            );
+            assert_ne!(hash, next.zobrist_hash());
+        }
+    }
+}
+
+#[test]
+fn test_zobrist_state_hasher_in_transposition_table() {
+    let depth = FIELD_SIZE * FIELD_SIZE;
+    let alpha_beta = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth);
+    let zobrist = AlphaBetaStrategy::<ReversiRule, BoardEvaluator, _>::new(depth)
+        .with_custom_transposition_table(TranspositionTable::with_hasher(
+            1 << 16,
+            ZobristStateHasher,
+        ));
+
+    for (board, actor) in enumerate_positions(1) {
+        let (expected, plain_stats) = alpha_beta.select_action_with_stats(&board, actor);
+        let (action, stats) = zobrist.select_action_with_stats(&board, actor);
+        assert_eq!(expected, action, "{}", board);
+        assert!(stats.nodes_evaluated <= plain_stats.nodes_evaluated);
+    }
+}